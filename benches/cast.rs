@@ -404,6 +404,223 @@ bench_impl! {
     f128, F128, i128
 }
 
+// ============================================================================
+// Slice conversions
+// ============================================================================
+
+/// Compares converting element by element with the bulk slice conversions.
+#[cfg(not(feature = "nightly"))]
+fn bench_f16_slices(c: &mut Criterion) {
+    use floats::ConvertSlice;
+
+    let f32s: Vec<f32> = (0..4096).map(|i| i as f32 * 0.37 - 700.0).collect();
+    let f64s: Vec<f64> = f32s.iter().map(|&v| v as f64).collect();
+    let halves: Vec<F16> = f32s.iter().map(|&v| v.cast_into()).collect();
+
+    let mut group = c.benchmark_group("f16_slice_from_f32");
+    let mut out = vec![F16::from_bits(0); f32s.len()];
+    group.bench_function("scalar", |b| {
+        b.iter(|| {
+            for (d, &s) in out.iter_mut().zip(&f32s) {
+                *d = s.cast_into();
+            }
+            black_box(&out);
+        })
+    });
+    group.bench_function("slice", |b| {
+        b.iter(|| F16::convert_from_f32_slice(black_box(&f32s), &mut out))
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("f16_slice_into_f32");
+    let mut out = vec![0f32; halves.len()];
+    group.bench_function("scalar", |b| {
+        b.iter(|| {
+            for (d, &s) in out.iter_mut().zip(&halves) {
+                *d = s.cast_into();
+            }
+            black_box(&out);
+        })
+    });
+    group.bench_function("slice", |b| {
+        b.iter(|| F16::convert_to_f32_slice(black_box(&halves), &mut out))
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("f16_slice_from_f64");
+    let mut out = vec![F16::from_bits(0); f64s.len()];
+    group.bench_function("scalar", |b| {
+        b.iter(|| {
+            for (d, &s) in out.iter_mut().zip(&f64s) {
+                *d = s.cast_into();
+            }
+            black_box(&out);
+        })
+    });
+    group.bench_function("slice", |b| {
+        b.iter(|| F16::convert_from_f64_slice(black_box(&f64s), &mut out))
+    });
+    group.finish();
+}
+
+// ============================================================================
+// Software conversions
+// ============================================================================
+
+/// The branchy software conversions that preceded the branchless and
+/// table-driven ones, kept to measure them against.
+#[cfg(not(feature = "nightly"))]
+mod baseline {
+    const F16_INF: u16 = 0x7C00;
+
+    const F16_TO_F32_EXP: [u32; 32] = {
+        let mut table = [0u32; 32];
+        let mut i = 1u32;
+        while i < 31 {
+            table[i as usize] = (i + 112) << 23;
+            i += 1;
+        }
+        table[31] = 0x7F800000;
+        table
+    };
+
+    pub fn f32_from_f16(bits: u16) -> f32 {
+        let bits = bits as u32;
+        let sign = (bits & 0x8000) << 16;
+        let exp = (bits >> 10) & 0x1F;
+        let mant = bits & 0x3FF;
+
+        if exp == 0x1F && mant != 0 {
+            return f32::from_bits(sign | 0x7FC0_0000 | (mant << 13));
+        }
+        if exp != 0 {
+            return f32::from_bits(sign | F16_TO_F32_EXP[exp as usize] | (mant << 13));
+        }
+        if mant == 0 {
+            return f32::from_bits(sign);
+        }
+
+        let shift = mant.leading_zeros() - 22;
+        let normalized_mant = ((mant << (shift + 1)) & 0x3FF) << 13;
+        let f32_exp = (112u32 - shift) << 23;
+        f32::from_bits(sign | f32_exp | normalized_mant)
+    }
+
+    pub fn f16_from_f32(value: f32) -> u16 {
+        let bits = value.to_bits();
+        let sign = ((bits >> 16) & 0x8000) as u16;
+        let f32_exp = (bits >> 23) & 0xFF;
+        let f32_mant = bits & 0x7FFFFF;
+
+        if f32_exp == 0 {
+            return sign;
+        }
+        if f32_exp == 255 {
+            let nan = if f32_mant != 0 {
+                0x0200 | (f32_mant >> 13) as u16
+            } else {
+                0
+            };
+            return sign | F16_INF | nan;
+        }
+
+        let f16_exp = f32_exp as i32 - 112;
+        if f16_exp > 30 {
+            return sign | F16_INF;
+        }
+
+        let (bits, shift) = if f16_exp > 0 {
+            (((f16_exp as u32) << 23) | f32_mant, 13)
+        } else {
+            (f32_mant | 0x800000, (14 - f16_exp) as u32)
+        };
+        if shift > 24 {
+            return sign;
+        }
+
+        let half = 1 << (shift - 1);
+        let rest = bits & ((half << 1) - 1);
+        let mut rounded = bits >> shift;
+        if rest > half || (rest == half && rounded & 1 == 1) {
+            rounded += 1;
+        }
+        sign | rounded as u16
+    }
+}
+
+/// Compares the software conversions with their predecessors on every f16
+/// in random order, so that branches cannot be predicted. Build with `tables`
+/// to measure the table-driven variant. The branchless one gains most where
+/// it vectorizes, which `opt-level = 1` prevents; set
+/// `CARGO_PROFILE_BENCH_OPT_LEVEL=3` to measure release builds.
+#[cfg(not(feature = "nightly"))]
+fn bench_f16_soft(c: &mut Criterion) {
+    use floats::soft;
+
+    let variant = if cfg!(feature = "tables") {
+        "tables"
+    } else {
+        "branchless"
+    };
+
+    // Every f16 in a fixed pseudorandom order
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let mut bits: Vec<u16> = (0..=u16::MAX).collect();
+    for i in (1..bits.len()).rev() {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        bits.swap(i, (state % (i as u64 + 1)) as usize);
+    }
+    let halves: Vec<F16> = bits.iter().map(|&b| F16::from_bits(b)).collect();
+
+    // Their f32 values, nudged off the representable points
+    let f32s: Vec<f32> = bits
+        .iter()
+        .map(|&b| f32::from_bits(baseline::f32_from_f16(b).to_bits() ^ (b as u32 & 0x1FFF)))
+        .collect();
+
+    let mut group = c.benchmark_group("f16_soft_into_f32");
+    let mut out = vec![0.0; bits.len()];
+    group.bench_function("baseline", |b| {
+        b.iter(|| {
+            for (d, &s) in out.iter_mut().zip(black_box(&bits)) {
+                *d = baseline::f32_from_f16(s);
+            }
+            black_box(&out);
+        })
+    });
+    group.bench_function(variant, |b| {
+        b.iter(|| {
+            for (d, &s) in out.iter_mut().zip(black_box(&halves)) {
+                *d = soft::f32_from_f16(s);
+            }
+            black_box(&out);
+        })
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("f16_soft_from_f32");
+    let mut out = vec![F16::from_bits(0); f32s.len()];
+    group.bench_function("baseline", |b| {
+        b.iter(|| {
+            for (d, &s) in out.iter_mut().zip(black_box(&f32s)) {
+                *d = F16::from_bits(baseline::f16_from_f32(s));
+            }
+            black_box(&out);
+        })
+    });
+    group.bench_function(variant, |b| {
+        b.iter(|| {
+            for (d, &s) in out.iter_mut().zip(black_box(&f32s)) {
+                *d = soft::f16_from_f32(s);
+            }
+            black_box(&out);
+        })
+    });
+    group.finish();
+}
+
 // ============================================================================
 // Criterion setup
 // ============================================================================
@@ -460,4 +677,11 @@ criterion_group!(
     bench_f128_into_f64,
 );
 
+#[cfg(not(feature = "nightly"))]
+criterion_group!(slices, bench_f16_slices, bench_f16_soft);
+
+#[cfg(not(feature = "nightly"))]
+criterion_main!(benches, slices);
+
+#[cfg(feature = "nightly")]
 criterion_main!(benches);
@@ -0,0 +1,67 @@
+#![cfg(feature = "casting")]
+
+//! Conversions to and from the IEEE 754 floats. Widening into `f32`, `f64`
+//! and `f128` is exact; into `f16`, the top binade overflows to infinity.
+
+use super::{f16ahp, ABS};
+use crate::round::{decode, round_pack, unpack, Decoded};
+use crate::{f128, f16};
+use casting::CastFrom;
+
+impl f16ahp {
+    /// Converts to the binary format with `exp_bits` exponent bits and
+    /// `mant_bits` stored mantissa bits, rounding to nearest even.
+    #[inline]
+    fn to_ieee(self, exp_bits: u32, mant_bits: u32) -> u128 {
+        // The fields are f16's, and the top exponent is an ordinary one
+        let (negative, mant, exp) = unpack(self.0 as u128, 5, 10);
+        round_pack(negative, mant, exp, false, exp_bits, mant_bits)
+    }
+
+    /// Converts from the binary format with `exp_bits` exponent bits and
+    /// `mant_bits` stored mantissa bits, rounding to nearest even.
+    ///
+    /// As on ARM, NaN becomes a zero of its sign, and infinities and
+    /// values that round beyond `MAX` saturate.
+    #[inline]
+    fn from_ieee(bits: u128, exp_bits: u32, mant_bits: u32) -> Self {
+        let sign = |negative: bool| (negative as u16) << 15;
+
+        match decode(bits, exp_bits, mant_bits) {
+            Decoded::Nan(negative, _) => Self(sign(negative)),
+            Decoded::Infinite(negative) => Self(sign(negative) | ABS),
+            Decoded::Finite(negative, mant, exp) => {
+                // Round into a format with one more exponent bit, where the
+                // bias of 31 instead of 15 leaves room above the top exponent
+                // to saturate
+                let rounded = round_pack(false, mant, exp - 16, false, 6, 10);
+                Self(sign(negative) | rounded.min(ABS as u128) as u16)
+            }
+        }
+    }
+}
+
+macro_rules! cast {
+    ($($float:ty: $bits:ty, $exp_bits:literal, $mant_bits:literal;)*) => {$(
+        impl CastFrom<$float> for f16ahp {
+            #[inline]
+            fn cast_from(value: $float) -> f16ahp {
+                f16ahp::from_ieee(value.to_bits() as u128, $exp_bits, $mant_bits)
+            }
+        }
+
+        impl CastFrom<f16ahp> for $float {
+            #[inline]
+            fn cast_from(value: f16ahp) -> $float {
+                <$float>::from_bits(value.to_ieee($exp_bits, $mant_bits) as $bits)
+            }
+        }
+    )*};
+}
+
+cast! {
+    f16: u16, 5, 10;
+    f32: u32, 8, 23;
+    f64: u64, 11, 52;
+    f128: u128, 15, 112;
+}
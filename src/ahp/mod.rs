@@ -0,0 +1,123 @@
+use core::ops::Neg;
+
+mod cast;
+
+const SIGN: u16 = 0x8000;
+const ABS: u16 = 0x7fff;
+
+/// 16-bit floating point type in ARM's alternative half-precision format,
+/// which `FPCR.AHP` selects.
+///
+/// The fields are those of `f16`, but the largest exponent holds ordinary
+/// numbers instead of infinities and NaNs, so every encoding is finite and
+/// the largest value is 131008. Conversions into it follow the hardware in
+/// its default round-to-nearest mode: NaN becomes a zero of the same sign,
+/// and infinities and values that round beyond `MAX` saturate to `MAX` or
+/// `MIN`.
+#[derive(Debug, Clone, Copy, Default)]
+#[allow(non_camel_case_types)]
+#[repr(transparent)]
+pub struct f16ahp(u16);
+
+impl Neg for f16ahp {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Self(self.0 ^ SIGN)
+    }
+}
+
+impl PartialEq for f16ahp {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        // +0.0 == -0.0; there is no NaN
+        (self.0 | other.0) & ABS == 0 || self.0 == other.0
+    }
+}
+
+impl f16ahp {
+    pub const MIN: Self = Self(SIGN | ABS);
+    pub const MAX: Self = Self(ABS);
+
+    /// The smallest positive normal value.
+    pub const MIN_POSITIVE: Self = Self(1 << 10);
+
+    pub const MANTISSA_DIGITS: u32 = 11;
+
+    /// Always `false`: the format has no NaN.
+    #[inline]
+    pub const fn is_nan(self) -> bool {
+        false
+    }
+
+    /// Always `false`: the format has no infinities.
+    #[inline]
+    pub const fn is_infinite(self) -> bool {
+        false
+    }
+
+    /// Always `true`: every encoding is a finite number.
+    #[inline]
+    pub const fn is_finite(self) -> bool {
+        true
+    }
+
+    #[inline]
+    pub const fn is_sign_positive(self) -> bool {
+        (self.0 & SIGN) == 0
+    }
+
+    #[inline]
+    pub const fn is_sign_negative(self) -> bool {
+        (self.0 & SIGN) != 0
+    }
+
+    /// Creates an `f16ahp` from its representation as a `u16`.
+    #[inline]
+    pub const fn from_bits(bits: u16) -> Self {
+        Self(bits)
+    }
+
+    /// Creates an `f16ahp` from its representation as a byte array in big endian.
+    #[inline]
+    pub const fn from_be_bytes(bytes: [u8; 2]) -> Self {
+        Self(u16::from_be_bytes(bytes))
+    }
+
+    /// Creates an `f16ahp` from its representation as a byte array in little endian.
+    #[inline]
+    pub const fn from_le_bytes(bytes: [u8; 2]) -> Self {
+        Self(u16::from_le_bytes(bytes))
+    }
+
+    /// Creates an `f16ahp` from its representation as a byte array in native endian.
+    #[inline]
+    pub const fn from_ne_bytes(bytes: [u8; 2]) -> Self {
+        Self(u16::from_ne_bytes(bytes))
+    }
+
+    /// Returns the raw representation of this `f16ahp` as a `u16`.
+    #[inline]
+    pub const fn to_bits(self) -> u16 {
+        self.0
+    }
+
+    /// Returns the memory representation of this `f16ahp` as a byte array in big endian.
+    #[inline]
+    pub const fn to_be_bytes(self) -> [u8; 2] {
+        self.0.to_be_bytes()
+    }
+
+    /// Returns the memory representation of this `f16ahp` as a byte array in little endian.
+    #[inline]
+    pub const fn to_le_bytes(self) -> [u8; 2] {
+        self.0.to_le_bytes()
+    }
+
+    /// Returns the memory representation of this `f16ahp` as a byte array in native endian.
+    #[inline]
+    pub const fn to_ne_bytes(self) -> [u8; 2] {
+        self.0.to_ne_bytes()
+    }
+}
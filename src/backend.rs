@@ -0,0 +1,207 @@
+/// An implementation of the `f16` or `bf16` conversions.
+///
+/// Conversions between `f16` and the primitive types use the best
+/// implementation available; see [`backend`]. The same goes for `bf16`; see
+/// [`bf16_backend`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Backend {
+    /// Portable software conversions.
+    Software,
+
+    /// The x86-64 F16C instructions. Only the `f32` conversions use them;
+    /// the rest are done in software.
+    F16c,
+
+    /// The x86-64 AVX-512 FP16 instructions.
+    Avx512Fp16,
+
+    /// The AArch64 FP16 instructions.
+    Fp16,
+
+    /// The compiler's own `f16`, re-exported by the `nightly` feature.
+    Native,
+
+    /// The x86-64 AVX-512 BF16 instructions, which only round from `f32` to
+    /// `bf16`.
+    Avx512Bf16,
+
+    /// The AArch64 BF16 instructions, which only round from `f32` to `bf16`,
+    /// and only while FPCR holds its default modes.
+    Bf16,
+}
+
+/// Returns the implementation used for `f16` conversions.
+///
+/// Hardware support is normally fixed at compile time by the enabled target
+/// features, so binaries built for a generic target use software
+/// conversions. With the `runtime-detect` feature, the CPU is checked on
+/// first use instead and the result cached.
+#[inline]
+pub fn backend() -> Backend {
+    if cfg!(feature = "nightly") {
+        Backend::Native
+    } else if cfg!(all(
+        feature = "asm",
+        target_arch = "x86_64",
+        target_feature = "avx512fp16"
+    )) {
+        Backend::Avx512Fp16
+    } else if cfg!(all(
+        feature = "asm",
+        target_arch = "aarch64",
+        target_feature = "fp16"
+    )) {
+        Backend::Fp16
+    } else {
+        detect()
+    }
+}
+
+/// Returns the implementation used for `bf16` conversions.
+///
+/// As with [`backend`], hardware support is fixed at compile time unless the
+/// `runtime-detect` feature is enabled. The compiler has no `bf16` of its
+/// own, so this is the same with or without the `nightly` feature.
+#[inline]
+pub fn bf16_backend() -> Backend {
+    if cfg!(all(
+        feature = "asm",
+        target_arch = "x86_64",
+        target_feature = "avx512bf16"
+    )) {
+        Backend::Avx512Bf16
+    } else if cfg!(all(
+        feature = "asm",
+        target_arch = "aarch64",
+        target_feature = "bf16"
+    )) {
+        Backend::Bf16
+    } else {
+        detect_bf16()
+    }
+}
+
+#[cfg(not(all(
+    feature = "runtime-detect",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+)))]
+#[inline]
+fn detect() -> Backend {
+    if cfg!(all(
+        feature = "asm",
+        target_arch = "x86_64",
+        target_feature = "f16c"
+    )) {
+        Backend::F16c
+    } else {
+        Backend::Software
+    }
+}
+
+#[cfg(not(all(
+    feature = "runtime-detect",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+)))]
+#[inline]
+fn detect_bf16() -> Backend {
+    Backend::Software
+}
+
+#[cfg(all(
+    feature = "runtime-detect",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+use core::sync::atomic::{AtomicU8, Ordering};
+
+#[cfg(all(
+    feature = "runtime-detect",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+const UNKNOWN: u8 = u8::MAX;
+
+#[cfg(all(
+    feature = "runtime-detect",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+#[inline]
+fn detect() -> Backend {
+    static DETECTED: AtomicU8 = AtomicU8::new(UNKNOWN);
+    cached(&DETECTED, probe)
+}
+
+#[cfg(all(
+    feature = "runtime-detect",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+#[inline]
+fn detect_bf16() -> Backend {
+    static DETECTED: AtomicU8 = AtomicU8::new(UNKNOWN);
+    cached(&DETECTED, probe_bf16)
+}
+
+/// Returns the backend stored in `detected`, probing for it on first use.
+#[cfg(all(
+    feature = "runtime-detect",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+#[inline]
+fn cached(detected: &AtomicU8, probe: fn() -> Backend) -> Backend {
+    // Racing threads detect the same backend, so relaxed ordering suffices
+    match detected.load(Ordering::Relaxed) {
+        UNKNOWN => {
+            let backend = probe();
+            detected.store(backend as u8, Ordering::Relaxed);
+            backend
+        }
+
+        n if n == Backend::F16c as u8 => Backend::F16c,
+        n if n == Backend::Avx512Fp16 as u8 => Backend::Avx512Fp16,
+        n if n == Backend::Fp16 as u8 => Backend::Fp16,
+        n if n == Backend::Avx512Bf16 as u8 => Backend::Avx512Bf16,
+        n if n == Backend::Bf16 as u8 => Backend::Bf16,
+        _ => Backend::Software,
+    }
+}
+
+#[cfg(all(feature = "runtime-detect", target_arch = "x86_64"))]
+#[cold]
+fn probe() -> Backend {
+    if std::arch::is_x86_feature_detected!("avx512fp16") {
+        Backend::Avx512Fp16
+    } else if std::arch::is_x86_feature_detected!("f16c") {
+        Backend::F16c
+    } else {
+        Backend::Software
+    }
+}
+
+#[cfg(all(feature = "runtime-detect", target_arch = "aarch64"))]
+#[cold]
+fn probe() -> Backend {
+    if std::arch::is_aarch64_feature_detected!("fp16") {
+        Backend::Fp16
+    } else {
+        Backend::Software
+    }
+}
+
+#[cfg(all(feature = "runtime-detect", target_arch = "x86_64"))]
+#[cold]
+fn probe_bf16() -> Backend {
+    if std::arch::is_x86_feature_detected!("avx512bf16") {
+        Backend::Avx512Bf16
+    } else {
+        Backend::Software
+    }
+}
+
+#[cfg(all(feature = "runtime-detect", target_arch = "aarch64"))]
+#[cold]
+fn probe_bf16() -> Backend {
+    if std::arch::is_aarch64_feature_detected!("bf16") {
+        Backend::Bf16
+    } else {
+        Backend::Software
+    }
+}
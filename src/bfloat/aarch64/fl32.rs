@@ -0,0 +1,32 @@
+use super::default_mode;
+use crate::bf16;
+use crate::bfloat::sw;
+
+#[inline]
+#[allow(unsafe_code)]
+#[target_feature(enable = "bf16")]
+pub(crate) unsafe fn bf16_from_f32(value: f32) -> bf16 {
+    if default_mode() {
+        bfcvt(value)
+    } else {
+        sw::bf16_from_f32(value)
+    }
+}
+
+/// Converts with the current FPCR mode, which the caller must have checked.
+#[inline]
+#[allow(unsafe_code)]
+#[target_feature(enable = "bf16")]
+pub(super) unsafe fn bfcvt(value: f32) -> bf16 {
+    let result: u32;
+
+    core::arch::asm!(
+        "bfcvt h0, s0",     // Convert f32 in s0 to bf16 in h0, clearing the rest
+        "fmov w0, s0",      // Move bf16 from s0 to w0 (u16 in low bits)
+        inout("s0") value => _,
+        out("w0") result,
+        options(pure, nomem, nostack)
+    );
+
+    bf16(result as u16)
+}
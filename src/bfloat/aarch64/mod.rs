@@ -0,0 +1,35 @@
+#![cfg(all(
+    feature = "asm",
+    target_arch = "aarch64",
+    any(target_feature = "bf16", feature = "runtime-detect")
+))]
+#![cfg(feature = "casting")]
+
+// Every function requires BF16 support, which the caller must check
+
+mod fl32;
+mod slice;
+
+pub(crate) use self::{fl32::*, slice::*};
+
+/// Returns whether FPCR selects what the software conversion does: rounding
+/// to nearest even (`RMode`), no flushing of subnormals (`FZ`, `FIZ`) and
+/// NaN payloads kept (`DN`, `AH`).
+///
+/// `bfcvt` and `bfcvtn` round with the current mode, so under any other the
+/// conversions fall back to software.
+#[inline]
+#[allow(unsafe_code)]
+fn default_mode() -> bool {
+    let fpcr: u64;
+
+    unsafe {
+        core::arch::asm!(
+            "mrs {fpcr}, fpcr",     // Read the floating-point control register
+            fpcr = out(reg) fpcr,
+            options(nomem, nostack, preserves_flags)
+        );
+    }
+
+    fpcr & (0b1111 << 22 | 0b11) == 0
+}
@@ -0,0 +1,36 @@
+use super::default_mode;
+use super::fl32::bfcvt;
+use crate::bf16;
+use crate::bfloat::sw;
+use crate::slice::each;
+
+// BF16: 4-wide f32 -> bf16 conversions, with the scalar form for the tail.
+// FPCR is checked once, and any mode but the default converts in software.
+
+#[inline]
+#[allow(unsafe_code)]
+#[target_feature(enable = "bf16")]
+pub(crate) unsafe fn bf16_from_f32_slice(src: &[f32], dst: &mut [bf16]) {
+    if !default_mode() {
+        return each(src, dst, sw::bf16_from_f32);
+    }
+
+    let mut src = src.chunks_exact(4);
+    let mut dst = dst.chunks_exact_mut(4);
+
+    for (s, d) in (&mut src).zip(&mut dst) {
+        core::arch::asm!(
+            "ldr q0, [{src}]",          // Load 4 f32
+            "bfcvtn v0.4h, v0.4s",      // Convert to bf16, rounding to nearest even
+            "str d0, [{dst}]",          // Store 4 bf16
+            src = in(reg) s.as_ptr(),
+            dst = in(reg) d.as_mut_ptr(),
+            out("v0") _,
+            options(nostack)
+        );
+    }
+
+    for (s, d) in src.remainder().iter().zip(dst.into_remainder()) {
+        *d = bfcvt(*s);
+    }
+}
@@ -0,0 +1,77 @@
+#![cfg(feature = "casting")]
+
+//! Conversions to and from the primitive floats.
+//!
+//! Rounding from `f32` is dispatched on [`bf16_backend`](crate::bf16_backend).
+//! The others are always converted in software: widening is a shift, and
+//! neither instruction set rounds from `f64`.
+
+use super::{bf16, sw};
+use casting::CastFrom;
+
+/// Returns the expression for the active backend from the enclosing
+/// function, or else evaluates the software one.
+///
+/// Hardware expressions are only compiled where their backend can be active,
+/// and only run when [`bf16_backend`](crate::bf16_backend) reports the CPU
+/// feature, which is what makes their `unsafe` calls sound.
+macro_rules! bf16_dispatch {
+    (
+        avx512bf16: $avx:expr,
+        bf16: $bf16:expr,
+        software: $sw:expr $(,)?
+    ) => {{
+        #[cfg(all(
+            feature = "asm",
+            target_arch = "x86_64",
+            any(target_feature = "avx512bf16", feature = "runtime-detect")
+        ))]
+        if crate::bf16_backend() == crate::Backend::Avx512Bf16 {
+            return $avx;
+        }
+
+        #[cfg(all(
+            feature = "asm",
+            target_arch = "aarch64",
+            any(target_feature = "bf16", feature = "runtime-detect")
+        ))]
+        if crate::bf16_backend() == crate::Backend::Bf16 {
+            return $bf16;
+        }
+
+        $sw
+    }};
+}
+
+impl CastFrom<f32> for bf16 {
+    #[inline]
+    #[allow(unsafe_code)]
+    fn cast_from(value: f32) -> bf16 {
+        bf16_dispatch! {
+            avx512bf16: unsafe { super::x86_64::avx::bf16_from_f32(value) },
+            bf16: unsafe { super::aarch64::bf16_from_f32(value) },
+            software: sw::bf16_from_f32(value),
+        }
+    }
+}
+
+impl CastFrom<bf16> for f32 {
+    #[inline]
+    fn cast_from(value: bf16) -> f32 {
+        sw::f32_from_bf16(value)
+    }
+}
+
+impl CastFrom<f64> for bf16 {
+    #[inline]
+    fn cast_from(value: f64) -> bf16 {
+        sw::bf16_from_f64(value)
+    }
+}
+
+impl CastFrom<bf16> for f64 {
+    #[inline]
+    fn cast_from(value: bf16) -> f64 {
+        sw::f64_from_bf16(value)
+    }
+}
@@ -0,0 +1,128 @@
+use core::ops::Neg;
+
+pub(crate) mod aarch64;
+#[macro_use]
+mod cast;
+mod slice;
+pub(crate) mod sw;
+pub(crate) mod x86_64;
+
+/// 16-bit brain floating point type (bfloat16).
+///
+/// This is the upper half of an `f32`: the same 8-bit exponent, with the
+/// mantissa cut to 7 bits. It provides construction methods and correctly
+/// rounded conversions.
+#[derive(Debug, Clone, Copy, Default)]
+#[allow(non_camel_case_types)]
+#[repr(transparent)]
+pub struct bf16(pub(crate) u16);
+
+impl Neg for bf16 {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Self(self.0 ^ (1 << 15))
+    }
+}
+
+impl PartialEq for bf16 {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        // IEEE 754 compliance: NaN != NaN and +0.0 == -0.0
+        if self.is_nan() || other.is_nan() {
+            return false;
+        }
+
+        if (self.0 | other.0) & 0x7FFF == 0 {
+            return true;
+        }
+
+        self.0 == other.0
+    }
+}
+
+impl bf16 {
+    pub const NAN: Self = Self(0x7fc0);
+
+    pub const MIN: Self = Self(0xff7f);
+    pub const MAX: Self = Self(0x7f7f);
+
+    pub const INFINITY: Self = Self(0x7f80);
+    pub const NEG_INFINITY: Self = Self(0xff80);
+
+    pub const MANTISSA_DIGITS: u32 = 8;
+
+    #[inline]
+    pub const fn is_nan(self) -> bool {
+        (self.0 & 0x7fff) > Self::INFINITY.0
+    }
+
+    #[inline]
+    pub const fn is_infinite(self) -> bool {
+        self.0 == Self::INFINITY.0 || self.0 == Self::NEG_INFINITY.0
+    }
+
+    #[inline]
+    pub const fn is_finite(self) -> bool {
+        !self.is_nan() && !self.is_infinite()
+    }
+
+    #[inline]
+    pub const fn is_sign_positive(self) -> bool {
+        (self.0 & (1 << 15)) == 0
+    }
+
+    #[inline]
+    pub const fn is_sign_negative(self) -> bool {
+        (self.0 & (1 << 15)) != 0
+    }
+
+    /// Creates a `bf16` from its representation as a `u16`.
+    #[inline]
+    pub const fn from_bits(bits: u16) -> Self {
+        Self(bits)
+    }
+
+    /// Creates a `bf16` from its representation as a byte array in big endian.
+    #[inline]
+    pub const fn from_be_bytes(bytes: [u8; 2]) -> Self {
+        Self(u16::from_be_bytes(bytes))
+    }
+
+    /// Creates a `bf16` from its representation as a byte array in little endian.
+    #[inline]
+    pub const fn from_le_bytes(bytes: [u8; 2]) -> Self {
+        Self(u16::from_le_bytes(bytes))
+    }
+
+    /// Creates a `bf16` from its representation as a byte array in native endian.
+    #[inline]
+    pub const fn from_ne_bytes(bytes: [u8; 2]) -> Self {
+        Self(u16::from_ne_bytes(bytes))
+    }
+
+    /// Returns the raw representation of this `bf16` as a `u16`.
+    #[inline]
+    pub const fn to_bits(self) -> u16 {
+        self.0
+    }
+
+    /// Returns the memory representation of this `bf16` as a byte array in big endian.
+    #[inline]
+    pub const fn to_be_bytes(self) -> [u8; 2] {
+        self.0.to_be_bytes()
+    }
+
+    /// Returns the memory representation of this `bf16` as a byte array in little endian.
+    #[inline]
+    pub const fn to_le_bytes(self) -> [u8; 2] {
+        self.0.to_le_bytes()
+    }
+
+    /// Returns the memory representation of this `bf16` as a byte array in native endian.
+    #[inline]
+    pub const fn to_ne_bytes(self) -> [u8; 2] {
+        self.0.to_ne_bytes()
+    }
+}
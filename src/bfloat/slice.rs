@@ -0,0 +1,58 @@
+#![cfg(feature = "casting")]
+
+use super::{bf16, sw};
+use crate::slice::{check_len, each};
+use crate::ConvertSlice;
+
+impl ConvertSlice for bf16 {
+    /// Converts a slice of `f32` to `bf16`.
+    ///
+    /// Each element is rounded exactly as by `CastFrom`, but the backend is
+    /// chosen once and hardware converts 16 (AVX-512 BF16) or 4 (AArch64
+    /// BF16) elements at a time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` and `dst` have different lengths.
+    #[allow(unsafe_code)]
+    fn convert_from_f32_slice(src: &[f32], dst: &mut [Self]) {
+        check_len(src, dst);
+
+        bf16_dispatch! {
+            avx512bf16: unsafe { super::x86_64::avx::bf16_from_f32_slice(src, dst) },
+            bf16: unsafe { super::aarch64::bf16_from_f32_slice(src, dst) },
+            software: each(src, dst, sw::bf16_from_f32),
+        }
+    }
+
+    /// Converts a slice of `bf16` to `f32`. Every element is exact.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` and `dst` have different lengths.
+    fn convert_to_f32_slice(src: &[Self], dst: &mut [f32]) {
+        check_len(src, dst);
+        each(src, dst, sw::f32_from_bf16);
+    }
+
+    /// Converts a slice of `f64` to `bf16`, rounding each element exactly as
+    /// by `CastFrom`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` and `dst` have different lengths.
+    fn convert_from_f64_slice(src: &[f64], dst: &mut [Self]) {
+        check_len(src, dst);
+        each(src, dst, sw::bf16_from_f64);
+    }
+
+    /// Converts a slice of `bf16` to `f64`. Every element is exact.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` and `dst` have different lengths.
+    fn convert_to_f64_slice(src: &[Self], dst: &mut [f64]) {
+        check_len(src, dst);
+        each(src, dst, sw::f64_from_bf16);
+    }
+}
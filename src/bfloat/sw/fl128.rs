@@ -0,0 +1,80 @@
+use super::bf16_from_f32;
+use crate::{bf16, f128};
+use casting::CastFrom;
+
+// These work on the bits, so they are the same for the core f128.
+
+const F128_MANT_MASK: u128 = (1 << 112) - 1;
+
+impl CastFrom<f128> for bf16 {
+    #[inline]
+    fn cast_from(value: f128) -> bf16 {
+        let bits = value.to_bits();
+        let sign = ((bits >> 127) as u32) << 31;
+        let exp = ((bits >> 112) & 0x7FFF) as i32;
+        let mant = bits & F128_MANT_MASK;
+
+        // Infinity or NaN: NaNs are quieted and keep the top payload bits
+        if exp == 0x7FFF {
+            let nan = if mant != 0 {
+                0x0040 | (mant >> 105) as u16
+            } else {
+                0
+            };
+            return bf16((sign >> 16) as u16 | 0x7F80 | nan);
+        }
+
+        // Round to odd into an f32, which has enough extra bits that the
+        // rounding to bf16 is then the only one
+        let f32_exp = exp - (16383 - 127);
+        let odd = if f32_exp >= 0xFF {
+            // Beyond any finite bf16
+            0x7F80_0000
+        } else if f32_exp > 0 {
+            let sticky = (mant & ((1 << 89) - 1) != 0) as u32;
+            ((f32_exp as u32) << 23) | (mant >> 89) as u32 | sticky
+        } else if exp == 0 && mant == 0 {
+            0
+        } else {
+            // Subnormal in f32, or too small for it: shift the significand
+            // with its implicit bit into place
+            let sig = mant | (1 << 112);
+            let shift = (90 - f32_exp) as u32;
+            if shift >= 128 {
+                1
+            } else {
+                let sticky = (sig & ((1 << shift) - 1) != 0) as u32;
+                (sig >> shift) as u32 | sticky
+            }
+        };
+
+        bf16_from_f32(f32::from_bits(sign | odd))
+    }
+}
+
+impl CastFrom<bf16> for f128 {
+    #[inline]
+    fn cast_from(value: bf16) -> f128 {
+        let bits = value.0;
+        let sign = ((bits >> 15) as u128) << 127;
+        let exp = ((bits >> 7) & 0xFF) as u32;
+        let mant = (bits & 0x7F) as u32;
+
+        let abs = if exp == 0xFF {
+            // Infinity or NaN: NaNs are quieted
+            let quiet = if mant != 0 { 1 << 111 } else { 0 };
+            (0x7FFF << 112) | quiet | ((mant as u128) << 105)
+        } else if exp != 0 {
+            ((exp as u128 + (16383 - 127)) << 112) | ((mant as u128) << 105)
+        } else if mant == 0 {
+            0
+        } else {
+            // Subnormal: normalize by the leading 1 bit
+            let shift = mant.leading_zeros() - 24;
+            let exp = 16383 - 126 - shift;
+            ((exp as u128) << 112) | ((((mant << shift) & 0x7F) as u128) << 105)
+        };
+
+        f128::from_bits(sign | abs)
+    }
+}
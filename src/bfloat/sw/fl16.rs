@@ -0,0 +1,19 @@
+use super::{bf16_from_f32, f32_from_bf16};
+use crate::{bf16, f16};
+use casting::CastFrom;
+
+// Every f16 and every bf16 is exact in f32, so going through it rounds once.
+
+impl CastFrom<f16> for bf16 {
+    #[inline]
+    fn cast_from(value: f16) -> bf16 {
+        bf16_from_f32(f32::cast_from(value))
+    }
+}
+
+impl CastFrom<bf16> for f16 {
+    #[inline]
+    fn cast_from(value: bf16) -> f16 {
+        f16::cast_from(f32_from_bf16(value))
+    }
+}
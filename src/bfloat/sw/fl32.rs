@@ -0,0 +1,32 @@
+use crate::bf16;
+
+// bf16 is the upper half of an f32, so widening is a shift and narrowing
+// rounds away the lower half.
+
+#[inline]
+pub(crate) fn f32_from_bf16(value: bf16) -> f32 {
+    let bits = (value.0 as u32) << 16;
+
+    // NaNs are quieted
+    if value.is_nan() {
+        return f32::from_bits(bits | 0x0040_0000);
+    }
+
+    f32::from_bits(bits)
+}
+
+#[inline]
+pub(crate) fn bf16_from_f32(value: f32) -> bf16 {
+    let bits = value.to_bits();
+
+    // NaNs are quieted and keep the top payload bits
+    if value.is_nan() {
+        return bf16((bits >> 16) as u16 | 0x0040);
+    }
+
+    // Round to nearest, ties to even, by adding just under half an ulp plus
+    // the parity bit. A carry moves into the exponent, and from the largest
+    // finite value into infinity.
+    let odd = (bits >> 16) & 1;
+    bf16(((bits + 0x7FFF + odd) >> 16) as u16)
+}
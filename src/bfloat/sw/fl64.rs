@@ -0,0 +1,42 @@
+use super::{bf16_from_f32, f32_from_bf16};
+use crate::bf16;
+
+// NaNs are handled here rather than through `as`, which does not specify
+// which NaN payload it produces.
+
+#[inline]
+pub(crate) fn f64_from_bf16(value: bf16) -> f64 {
+    if value.is_nan() {
+        let sign = ((value.0 & 0x8000) as u64) << 48;
+        let payload = ((value.0 & 0x3F) as u64) << 45;
+        return f64::from_bits(sign | 0x7FF8_0000_0000_0000 | payload);
+    }
+
+    f32_from_bf16(value) as f64
+}
+
+#[inline]
+pub(crate) fn bf16_from_f64(value: f64) -> bf16 {
+    let bits = value.to_bits();
+
+    if value.is_nan() {
+        let sign = ((bits >> 48) & 0x8000) as u16;
+        let payload = ((bits >> 45) & 0x3F) as u16;
+        return bf16(sign | 0x7FC0 | payload);
+    }
+
+    // Rounding through f32 would round twice. Rounding to odd instead keeps
+    // an inexact result off the bf16 midpoints, so the second rounding is
+    // the only one.
+    let x = value as f32;
+    let mut bits = x.to_bits();
+    if x.is_finite() && f64::from(x) != value {
+        // Step toward zero if the magnitude was rounded up
+        if (f64::from(x) > value) == (value > 0.0) {
+            bits -= 1;
+        }
+        bits |= 1;
+    }
+
+    bf16_from_f32(f32::from_bits(bits))
+}
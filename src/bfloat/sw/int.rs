@@ -0,0 +1,68 @@
+use super::{bf16_from_f32, bf16_from_f64, f32_from_bf16};
+use crate::bf16;
+use casting::CastFrom;
+
+// Integers of up to 32 bits are exact in f64, so they round once from
+// there. Wider ones are rounded to odd at f32 precision first, as
+// rounding them through any float would round twice.
+
+macro_rules! narrow {
+    ($($int:ty),*) => {$(
+        impl CastFrom<$int> for bf16 {
+            #[inline]
+            fn cast_from(value: $int) -> bf16 {
+                bf16_from_f64(value as f64)
+            }
+        }
+    )*};
+}
+
+macro_rules! wide {
+    ($($int:ty => $uint:ty),*) => {$(
+        impl CastFrom<$int> for bf16 {
+            #[inline]
+            #[allow(unused_comparisons)]
+            fn cast_from(value: $int) -> bf16 {
+                let negative = value < 0;
+                let mut abs = if negative {
+                    (value as $uint).wrapping_neg()
+                } else {
+                    value as $uint
+                };
+
+                // Keep the top 24 significant bits, with any bits shifted
+                // out collected into the lowest one
+                let width = <$uint>::BITS - abs.leading_zeros();
+                if width > 24 {
+                    let shift = width - 24;
+                    let sticky = (abs & ((1 << shift) - 1) != 0) as $uint;
+                    abs = ((abs >> shift) | sticky) << shift;
+                }
+
+                let x = abs as f32;
+                bf16_from_f32(if negative { -x } else { x })
+            }
+        }
+    )*};
+}
+
+macro_rules! from {
+    ($($int:ty),*) => {$(
+        impl CastFrom<bf16> for $int {
+            #[inline]
+            fn cast_from(value: bf16) -> $int {
+                f32_from_bf16(value) as $int
+            }
+        }
+    )*};
+}
+
+narrow!(u8, u16, u32, i8, i16, i32);
+wide!(u64 => u64, u128 => u128, i64 => u64, i128 => u128);
+from!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, usize, isize);
+
+#[cfg(any(target_pointer_width = "16", target_pointer_width = "32"))]
+narrow!(usize, isize);
+
+#[cfg(target_pointer_width = "64")]
+wide!(usize => usize, isize => usize);
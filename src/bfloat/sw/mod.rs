@@ -0,0 +1,9 @@
+#![cfg(feature = "casting")]
+
+mod fl128;
+mod fl16;
+mod fl32;
+mod fl64;
+mod int;
+
+pub(crate) use self::{fl32::*, fl64::*};
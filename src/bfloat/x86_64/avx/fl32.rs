@@ -0,0 +1,29 @@
+use crate::bf16;
+use crate::bfloat::sw;
+
+// AVX-512 BF16: f32 -> bf16 through the 512-bit form, which unlike the
+// narrower ones does not also need AVX-512 VL. Subnormal inputs, which the
+// instruction treats as zero, are converted in software.
+
+#[inline]
+#[allow(unsafe_code)]
+#[target_feature(enable = "avx512bf16")]
+pub(crate) unsafe fn bf16_from_f32(value: f32) -> bf16 {
+    if value.to_bits() & 0x7F80_0000 == 0 {
+        return sw::bf16_from_f32(value);
+    }
+
+    let result: u32;
+
+    core::arch::asm!(
+        "vmovd xmm0, {value:e}",        // Move f32 to xmm0, zeroing the other lanes
+        "vcvtneps2bf16 ymm0, zmm0",     // Convert to bf16 with round-to-nearest
+        "vmovd {result:e}, xmm0",       // Move bf16 to result (u16 in low bits)
+        value = in(reg) value.to_bits(),
+        result = lateout(reg) result,
+        out("zmm0") _,
+        options(pure, nomem, nostack)
+    );
+
+    bf16(result as u16)
+}
@@ -0,0 +1,37 @@
+use crate::bf16;
+use crate::bfloat::sw::bf16_from_f32;
+
+// AVX-512 BF16: 16-wide f32 -> bf16 conversions (available since Cooper
+// Lake, 2020), with software for the tail. The instruction always rounds to
+// nearest even, but treats subnormal inputs as zero, so those are converted
+// again in software.
+
+#[inline]
+#[allow(unsafe_code)]
+#[target_feature(enable = "avx512bf16")]
+pub(crate) unsafe fn bf16_from_f32_slice(src: &[f32], dst: &mut [bf16]) {
+    let mut src = src.chunks_exact(16);
+    let mut dst = dst.chunks_exact_mut(16);
+
+    for (s, d) in (&mut src).zip(&mut dst) {
+        core::arch::asm!(
+            "vmovups zmm0, zmmword ptr [{src}]",   // Load 16 f32
+            "vcvtneps2bf16 ymm0, zmm0",            // Convert to bf16 with round-to-nearest
+            "vmovups ymmword ptr [{dst}], ymm0",   // Store 16 bf16
+            src = in(reg) s.as_ptr(),
+            dst = in(reg) d.as_mut_ptr(),
+            out("zmm0") _,
+            options(nostack)
+        );
+
+        for (s, d) in s.iter().zip(d) {
+            if s.to_bits() & 0x7F80_0000 == 0 {
+                *d = bf16_from_f32(*s);
+            }
+        }
+    }
+
+    for (s, d) in src.remainder().iter().zip(dst.into_remainder()) {
+        *d = bf16_from_f32(*s);
+    }
+}
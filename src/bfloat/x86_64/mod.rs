@@ -0,0 +1,8 @@
+#![cfg(all(feature = "asm", target_arch = "x86_64"))]
+#![cfg(feature = "casting")]
+
+// Every function requires the feature its module is named for, which the
+// caller must check
+
+#[cfg(any(target_feature = "avx512bf16", feature = "runtime-detect"))]
+pub(crate) mod avx;
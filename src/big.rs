@@ -0,0 +1,222 @@
+//! A fixed-capacity big integer for exact conversions between binary and
+//! decimal, whose values can span thousands of bits.
+
+use crate::uint::{pow10, U256};
+
+/// An unsigned integer of up to `N` 64-bit limbs, least significant first.
+pub(crate) struct Big<const N: usize> {
+    limbs: [u64; N],
+    len: usize,
+}
+
+impl<const N: usize> Big<N> {
+    #[inline]
+    pub fn new(value: U256) -> Self {
+        let mut big = Self {
+            limbs: [0; N],
+            len: 4,
+        };
+        big.limbs[0] = value.lo as u64;
+        big.limbs[1] = (value.lo >> 64) as u64;
+        big.limbs[2] = value.hi as u64;
+        big.limbs[3] = (value.hi >> 64) as u64;
+        big.trim();
+        big
+    }
+
+    /// Sets the value to that of `other`, which must fit. Copying in place
+    /// spares a second buffer when `N` limbs take a lot of stack.
+    #[inline]
+    pub fn set<const M: usize>(&mut self, other: &Big<M>) {
+        self.limbs[other.len..self.len.max(other.len)]
+            .iter_mut()
+            .for_each(|limb| *limb = 0);
+        self.limbs[..other.len].copy_from_slice(&other.limbs[..other.len]);
+        self.len = other.len;
+    }
+
+    #[inline]
+    fn trim(&mut self) {
+        while self.len > 0 && self.limbs[self.len - 1] == 0 {
+            self.len -= 1;
+        }
+    }
+
+    #[inline]
+    pub fn is_zero(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    pub fn bits(&self) -> u32 {
+        match self.len {
+            0 => 0,
+            len => len as u32 * 64 - self.limbs[len - 1].leading_zeros(),
+        }
+    }
+
+    #[inline]
+    pub fn mul_small(&mut self, m: u64) {
+        let mut carry = 0;
+        for limb in &mut self.limbs[..self.len] {
+            let wide = *limb as u128 * m as u128 + carry;
+            *limb = wide as u64;
+            carry = wide >> 64;
+        }
+        if carry != 0 {
+            self.limbs[self.len] = carry as u64;
+            self.len += 1;
+        }
+    }
+
+    #[inline]
+    pub fn add_small(&mut self, a: u64) {
+        let mut carry = a;
+        for limb in &mut self.limbs[..self.len] {
+            let (sum, c) = limb.overflowing_add(carry);
+            *limb = sum;
+            carry = c as u64;
+            if carry == 0 {
+                return;
+            }
+        }
+        if carry != 0 {
+            self.limbs[self.len] = carry;
+            self.len += 1;
+        }
+    }
+
+    #[inline]
+    pub fn mul_pow10(&mut self, mut exp: u32) {
+        while exp > 0 {
+            let step = exp.min(19);
+            self.mul_small(pow10(step) as u64);
+            exp -= step;
+        }
+    }
+
+    /// Divides by `d`, returning the remainder.
+    #[inline]
+    pub fn div_small(&mut self, d: u64) -> u64 {
+        let mut rem = 0;
+        for limb in self.limbs[..self.len].iter_mut().rev() {
+            let wide = (rem as u128) << 64 | *limb as u128;
+            *limb = (wide / d as u128) as u64;
+            rem = (wide % d as u128) as u64;
+        }
+        self.trim();
+        rem
+    }
+
+    /// Divides by 10^`exp`, returning whether the remainder is nonzero.
+    #[inline]
+    pub fn div_pow10(&mut self, mut exp: u32) -> bool {
+        let mut sticky = false;
+        while exp > 0 {
+            let step = exp.min(19);
+            sticky |= self.div_small(pow10(step) as u64) != 0;
+            exp -= step;
+        }
+        sticky
+    }
+
+    #[inline]
+    pub fn shl(&mut self, bits: u32) {
+        let (limbs, bits) = ((bits / 64) as usize, bits % 64);
+        if self.len == 0 {
+            return;
+        }
+
+        self.limbs[self.len] = 0;
+        for i in (0..=self.len).rev() {
+            let below = if bits == 0 || i == 0 {
+                0
+            } else {
+                self.limbs[i - 1] >> (64 - bits)
+            };
+            self.limbs[i + limbs] = self.limbs[i] << bits | below;
+        }
+        self.limbs[..limbs].iter_mut().for_each(|limb| *limb = 0);
+        self.len += limbs + 1;
+        self.trim();
+    }
+
+    /// Shifts right by `bits`, returning whether any ones were shifted out.
+    #[inline]
+    pub fn shr(&mut self, bits: u32) -> bool {
+        let (limbs, bits) = ((bits / 64) as usize, bits % 64);
+        if limbs >= self.len {
+            let sticky = self.len != 0;
+            self.limbs[..self.len].iter_mut().for_each(|limb| *limb = 0);
+            self.len = 0;
+            return sticky;
+        }
+
+        let mut sticky = self.limbs[..limbs].iter().any(|&limb| limb != 0);
+        sticky |= bits != 0 && self.limbs[limbs] << (64 - bits) != 0;
+
+        for i in 0..self.len - limbs {
+            let above = if bits != 0 && i + limbs + 1 < self.len {
+                self.limbs[i + limbs + 1] << (64 - bits)
+            } else {
+                0
+            };
+            self.limbs[i] = self.limbs[i + limbs] >> bits | above;
+        }
+        self.limbs[self.len - limbs..self.len]
+            .iter_mut()
+            .for_each(|limb| *limb = 0);
+        self.len -= limbs;
+        self.trim();
+        sticky
+    }
+
+    /// Removes and returns the bits at and above `bits`, which must fit in
+    /// 64 bits.
+    #[inline]
+    pub fn split_off(&mut self, bits: u32) -> u64 {
+        let (limb, shift) = ((bits / 64) as usize, bits % 64);
+        if limb >= self.len {
+            return 0;
+        }
+
+        let mut high = self.limbs[limb] >> shift;
+        if shift != 0 && limb + 1 < self.len {
+            high |= self.limbs[limb + 1] << (64 - shift);
+        }
+
+        self.limbs[limb] &= (1 << shift) - 1;
+        self.limbs[limb + 1..self.len]
+            .iter_mut()
+            .for_each(|limb| *limb = 0);
+        self.len = limb + 1;
+        self.trim();
+        high
+    }
+
+    /// Shifts right until the value fits in 256 bits, returning the
+    /// shifted value, the shift and whether any ones were shifted out.
+    #[inline]
+    pub fn top256(&mut self) -> (U256, u32, bool) {
+        let shift = self.bits().saturating_sub(256);
+        let sticky = self.shr(shift);
+        (self.to_u256(), shift, sticky)
+    }
+
+    /// Returns the value, which must fit in 256 bits.
+    #[inline]
+    pub fn to_u256(&self) -> U256 {
+        debug_assert!(self.len <= 4);
+        U256 {
+            hi: (self.limbs[3] as u128) << 64 | self.limbs[2] as u128,
+            lo: (self.limbs[1] as u128) << 64 | self.limbs[0] as u128,
+        }
+    }
+}
+
+/// Returns `floor(log10(2^exp))`, give or take one, for exponents within
+/// a few hundred thousand of zero.
+#[inline]
+pub(crate) fn log10_pow2(exp: i32) -> i32 {
+    (exp as i64 * 30_103).div_euclid(100_000) as i32
+}
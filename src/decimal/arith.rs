@@ -0,0 +1,224 @@
+//! Decimal arithmetic. Each result is computed exactly, in a `U256` or with
+//! a sticky digit, and rounded once, so it is correctly rounded, with the
+//! quantum IEEE 754 specifies for exact results.
+
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+
+use super::{d128, d32, d64, finish, Format, Value};
+use crate::uint::{digits, U256};
+
+/// The default NaN, the result of invalid operations.
+const INVALID: Value = Value::Nan(false, false, 0);
+
+/// Returns the NaN an operation on `a` and `b` propagates, if either is
+/// NaN: the first signaling one, else the first, quieted.
+#[inline]
+fn propagate(a: Value, b: Value) -> Option<Value> {
+    let nan = match (a, b) {
+        (Value::Nan(_, true, _), _) => a,
+        (_, Value::Nan(_, true, _)) => b,
+        (Value::Nan(..), _) => a,
+        (_, Value::Nan(..)) => b,
+        _ => return None,
+    };
+
+    match nan {
+        Value::Nan(negative, _, payload) => Some(Value::Nan(negative, false, payload)),
+        _ => unreachable!(),
+    }
+}
+
+#[inline]
+fn add(a: Value, b: Value, f: Format) -> Value {
+    if let Some(nan) = propagate(a, b) {
+        return nan;
+    }
+
+    let ((na, ca, ea), (nb, cb, eb)) = match (a, b) {
+        (Value::Infinite(na), Value::Infinite(nb)) if na != nb => return INVALID,
+        (Value::Infinite(_), _) => return a,
+        (_, Value::Infinite(_)) => return b,
+        (Value::Finite(na, ca, ea), Value::Finite(nb, cb, eb)) => ((na, ca, ea), (nb, cb, eb)),
+        _ => unreachable!(),
+    };
+
+    let exp = ea.min(eb);
+
+    // A zero only lowers the exponent of the other operand, as far as
+    // its precision allows
+    if ca == 0 || cb == 0 {
+        let (negative, coeff, e) = match (ca, cb) {
+            (0, 0) => (na && nb, 0, exp),
+            (0, _) => (nb, cb, eb),
+            _ => (na, ca, ea),
+        };
+
+        let shift = ((e - exp) as u32).min(f.digits - digits(coeff));
+        let coeff = U256::new(coeff).mul_pow10(shift);
+        return finish(negative, coeff, e - shift as i32, false, f);
+    }
+
+    // Order the operands by exponent
+    let ((na, ca, ea), (nb, cb, eb)) = if ea >= eb {
+        ((na, ca, ea), (nb, cb, eb))
+    } else {
+        ((nb, cb, eb), (na, ca, ea))
+    };
+
+    // An operand entirely below the rounding digit of the other acts only
+    // as a sticky unit, which keeps the aligned sum within 256 bits
+    let (da, db) = (digits(ca) as i32, digits(cb) as i32);
+    let (cb, eb) = if ea + da > eb + db + f.digits as i32 + 1 {
+        (1, ea + da - f.digits as i32 - 3)
+    } else {
+        (cb, eb)
+    };
+
+    let big = U256::new(ca).mul_pow10((ea - eb) as u32);
+    let small = U256::new(cb);
+
+    let (negative, coeff) = if na == nb {
+        (na, big.add(small))
+    } else if big >= small {
+        (na, big.sub(small))
+    } else {
+        (nb, small.sub(big))
+    };
+
+    // An exact zero sum is positive when rounding to nearest
+    let negative = negative && !coeff.is_zero();
+    finish(negative, coeff, eb, false, f)
+}
+
+#[inline]
+fn mul(a: Value, b: Value, f: Format) -> Value {
+    if let Some(nan) = propagate(a, b) {
+        return nan;
+    }
+
+    let negative = a.is_negative() != b.is_negative();
+    match (a, b) {
+        (Value::Infinite(_), Value::Finite(_, 0, _))
+        | (Value::Finite(_, 0, _), Value::Infinite(_)) => INVALID,
+        (Value::Infinite(_), _) | (_, Value::Infinite(_)) => Value::Infinite(negative),
+        (Value::Finite(_, ca, ea), Value::Finite(_, cb, eb)) => {
+            finish(negative, U256::mul(ca, cb), ea + eb, false, f)
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[inline]
+fn div(a: Value, b: Value, f: Format) -> Value {
+    if let Some(nan) = propagate(a, b) {
+        return nan;
+    }
+
+    let negative = a.is_negative() != b.is_negative();
+    let ((ca, ea), (cb, eb)) = match (a, b) {
+        (Value::Infinite(_), Value::Infinite(_)) => return INVALID,
+        (Value::Infinite(_), _) => return Value::Infinite(negative),
+        (_, Value::Infinite(_)) => return Value::Finite(negative, 0, f.etiny()),
+        (Value::Finite(_, 0, _), Value::Finite(_, 0, _)) => return INVALID,
+        (_, Value::Finite(_, 0, _)) => return Value::Infinite(negative),
+        (Value::Finite(_, ca, ea), Value::Finite(_, cb, eb)) => ((ca, ea), (cb, eb)),
+        _ => unreachable!(),
+    };
+
+    let ideal = ea - eb;
+    if ca == 0 {
+        return finish(negative, U256::ZERO, ideal, false, f);
+    }
+
+    // Scale the dividend for a quotient of at least digits + 1 digits
+    let (da, db) = (digits(ca) as i32, digits(cb) as i32);
+    let scale = (f.digits as i32 + 1 + db - da).max(0) as u32;
+    let (mut q, r) = U256::new(ca).mul_pow10(scale).div_rem(cb);
+    let mut exp = ideal - scale as i32;
+
+    // An exact quotient takes the exponent nearest the ideal one
+    if r == 0 {
+        while exp < ideal {
+            let (next, digit) = q.div_rem(10);
+            if digit != 0 {
+                break;
+            }
+            q = next;
+            exp += 1;
+        }
+    }
+
+    finish(negative, q, exp, r != 0, f)
+}
+
+macro_rules! arith {
+    ($($name:ident($format:ident)),*) => {$(
+        impl Add for $name {
+            type Output = Self;
+
+            #[inline]
+            fn add(self, rhs: Self) -> Self::Output {
+                Self::new(add(self.value(), rhs.value(), super::$format))
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+
+            #[inline]
+            fn sub(self, rhs: Self) -> Self::Output {
+                // Negating a NaN would change the sign it propagates with
+                let rhs = if rhs.is_nan() { rhs } else { -rhs };
+                self + rhs
+            }
+        }
+
+        impl Mul for $name {
+            type Output = Self;
+
+            #[inline]
+            fn mul(self, rhs: Self) -> Self::Output {
+                Self::new(mul(self.value(), rhs.value(), super::$format))
+            }
+        }
+
+        impl Div for $name {
+            type Output = Self;
+
+            #[inline]
+            fn div(self, rhs: Self) -> Self::Output {
+                Self::new(div(self.value(), rhs.value(), super::$format))
+            }
+        }
+
+        impl AddAssign for $name {
+            #[inline]
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl SubAssign for $name {
+            #[inline]
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl MulAssign for $name {
+            #[inline]
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl DivAssign for $name {
+            #[inline]
+            fn div_assign(&mut self, rhs: Self) {
+                *self = *self / rhs;
+            }
+        }
+    )*};
+}
+
+arith!(d32(D32), d64(D64), d128(D128));
@@ -0,0 +1,211 @@
+#![cfg(feature = "casting")]
+
+//! Conversions between the decimal formats and to and from the binary
+//! ones, all rounding to nearest, ties to even.
+//!
+//! Binary and decimal values convert through their exact value, held in a
+//! big integer scaled by a power of two or ten, so every conversion is
+//! correctly rounded. A binary value that is exact in decimal takes the
+//! exponent nearest zero, so `0.5` converts to `5E-1`. NaNs are quieted,
+//! keeping their sign, and their payload if it fits.
+
+use super::{d128, d32, d64, finish, Format, Value, D128, D32, D64};
+use crate::big::{log10_pow2, Big};
+use crate::round::{round_pack, unpack};
+use crate::uint::{digits, pow10, U256};
+use crate::{f128, f16};
+use casting::CastFrom;
+
+/// Limbs enough for any finite `f128` or `d128`, scaled as below.
+const LIMBS: usize = 272;
+
+impl Value {
+    /// Rounds to the binary format with `exp_bits` exponent bits and
+    /// `mant_bits` stored mantissa bits.
+    fn to_binary(self, exp_bits: u32, mant_bits: u32) -> u128 {
+        let inf = ((1 << exp_bits) - 1) << mant_bits;
+        let sign = |negative: bool| (negative as u128) << (exp_bits + mant_bits);
+
+        let (negative, coeff, exp) = match self {
+            Self::Finite(negative, coeff, exp) => (negative, coeff, exp),
+            Self::Infinite(negative) => return sign(negative) | inf,
+            Self::Nan(negative, _, payload) => {
+                let quiet = 1 << (mant_bits - 1);
+                let payload = if payload < quiet { payload } else { 0 };
+                return sign(negative) | inf | quiet | payload;
+            }
+        };
+
+        if coeff == 0 {
+            return sign(negative);
+        }
+
+        // Values of at least 10^max overflow, and those below 10^min are
+        // below half the smallest subnormal
+        let bias = (1 << (exp_bits - 1)) - 1;
+        let max = log10_pow2(bias + 1) + 1;
+        let min = log10_pow2(1 - bias - mant_bits as i32) - 1;
+        let top = digits(coeff) as i32 + exp;
+        if top > max {
+            return sign(negative) | inf;
+        }
+        if top <= min {
+            return sign(negative);
+        }
+
+        let mut big = Big::<LIMBS>::new(U256::new(coeff));
+        let (shift, mut sticky) = if exp >= 0 {
+            big.mul_pow10(exp as u32);
+            (0, false)
+        } else {
+            // Scale by a power of two for a quotient of over 128 bits
+            let bits = (-exp as i64 * 3_322 / 1_000) as i32 + 132 - big.bits() as i32;
+            let scale = bits.max(0) as u32;
+            big.shl(scale);
+            (-(scale as i32), big.div_pow10(-exp as u32))
+        };
+
+        // Keep the top 128 bits, and the rest as sticky
+        let excess = big.bits().saturating_sub(128);
+        sticky |= big.shr(excess);
+        let mant = big.to_u256().lo;
+
+        round_pack(
+            negative,
+            mant,
+            shift + excess as i32,
+            sticky,
+            exp_bits,
+            mant_bits,
+        )
+    }
+
+    /// Converts from the binary format with `exp_bits` exponent bits and
+    /// `mant_bits` stored mantissa bits, rounding to `f`.
+    fn from_binary(bits: u128, exp_bits: u32, mant_bits: u32, f: Format) -> Self {
+        let negative = bits >> (exp_bits + mant_bits) & 1 != 0;
+        let abs = bits & ((1 << (exp_bits + mant_bits)) - 1);
+        let inf = ((1 << exp_bits) - 1) << mant_bits;
+
+        if abs > inf {
+            let payload = abs & ((1 << (mant_bits - 1)) - 1);
+            let payload = if payload < pow10(f.digits - 1) {
+                payload
+            } else {
+                0
+            };
+            return Self::Nan(negative, false, payload);
+        }
+
+        if abs == inf {
+            return Self::Infinite(negative);
+        }
+
+        let (negative, mant, exp) = unpack(bits, exp_bits, mant_bits);
+        if mant == 0 {
+            return Self::Finite(negative, 0, 0);
+        }
+
+        // The value is at least 10^top, within one, so a quotient by
+        // 10^(top - digits - 2) has about digits + 3 digits
+        let top = log10_pow2(128 - mant.leading_zeros() as i32 - 1 + exp);
+        if top > f.emax + 1 {
+            return Self::Infinite(negative);
+        }
+        if top < f.etiny() - 2 {
+            return Self::Finite(negative, 0, f.etiny());
+        }
+
+        let scale = top - f.digits as i32 - 2;
+        let mut big = Big::<LIMBS>::new(U256::new(mant));
+        let mut sticky = false;
+
+        if scale < 0 {
+            big.mul_pow10(-scale as u32);
+        }
+        if exp >= 0 {
+            big.shl(exp as u32);
+        } else {
+            sticky |= big.shr(-exp as u32);
+        }
+        if scale > 0 {
+            sticky |= big.div_pow10(scale as u32);
+        }
+
+        // An exact value takes the exponent nearest zero that it can
+        let (mut coeff, mut exp) = (big.to_u256().lo, scale);
+        while !sticky && exp < 0 && coeff % 10 == 0 {
+            coeff /= 10;
+            exp += 1;
+        }
+
+        finish(negative, U256::new(coeff), exp, sticky, f)
+    }
+
+    /// Rounds to the decimal format `f`.
+    #[inline]
+    fn to_decimal(self, f: Format) -> Self {
+        match self {
+            Self::Finite(negative, coeff, exp) => finish(negative, U256::new(coeff), exp, false, f),
+            Self::Infinite(_) => self,
+            Self::Nan(negative, _, payload) => {
+                let payload = if payload < pow10(f.digits - 1) {
+                    payload
+                } else {
+                    0
+                };
+                Self::Nan(negative, false, payload)
+            }
+        }
+    }
+}
+
+macro_rules! binary {
+    (@ $float:ty, $bits:ty, $exp_bits:literal, $mant_bits:literal, $($decimal:ident($format:ident)),*) => {$(
+        impl CastFrom<$float> for $decimal {
+            #[inline]
+            fn cast_from(value: $float) -> $decimal {
+                let bits = value.to_bits() as u128;
+                $decimal::new(Value::from_binary(bits, $exp_bits, $mant_bits, $format))
+            }
+        }
+
+        impl CastFrom<$decimal> for $float {
+            #[inline]
+            fn cast_from(value: $decimal) -> $float {
+                <$float>::from_bits(value.value().to_binary($exp_bits, $mant_bits) as $bits)
+            }
+        }
+    )*};
+
+    ($($float:ty: $bits:ty, $exp_bits:literal, $mant_bits:literal;)*) => {$(
+        binary!(@ $float, $bits, $exp_bits, $mant_bits, d32(D32), d64(D64), d128(D128));
+    )*};
+}
+
+binary! {
+    f16: u16, 5, 10;
+    f32: u32, 8, 23;
+    f64: u64, 11, 52;
+    f128: u128, 15, 112;
+}
+
+macro_rules! convert {
+    ($($from:ident => $to:ident($format:ident);)*) => {$(
+        impl CastFrom<$from> for $to {
+            #[inline]
+            fn cast_from(value: $from) -> $to {
+                $to::new(value.value().to_decimal($format))
+            }
+        }
+    )*};
+}
+
+convert! {
+    d32 => d64(D64);
+    d32 => d128(D128);
+    d64 => d32(D32);
+    d64 => d128(D128);
+    d128 => d32(D32);
+    d128 => d64(D64);
+}
@@ -0,0 +1,47 @@
+//! Densely packed decimal: three digits in each 10-bit declet.
+
+/// Decodes a declet into its value, 0 to 999. Every one of the 1024
+/// declets decodes, the 24 redundant ones to the value of their canonical
+/// form.
+#[inline]
+pub(crate) const fn decode(declet: u32) -> u32 {
+    let (b0, b4, b7) = (declet & 1, declet >> 4 & 1, declet >> 7 & 1);
+    let (hi, mid, lo) = (declet >> 7 & 7, declet >> 4 & 7, declet & 7);
+
+    let (d2, d1, d0) = if declet & 8 == 0 {
+        (hi, mid, lo)
+    } else {
+        match declet >> 1 & 3 {
+            0 => (hi, mid, 8 | b0),
+            1 => (hi, 8 | b4, declet >> 4 & 6 | b0),
+            2 => (8 | b7, mid, declet >> 7 & 6 | b0),
+            _ => match declet >> 5 & 3 {
+                0 => (8 | b7, 8 | b4, declet >> 7 & 6 | b0),
+                1 => (8 | b7, declet >> 7 & 6 | b4, 8 | b0),
+                2 => (hi, 8 | b4, 8 | b0),
+                _ => (8 | b7, 8 | b4, 8 | b0),
+            },
+        }
+    };
+
+    d2 * 100 + d1 * 10 + d0
+}
+
+/// Encodes a value below 1000 into its canonical declet.
+#[inline]
+pub(crate) const fn encode(value: u32) -> u32 {
+    let (d2, d1, d0) = (value / 100, value / 10 % 10, value % 10);
+    let (a, b, c) = (d2 & 7, d1 & 7, d0 & 7);
+    let (i, m, y) = (d2 & 1, d1 & 1, d0 & 1);
+
+    match (d2 > 7, d1 > 7, d0 > 7) {
+        (false, false, false) => a << 7 | b << 4 | c,
+        (false, false, true) => a << 7 | b << 4 | 0b1000 | y,
+        (false, true, false) => a << 7 | (c & 6) << 4 | m << 4 | 0b1010 | y,
+        (true, false, false) => (c & 6) << 7 | i << 7 | b << 4 | 0b1100 | y,
+        (true, true, false) => (c & 6) << 7 | i << 7 | m << 4 | 0b0001110 | y,
+        (true, false, true) => (b & 6) << 7 | i << 7 | 0b0101110 | m << 4 | y,
+        (false, true, true) => a << 7 | 0b1001110 | m << 4 | y,
+        (true, true, true) => i << 7 | 0b1101110 | m << 4 | y,
+    }
+}
@@ -0,0 +1,241 @@
+//! Decimal strings, in the syntax of IEEE 754 and the General Decimal
+//! Arithmetic specification.
+
+use core::fmt::{self, Write};
+use core::str::FromStr;
+
+use super::{d128, d32, d64, finish, Format, Value};
+use crate::uint::{pow10, U256};
+
+/// The error returned when parsing a decimal fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDecimalError(());
+
+impl fmt::Display for ParseDecimalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid decimal literal")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseDecimalError {}
+
+/// Parses a run of ASCII digits, returning `None` if it is empty, has
+/// other characters, or is not below `limit`.
+#[inline]
+fn integer(bytes: &[u8], limit: u128) -> Option<u128> {
+    if bytes.is_empty() {
+        return None;
+    }
+
+    let mut value: u128 = 0;
+    for &byte in bytes {
+        if !byte.is_ascii_digit() {
+            return None;
+        }
+        value = value.checked_mul(10)?.checked_add((byte - b'0') as u128)?;
+        if value >= limit {
+            return None;
+        }
+    }
+
+    Some(value)
+}
+
+/// Parses `s`, rounding to nearest, ties to even.
+fn parse(s: &str, f: Format) -> Result<Value, ParseDecimalError> {
+    let error = ParseDecimalError(());
+    let (negative, rest) = match s.as_bytes() {
+        [b'-', rest @ ..] => (true, rest),
+        [b'+', rest @ ..] => (false, rest),
+        rest => (false, rest),
+    };
+
+    let lower = |prefix: &[u8]| {
+        rest.len() >= prefix.len() && rest[..prefix.len()].eq_ignore_ascii_case(prefix)
+    };
+
+    if rest.eq_ignore_ascii_case(b"inf") || rest.eq_ignore_ascii_case(b"infinity") {
+        return Ok(Value::Infinite(negative));
+    }
+
+    // NaNs may carry a payload of fewer digits than the precision
+    for &(prefix, signaling) in &[(&b"nan"[..], false), (&b"snan"[..], true)] {
+        if lower(prefix) {
+            let digits = &rest[prefix.len()..];
+            let payload = if digits.is_empty() {
+                0
+            } else {
+                integer(digits, pow10(f.digits - 1)).ok_or(error.clone())?
+            };
+            return Ok(Value::Nan(negative, signaling, payload));
+        }
+    }
+
+    // Keep enough digits to round once, and note any others as sticky
+    let limit = U256::pow10(76);
+    let mut coeff = U256::ZERO;
+    let mut exp: i64 = 0;
+    let mut sticky = false;
+    let mut seen = false;
+    let mut point = false;
+    let mut i = 0;
+
+    while i < rest.len() {
+        match rest[i] {
+            b'.' if !point => point = true,
+            byte @ b'0'..=b'9' => {
+                seen = true;
+                let digit = (byte - b'0') as u128;
+                if coeff < limit {
+                    coeff = coeff.mul_u128(10).add(U256::new(digit));
+                    exp -= point as i64;
+                } else {
+                    sticky |= digit != 0;
+                    exp += !point as i64;
+                }
+            }
+            _ => break,
+        }
+        i += 1;
+    }
+
+    if !seen {
+        return Err(error);
+    }
+
+    if i < rest.len() {
+        let (first, digits) = rest[i + 1..].split_first().ok_or(error.clone())?;
+        if !rest[i].eq_ignore_ascii_case(&b'e') {
+            return Err(error);
+        }
+
+        let (negative, digits) = match first {
+            b'-' => (true, digits),
+            b'+' => (false, digits),
+            _ => (false, &rest[i + 1..]),
+        };
+
+        // Exponents beyond any format saturate
+        if digits.is_empty() || !digits.iter().all(u8::is_ascii_digit) {
+            return Err(error);
+        }
+        let value = digits
+            .iter()
+            .fold(0i64, |n, &b| (n * 10 + (b - b'0') as i64).min(1 << 40));
+        exp += if negative { -value } else { value };
+    }
+
+    let exp = exp.clamp(-(1 << 30), 1 << 30) as i32;
+    Ok(finish(negative, coeff, exp, sticky, f))
+}
+
+/// A stack buffer for formatting one decimal.
+struct Buffer {
+    bytes: [u8; 64],
+    len: usize,
+}
+
+impl Write for Buffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        self.bytes
+            .get_mut(self.len..end)
+            .ok_or(fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+#[inline]
+fn text(bytes: &[u8]) -> Result<&str, fmt::Error> {
+    core::str::from_utf8(bytes).map_err(|_| fmt::Error)
+}
+
+/// Writes `value` as the to-scientific-string of the specification, which
+/// keeps the exponent: plain notation if the exponent is not positive and
+/// the adjusted exponent is at least -6, or else exponential notation with
+/// one digit before the point.
+fn write(value: Value, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let mut buffer = Buffer {
+        bytes: [0; 64],
+        len: 0,
+    };
+
+    let (negative, coeff, exp) = match value {
+        Value::Finite(negative, coeff, exp) => (negative, coeff, exp),
+        Value::Infinite(negative) => return f.pad_integral(!negative, "", "Infinity"),
+        Value::Nan(negative, signaling, payload) => {
+            buffer.write_str(if signaling { "sNaN" } else { "NaN" })?;
+            if payload != 0 {
+                write!(buffer, "{}", payload)?;
+            }
+            return f.pad_integral(!negative, "", text(&buffer.bytes[..buffer.len])?);
+        }
+    };
+
+    let mut digits = Buffer {
+        bytes: [0; 64],
+        len: 0,
+    };
+    write!(digits, "{}", coeff)?;
+    let digits = &digits.bytes[..digits.len];
+
+    let adjusted = exp + digits.len() as i32 - 1;
+    if exp <= 0 && adjusted >= -6 {
+        let point = digits.len() as i32 + exp;
+        if exp == 0 {
+            buffer.write_str(text(digits)?)?;
+        } else if point > 0 {
+            let (int, frac) = digits.split_at(point as usize);
+            write!(buffer, "{}.{}", text(int)?, text(frac)?)?;
+        } else {
+            buffer.write_str("0.")?;
+            for _ in point..0 {
+                buffer.write_char('0')?;
+            }
+            buffer.write_str(text(digits)?)?;
+        }
+    } else {
+        let (int, frac) = digits.split_at(1);
+        buffer.write_str(text(int)?)?;
+        if !frac.is_empty() {
+            write!(buffer, ".{}", text(frac)?)?;
+        }
+        write!(buffer, "E{:+}", adjusted)?;
+    }
+
+    f.pad_integral(!negative, "", text(&buffer.bytes[..buffer.len])?)
+}
+
+macro_rules! strings {
+    ($($name:ident($format:ident)),*) => {$(
+        impl FromStr for $name {
+            type Err = ParseDecimalError;
+
+            /// Parses a decimal, such as `-1.25`, `1.25E-3`, `Infinity`,
+            /// `NaN` or `sNaN` with an optional payload, ignoring case, and
+            /// rounds it to nearest, ties to even.
+            ///
+            /// The digits determine the exponent: `1.50` has the exponent
+            /// -2, and keeps it when printed.
+            #[inline]
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                parse(s, super::$format).map(Self::new)
+            }
+        }
+
+        impl fmt::Display for $name {
+            /// Writes the to-scientific-string of the General Decimal
+            /// Arithmetic specification, which reads back with the same
+            /// exponent.
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write(self.value(), f)
+            }
+        }
+    )*};
+}
+
+strings!(d32(D32), d64(D64), d128(D128));
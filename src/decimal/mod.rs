@@ -0,0 +1,541 @@
+//! IEEE 754 decimal floating point, stored in the binary integer decimal
+//! (BID) encoding.
+
+use core::cmp::Ordering;
+use core::ops::Neg;
+
+mod arith;
+mod cast;
+mod dpd;
+mod fmt;
+
+pub use fmt::ParseDecimalError;
+
+use crate::uint::{digits, pow10, U256};
+
+/// The parameters of a decimal interchange format.
+#[derive(Clone, Copy)]
+pub(crate) struct Format {
+    /// The precision in digits.
+    digits: u32,
+
+    /// The largest adjusted exponent.
+    emax: i32,
+
+    /// The exponent continuation bits.
+    w: u32,
+
+    /// The trailing significand bits.
+    t: u32,
+}
+
+const D32: Format = Format {
+    digits: 7,
+    emax: 96,
+    w: 6,
+    t: 20,
+};
+
+const D64: Format = Format {
+    digits: 16,
+    emax: 384,
+    w: 8,
+    t: 50,
+};
+
+const D128: Format = Format {
+    digits: 34,
+    emax: 6144,
+    w: 12,
+    t: 110,
+};
+
+impl Format {
+    #[inline]
+    const fn bits(self) -> u32 {
+        6 + self.w + self.t
+    }
+
+    /// The exponent of the smallest quantum, that of the subnormals.
+    #[inline]
+    const fn etiny(self) -> i32 {
+        2 - self.emax - self.digits as i32
+    }
+
+    /// The exponent of the largest quantum.
+    #[inline]
+    const fn qmax(self) -> i32 {
+        self.emax - self.digits as i32 + 1
+    }
+
+    #[inline]
+    const fn sign(self) -> u128 {
+        1 << (self.bits() - 1)
+    }
+
+    /// Returns the combination field prefix `prefix` of `len` bits, placed
+    /// after the sign.
+    #[inline]
+    const fn prefix(self, prefix: u128, len: u32) -> u128 {
+        prefix << (self.bits() - 1 - len)
+    }
+}
+
+/// A decoded value.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum Value {
+    /// `(-1)^negative * coeff * 10^exp`
+    Finite(bool, u128, i32),
+    Infinite(bool),
+    /// A NaN with its sign, whether it signals, and its payload.
+    Nan(bool, bool, u128),
+}
+
+impl Value {
+    #[inline]
+    const fn is_negative(self) -> bool {
+        match self {
+            Self::Finite(negative, ..) | Self::Infinite(negative) | Self::Nan(negative, ..) => {
+                negative
+            }
+        }
+    }
+
+    /// Decodes BID `bits`. Non-canonical coefficients and payloads, those
+    /// beyond the precision, read as zero.
+    #[inline]
+    const fn from_bid(bits: u128, f: Format) -> Self {
+        let negative = bits & f.sign() != 0;
+        let combination = bits >> (f.bits() - 6) & 0x1f;
+        let exp_mask = (1 << (f.w + 2)) - 1;
+
+        if combination == 0x1e {
+            return Self::Infinite(negative);
+        }
+
+        if combination == 0x1f {
+            let signaling = bits >> (f.bits() - 7) & 1 != 0;
+            let payload = bits & ((1 << f.t) - 1);
+            let payload = if payload < pow10(f.digits - 1) {
+                payload
+            } else {
+                0
+            };
+            return Self::Nan(negative, signaling, payload);
+        }
+
+        let (exp, coeff) = if combination >> 3 != 3 {
+            (bits >> (f.t + 3) & exp_mask, bits & ((1 << (f.t + 3)) - 1))
+        } else {
+            let coeff = 0b100 << (f.t + 1) | bits & ((1 << (f.t + 1)) - 1);
+            (bits >> (f.t + 1) & exp_mask, coeff)
+        };
+
+        let coeff = if coeff < pow10(f.digits) { coeff } else { 0 };
+        Self::Finite(negative, coeff, exp as i32 + f.etiny())
+    }
+
+    /// Encodes as BID. Finite values must be representable, and payloads
+    /// below 10^(digits - 1).
+    #[inline]
+    const fn to_bid(self, f: Format) -> u128 {
+        match self {
+            Self::Finite(negative, coeff, exp) => {
+                let sign = if negative { f.sign() } else { 0 };
+                let exp = (exp - f.etiny()) as u128;
+
+                if coeff >> (f.t + 3) == 0 {
+                    sign | exp << (f.t + 3) | coeff
+                } else {
+                    let coeff = coeff & ((1 << (f.t + 1)) - 1);
+                    sign | f.prefix(3, 2) | exp << (f.t + 1) | coeff
+                }
+            }
+            Self::Infinite(negative) => {
+                let sign = if negative { f.sign() } else { 0 };
+                sign | f.prefix(0x1e, 5)
+            }
+            Self::Nan(negative, signaling, payload) => {
+                let sign = if negative { f.sign() } else { 0 };
+                sign | f.prefix(0x3e | signaling as u128, 6) | payload
+            }
+        }
+    }
+
+    /// Decodes the declets of the trailing significand.
+    #[inline]
+    const fn declets(bits: u128, f: Format) -> u128 {
+        let mut value = 0;
+        let mut shift = f.t;
+        while shift > 0 {
+            shift -= 10;
+            value = value * 1000 + dpd::decode((bits >> shift) as u32 & 0x3ff) as u128;
+        }
+        value
+    }
+
+    /// Decodes DPD `bits`. Every encoding is canonical but for the
+    /// redundant declets, which read as their canonical values.
+    #[inline]
+    const fn from_dpd(bits: u128, f: Format) -> Self {
+        let negative = bits & f.sign() != 0;
+        let combination = (bits >> (f.bits() - 6) & 0x1f) as u32;
+        let trailing = Self::declets(bits, f);
+
+        if combination == 0x1e {
+            return Self::Infinite(negative);
+        }
+
+        if combination == 0x1f {
+            let signaling = bits >> (f.bits() - 7) & 1 != 0;
+            return Self::Nan(negative, signaling, trailing);
+        }
+
+        let (msbs, lead) = if combination >> 3 != 3 {
+            (combination >> 3, combination & 7)
+        } else {
+            (combination >> 1 & 3, 8 | combination & 1)
+        };
+
+        let exp = (msbs as u128) << f.w | bits >> f.t & ((1 << f.w) - 1);
+        let coeff = lead as u128 * pow10(f.digits - 1) + trailing;
+        Self::Finite(negative, coeff, exp as i32 + f.etiny())
+    }
+
+    /// Encodes as DPD, with the same requirements as BID.
+    #[inline]
+    const fn to_dpd(self, f: Format) -> u128 {
+        let (negative, combination, exp, trailing) = match self {
+            Self::Finite(negative, coeff, exp) => {
+                let exp = (exp - f.etiny()) as u128;
+                let lead = coeff / pow10(f.digits - 1);
+                let msbs = exp >> f.w;
+                let combination = if lead < 8 {
+                    msbs << 3 | lead
+                } else {
+                    0x18 | msbs << 1 | lead & 1
+                };
+                let exp = exp & ((1 << f.w) - 1);
+                (negative, combination, exp, coeff % pow10(f.digits - 1))
+            }
+            Self::Infinite(negative) => (negative, 0x1e, 0, 0),
+            Self::Nan(negative, signaling, payload) => {
+                (negative, 0x1f, (signaling as u128) << (f.w - 1), payload)
+            }
+        };
+
+        let mut bits = 0;
+        let mut rest = trailing;
+        let mut shift = 0;
+        while shift < f.t {
+            bits |= (dpd::encode((rest % 1000) as u32) as u128) << shift;
+            rest /= 1000;
+            shift += 10;
+        }
+
+        let sign = if negative { f.sign() } else { 0 };
+        sign | f.prefix(combination, 5) | exp << f.t | bits
+    }
+
+    /// Compares by value: NaNs are unordered, and zeros of either sign and
+    /// any exponent are equal.
+    #[inline]
+    fn partial_cmp(self, other: Self) -> Option<Ordering> {
+        let magnitude = |v: Self| match v {
+            Self::Finite(_, 0, _) => Some(0),
+            Self::Finite(..) => Some(1),
+            Self::Infinite(..) => Some(2),
+            Self::Nan(..) => None,
+        };
+
+        let (a, b) = (magnitude(self)?, magnitude(other)?);
+        let sign = |v: Self, m: i32| if v.is_negative() { -m } else { m };
+        let (sa, sb) = (sign(self, a), sign(other, b));
+        if sa.signum() != sb.signum() || a != 1 || b != 1 {
+            return Some(sa.cmp(&sb));
+        }
+
+        // Both finite and nonzero, of the same sign
+        let (ca, ea, cb, eb) = match (self, other) {
+            (Self::Finite(_, ca, ea), Self::Finite(_, cb, eb)) => (ca, ea, cb, eb),
+            _ => unreachable!(),
+        };
+
+        let (aa, ab) = (ea + digits(ca) as i32, eb + digits(cb) as i32);
+        let order = if aa != ab {
+            aa.cmp(&ab)
+        } else if ea >= eb {
+            (ca * pow10((ea - eb) as u32)).cmp(&cb)
+        } else {
+            ca.cmp(&(cb * pow10((eb - ea) as u32)))
+        };
+
+        Some(if sa < 0 { order.reverse() } else { order })
+    }
+}
+
+/// Rounds `(-1)^negative * (coeff + sticky) * 10^exp` to nearest, ties to
+/// even, where `sticky` is a nonzero fraction of a unit well below one
+/// half. A value that is inexact in this way must have more digits than
+/// the precision.
+///
+/// Exact results keep their exponent as near to `exp` as the format
+/// allows, so that arithmetic yields the quantum IEEE 754 specifies.
+#[inline]
+pub(crate) fn finish(negative: bool, coeff: U256, exp: i32, sticky: bool, f: Format) -> Value {
+    let n = coeff.digits() as i32;
+    let drop = (n - f.digits as i32).max(f.etiny() - exp).max(0);
+
+    let (coeff, exp) = if drop == 0 {
+        (coeff.lo, exp)
+    } else if drop > n {
+        (0, exp + drop)
+    } else {
+        let (q, r) = coeff.div_rem_pow10(drop as u32);
+        let half = U256::pow10(drop as u32 - 1).mul_u128(5);
+        let odd = q.lo & 1 != 0;
+        let up = r > half || (r == half && (sticky || odd));
+        let q = if up { q.lo + 1 } else { q.lo };
+
+        if q == pow10(f.digits) {
+            (q / 10, exp + drop + 1)
+        } else {
+            (q, exp + drop)
+        }
+    };
+
+    if coeff == 0 {
+        return Value::Finite(negative, 0, exp.clamp(f.etiny(), f.qmax()));
+    }
+
+    if exp > f.qmax() {
+        if exp + digits(coeff) as i32 - 1 > f.emax {
+            return Value::Infinite(negative);
+        }
+
+        // Fold the exponent into the coefficient, which has room for it
+        return Value::Finite(negative, coeff * pow10((exp - f.qmax()) as u32), f.qmax());
+    }
+
+    Value::Finite(negative, coeff, exp)
+}
+
+macro_rules! decimal {
+    ($(#[$meta:meta])* $name:ident($bits:ty, $bytes:literal, $format:ident)) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy)]
+        #[allow(non_camel_case_types)]
+        #[repr(transparent)]
+        pub struct $name($bits);
+
+        impl Default for $name {
+            /// Returns positive zero with an exponent of zero.
+            #[inline]
+            fn default() -> Self {
+                Self::from_parts(false, 0, 0)
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+
+            #[inline]
+            fn neg(self) -> Self::Output {
+                Self(self.0 ^ $format.sign() as $bits)
+            }
+        }
+
+        impl PartialEq for $name {
+            /// Compares by value, so that members of a cohort, which differ
+            /// only in exponent, are equal.
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                self.partial_cmp(other) == Some(Ordering::Equal)
+            }
+        }
+
+        impl PartialOrd for $name {
+            #[inline]
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                self.value().partial_cmp(other.value())
+            }
+        }
+
+        impl $name {
+            pub const NAN: Self = Self::new(Value::Nan(false, false, 0));
+
+            pub const MIN: Self = Self::new(Value::Finite(
+                true,
+                pow10($format.digits) - 1,
+                $format.qmax(),
+            ));
+            pub const MAX: Self = Self::new(Value::Finite(
+                false,
+                pow10($format.digits) - 1,
+                $format.qmax(),
+            ));
+
+            /// The smallest positive normal value, 10^(1 - `MAX_EXP`).
+            pub const MIN_POSITIVE: Self = Self::new(Value::Finite(false, 1, 1 - $format.emax));
+
+            pub const INFINITY: Self = Self::new(Value::Infinite(false));
+            pub const NEG_INFINITY: Self = Self::new(Value::Infinite(true));
+
+            /// The precision in decimal digits.
+            pub const DIGITS: u32 = $format.digits;
+
+            /// The largest adjusted exponent, the exponent of `MAX` in
+            /// scientific notation.
+            pub const MAX_EXP: i32 = $format.emax;
+
+            #[inline]
+            const fn new(value: Value) -> Self {
+                Self(value.to_bid($format) as $bits)
+            }
+
+            #[inline]
+            const fn value(self) -> Value {
+                Value::from_bid(self.0 as u128, $format)
+            }
+
+            /// Returns `(-1)^negative * coefficient * 10^exponent`, rounded
+            /// to nearest, ties to even, if it has too many digits.
+            ///
+            /// An exact value keeps its exponent, or the one nearest to it
+            /// that the format allows.
+            #[inline]
+            pub fn from_parts(negative: bool, coefficient: u128, exponent: i32) -> Self {
+                Self::new(finish(negative, U256::new(coefficient), exponent, false, $format))
+            }
+
+            /// Returns the sign, coefficient and exponent of a finite value.
+            #[inline]
+            pub const fn to_parts(self) -> Option<(bool, u128, i32)> {
+                match self.value() {
+                    Value::Finite(negative, coeff, exp) => Some((negative, coeff, exp)),
+                    _ => None,
+                }
+            }
+
+            #[inline]
+            pub const fn is_nan(self) -> bool {
+                matches!(self.value(), Value::Nan(..))
+            }
+
+            #[inline]
+            pub const fn is_signaling_nan(self) -> bool {
+                matches!(self.value(), Value::Nan(_, true, _))
+            }
+
+            #[inline]
+            pub const fn is_infinite(self) -> bool {
+                matches!(self.value(), Value::Infinite(..))
+            }
+
+            #[inline]
+            pub const fn is_finite(self) -> bool {
+                matches!(self.value(), Value::Finite(..))
+            }
+
+            #[inline]
+            pub const fn is_sign_positive(self) -> bool {
+                !self.is_sign_negative()
+            }
+
+            #[inline]
+            pub const fn is_sign_negative(self) -> bool {
+                self.0 as u128 & $format.sign() != 0
+            }
+
+            /// Reads BID `bits`, keeping them as they are.
+            #[inline]
+            pub const fn from_bits(bits: $bits) -> Self {
+                Self(bits)
+            }
+
+            #[inline]
+            pub const fn from_be_bytes(bytes: [u8; $bytes]) -> Self {
+                Self(<$bits>::from_be_bytes(bytes))
+            }
+
+            #[inline]
+            pub const fn from_le_bytes(bytes: [u8; $bytes]) -> Self {
+                Self(<$bits>::from_le_bytes(bytes))
+            }
+
+            #[inline]
+            pub const fn from_ne_bytes(bytes: [u8; $bytes]) -> Self {
+                Self(<$bits>::from_ne_bytes(bytes))
+            }
+
+            /// Returns the BID bits.
+            #[inline]
+            pub const fn to_bits(self) -> $bits {
+                self.0
+            }
+
+            #[inline]
+            pub const fn to_be_bytes(self) -> [u8; $bytes] {
+                self.0.to_be_bytes()
+            }
+
+            #[inline]
+            pub const fn to_le_bytes(self) -> [u8; $bytes] {
+                self.0.to_le_bytes()
+            }
+
+            #[inline]
+            pub const fn to_ne_bytes(self) -> [u8; $bytes] {
+                self.0.to_ne_bytes()
+            }
+
+            /// Reads densely packed decimal (DPD) `bits`, the encoding of
+            /// IBM POWER and z/Architecture, re-encoding them as BID.
+            ///
+            /// Both encodings represent the same values, so the conversion
+            /// is exact.
+            #[inline]
+            pub const fn from_dpd(bits: $bits) -> Self {
+                Self::new(Value::from_dpd(bits as u128, $format))
+            }
+
+            /// Returns the densely packed decimal (DPD) encoding.
+            ///
+            /// Non-canonical BID coefficients and payloads are encoded as
+            /// zero, the value they have.
+            #[inline]
+            pub const fn to_dpd(self) -> $bits {
+                self.value().to_dpd($format) as $bits
+            }
+        }
+    };
+}
+
+decimal! {
+    /// 32-bit IEEE 754 decimal floating point type (decimal32), with 7
+    /// digits and exponents from -95 to 96.
+    ///
+    /// Values are stored in the binary integer decimal (BID) encoding, and
+    /// can be read from or written to the densely packed decimal (DPD) one.
+    /// Arithmetic is correctly rounded, ties to even, and gives the quantum,
+    /// the exponent of the coefficient, IEEE 754 specifies: `1.20 + 1.3`
+    /// is `2.50`. Equality is by value, so members of a cohort compare
+    /// equal while their bits differ.
+    d32(u32, 4, D32)
+}
+
+decimal! {
+    /// 64-bit IEEE 754 decimal floating point type (decimal64), with 16
+    /// digits and exponents from -383 to 384.
+    ///
+    /// It otherwise behaves as [`d32`] does.
+    d64(u64, 8, D64)
+}
+
+decimal! {
+    /// 128-bit IEEE 754 decimal floating point type (decimal128), with 34
+    /// digits and exponents from -6143 to 6144.
+    ///
+    /// It otherwise behaves as [`d32`] does.
+    d128(u128, 16, D128)
+}
@@ -0,0 +1,115 @@
+//! Double-double arithmetic, following Joldes, Muller and Popescu, "Tight
+//! and rigorous error bounds for basic building blocks of double-word
+//! arithmetic" (2017). The relative errors of addition, multiplication and
+//! division are within 3u², 7u² and 15u², up to terms in u³, where
+//! u = 2^-53, barring overflow and underflow.
+
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use super::DoubleDouble;
+
+impl DoubleDouble {
+    /// Returns `self` if it is finite, or else the `f64` result of the
+    /// operation on the high parts, whose error terms are meaningless.
+    #[inline]
+    fn or_else(self, hi: f64) -> Self {
+        if self.is_finite() {
+            self
+        } else {
+            Self::new(hi, 0.0)
+        }
+    }
+
+    /// Multiplies by a single `f64` (DWTimesFP1), within 1.5u² + 4u³.
+    #[inline]
+    fn mul_f64(self, rhs: f64) -> Self {
+        let c = Self::two_prod(self.hi, rhs);
+        let t = Self::fast_two_sum(c.hi, self.lo * rhs);
+        Self::fast_two_sum(t.hi, t.lo + c.lo)
+    }
+}
+
+impl Neg for DoubleDouble {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Self::new(-self.hi, -self.lo)
+    }
+}
+
+impl Add for DoubleDouble {
+    type Output = Self;
+
+    /// AccurateDWPlusDW.
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        let s = Self::two_sum(self.hi, rhs.hi);
+        let t = Self::two_sum(self.lo, rhs.lo);
+        let v = Self::fast_two_sum(s.hi, s.lo + t.hi);
+        Self::fast_two_sum(v.hi, t.lo + v.lo).or_else(self.hi + rhs.hi)
+    }
+}
+
+impl Sub for DoubleDouble {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Mul for DoubleDouble {
+    type Output = Self;
+
+    /// DWTimesDW1: the cross terms are summed in plain `f64`.
+    #[inline]
+    fn mul(self, rhs: Self) -> Self::Output {
+        let c = Self::two_prod(self.hi, rhs.hi);
+        let t = self.hi * rhs.lo + self.lo * rhs.hi;
+        Self::fast_two_sum(c.hi, c.lo + t).or_else(self.hi * rhs.hi)
+    }
+}
+
+impl Div for DoubleDouble {
+    type Output = Self;
+
+    /// DWDivDW2: one correction of the quotient of the high parts, whose
+    /// product with `rhs` is DWTimesFP1 as the bound requires.
+    #[inline]
+    fn div(self, rhs: Self) -> Self::Output {
+        let hi = self.hi / rhs.hi;
+        let r = rhs.mul_f64(hi);
+        let d = (self.hi - r.hi) + (self.lo - r.lo);
+        Self::fast_two_sum(hi, d / rhs.hi).or_else(hi)
+    }
+}
+
+impl AddAssign for DoubleDouble {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for DoubleDouble {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign for DoubleDouble {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl DivAssign for DoubleDouble {
+    #[inline]
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
@@ -0,0 +1,119 @@
+#![cfg(feature = "casting")]
+
+//! Conversions to and from `f128`, computed on the bits so that they are
+//! the same with the custom and the nightly `f128`.
+
+use super::DoubleDouble;
+use crate::f128;
+use crate::round::{round_pack, unpack};
+use casting::CastFrom;
+
+/// Headroom for the larger part when summing, so that the sum keeps 14 bits
+/// below the 113 that are rounded to.
+const SHIFT: i32 = 74;
+
+/// Widens an infinite or NaN `f64`, quieting NaNs and keeping their sign
+/// and payload.
+#[inline]
+fn widen_special(bits: u64) -> u128 {
+    let sign = ((bits >> 63) as u128) << 127;
+    let mant = (bits & ((1 << 52) - 1)) as u128;
+    let quiet = if mant == 0 { 0 } else { 1 << 111 };
+    sign | 0x7fff << 112 | quiet | mant << 60
+}
+
+impl CastFrom<DoubleDouble> for f128 {
+    /// Returns `hi + lo` rounded once to `f128`, which is exact whenever the
+    /// sum fits in 113 bits, such as for every pair split from an `f128`.
+    #[inline]
+    fn cast_from(value: DoubleDouble) -> f128 {
+        let (hi, lo) = (value.hi.to_bits(), value.lo.to_bits());
+
+        if !value.hi.is_finite() {
+            return f128::from_bits(widen_special(hi));
+        } else if !value.lo.is_finite() {
+            return f128::from_bits(widen_special(lo));
+        }
+
+        let h = unpack(hi as u128, 11, 52);
+        let l = unpack(lo as u128, 11, 52);
+
+        let (sign, mant, exp) = if l.1 == 0 {
+            // The sum of zeros is only negative if both are
+            (h.0 && (h.1 != 0 || l.0), h.1, h.2)
+        } else if h.1 == 0 {
+            l
+        } else {
+            // Align the part with the smaller exponent below the other,
+            // keeping any bits shifted out as a sticky bit
+            let (a, b) = if h.2 >= l.2 { (h, l) } else { (l, h) };
+            let shift = (a.2 - b.2 - SHIFT) as u32;
+            let am = a.1 << SHIFT;
+            let bm = if b.2 + SHIFT >= a.2 {
+                b.1 << (b.2 + SHIFT - a.2)
+            } else if shift < 128 {
+                b.1 >> shift | (b.1 & ((1 << shift) - 1) != 0) as u128
+            } else {
+                1
+            };
+
+            if a.0 == b.0 {
+                (a.0, am + bm, a.2 - SHIFT)
+            } else if am >= bm {
+                (a.0 && am != bm, am - bm, a.2 - SHIFT)
+            } else {
+                (b.0, bm - am, a.2 - SHIFT)
+            }
+        };
+
+        f128::from_bits(round_pack(sign, mant, exp, false, 15, 112))
+    }
+}
+
+impl CastFrom<f128> for DoubleDouble {
+    /// Splits `value` into `hi`, its nearest `f64`, and `lo`, the nearest
+    /// `f64` to the remainder. The split is exact whenever the value fits
+    /// in a pair, which includes every sum of two `f64`; otherwise `lo`
+    /// drops the last few bits. Values beyond the `f64` range become
+    /// infinite, and NaNs keep their sign and top payload bits.
+    #[inline]
+    fn cast_from(value: f128) -> DoubleDouble {
+        let bits = value.to_bits();
+        let sign = (bits >> 127) as u64;
+        let abs = bits & (u128::MAX >> 1);
+
+        if abs >= 0x7fff << 112 {
+            let mant = (abs & ((1 << 112) - 1)) >> 60;
+            let quiet = if mant == 0 && abs == 0x7fff << 112 {
+                0
+            } else {
+                1 << 51
+            };
+            let hi = sign << 63 | 0x7ff << 52 | quiet | mant as u64;
+            return DoubleDouble::new(f64::from_bits(hi), 0.0);
+        }
+
+        let (negative, mant, exp) = unpack(bits, 15, 112);
+        let hi = round_pack(negative, mant, exp, false, 11, 52);
+        let (_, hm, he) = unpack(hi, 11, 52);
+        let hi = f64::from_bits(hi as u64);
+
+        if hi.is_infinite() {
+            return DoubleDouble::new(hi, 0.0);
+        } else if hm == 0 {
+            // Too small for either part, which are zeros of its sign
+            return DoubleDouble::new(hi, hi);
+        }
+
+        // The remainder is exact, as hi's last bit is no finer than value's
+        let hm = hm << (he - exp);
+        let (negative, rem) = if mant >= hm {
+            (negative, mant - hm)
+        } else {
+            (!negative, hm - mant)
+        };
+        let lo = round_pack(negative, rem, exp, false, 11, 52);
+
+        DoubleDouble::new(hi, f64::from_bits(lo as u64))
+    }
+}
@@ -0,0 +1,176 @@
+mod arith;
+mod cast;
+
+/// An unevaluated sum of two `f64`, giving about 106 bits of precision at
+/// hardware speed.
+///
+/// The value is `hi + lo` evaluated exactly. The operations keep the pair
+/// normalized, so that `hi` is the sum rounded to `f64` and `lo` is the
+/// rounding error, which is what the ordering assumes. This is also the IBM
+/// `long double` of PowerPC, which can be read with
+/// [`from_be_bytes`](Self::from_be_bytes) and
+/// [`from_le_bytes`](Self::from_le_bytes).
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct DoubleDouble {
+    pub hi: f64,
+    pub lo: f64,
+}
+
+/// Veltkamp's splitting constant, 2^27 + 1.
+const SPLIT: f64 = 134_217_729.0;
+
+/// Splits `a` into two halves of 26 bits each, whose sum is `a`.
+#[inline]
+fn split(a: f64) -> (f64, f64) {
+    let t = SPLIT * a;
+    let hi = t - (t - a);
+    (hi, a - hi)
+}
+
+impl DoubleDouble {
+    pub const ZERO: Self = Self::new(0.0, 0.0);
+    pub const ONE: Self = Self::new(1.0, 0.0);
+
+    pub const NAN: Self = Self::new(f64::NAN, 0.0);
+
+    pub const INFINITY: Self = Self::new(f64::INFINITY, 0.0);
+    pub const NEG_INFINITY: Self = Self::new(f64::NEG_INFINITY, 0.0);
+
+    /// Creates a pair from its parts, as they are.
+    #[inline]
+    pub const fn new(hi: f64, lo: f64) -> Self {
+        Self { hi, lo }
+    }
+
+    /// Returns `a + b` exactly, as the rounded sum and its error, provided
+    /// that `|a| >= |b|` or `a` is zero (Dekker's Fast2Sum).
+    #[inline]
+    pub fn fast_two_sum(a: f64, b: f64) -> Self {
+        let hi = a + b;
+        let lo = b - (hi - a);
+        Self { hi, lo }
+    }
+
+    /// Returns `a + b` exactly, as the rounded sum and its error, for any
+    /// finite `a` and `b` (Knuth's 2Sum).
+    #[inline]
+    pub fn two_sum(a: f64, b: f64) -> Self {
+        let hi = a + b;
+        let a1 = hi - b;
+        let b1 = hi - a1;
+        let lo = (a - a1) + (b - b1);
+        Self { hi, lo }
+    }
+
+    /// Returns `a * b` exactly, as the rounded product and its error.
+    ///
+    /// This is Dekker's product, which needs no fused multiply-add. It is
+    /// exact unless a factor exceeds about 2^996 or the error underflows.
+    #[inline]
+    pub fn two_prod(a: f64, b: f64) -> Self {
+        let hi = a * b;
+        let (ah, al) = split(a);
+        let (bh, bl) = split(b);
+        let lo = ((ah * bh - hi) + ah * bl + al * bh) + al * bl;
+        Self { hi, lo }
+    }
+
+    /// Returns the value rounded to `f64`.
+    #[inline]
+    pub fn to_f64(self) -> f64 {
+        self.hi + self.lo
+    }
+
+    #[inline]
+    pub fn is_nan(self) -> bool {
+        self.hi.is_nan() || self.lo.is_nan()
+    }
+
+    #[inline]
+    pub fn is_infinite(self) -> bool {
+        !self.is_nan() && (self.hi.is_infinite() || self.lo.is_infinite())
+    }
+
+    #[inline]
+    pub fn is_finite(self) -> bool {
+        self.hi.is_finite() && self.lo.is_finite()
+    }
+
+    #[inline]
+    pub fn is_sign_positive(self) -> bool {
+        self.hi.is_sign_positive()
+    }
+
+    #[inline]
+    pub fn is_sign_negative(self) -> bool {
+        self.hi.is_sign_negative()
+    }
+
+    /// Creates a pair from an IBM `long double` stored in big endian, as on
+    /// 32-bit and big endian 64-bit PowerPC: `hi` then `lo`.
+    #[inline]
+    pub fn from_be_bytes(bytes: [u8; 16]) -> Self {
+        let (hi, lo) = halves(bytes);
+        Self::new(f64::from_be_bytes(hi), f64::from_be_bytes(lo))
+    }
+
+    /// Creates a pair from an IBM `long double` stored in little endian, as
+    /// on 64-bit little endian PowerPC: `hi` then `lo`, each little endian.
+    #[inline]
+    pub fn from_le_bytes(bytes: [u8; 16]) -> Self {
+        let (hi, lo) = halves(bytes);
+        Self::new(f64::from_le_bytes(hi), f64::from_le_bytes(lo))
+    }
+
+    /// Creates a pair from an IBM `long double` stored in native endian.
+    #[inline]
+    pub fn from_ne_bytes(bytes: [u8; 16]) -> Self {
+        let (hi, lo) = halves(bytes);
+        Self::new(f64::from_ne_bytes(hi), f64::from_ne_bytes(lo))
+    }
+
+    /// Returns the memory representation of this pair as an IBM
+    /// `long double` in big endian.
+    #[inline]
+    pub fn to_be_bytes(self) -> [u8; 16] {
+        join(self.hi.to_be_bytes(), self.lo.to_be_bytes())
+    }
+
+    /// Returns the memory representation of this pair as an IBM
+    /// `long double` in little endian.
+    #[inline]
+    pub fn to_le_bytes(self) -> [u8; 16] {
+        join(self.hi.to_le_bytes(), self.lo.to_le_bytes())
+    }
+
+    /// Returns the memory representation of this pair as an IBM
+    /// `long double` in native endian.
+    #[inline]
+    pub fn to_ne_bytes(self) -> [u8; 16] {
+        join(self.hi.to_ne_bytes(), self.lo.to_ne_bytes())
+    }
+}
+
+impl From<f64> for DoubleDouble {
+    #[inline]
+    fn from(value: f64) -> Self {
+        Self::new(value, 0.0)
+    }
+}
+
+#[inline]
+fn halves(bytes: [u8; 16]) -> ([u8; 8], [u8; 8]) {
+    let mut hi = [0; 8];
+    let mut lo = [0; 8];
+    hi.copy_from_slice(&bytes[..8]);
+    lo.copy_from_slice(&bytes[8..]);
+    (hi, lo)
+}
+
+#[inline]
+fn join(hi: [u8; 8], lo: [u8; 8]) -> [u8; 16] {
+    let mut bytes = [0; 16];
+    bytes[..8].copy_from_slice(&hi);
+    bytes[8..].copy_from_slice(&lo);
+    bytes
+}
@@ -0,0 +1,93 @@
+#![cfg(feature = "casting")]
+
+//! Conversions to and from the other floats. Widening into `f128` is exact,
+//! and every other conversion rounds to nearest even.
+
+use super::{f80, EXP_MASK, FRAC_MASK, INT_BIT};
+use crate::round::{decode, rescale_payload, round_pack, Decoded};
+use crate::{f128, f16};
+use casting::CastFrom;
+
+impl f80 {
+    /// Converts to the binary format with `exp_bits` exponent bits and
+    /// `mant_bits` stored mantissa bits.
+    ///
+    /// NaNs are quieted and keep their sign and top payload bits; the
+    /// encodings the x87 rejects become the default NaN of their sign.
+    #[inline]
+    fn to_ieee(self, exp_bits: u32, mant_bits: u32) -> u128 {
+        let negative = self.is_sign_negative();
+        let inf =
+            ((negative as u128) << (exp_bits + mant_bits)) | (((1 << exp_bits) - 1) << mant_bits);
+
+        if self.is_nan() {
+            let frac = if self.0 & INT_BIT == 0 {
+                0
+            } else {
+                self.0 & FRAC_MASK
+            };
+            return inf | (1 << (mant_bits - 1)) | rescale_payload(frac, 63, mant_bits);
+        }
+
+        if self.is_infinite() {
+            return inf;
+        }
+
+        // Pseudo-denormals and denormals alike have the exponent of 1
+        let exp = ((self.0 & EXP_MASK) >> 64) as i32;
+        let exp = if exp == 0 { 1 } else { exp } - 16383 - 63;
+        let mant = self.0 & (INT_BIT | FRAC_MASK);
+
+        round_pack(negative, mant, exp, false, exp_bits, mant_bits)
+    }
+
+    /// Converts from the binary format with `exp_bits` exponent bits and
+    /// `mant_bits` stored mantissa bits. NaNs are quieted and keep their
+    /// sign and top payload bits.
+    #[inline]
+    fn from_ieee(bits: u128, exp_bits: u32, mant_bits: u32) -> Self {
+        let sign = |negative: bool| (negative as u128) << 79;
+
+        match decode(bits, exp_bits, mant_bits) {
+            Decoded::Nan(negative, mant) => {
+                Self(sign(negative) | Self::NAN.0 | rescale_payload(mant, mant_bits, 63))
+            }
+            Decoded::Infinite(negative) => Self(sign(negative) | Self::INFINITY.0),
+            Decoded::Finite(negative, mant, exp) => {
+                // Round into the IEEE layout with 63 stored bits, then make
+                // the integer bit explicit: it is set for every nonzero
+                // exponent
+                let packed = round_pack(negative, mant, exp, false, 15, 63) & !(1 << 78);
+                let exp = packed >> 63;
+                let int = ((exp != 0) as u128) << 63;
+
+                Self(sign(negative) | exp << 64 | int | (packed & FRAC_MASK))
+            }
+        }
+    }
+}
+
+macro_rules! cast {
+    ($($float:ty: $bits:ty, $exp_bits:literal, $mant_bits:literal;)*) => {$(
+        impl CastFrom<$float> for f80 {
+            #[inline]
+            fn cast_from(value: $float) -> f80 {
+                f80::from_ieee(value.to_bits() as u128, $exp_bits, $mant_bits)
+            }
+        }
+
+        impl CastFrom<f80> for $float {
+            #[inline]
+            fn cast_from(value: f80) -> $float {
+                <$float>::from_bits(value.to_ieee($exp_bits, $mant_bits) as $bits)
+            }
+        }
+    )*};
+}
+
+cast! {
+    f16: u16, 5, 10;
+    f32: u32, 8, 23;
+    f64: u64, 11, 52;
+    f128: u128, 15, 112;
+}
@@ -0,0 +1,194 @@
+use core::ops::Neg;
+
+mod cast;
+
+const EXP_MASK: u128 = 0x7fff << 64;
+const INT_BIT: u128 = 1 << 63;
+const FRAC_MASK: u128 = INT_BIT - 1;
+const SIGN: u128 = 1 << 79;
+const BITS_MASK: u128 = (1 << 80) - 1;
+
+/// 80-bit x87 extended precision floating point type, the `long double` of
+/// C on x86.
+///
+/// Unlike the IEEE 754 interchange formats, the significand stores its
+/// integer bit explicitly, which allows encodings the x87 does not generate.
+/// They are read as the x87 (80387 and later) reads them: pseudo-denormals,
+/// with a zero exponent and the integer bit set, have their value, while
+/// unnormals, pseudo-infinities and pseudo-NaNs, with the integer bit clear
+/// above a zero exponent, are NaN. The type is a storage format implemented
+/// in software, with conversions to and from the other floats.
+#[derive(Debug, Clone, Copy, Default)]
+#[allow(non_camel_case_types)]
+#[repr(transparent)]
+pub struct f80(u128);
+
+impl Neg for f80 {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Self(self.0 ^ SIGN)
+    }
+}
+
+impl PartialEq for f80 {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        // IEEE 754 compliance: NaN != NaN and +0.0 == -0.0
+        if self.is_nan() || other.is_nan() {
+            return false;
+        }
+
+        let (a, b) = (self.canonical(), other.canonical());
+        if (a | b) & !SIGN == 0 {
+            return true;
+        }
+
+        a == b
+    }
+}
+
+impl f80 {
+    pub const NAN: Self = Self(EXP_MASK | INT_BIT | 1 << 62);
+
+    pub const MIN: Self = Self(SIGN | 0x7ffe << 64 | INT_BIT | FRAC_MASK);
+    pub const MAX: Self = Self(0x7ffe << 64 | INT_BIT | FRAC_MASK);
+
+    pub const INFINITY: Self = Self(EXP_MASK | INT_BIT);
+    pub const NEG_INFINITY: Self = Self(SIGN | EXP_MASK | INT_BIT);
+
+    pub const MANTISSA_DIGITS: u32 = 64;
+
+    /// Returns `true` if `self` is NaN, including the unnormals,
+    /// pseudo-infinities and pseudo-NaNs that the x87 treats as NaN.
+    #[inline]
+    pub const fn is_nan(self) -> bool {
+        let exp = self.0 & EXP_MASK;
+
+        if exp == 0 {
+            false
+        } else if self.0 & INT_BIT == 0 {
+            true
+        } else {
+            exp == EXP_MASK && self.0 & FRAC_MASK != 0
+        }
+    }
+
+    #[inline]
+    pub const fn is_infinite(self) -> bool {
+        self.0 & !SIGN == Self::INFINITY.0
+    }
+
+    #[inline]
+    pub const fn is_finite(self) -> bool {
+        !self.is_nan() && !self.is_infinite()
+    }
+
+    #[inline]
+    pub const fn is_sign_positive(self) -> bool {
+        (self.0 & SIGN) == 0
+    }
+
+    #[inline]
+    pub const fn is_sign_negative(self) -> bool {
+        (self.0 & SIGN) != 0
+    }
+
+    /// Returns the bits with a pseudo-denormal given its normal encoding,
+    /// which has the same value.
+    #[inline]
+    const fn canonical(self) -> u128 {
+        if self.0 & EXP_MASK == 0 && self.0 & INT_BIT != 0 {
+            self.0 | 1 << 64
+        } else {
+            self.0
+        }
+    }
+
+    /// Creates an `f80` from its representation in the low 80 bits of a
+    /// `u128`. The upper bits are ignored.
+    #[inline]
+    pub const fn from_bits(bits: u128) -> Self {
+        Self(bits & BITS_MASK)
+    }
+
+    /// Creates an `f80` from its representation as a byte array in big endian.
+    #[inline]
+    pub const fn from_be_bytes(bytes: [u8; 10]) -> Self {
+        let mut wide = [0; 16];
+        let mut i = 0;
+        while i < 10 {
+            wide[6 + i] = bytes[i];
+            i += 1;
+        }
+        Self(u128::from_be_bytes(wide))
+    }
+
+    /// Creates an `f80` from its representation as a byte array in little
+    /// endian, the layout of a `long double` in memory on x86.
+    #[inline]
+    pub const fn from_le_bytes(bytes: [u8; 10]) -> Self {
+        let mut wide = [0; 16];
+        let mut i = 0;
+        while i < 10 {
+            wide[i] = bytes[i];
+            i += 1;
+        }
+        Self(u128::from_le_bytes(wide))
+    }
+
+    /// Creates an `f80` from its representation as a byte array in native endian.
+    #[inline]
+    pub const fn from_ne_bytes(bytes: [u8; 10]) -> Self {
+        if cfg!(target_endian = "big") {
+            Self::from_be_bytes(bytes)
+        } else {
+            Self::from_le_bytes(bytes)
+        }
+    }
+
+    /// Returns the raw representation of this `f80` in the low 80 bits of a
+    /// `u128`.
+    #[inline]
+    pub const fn to_bits(self) -> u128 {
+        self.0
+    }
+
+    /// Returns the memory representation of this `f80` as a byte array in big endian.
+    #[inline]
+    pub const fn to_be_bytes(self) -> [u8; 10] {
+        let wide = self.0.to_be_bytes();
+        let mut bytes = [0; 10];
+        let mut i = 0;
+        while i < 10 {
+            bytes[i] = wide[6 + i];
+            i += 1;
+        }
+        bytes
+    }
+
+    /// Returns the memory representation of this `f80` as a byte array in
+    /// little endian, the layout of a `long double` in memory on x86.
+    #[inline]
+    pub const fn to_le_bytes(self) -> [u8; 10] {
+        let wide = self.0.to_le_bytes();
+        let mut bytes = [0; 10];
+        let mut i = 0;
+        while i < 10 {
+            bytes[i] = wide[i];
+            i += 1;
+        }
+        bytes
+    }
+
+    /// Returns the memory representation of this `f80` as a byte array in native endian.
+    #[inline]
+    pub const fn to_ne_bytes(self) -> [u8; 10] {
+        if cfg!(target_endian = "big") {
+            self.to_be_bytes()
+        } else {
+            self.to_le_bytes()
+        }
+    }
+}
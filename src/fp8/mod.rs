@@ -0,0 +1,198 @@
+//! 8-bit floating point formats.
+//!
+//! The OCP formats `f8e4m3` and `f8e5m2`, and the `fnuz` variants, which
+//! have no negative zero and use its encoding as their only NaN. Every value
+//! of every format is exact in `f16`.
+
+use core::ops::Neg;
+
+pub(crate) mod sw;
+
+/// The layout and special encodings of an 8-bit format.
+#[cfg(feature = "casting")]
+pub(crate) trait Format: Copy {
+    /// The width of the stored mantissa.
+    const MANT: u32;
+
+    /// The exponent bias.
+    const BIAS: i32;
+
+    /// The bits of the largest finite value.
+    const MAX: u8;
+
+    /// Whether the encoding after `MAX` is infinity, with NaNs above it.
+    const INF: bool;
+
+    /// Whether `0x80` is the only NaN, with no negative zero.
+    const FNUZ: bool;
+
+    fn from_bits(bits: u8) -> Self;
+    fn to_bits(self) -> u8;
+}
+
+/// Returns `true` if `bits` is a NaN of the format with these parameters.
+#[inline]
+const fn is_nan(bits: u8, max: u8, inf: bool, fnuz: bool) -> bool {
+    if fnuz {
+        bits == 0x80
+    } else if inf {
+        bits & 0x7f > max + 1
+    } else {
+        bits & 0x7f > max
+    }
+}
+
+/// Returns `true` if `bits` is an infinity of the format with these
+/// parameters.
+#[inline]
+const fn is_infinite(bits: u8, max: u8, inf: bool) -> bool {
+    inf && bits & 0x7f == max + 1
+}
+
+macro_rules! fp8 {
+    ($(
+        $(#[$attr:meta])*
+        $name:ident: mant $mant:literal, bias $bias:literal, max $max:literal, nan $nan:literal,
+            inf $inf:literal, fnuz $fnuz:literal;
+    )*) => {$(
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy, Default)]
+        #[allow(non_camel_case_types)]
+        #[repr(transparent)]
+        pub struct $name(u8);
+
+        #[cfg(feature = "casting")]
+        impl Format for $name {
+            const MANT: u32 = $mant;
+            const BIAS: i32 = $bias;
+            const MAX: u8 = $max;
+            const INF: bool = $inf;
+            const FNUZ: bool = $fnuz;
+
+            #[inline]
+            fn from_bits(bits: u8) -> Self {
+                Self(bits)
+            }
+
+            #[inline]
+            fn to_bits(self) -> u8 {
+                self.0
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+
+            #[inline]
+            fn neg(self) -> Self::Output {
+                // Zero and the NaN have no negative counterparts
+                if $fnuz && self.0 & 0x7f == 0 {
+                    self
+                } else {
+                    Self(self.0 ^ 0x80)
+                }
+            }
+        }
+
+        impl PartialEq for $name {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                // IEEE 754 compliance: NaN != NaN and +0.0 == -0.0
+                if self.is_nan() || other.is_nan() {
+                    return false;
+                }
+
+                if (self.0 | other.0) & 0x7f == 0 {
+                    return true;
+                }
+
+                self.0 == other.0
+            }
+        }
+
+        impl $name {
+            /// The NaN produced by conversions.
+            pub const NAN: Self = Self($nan);
+
+            pub const MIN: Self = Self($max | 0x80);
+            pub const MAX: Self = Self($max);
+
+            /// The smallest positive normal value.
+            pub const MIN_POSITIVE: Self = Self(1 << $mant);
+
+            pub const MANTISSA_DIGITS: u32 = $mant + 1;
+
+            #[inline]
+            pub const fn is_nan(self) -> bool {
+                is_nan(self.0, $max, $inf, $fnuz)
+            }
+
+            #[inline]
+            pub const fn is_infinite(self) -> bool {
+                is_infinite(self.0, $max, $inf)
+            }
+
+            #[inline]
+            pub const fn is_finite(self) -> bool {
+                !self.is_nan() && !self.is_infinite()
+            }
+
+            #[inline]
+            pub const fn is_sign_positive(self) -> bool {
+                (self.0 & 0x80) == 0
+            }
+
+            #[inline]
+            pub const fn is_sign_negative(self) -> bool {
+                (self.0 & 0x80) != 0
+            }
+
+            #[doc = concat!("Creates an `", stringify!($name), "` from its representation as a `u8`.")]
+            #[inline]
+            pub const fn from_bits(bits: u8) -> Self {
+                Self(bits)
+            }
+
+            #[doc = concat!("Returns the raw representation of this `", stringify!($name), "` as a `u8`.")]
+            #[inline]
+            pub const fn to_bits(self) -> u8 {
+                self.0
+            }
+        }
+    )*};
+}
+
+fp8! {
+    /// 8-bit floating point type with 4 exponent and 3 mantissa bits (OCP
+    /// FP8 E4M3).
+    ///
+    /// There are no infinities and one NaN of each sign, `S.1111.111`, so
+    /// the largest finite value is 448.
+    f8e4m3: mant 3, bias 7, max 0x7e, nan 0x7f, inf false, fnuz false;
+
+    /// 8-bit floating point type with 5 exponent and 2 mantissa bits (OCP
+    /// FP8 E5M2).
+    ///
+    /// This follows the IEEE 754 rules, and is the upper half of an `f16`.
+    /// The largest finite value is 57344.
+    f8e5m2: mant 2, bias 15, max 0x7b, nan 0x7e, inf true, fnuz false;
+
+    /// 8-bit floating point type with 4 exponent and 3 mantissa bits and
+    /// bias 8, as used by AMD and Graphcore (E4M3FNUZ).
+    ///
+    /// There are no infinities and no negative zero: `0x80` is the only NaN.
+    /// The largest finite value is 240.
+    f8e4m3fnuz: mant 3, bias 8, max 0x7f, nan 0x80, inf false, fnuz true;
+
+    /// 8-bit floating point type with 5 exponent and 2 mantissa bits and
+    /// bias 16, as used by AMD and Graphcore (E5M2FNUZ).
+    ///
+    /// There are no infinities and no negative zero: `0x80` is the only NaN.
+    /// The largest finite value is 57344.
+    f8e5m2fnuz: mant 2, bias 16, max 0x7f, nan 0x80, inf false, fnuz true;
+}
+
+impl f8e5m2 {
+    pub const INFINITY: Self = Self(0x7c);
+    pub const NEG_INFINITY: Self = Self(0xfc);
+}
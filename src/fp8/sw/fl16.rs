@@ -0,0 +1,39 @@
+use super::{narrow, widen};
+use crate::f16;
+use crate::fp8::{f8e4m3, f8e4m3fnuz, f8e5m2, f8e5m2fnuz};
+use casting::CastFrom;
+
+// Every 8-bit value is exact in f16, so widening through f32 is exact too.
+
+macro_rules! fl16 {
+    ($($fp8:ident),*) => {$(
+        impl CastFrom<f16> for $fp8 {
+            /// Rounds to nearest even; out-of-range values become infinity
+            /// or, where there is none, NaN.
+            #[inline]
+            fn cast_from(value: f16) -> $fp8 {
+                narrow(f64::cast_from(value), false)
+            }
+        }
+
+        impl CastFrom<$fp8> for f16 {
+            #[inline]
+            fn cast_from(value: $fp8) -> f16 {
+                f16::cast_from(widen(value))
+            }
+        }
+
+        impl $fp8 {
+            #[doc = concat!("Converts an `f16` to `", stringify!($fp8), "`, rounding to nearest even.")]
+            ///
+            /// Out-of-range values and infinities become `MAX` or `MIN`; NaN
+            /// stays NaN.
+            #[inline]
+            pub fn from_f16_saturating(value: f16) -> Self {
+                narrow(f64::cast_from(value), true)
+            }
+        }
+    )*};
+}
+
+fl16!(f8e4m3, f8e5m2, f8e4m3fnuz, f8e5m2fnuz);
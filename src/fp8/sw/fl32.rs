@@ -0,0 +1,36 @@
+use super::{f64_from_f32, narrow, widen};
+use crate::fp8::{f8e4m3, f8e4m3fnuz, f8e5m2, f8e5m2fnuz};
+use casting::CastFrom;
+
+macro_rules! fl32 {
+    ($($fp8:ident),*) => {$(
+        impl CastFrom<f32> for $fp8 {
+            /// Rounds to nearest even; out-of-range values become infinity
+            /// or, where there is none, NaN.
+            #[inline]
+            fn cast_from(value: f32) -> $fp8 {
+                narrow(f64_from_f32(value), false)
+            }
+        }
+
+        impl CastFrom<$fp8> for f32 {
+            #[inline]
+            fn cast_from(value: $fp8) -> f32 {
+                widen(value)
+            }
+        }
+
+        impl $fp8 {
+            #[doc = concat!("Converts an `f32` to `", stringify!($fp8), "`, rounding to nearest even.")]
+            ///
+            /// Out-of-range values and infinities become `MAX` or `MIN`; NaN
+            /// stays NaN.
+            #[inline]
+            pub fn from_f32_saturating(value: f32) -> Self {
+                narrow(f64_from_f32(value), true)
+            }
+        }
+    )*};
+}
+
+fl32!(f8e4m3, f8e5m2, f8e4m3fnuz, f8e5m2fnuz);
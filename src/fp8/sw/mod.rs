@@ -0,0 +1,130 @@
+#![cfg(feature = "casting")]
+
+mod fl16;
+mod fl32;
+mod fl64;
+
+use super::Format;
+
+// Every 8-bit value is exact in f32, and f16 and f32 are exact in f64, so
+// each narrowing conversion goes through f64 and rounds once, here.
+
+/// Rounds `value` to nearest even in `F`.
+///
+/// NaN stays NaN. Values that round beyond `MAX`, and infinities, become
+/// `MAX` if `saturate` is set, and otherwise infinity or, where `F` has
+/// none, NaN.
+pub(crate) fn narrow<F: Format>(value: f64, saturate: bool) -> F {
+    let bits = value.to_bits();
+    let sign = ((bits >> 56) & 0x80) as u8;
+    let abs = bits & 0x7FFF_FFFF_FFFF_FFFF;
+
+    // The sign of a NaN or a zero is dropped where 0x80 is the NaN
+    let nan = if F::FNUZ {
+        0x80
+    } else if F::INF {
+        // Quieted, keeping the top payload bits
+        let payload = (abs >> (52 - F::MANT)) as u8 & ((1 << (F::MANT - 1)) - 1);
+        sign | (F::MAX + 1) | (1 << (F::MANT - 1)) | payload
+    } else {
+        sign | 0x7F
+    };
+    let zero = if F::FNUZ { 0 } else { sign };
+
+    let overflow = if saturate {
+        sign | F::MAX
+    } else if F::INF {
+        sign | (F::MAX + 1)
+    } else {
+        nan
+    };
+
+    if abs > 0x7FF0_0000_0000_0000 {
+        return F::from_bits(nan);
+    }
+    if abs == 0x7FF0_0000_0000_0000 {
+        return F::from_bits(overflow);
+    }
+
+    // The exponent field the value would have in F, below 1 if subnormal
+    let exp = (abs >> 52) as i32 - 1023 + F::BIAS;
+    let sig = (abs & 0x000F_FFFF_FFFF_FFFF) | (1 << 52);
+    let shift = 52 - F::MANT + if exp < 1 { (1 - exp) as u32 } else { 0 };
+
+    // Below half the smallest subnormal, which also covers f64 subnormals
+    if shift > 53 {
+        return F::from_bits(zero);
+    }
+
+    let half = 1 << (shift - 1);
+    let rem = sig & ((half << 1) - 1);
+    let mut mant = sig >> shift;
+    if rem > half || (rem == half && mant & 1 == 1) {
+        mant += 1;
+    }
+
+    // A normal mantissa still has its implicit bit, which adds one to the
+    // exponent: so does a carry out of the mantissa, and a subnormal that
+    // rounds up to the smallest normal needs no special case
+    let mag = if exp < 1 {
+        mant
+    } else {
+        (((exp - 1) as u64) << F::MANT) + mant
+    };
+
+    if mag > F::MAX as u64 {
+        F::from_bits(overflow)
+    } else if mag == 0 {
+        F::from_bits(zero)
+    } else {
+        F::from_bits(sign | mag as u8)
+    }
+}
+
+/// Converts `value` to f32, which is always exact.
+pub(crate) fn widen<F: Format>(value: F) -> f32 {
+    let bits = value.to_bits();
+    let sign = ((bits & 0x80) as u32) << 24;
+    let abs = bits & 0x7F;
+
+    if F::FNUZ && bits == 0x80 {
+        return f32::NAN;
+    }
+    if F::INF && abs > F::MAX {
+        // Infinity, or a NaN quieted with its payload in the top bits
+        let mant = ((abs & ((1 << F::MANT) - 1)) as u32) << (23 - F::MANT);
+        let quiet = if abs > F::MAX + 1 { 0x0040_0000 } else { 0 };
+        return f32::from_bits(sign | 0x7F80_0000 | mant | quiet);
+    }
+    if !F::INF && !F::FNUZ && abs > F::MAX {
+        return f32::from_bits(sign | 0x7FC0_0000);
+    }
+
+    let exp = (abs >> F::MANT) as i32;
+    let mant = (abs & ((1 << F::MANT) - 1)) as u32;
+    let (sig, exp) = if exp == 0 {
+        (mant, 1)
+    } else {
+        (mant | (1 << F::MANT), exp)
+    };
+
+    // The significand is a small integer, and the scale a normal power of
+    // two, so the product is exact
+    let scale = f32::from_bits(((exp - F::BIAS - F::MANT as i32 + 127) as u32) << 23);
+    f32::from_bits((sig as f32 * scale).to_bits() | sign)
+}
+
+/// Widens `value` exactly, keeping the payload of a NaN, which `as` does not
+/// specify.
+#[inline]
+pub(super) fn f64_from_f32(value: f32) -> f64 {
+    let bits = value.to_bits() as u64;
+
+    if value.is_nan() {
+        return f64::from_bits(
+            (bits & 0x8000_0000) << 32 | 0x7FF0_0000_0000_0000 | (bits & 0x007F_FFFF) << 29,
+        );
+    }
+
+    value as f64
+}
@@ -1,42 +1,35 @@
 use super::super::f16;
-use casting::CastFrom;
 
-impl CastFrom<f16> for f64 {
-    #[inline]
-    #[allow(unsafe_code)]
-    fn cast_from(value: f16) -> f64 {
-        let result: f64;
+#[inline]
+#[allow(unsafe_code)]
+#[target_feature(enable = "fp16")]
+pub(crate) unsafe fn f64_from_f16(value: f16) -> f64 {
+    let result: f64;
 
-        unsafe {
-            core::arch::asm!(
-                "fmov h0, w0",      // Move u16 from w0 to h0
-                "fcvt d0, h0",      // Convert f16 in h0 to f64 in d0
-                in("w0") value.0,
-                out("d0") result,
-                options(pure, nomem, nostack)
-            );
-        }
+    core::arch::asm!(
+        "fmov h0, w0",      // Move u16 from w0 to h0
+        "fcvt d0, h0",      // Convert f16 in h0 to f64 in d0
+        in("w0") value.0,
+        out("d0") result,
+        options(pure, nomem, nostack)
+    );
 
-        result
-    }
+    result
 }
 
-impl CastFrom<f64> for f16 {
-    #[inline]
-    #[allow(unsafe_code)]
-    fn cast_from(value: f64) -> f16 {
-        let result: u16;
+#[inline]
+#[allow(unsafe_code)]
+#[target_feature(enable = "fp16")]
+pub(crate) unsafe fn f16_from_f64(value: f64) -> f16 {
+    let result: u16;
 
-        unsafe {
-            core::arch::asm!(
-                "fcvt h0, d0",      // Convert f64 in d0 to f16 in h0
-                "fmov w0, h0",      // Move f16 from h0 to w0 (u16 in low bits)
-                in("d0") value,
-                out("w0") result,
-                options(pure, nomem, nostack)
-            );
-        }
+    core::arch::asm!(
+        "fcvt h0, d0",      // Convert f64 in d0 to f16 in h0
+        "fmov w0, h0",      // Move f16 from h0 to w0 (u16 in low bits)
+        inout("d0") value => _,
+        out("w0") result,
+        options(pure, nomem, nostack)
+    );
 
-        f16(result)
-    }
+    f16(result)
 }
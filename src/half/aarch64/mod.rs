@@ -1,7 +1,15 @@
-#![cfg(all(feature = "asm", target_arch = "aarch64", target_feature = "fp16"))]
+#![cfg(all(
+    feature = "asm",
+    target_arch = "aarch64",
+    any(target_feature = "fp16", feature = "runtime-detect")
+))]
 #![cfg(feature = "casting")]
 
+// Every function requires FP16 support, which the caller must check
+
 mod fl32;
 mod fl64;
 mod int32;
 mod int64;
+
+pub(crate) use self::{fl32::*, fl64::*, int32::*, int64::*};
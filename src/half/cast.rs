@@ -0,0 +1,92 @@
+#![cfg(feature = "casting")]
+
+//! Conversions with a hardware implementation, dispatched on
+//! [`backend`](crate::backend).
+//!
+//! When the target features are enabled at compile time, `backend` is a
+//! constant and the unused branches are optimized away.
+
+use super::{f16, sw};
+use casting::CastFrom;
+
+/// Returns the expression for the active backend from the enclosing
+/// function, or else evaluates the software one.
+///
+/// Hardware expressions are only compiled where their backend can be active,
+/// and only run when [`backend`](crate::backend) reports the CPU feature,
+/// which is what makes their `unsafe` calls sound.
+macro_rules! dispatch {
+    (
+        $(avx512fp16: $avx:expr,)?
+        $(f16c: $f16c:expr,)?
+        $(fp16: $fp16:expr,)?
+        software: $sw:expr $(,)?
+    ) => {{
+        $(
+            #[cfg(all(
+                feature = "asm",
+                target_arch = "x86_64",
+                any(target_feature = "avx512fp16", feature = "runtime-detect")
+            ))]
+            if crate::backend() == crate::Backend::Avx512Fp16 {
+                return $avx;
+            }
+        )?
+
+        $(
+            #[cfg(all(
+                feature = "asm",
+                target_arch = "x86_64",
+                any(target_feature = "f16c", feature = "runtime-detect")
+            ))]
+            if crate::backend() == crate::Backend::F16c {
+                return $f16c;
+            }
+        )?
+
+        $(
+            #[cfg(all(
+                feature = "asm",
+                target_arch = "aarch64",
+                any(target_feature = "fp16", feature = "runtime-detect")
+            ))]
+            if crate::backend() == crate::Backend::Fp16 {
+                return $fp16;
+            }
+        )?
+
+        $sw
+    }};
+}
+
+/// Implements a conversion with the best available backend. Conversions that
+/// F16C provides are marked `f16c`.
+macro_rules! cast {
+    ($from:ty => $to:ty, $name:ident $(, $f16c:ident)?) => {
+        impl CastFrom<$from> for $to {
+            #[inline]
+            #[allow(unsafe_code)]
+            fn cast_from(value: $from) -> $to {
+                dispatch! {
+                    avx512fp16: unsafe { super::x86_64::avx::$name(value) },
+                    $(f16c: unsafe { super::x86_64::$f16c::$name(value) },)?
+                    fp16: unsafe { super::aarch64::$name(value) },
+                    software: sw::$name(value),
+                }
+            }
+        }
+    };
+}
+
+cast!(f16 => f32, f32_from_f16, f16c);
+cast!(f32 => f16, f16_from_f32, f16c);
+cast!(f16 => f64, f64_from_f16);
+cast!(f64 => f16, f16_from_f64);
+cast!(f16 => i32, i32_from_f16);
+cast!(i32 => f16, f16_from_i32);
+cast!(f16 => u32, u32_from_f16);
+cast!(u32 => f16, f16_from_u32);
+cast!(f16 => i64, i64_from_f16);
+cast!(i64 => f16, f16_from_i64);
+cast!(f16 => u64, u64_from_f16);
+cast!(u64 => f16, f16_from_u64);
@@ -0,0 +1,19 @@
+//! Elementary functions.
+//!
+//! Each function handles the special cases of C23 Annex F directly and
+//! evaluates everything else in 256-bit `Wide` arithmetic, rounding once
+//! at the end. Nothing goes through `f32` or a platform library, so every
+//! result is correctly rounded and identical on every platform and with
+//! either conversion backend, assembly or software. The functions shared
+//! with `f128` are written once, in `crate::wide::float`.
+
+use super::f16;
+
+mod pow;
+mod root;
+
+elementary!(f16: bits u16, exp 5, mant 10, range 5, overflow 4, saturate 3);
+
+impl f16 {
+    propagate_nan!(pub(super));
+}
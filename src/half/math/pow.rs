@@ -0,0 +1,95 @@
+use super::f16;
+use crate::wide::Wide;
+use crate::IeeeFloat;
+
+/// Results within `2^-TOLERANCE` (relative) of a rounding boundary are taken
+/// to lie exactly on it; see [`Wide::to_bits_snapped`].
+pub(super) const TOLERANCE: u32 = 224;
+
+impl f16 {
+    /// Raises `self` to the power `n`.
+    ///
+    /// Special cases follow C23 Annex F: `powf(x, ±0)` and `powf(+1, y)` are
+    /// `1` even for NaN, `powf(-1, ±∞)` is `1`, a zero or infinite base
+    /// gives a zero or infinity whose sign is kept only for odd integral
+    /// exponents, and a finite negative base with a finite non-integral
+    /// exponent gives NaN.
+    pub fn powf(self, n: Self) -> Self {
+        let one = Self::from_wide(Wide::ONE);
+
+        if n.is_zero() || self == one {
+            return one;
+        } else if self.is_nan() || n.is_nan() {
+            return self.propagate_nan(n);
+        }
+
+        // Limits as the exponent grows without bound
+        if n.is_infinite() {
+            let abs = Self(self.0 & 0x7fff);
+            return if abs == one {
+                one
+            } else if (abs.ilogb() < 0) == n.is_sign_negative() {
+                Self::INFINITY
+            } else {
+                Self(0)
+            };
+        }
+
+        self.pow_wide(n.to_wide())
+    }
+
+    /// Raises `self` to the integer power `n`.
+    ///
+    /// Unlike a chain of multiplications, the result is rounded only once.
+    /// Special cases are as for [`Self::powf`], even for exponents too large
+    /// to be represented as an `f16`.
+    #[inline]
+    pub fn powi(self, n: i32) -> Self {
+        let one = Self::from_wide(Wide::ONE);
+
+        if n == 0 || self == one {
+            one
+        } else if self.is_nan() {
+            self.quiet()
+        } else {
+            self.pow_wide(Wide::from_i64(n as i64))
+        }
+    }
+
+    /// Raises `self` to a finite nonzero power `n`.
+    fn pow_wide(self, n: Wide) -> Self {
+        let parity = n.parity();
+        let odd = parity == Some(true);
+
+        // Zero and infinite bases only contribute their sign
+        if self.is_zero() || self.is_infinite() {
+            let inf = self.is_infinite() != n.neg;
+            let neg = odd && self.is_sign_negative();
+            let abs = if inf { Self::INFINITY } else { Self(0) };
+            return Self(abs.0 | ((neg as u16) << 15));
+        }
+
+        if self.is_sign_negative() && parity.is_none() {
+            return Self::NAN;
+        }
+
+        let neg = odd && self.is_sign_negative();
+        let t = n.mul(self.to_wide().abs().ln());
+
+        let abs = if !t.is_zero() && t.ilogb() >= 15 {
+            if t.neg {
+                Self(0)
+            } else {
+                Self::INFINITY
+            }
+        } else {
+            // Exact results may fall on a midpoint, so snap to boundaries
+            let bits = t
+                .exp()
+                .to_bits_snapped(super::EXP_BITS, super::MANT_BITS, TOLERANCE);
+            Self(bits as u16)
+        };
+
+        Self(abs.0 | ((neg as u16) << 15))
+    }
+}
@@ -0,0 +1,63 @@
+use super::f16;
+use super::pow::TOLERANCE;
+use crate::wide::Wide;
+
+impl f16 {
+    /// Returns the square root of `self`.
+    ///
+    /// `sqrt(±0)` is `±0`, `sqrt(+∞)` is `+∞` and negative arguments give
+    /// NaN.
+    #[inline]
+    pub fn sqrt(self) -> Self {
+        if self.is_nan() {
+            self.quiet()
+        } else if self.is_zero() || self.0 == Self::INFINITY.0 {
+            self
+        } else if self.is_sign_negative() {
+            Self::NAN
+        } else {
+            Self::from_wide(self.to_wide().sqrt())
+        }
+    }
+
+    /// Returns the cube root of `self`.
+    ///
+    /// `cbrt(±0)` is `±0` and `cbrt(±∞)` is `±∞`. Exact cubes give exact
+    /// results.
+    #[inline]
+    pub fn cbrt(self) -> Self {
+        if self.is_nan() {
+            self.quiet()
+        } else if self.is_zero() || self.is_infinite() {
+            self
+        } else {
+            // The cube root of an f16 is never a rounding boundary, so
+            // e^(ln|x| / 3) needs no snapping
+            let x = self.to_wide();
+            let cbrt = x.abs().ln().div_u64(3).exp();
+            Self::from_wide(Wide::from_sign(x.neg, cbrt))
+        }
+    }
+
+    /// Computes `sqrt(self^2 + other^2)` without intermediate overflow or
+    /// underflow.
+    ///
+    /// Special cases follow C23 Annex F: an infinite argument gives `+∞`
+    /// even if the other is NaN.
+    pub fn hypot(self, other: Self) -> Self {
+        if self.is_infinite() || other.is_infinite() {
+            return Self::INFINITY;
+        } else if self.is_nan() || other.is_nan() {
+            return self.propagate_nan(other);
+        }
+
+        // The exact sum of squares may root to a midpoint, as with
+        // hypot(1479, 1972) = 2465
+        let (x, y) = (self.to_wide(), other.to_wide());
+        let sum = x.mul(x).add(y.mul(y));
+        let bits = sum
+            .sqrt()
+            .to_bits_snapped(super::EXP_BITS, super::MANT_BITS, TOLERANCE);
+        Self(bits as u16)
+    }
+}
@@ -1,13 +1,19 @@
 use core::ops::Neg;
 
-mod aarch64;
-mod sw;
-mod x86_64;
+pub(crate) mod aarch64;
+#[macro_use]
+mod cast;
+mod math;
+mod nan;
+mod rem;
+mod slice;
+pub(crate) mod sw;
+pub(crate) mod x86_64;
 
 /// 16-bit floating point type (IEEE 754 half-precision).
 ///
-/// This provides a minimal implementation with construction methods and
-/// conversions through f32.
+/// This provides construction methods, conversions through f32 and correctly
+/// rounded software math functions.
 #[derive(Debug, Clone, Copy, Default)]
 #[allow(non_camel_case_types)]
 #[repr(transparent)]
@@ -57,19 +63,19 @@ impl PartialEq for f16 {
 }
 
 impl f16 {
-    pub const NAN: Self = Self(u16::MAX);
+    pub const NAN: Self = Self(0x7e00);
 
     pub const MIN: Self = Self(0xfbff);
     pub const MAX: Self = Self(0x7bff);
 
-    pub const INFINITY: Self = Self(u16::MAX - 1);
-    pub const NEG_INFINITY: Self = Self(u16::MAX - 2);
+    pub const INFINITY: Self = Self(0x7c00);
+    pub const NEG_INFINITY: Self = Self(0xfc00);
 
     pub const MANTISSA_DIGITS: u32 = 11;
 
     #[inline]
     pub const fn is_nan(self) -> bool {
-        self.0 == Self::NAN.0
+        (self.0 & 0x7fff) > Self::INFINITY.0
     }
 
     #[inline]
@@ -92,6 +98,44 @@ impl f16 {
         (self.0 & (1 << 15)) != 0
     }
 
+    /// Returns the least number greater than `self` (IEEE 754 `nextUp`).
+    ///
+    /// NaN and `INFINITY` are returned unchanged, `NEG_INFINITY` becomes
+    /// `MIN`, and both zeros become the smallest positive subnormal.
+    #[inline]
+    pub const fn next_up(self) -> Self {
+        let abs = self.0 & 0x7fff;
+
+        if self.is_nan() || self.0 == Self::INFINITY.0 {
+            self
+        } else if abs == 0 {
+            Self(1)
+        } else if self.0 == abs {
+            Self(self.0 + 1)
+        } else {
+            Self(self.0 - 1)
+        }
+    }
+
+    /// Returns the greatest number less than `self` (IEEE 754 `nextDown`).
+    ///
+    /// NaN and `NEG_INFINITY` are returned unchanged, `INFINITY` becomes
+    /// `MAX`, and both zeros become the smallest negative subnormal.
+    #[inline]
+    pub const fn next_down(self) -> Self {
+        let abs = self.0 & 0x7fff;
+
+        if self.is_nan() || self.0 == Self::NEG_INFINITY.0 {
+            self
+        } else if abs == 0 {
+            Self(0x8001)
+        } else if self.0 == abs {
+            Self(self.0 - 1)
+        } else {
+            Self(self.0 + 1)
+        }
+    }
+
     /// Creates an `f16` from its representation as a `u16`.
     #[inline]
     pub const fn from_bits(bits: u16) -> Self {
@@ -0,0 +1,11 @@
+use super::f16;
+
+const QUIET_BIT: u16 = 0x200;
+
+impl f16 {
+    /// Sets the quiet bit, turning a signaling NaN into a quiet NaN.
+    #[inline]
+    pub(crate) const fn quiet(self) -> Self {
+        Self(self.0 | QUIET_BIT)
+    }
+}
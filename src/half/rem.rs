@@ -0,0 +1,25 @@
+use core::ops::{Rem, RemAssign};
+
+use super::f16;
+use crate::rem;
+
+/// The remainder of truncated division, as C `fmod`.
+///
+/// The result is exact and has the sign of `self`. NaNs propagate, an
+/// infinite `self` or a zero `rhs` gives NaN, and an infinite `rhs` leaves
+/// a finite `self` unchanged.
+impl Rem for f16 {
+    type Output = Self;
+
+    #[inline]
+    fn rem(self, rhs: Self) -> Self::Output {
+        rem::fmod(self, rhs)
+    }
+}
+
+impl RemAssign for f16 {
+    #[inline]
+    fn rem_assign(&mut self, rhs: Self) {
+        *self = *self % rhs;
+    }
+}
@@ -0,0 +1,87 @@
+#![cfg(feature = "casting")]
+
+use super::{f16, sw};
+use crate::slice::{check_len, each};
+use crate::ConvertSlice;
+
+impl ConvertSlice for f16 {
+    /// Converts a slice of `f32` to `f16`.
+    ///
+    /// Each element is rounded exactly as by `CastFrom`, but the backend is
+    /// chosen once and x86-64 hardware converts 8 (F16C) or 16 (AVX-512)
+    /// elements at a time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` and `dst` have different lengths.
+    #[allow(unsafe_code)]
+    fn convert_from_f32_slice(src: &[f32], dst: &mut [Self]) {
+        check_len(src, dst);
+
+        dispatch! {
+            avx512fp16: unsafe { super::x86_64::avx::f16_from_f32_slice(src, dst) },
+            f16c: unsafe { super::x86_64::f16c::f16_from_f32_slice(src, dst) },
+            fp16: each(src, dst, |x| unsafe { super::aarch64::f16_from_f32(x) }),
+            software: each(src, dst, sw::f16_from_f32),
+        }
+    }
+
+    /// Converts a slice of `f16` to `f32`.
+    ///
+    /// Each element is converted exactly as by `CastFrom`, but the backend
+    /// is chosen once and x86-64 hardware converts 8 (F16C) or 16 (AVX-512)
+    /// elements at a time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` and `dst` have different lengths.
+    #[allow(unsafe_code)]
+    fn convert_to_f32_slice(src: &[Self], dst: &mut [f32]) {
+        check_len(src, dst);
+
+        dispatch! {
+            avx512fp16: unsafe { super::x86_64::avx::f32_from_f16_slice(src, dst) },
+            f16c: unsafe { super::x86_64::f16c::f32_from_f16_slice(src, dst) },
+            fp16: each(src, dst, |x| unsafe { super::aarch64::f32_from_f16(x) }),
+            software: each(src, dst, sw::f32_from_f16),
+        }
+    }
+
+    /// Converts a slice of `f64` to `f16`.
+    ///
+    /// Each element is rounded exactly as by `CastFrom`, but the backend is
+    /// chosen once and AVX-512 FP16 converts 8 elements at a time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` and `dst` have different lengths.
+    #[allow(unsafe_code)]
+    fn convert_from_f64_slice(src: &[f64], dst: &mut [Self]) {
+        check_len(src, dst);
+
+        dispatch! {
+            avx512fp16: unsafe { super::x86_64::avx::f16_from_f64_slice(src, dst) },
+            fp16: each(src, dst, |x| unsafe { super::aarch64::f16_from_f64(x) }),
+            software: each(src, dst, sw::f16_from_f64),
+        }
+    }
+
+    /// Converts a slice of `f16` to `f64`.
+    ///
+    /// Each element is converted exactly as by `CastFrom`, but the backend
+    /// is chosen once and AVX-512 FP16 converts 8 elements at a time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` and `dst` have different lengths.
+    #[allow(unsafe_code)]
+    fn convert_to_f64_slice(src: &[Self], dst: &mut [f64]) {
+        check_len(src, dst);
+
+        dispatch! {
+            avx512fp16: unsafe { super::x86_64::avx::f64_from_f16_slice(src, dst) },
+            fp16: each(src, dst, |x| unsafe { super::aarch64::f64_from_f16(x) }),
+            software: each(src, dst, sw::f64_from_f16),
+        }
+    }
+}
@@ -1,107 +1,76 @@
-#![cfg(not(any(
-    all(feature = "asm", target_arch = "aarch64", target_feature = "fp16"),
-    all(feature = "asm", target_arch = "x86_64", target_feature = "f16c")
-)))]
+#![cfg(not(feature = "tables"))]
 
 use crate::f16;
 
-use casting::CastFrom;
-
-const F16_INF: u16 = 0x7C00;
-
-/// Lookup table for f16 exponent -> f32 exponent conversion.
-/// Index is f16 exponent (0-31), value is f32 exponent shifted to position.
-/// Special cases: 0 = denormal/zero, 31 = inf/nan
-const F16_TO_F32_EXP: [u32; 32] = {
-    let mut table = [0u32; 32];
-    let mut i = 1u32;
-    while i < 31 {
-        // f16 exp i maps to f32 exp (i + 112), shifted to bit position 23
-        // 112 = f32 bias (127) - f16 bias (15)
-        table[i as usize] = (i + 112) << 23;
-        i += 1;
-    }
-    // exp 31 = infinity/NaN
-    table[31] = 0x7F800000;
-    table
-};
-
-// Only provide software implementation if no hardware acceleration is available
-
-impl CastFrom<f16> for f32 {
-    #[inline]
-    fn cast_from(value: f16) -> f32 {
-        let bits = value.0 as u32;
-        let sign = (bits & 0x8000) << 16;
-        let exp = (bits >> 10) & 0x1F;
-        let mant = bits & 0x3FF;
-
-        // Fast path: normal numbers and infinity/NaN (exponent 1-31)
-        if exp != 0 {
-            return f32::from_bits(sign | F16_TO_F32_EXP[exp as usize] | (mant << 13));
-        }
-
-        // Zero or denormalized
-        if mant == 0 {
-            return f32::from_bits(sign);
-        }
-
-        // Denormalized: normalize by finding the leading 1 bit
-        let shift = mant.leading_zeros() - 22;
-        let normalized_mant = ((mant << (shift + 1)) & 0x3FF) << 13;
-        let f32_exp = (113u32 - shift) << 23;
-        f32::from_bits(sign | f32_exp | normalized_mant)
-    }
+// Both conversions compute every case and select the result with masks, so
+// their speed does not depend on the input. Subnormals are handled by f32
+// arithmetic, which is exact or rounds ties to even as required.
+
+/// Returns all ones if `cond` holds, else zero.
+#[inline]
+fn mask(cond: bool) -> u32 {
+    (cond as u32).wrapping_neg()
+}
+
+/// The f32 exponent of 2^-14, the smallest normal f16.
+const MIN_NORMAL: u32 = 113 << 23;
+
+#[inline]
+pub(crate) fn f32_from_f16(value: f16) -> f32 {
+    let bits = value.0 as u32;
+    let sign = (bits & 0x8000) << 16;
+
+    // Exponent and mantissa in their f32 positions
+    let abs = (bits & 0x7FFF) << 13;
+    let exp = abs & 0x0F80_0000;
+
+    // Normal numbers only need the exponent rebiased, by 127 - 15
+    let normal = abs + (112 << 23);
+
+    // Infinity and NaN move to the top exponent, and NaNs are quieted with
+    // the payload kept in the top mantissa bits
+    let special = (normal + (112 << 23)) | (mask(abs > 0x0F80_0000) & 0x0040_0000);
+
+    // Subnormals are the mantissa times 2^-24: scale it as a normal number,
+    // then subtract the implicit bit
+    let subnormal = (f32::from_bits(abs | MIN_NORMAL) - f32::from_bits(MIN_NORMAL)).to_bits();
+
+    let is_special = mask(exp == 0x0F80_0000);
+    let is_subnormal = mask(exp == 0);
+    let is_normal = !(is_special | is_subnormal);
+
+    f32::from_bits(
+        sign | (normal & is_normal) | (special & is_special) | (subnormal & is_subnormal),
+    )
 }
 
-impl CastFrom<f32> for f16 {
-    #[inline]
-    fn cast_from(value: f32) -> Self {
-        let bits = value.to_bits();
-        let sign = ((bits >> 16) & 0x8000) as u16;
-        let f32_exp = (bits >> 23) & 0xFF;
-        let f32_mant = bits & 0x7FFFFF;
-
-        // Zero or denormal f32 → zero
-        if f32_exp == 0 {
-            return Self(sign);
-        }
-
-        // Infinity or NaN
-        if f32_exp == 255 {
-            // Preserve NaN payload (use quiet NaN bit)
-            return Self(sign | F16_INF | if f32_mant != 0 { 0x0200 } else { 0 });
-        }
-
-        // Rebias exponent
-        let f16_exp = f32_exp as i32 - 112;
-
-        // Overflow → infinity (exp > 30, since 31 is reserved for inf/nan)
-        if f16_exp > 30 {
-            return Self(sign | F16_INF);
-        }
-
-        // Denormalized result
-        if f16_exp <= 0 {
-            let shift = (14 - f16_exp) as u32;
-            return if shift >= 24 {
-                Self(sign)
-            } else {
-                Self(sign | (((f32_mant | 0x800000) >> shift) as u16))
-            };
-        }
-
-        // Normal case with rounding
-        let mant = (f32_mant >> 13) as u16;
-        let round = ((f32_mant >> 12) & 1) as u16;
-        let rounded = mant + round;
-
-        if rounded < 0x400 {
-            Self(sign | ((f16_exp as u16) << 10) | rounded)
-        } else if f16_exp >= 30 {
-            Self(sign | F16_INF)
-        } else {
-            Self(sign | (((f16_exp + 1) as u16) << 10))
-        }
-    }
+#[inline]
+pub(crate) fn f16_from_f32(value: f32) -> f16 {
+    let bits = value.to_bits();
+    let sign = (bits >> 16) & 0x8000;
+    let abs = bits & 0x7FFF_FFFF;
+
+    // Overflow, infinity and NaN: NaNs are quieted and keep the top payload
+    // bits. 2^16 is the first magnitude that rounds to infinity.
+    let nan = mask(abs > 0x7F80_0000) & (0x0200 | ((abs >> 13) & 0x03FF));
+    let special = 0x7C00 | nan;
+
+    // Subnormal results: adding 0.5 leaves the f16 mantissa in the low bits,
+    // rounded to nearest even by the addition itself
+    let half = f32::from_bits(126 << 23);
+    let subnormal = (f32::from_bits(abs) + half)
+        .to_bits()
+        .wrapping_sub(126 << 23);
+
+    // Normal results: rebias, then round to nearest even by adding just
+    // under half an ulp plus the parity bit. A carry moves into the exponent,
+    // and from the largest finite value into infinity.
+    let odd = (abs >> 13) & 1;
+    let normal = abs.wrapping_sub(112 << 23).wrapping_add(0x0FFF + odd) >> 13;
+
+    let is_special = mask(abs >= 143 << 23);
+    let is_subnormal = mask(abs < MIN_NORMAL);
+    let is_normal = !(is_special | is_subnormal);
+
+    f16((sign | (normal & is_normal) | (special & is_special) | (subnormal & is_subnormal)) as u16)
 }
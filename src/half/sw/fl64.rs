@@ -1,21 +1,42 @@
-#![cfg(not(any(
-    all(feature = "asm", target_arch = "aarch64", target_feature = "fp16"),
-    all(feature = "asm", target_arch = "x86_64", target_feature = "avx512fp16")
-)))]
-
 use super::super::f16;
-use casting::CastFrom;
+use super::{f16_from_f32, f32_from_f16};
+
+// NaNs are handled here rather than through `as`, which does not specify
+// which NaN payload it produces.
 
-impl CastFrom<f16> for f64 {
-    #[inline]
-    fn cast_from(value: f16) -> f64 {
-        f32::cast_from(value) as f64
+#[inline]
+pub(crate) fn f64_from_f16(value: f16) -> f64 {
+    if value.is_nan() {
+        let sign = ((value.0 & 0x8000) as u64) << 48;
+        let payload = ((value.0 & 0x3FF) as u64) << 42;
+        return f64::from_bits(sign | 0x7FF8_0000_0000_0000 | payload);
     }
+
+    f32_from_f16(value) as f64
 }
 
-impl CastFrom<f64> for f16 {
-    #[inline]
-    fn cast_from(value: f64) -> f16 {
-        f16::cast_from(value as f32)
+#[inline]
+pub(crate) fn f16_from_f64(value: f64) -> f16 {
+    let bits = value.to_bits();
+
+    if value.is_nan() {
+        let sign = ((bits >> 48) & 0x8000) as u16;
+        let payload = ((bits >> 42) & 0x3FF) as u16;
+        return f16(sign | 0x7E00 | payload);
     }
+
+    // Rounding through f32 would round twice. Rounding to odd instead keeps
+    // an inexact result off the f16 midpoints, so the second rounding is
+    // the only one.
+    let x = value as f32;
+    let mut bits = x.to_bits();
+    if x.is_finite() && f64::from(x) != value {
+        // Step toward zero if the magnitude was rounded up
+        if (f64::from(x) > value) == (value > 0.0) {
+            bits -= 1;
+        }
+        bits |= 1;
+    }
+
+    f16_from_f32(f32::from_bits(bits))
 }
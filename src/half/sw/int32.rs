@@ -1,35 +1,22 @@
-#![cfg(not(any(
-    all(feature = "asm", target_arch = "aarch64", target_feature = "fp16"),
-    all(feature = "asm", target_arch = "x86_64", target_feature = "avx512fp16")
-)))]
-
 use super::super::f16;
-use casting::CastFrom;
+use super::{f16_from_f32, f32_from_f16};
 
-impl CastFrom<u32> for f16 {
-    #[inline]
-    fn cast_from(value: u32) -> f16 {
-        f16::cast_from(value as f32)
-    }
+#[inline]
+pub(crate) fn f16_from_u32(value: u32) -> f16 {
+    f16_from_f32(value as f32)
 }
 
-impl CastFrom<f16> for u32 {
-    #[inline]
-    fn cast_from(value: f16) -> u32 {
-        f32::cast_from(value).clamp(u32::MIN as f32, u32::MAX as f32) as u32
-    }
+#[inline]
+pub(crate) fn u32_from_f16(value: f16) -> u32 {
+    f32_from_f16(value).clamp(u32::MIN as f32, u32::MAX as f32) as u32
 }
 
-impl CastFrom<i32> for f16 {
-    #[inline]
-    fn cast_from(value: i32) -> f16 {
-        f16::cast_from(value as f32)
-    }
+#[inline]
+pub(crate) fn f16_from_i32(value: i32) -> f16 {
+    f16_from_f32(value as f32)
 }
 
-impl CastFrom<f16> for i32 {
-    #[inline]
-    fn cast_from(value: f16) -> i32 {
-        f32::cast_from(value).clamp(i32::MIN as f32, i32::MAX as f32) as i32
-    }
+#[inline]
+pub(crate) fn i32_from_f16(value: f16) -> i32 {
+    f32_from_f16(value).clamp(i32::MIN as f32, i32::MAX as f32) as i32
 }
@@ -7,3 +7,12 @@ mod int16;
 mod int32;
 mod int64;
 mod int8;
+mod tables;
+
+pub(crate) use self::{fl64::*, int32::*, int64::*};
+
+#[cfg(not(feature = "tables"))]
+pub(crate) use self::fl32::*;
+
+#[cfg(feature = "tables")]
+pub(crate) use self::tables::*;
@@ -0,0 +1,120 @@
+#![cfg(feature = "tables")]
+
+use crate::f16;
+
+// Table-driven conversions: every f16 has its f32 precomputed (256 KiB), and
+// f32 -> f16 looks up a base and a shift by sign and exponent (1.5 KiB).
+// The tables are built by integer code at compile time.
+
+/// The f32 bits of every f16, in quarters by the top two bits, since rustc
+/// limits how long a single constant may take to evaluate.
+#[allow(clippy::large_const_arrays)]
+static F16_TO_F32: [[u32; 1 << 14]; 4] = {
+    const Q0: [u32; 1 << 14] = quarter(0);
+    const Q1: [u32; 1 << 14] = quarter(1);
+    const Q2: [u32; 1 << 14] = quarter(2);
+    const Q3: [u32; 1 << 14] = quarter(3);
+    [Q0, Q1, Q2, Q3]
+};
+
+const fn quarter(top: u32) -> [u32; 1 << 14] {
+    let mut table = [0; 1 << 14];
+    let mut i = 0;
+    while i < table.len() {
+        table[i] = f32_bits((top << 14) | i as u32);
+        i += 1;
+    }
+    table
+}
+
+const fn f32_bits(bits: u32) -> u32 {
+    let sign = (bits & 0x8000) << 16;
+    let exp = (bits >> 10) & 0x1F;
+    let mant = bits & 0x3FF;
+
+    if exp == 0x1F {
+        // Infinity, and NaNs quieted with the payload in the top bits
+        let quiet = if mant != 0 { 0x0040_0000 } else { 0 };
+        sign | 0x7F80_0000 | quiet | (mant << 13)
+    } else if exp != 0 {
+        sign | ((exp + 112) << 23) | (mant << 13)
+    } else if mant == 0 {
+        sign
+    } else {
+        // Subnormal: normalize by the leading 1 bit
+        let shift = mant.leading_zeros() - 21;
+        sign | ((113 - shift) << 23) | (((mant << shift) & 0x3FF) << 13)
+    }
+}
+
+/// The f16 bits for an f32 sign and exponent, to which the rounded
+/// mantissa is added.
+static BASE: [u16; 512] = {
+    let mut table = [0; 512];
+    let mut i = 0;
+    while i < 512 {
+        let (base, _) = base_shift(i as u32);
+        table[i] = base;
+        i += 1;
+    }
+    table
+};
+
+/// How far the f32 mantissa, with its implicit bit, shifts right to become
+/// the f16 mantissa.
+static SHIFT: [u8; 512] = {
+    let mut table = [0; 512];
+    let mut i = 0;
+    while i < 512 {
+        let (_, shift) = base_shift(i as u32);
+        table[i] = shift;
+        i += 1;
+    }
+    table
+};
+
+const fn base_shift(index: u32) -> (u16, u8) {
+    let sign = ((index & 0x100) << 7) as u16;
+    let exp = (index & 0xFF) as i32 - 112;
+
+    if exp >= 31 {
+        // Overflow, infinity and NaN, with nothing left of the mantissa
+        (sign | 0x7C00, 25)
+    } else if exp > 0 {
+        // Normal: the implicit bit adds one to the exponent
+        (sign | ((exp - 1) << 10) as u16, 13)
+    } else if exp >= -10 {
+        // Subnormal
+        (sign, (14 - exp) as u8)
+    } else {
+        // Underflow to zero, even when rounding up
+        (sign, 25)
+    }
+}
+
+#[inline]
+pub(crate) fn f32_from_f16(value: f16) -> f32 {
+    let bits = value.0 as usize;
+    f32::from_bits(F16_TO_F32[bits >> 14][bits & 0x3FFF])
+}
+
+#[inline]
+pub(crate) fn f16_from_f32(value: f32) -> f16 {
+    let bits = value.to_bits();
+    let index = (bits >> 23) as usize;
+    let shift = SHIFT[index] as u32;
+    let mant = (bits & 0x007F_FFFF) | 0x0080_0000;
+
+    // Round to nearest, ties to even, by adding just under half an ulp plus
+    // the parity bit. A carry moves into the exponent, and from the largest
+    // finite value into infinity.
+    let odd = (mant >> shift) & 1;
+    let mut rounded = BASE[index] as u32 + ((mant + (1 << (shift - 1)) - 1 + odd) >> shift);
+
+    // NaNs are quieted and keep the top payload bits
+    if bits & 0x7FFF_FFFF > 0x7F80_0000 {
+        rounded |= 0x0200 | ((bits >> 13) & 0x03FF);
+    }
+
+    f16(rounded as u16)
+}
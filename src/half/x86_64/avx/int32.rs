@@ -1,84 +1,95 @@
-use super::super::f16;
-use casting::CastFrom;
+use crate::f16;
 
 // AVX-512 FP16: f16 <-> i32/u32 conversions (available since Sapphire Rapids, 2023)
+//
+// Out-of-range inputs give the "integer indefinite" value instead of
+// saturating as `as` does, so NaNs, infinities and negative unsigned results
+// are handled first. Every finite f16 is in range otherwise.
 
-impl CastFrom<f16> for i32 {
-    #[inline]
-    #[allow(unsafe_code)]
-    fn cast_from(value: f16) -> i32 {
-        let result: i32;
+#[inline]
+#[allow(unsafe_code)]
+#[target_feature(enable = "avx512fp16")]
+pub(crate) unsafe fn i32_from_f16(value: f16) -> i32 {
+    if !value.is_finite() {
+        return if value.is_nan() {
+            0
+        } else if value.is_sign_negative() {
+            i32::MIN
+        } else {
+            i32::MAX
+        };
+    }
 
-        unsafe {
-            core::arch::asm!(
-                "vmovd xmm0, eax",          // Move u16 to xmm0
-                "vcvtsh2si eax, xmm0",      // Convert scalar f16 to i32
-                in("eax") value.0 as u32,
-                out("eax") result,
-                options(pure, nomem, nostack)
-            );
-        }
+    let result: i32;
 
-        result
-    }
+    core::arch::asm!(
+        "vmovd xmm0, eax",          // Move u16 to xmm0
+        "vcvttsh2si eax, xmm0",     // Truncate scalar f16 to i32
+        in("eax") value.0 as u32,
+        lateout("eax") result,
+        out("xmm0") _,
+        options(pure, nomem, nostack)
+    );
+
+    result
 }
 
-impl CastFrom<i32> for f16 {
-    #[inline]
-    #[allow(unsafe_code)]
-    fn cast_from(value: i32) -> f16 {
-        let result: u16;
+#[inline]
+#[allow(unsafe_code)]
+#[target_feature(enable = "avx512fp16")]
+pub(crate) unsafe fn f16_from_i32(value: i32) -> f16 {
+    let result: u16;
 
-        unsafe {
-            core::arch::asm!(
-                "vcvtsi2sh xmm0, xmm0, eax",   // Convert i32 to scalar f16
-                "vmovd eax, xmm0",             // Move result to eax
-                in("eax") value,
-                out("eax") result,
-                options(pure, nomem, nostack)
-            );
-        }
+    core::arch::asm!(
+        "vcvtsi2sh xmm0, xmm0, eax",   // Convert i32 to scalar f16
+        "vmovd eax, xmm0",             // Move result to eax
+        in("eax") value,
+        lateout("eax") result,
+        out("xmm0") _,
+        options(pure, nomem, nostack)
+    );
 
-        f16(result)
-    }
+    f16(result)
 }
 
-impl CastFrom<f16> for u32 {
-    #[inline]
-    #[allow(unsafe_code)]
-    fn cast_from(value: f16) -> u32 {
-        let result: u32;
+#[inline]
+#[allow(unsafe_code)]
+#[target_feature(enable = "avx512fp16")]
+pub(crate) unsafe fn u32_from_f16(value: f16) -> u32 {
+    if value.is_nan() || value.is_sign_negative() {
+        return 0;
+    } else if value.is_infinite() {
+        return u32::MAX;
+    }
 
-        unsafe {
-            core::arch::asm!(
-                "vmovd xmm0, eax",          // Move u16 to xmm0
-                "vcvtsh2usi eax, xmm0",     // Convert scalar f16 to u32
-                in("eax") value.0 as u32,
-                out("eax") result,
-                options(pure, nomem, nostack)
-            );
-        }
+    let result: u32;
 
-        result
-    }
+    core::arch::asm!(
+        "vmovd xmm0, eax",          // Move u16 to xmm0
+        "vcvttsh2usi eax, xmm0",    // Truncate scalar f16 to u32
+        in("eax") value.0 as u32,
+        lateout("eax") result,
+        out("xmm0") _,
+        options(pure, nomem, nostack)
+    );
+
+    result
 }
 
-impl CastFrom<u32> for f16 {
-    #[inline]
-    #[allow(unsafe_code)]
-    fn cast_from(value: u32) -> f16 {
-        let result: u16;
+#[inline]
+#[allow(unsafe_code)]
+#[target_feature(enable = "avx512fp16")]
+pub(crate) unsafe fn f16_from_u32(value: u32) -> f16 {
+    let result: u16;
 
-        unsafe {
-            core::arch::asm!(
-                "vcvtusi2sh xmm0, xmm0, eax",  // Convert u32 to scalar f16
-                "vmovd eax, xmm0",             // Move result to eax
-                in("eax") value,
-                out("eax") result,
-                options(pure, nomem, nostack)
-            );
-        }
+    core::arch::asm!(
+        "vcvtusi2sh xmm0, xmm0, eax",  // Convert u32 to scalar f16
+        "vmovd eax, xmm0",             // Move result to eax
+        in("eax") value,
+        lateout("eax") result,
+        out("xmm0") _,
+        options(pure, nomem, nostack)
+    );
 
-        f16(result)
-    }
+    f16(result)
 }
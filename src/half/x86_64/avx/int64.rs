@@ -1,84 +1,95 @@
-use super::super::f16;
-use casting::CastFrom;
+use crate::f16;
 
 // AVX-512 FP16: f16 <-> i64/u64 conversions (available since Sapphire Rapids, 2023)
+//
+// Out-of-range inputs give the "integer indefinite" value instead of
+// saturating as `as` does, so NaNs, infinities and negative unsigned results
+// are handled first. Every finite f16 is in range otherwise.
 
-impl CastFrom<f16> for i64 {
-    #[inline]
-    #[allow(unsafe_code)]
-    fn cast_from(value: f16) -> i64 {
-        let result: i64;
+#[inline]
+#[allow(unsafe_code)]
+#[target_feature(enable = "avx512fp16")]
+pub(crate) unsafe fn i64_from_f16(value: f16) -> i64 {
+    if !value.is_finite() {
+        return if value.is_nan() {
+            0
+        } else if value.is_sign_negative() {
+            i64::MIN
+        } else {
+            i64::MAX
+        };
+    }
 
-        unsafe {
-            core::arch::asm!(
-                "vmovd xmm0, eax",          // Move u16 to xmm0
-                "vcvtsh2si rax, xmm0",      // Convert scalar f16 to i64
-                in("eax") value.0 as u32,
-                out("rax") result,
-                options(pure, nomem, nostack)
-            );
-        }
+    let result: i64;
 
-        result
-    }
+    core::arch::asm!(
+        "vmovd xmm0, eax",          // Move u16 to xmm0
+        "vcvttsh2si rax, xmm0",     // Truncate scalar f16 to i64
+        in("rax") value.0 as u64,
+        lateout("rax") result,
+        out("xmm0") _,
+        options(pure, nomem, nostack)
+    );
+
+    result
 }
 
-impl CastFrom<i64> for f16 {
-    #[inline]
-    #[allow(unsafe_code)]
-    fn cast_from(value: i64) -> f16 {
-        let result: u16;
+#[inline]
+#[allow(unsafe_code)]
+#[target_feature(enable = "avx512fp16")]
+pub(crate) unsafe fn f16_from_i64(value: i64) -> f16 {
+    let result: u16;
 
-        unsafe {
-            core::arch::asm!(
-                "vcvtsi2sh xmm0, xmm0, rax",   // Convert i64 to scalar f16
-                "vmovd eax, xmm0",             // Move result to eax
-                in("rax") value,
-                out("eax") result,
-                options(pure, nomem, nostack)
-            );
-        }
+    core::arch::asm!(
+        "vcvtsi2sh xmm0, xmm0, rax",   // Convert i64 to scalar f16
+        "vmovd eax, xmm0",             // Move result to eax
+        in("rax") value,
+        lateout("rax") result,
+        out("xmm0") _,
+        options(pure, nomem, nostack)
+    );
 
-        f16(result)
-    }
+    f16(result)
 }
 
-impl CastFrom<f16> for u64 {
-    #[inline]
-    #[allow(unsafe_code)]
-    fn cast_from(value: f16) -> u64 {
-        let result: u64;
+#[inline]
+#[allow(unsafe_code)]
+#[target_feature(enable = "avx512fp16")]
+pub(crate) unsafe fn u64_from_f16(value: f16) -> u64 {
+    if value.is_nan() || value.is_sign_negative() {
+        return 0;
+    } else if value.is_infinite() {
+        return u64::MAX;
+    }
 
-        unsafe {
-            core::arch::asm!(
-                "vmovd xmm0, eax",          // Move u16 to xmm0
-                "vcvtsh2usi rax, xmm0",     // Convert scalar f16 to u64
-                in("eax") value.0 as u32,
-                out("rax") result,
-                options(pure, nomem, nostack)
-            );
-        }
+    let result: u64;
 
-        result
-    }
+    core::arch::asm!(
+        "vmovd xmm0, eax",          // Move u16 to xmm0
+        "vcvttsh2usi rax, xmm0",    // Truncate scalar f16 to u64
+        in("rax") value.0 as u64,
+        lateout("rax") result,
+        out("xmm0") _,
+        options(pure, nomem, nostack)
+    );
+
+    result
 }
 
-impl CastFrom<u64> for f16 {
-    #[inline]
-    #[allow(unsafe_code)]
-    fn cast_from(value: u64) -> f16 {
-        let result: u16;
+#[inline]
+#[allow(unsafe_code)]
+#[target_feature(enable = "avx512fp16")]
+pub(crate) unsafe fn f16_from_u64(value: u64) -> f16 {
+    let result: u16;
 
-        unsafe {
-            core::arch::asm!(
-                "vcvtusi2sh xmm0, xmm0, rax",  // Convert u64 to scalar f16
-                "vmovd eax, xmm0",             // Move result to eax
-                in("rax") value,
-                out("eax") result,
-                options(pure, nomem, nostack)
-            );
-        }
+    core::arch::asm!(
+        "vcvtusi2sh xmm0, xmm0, rax",  // Convert u64 to scalar f16
+        "vmovd eax, xmm0",             // Move result to eax
+        in("rax") value,
+        lateout("rax") result,
+        out("xmm0") _,
+        options(pure, nomem, nostack)
+    );
 
-        f16(result)
-    }
+    f16(result)
 }
@@ -2,3 +2,6 @@ mod fl32;
 mod fl64;
 mod int32;
 mod int64;
+mod slice;
+
+pub(crate) use self::{fl32::*, fl64::*, int32::*, int64::*, slice::*};
@@ -0,0 +1,97 @@
+use super::{f16_from_f32, f16_from_f64, f32_from_f16, f64_from_f16};
+use crate::f16;
+
+// AVX-512: 16-wide f16 <-> f32 and 8-wide f16 <-> f64 conversions, with the
+// scalar forms for the tail. The f32 forms need only AVX-512F.
+
+#[inline]
+#[allow(unsafe_code)]
+#[target_feature(enable = "avx512fp16")]
+pub(crate) unsafe fn f32_from_f16_slice(src: &[f16], dst: &mut [f32]) {
+    let mut src = src.chunks_exact(16);
+    let mut dst = dst.chunks_exact_mut(16);
+
+    for (s, d) in (&mut src).zip(&mut dst) {
+        core::arch::asm!(
+            "vcvtph2ps zmm0, ymmword ptr [{src}]",  // Convert 16 f16 to f32
+            "vmovups zmmword ptr [{dst}], zmm0",    // Store 16 f32
+            src = in(reg) s.as_ptr(),
+            dst = in(reg) d.as_mut_ptr(),
+            out("zmm0") _,
+            options(nostack)
+        );
+    }
+
+    for (s, d) in src.remainder().iter().zip(dst.into_remainder()) {
+        *d = f32_from_f16(*s);
+    }
+}
+
+#[inline]
+#[allow(unsafe_code)]
+#[target_feature(enable = "avx512fp16")]
+pub(crate) unsafe fn f16_from_f32_slice(src: &[f32], dst: &mut [f16]) {
+    let mut src = src.chunks_exact(16);
+    let mut dst = dst.chunks_exact_mut(16);
+
+    for (s, d) in (&mut src).zip(&mut dst) {
+        core::arch::asm!(
+            "vmovups zmm0, zmmword ptr [{src}]",        // Load 16 f32
+            "vcvtps2ph ymmword ptr [{dst}], zmm0, 0",   // Convert to f16 with round-to-nearest
+            src = in(reg) s.as_ptr(),
+            dst = in(reg) d.as_mut_ptr(),
+            out("zmm0") _,
+            options(nostack)
+        );
+    }
+
+    for (s, d) in src.remainder().iter().zip(dst.into_remainder()) {
+        *d = f16_from_f32(*s);
+    }
+}
+
+#[inline]
+#[allow(unsafe_code)]
+#[target_feature(enable = "avx512fp16")]
+pub(crate) unsafe fn f64_from_f16_slice(src: &[f16], dst: &mut [f64]) {
+    let mut src = src.chunks_exact(8);
+    let mut dst = dst.chunks_exact_mut(8);
+
+    for (s, d) in (&mut src).zip(&mut dst) {
+        core::arch::asm!(
+            "vcvtph2pd zmm0, xmmword ptr [{src}]",  // Convert 8 f16 to f64
+            "vmovupd zmmword ptr [{dst}], zmm0",    // Store 8 f64
+            src = in(reg) s.as_ptr(),
+            dst = in(reg) d.as_mut_ptr(),
+            out("zmm0") _,
+            options(nostack)
+        );
+    }
+
+    for (s, d) in src.remainder().iter().zip(dst.into_remainder()) {
+        *d = f64_from_f16(*s);
+    }
+}
+
+#[inline]
+#[allow(unsafe_code)]
+#[target_feature(enable = "avx512fp16")]
+pub(crate) unsafe fn f16_from_f64_slice(src: &[f64], dst: &mut [f16]) {
+    let mut src = src.chunks_exact(8);
+    let mut dst = dst.chunks_exact_mut(8);
+
+    for (s, d) in (&mut src).zip(&mut dst) {
+        core::arch::asm!(
+            "vcvtpd2ph xmm0, zmmword ptr [{src}]",  // Convert 8 f64 to f16
+            "vmovdqu xmmword ptr [{dst}], xmm0",    // Store 8 f16
+            src = in(reg) s.as_ptr(),
+            dst = in(reg) d.as_mut_ptr(),
+            out("zmm0") _,
+            options(nostack)
+        );
+    }
+
+    for (s, d) in src.remainder().iter().zip(dst.into_remainder()) {
+        *d = f16_from_f64(*s);
+    }
+}
@@ -1 +1,4 @@
 mod fl32;
+mod slice;
+
+pub(crate) use self::{fl32::*, slice::*};
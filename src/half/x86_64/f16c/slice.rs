@@ -0,0 +1,50 @@
+use super::{f16_from_f32, f32_from_f16};
+use crate::f16;
+
+// F16C: 8-wide f16 <-> f32 conversions, with the scalar forms for the tail
+
+#[inline]
+#[allow(unsafe_code)]
+#[target_feature(enable = "f16c")]
+pub(crate) unsafe fn f32_from_f16_slice(src: &[f16], dst: &mut [f32]) {
+    let mut src = src.chunks_exact(8);
+    let mut dst = dst.chunks_exact_mut(8);
+
+    for (s, d) in (&mut src).zip(&mut dst) {
+        core::arch::asm!(
+            "vcvtph2ps ymm0, xmmword ptr [{src}]",  // Convert 8 f16 to f32
+            "vmovups ymmword ptr [{dst}], ymm0",    // Store 8 f32
+            src = in(reg) s.as_ptr(),
+            dst = in(reg) d.as_mut_ptr(),
+            out("ymm0") _,
+            options(nostack)
+        );
+    }
+
+    for (s, d) in src.remainder().iter().zip(dst.into_remainder()) {
+        *d = f32_from_f16(*s);
+    }
+}
+
+#[inline]
+#[allow(unsafe_code)]
+#[target_feature(enable = "f16c")]
+pub(crate) unsafe fn f16_from_f32_slice(src: &[f32], dst: &mut [f16]) {
+    let mut src = src.chunks_exact(8);
+    let mut dst = dst.chunks_exact_mut(8);
+
+    for (s, d) in (&mut src).zip(&mut dst) {
+        core::arch::asm!(
+            "vmovups ymm0, ymmword ptr [{src}]",        // Load 8 f32
+            "vcvtps2ph xmmword ptr [{dst}], ymm0, 0",   // Convert to f16 with round-to-nearest
+            src = in(reg) s.as_ptr(),
+            dst = in(reg) d.as_mut_ptr(),
+            out("ymm0") _,
+            options(nostack)
+        );
+    }
+
+    for (s, d) in src.remainder().iter().zip(dst.into_remainder()) {
+        *d = f16_from_f32(*s);
+    }
+}
@@ -1,8 +1,11 @@
 #![cfg(all(feature = "asm", target_arch = "x86_64"))]
 #![cfg(feature = "casting")]
 
-#[cfg(target_feature = "avx512fp16")]
-mod avx;
+// Every function requires the feature its module is named for, which the
+// caller must check
 
-#[cfg(all(not(target_feature = "avx512fp16"), target_feature = "f16c"))]
-mod f16c;
+#[cfg(any(target_feature = "avx512fp16", feature = "runtime-detect"))]
+pub(crate) mod avx;
+
+#[cfg(any(target_feature = "f16c", feature = "runtime-detect"))]
+pub(crate) mod f16c;
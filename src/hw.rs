@@ -0,0 +1,200 @@
+#![cfg(feature = "casting")]
+
+//! Hardware conversions.
+//!
+//! The functions here convert with the active [`Backend`](crate::Backend)
+//! and return `None` when it has no instruction for the conversion, so they
+//! never fall back to [`soft`](crate::soft) silently.
+//!
+//! The submodules expose each instruction set directly. One is compiled
+//! wherever its backend can be active: when the target feature is enabled at
+//! compile time, or on the matching architecture with `runtime-detect`. Its
+//! functions are `unsafe` because they execute the instructions
+//! unconditionally; check `is_available` first.
+
+use crate::f16;
+
+/// Returns whether the CPU supports a target feature.
+#[allow(unused_macros)]
+macro_rules! available {
+    ($detect:ident, $feature:tt) => {{
+        #[cfg(feature = "runtime-detect")]
+        let available = std::arch::$detect!($feature);
+        #[cfg(not(feature = "runtime-detect"))]
+        let available = cfg!(target_feature = $feature);
+        available
+    }};
+}
+
+/// Exposes the scalar conversions of the backend module `imp`.
+#[allow(unused_macros)]
+macro_rules! unchecked {
+    ($feature:literal; $($name:ident: $from:ty => $to:ty;)*) => {$(
+        #[doc = concat!("Converts `", stringify!($from), "` to `", stringify!($to), "`.")]
+        ///
+        /// # Safety
+        ///
+        #[doc = concat!("The CPU must support ", $feature, "; see [`is_available`].")]
+        #[inline]
+        #[allow(unsafe_code)]
+        pub unsafe fn $name(value: $from) -> $to {
+            imp::$name(value)
+        }
+    )*};
+}
+
+/// Exposes the slice conversions of the backend module `imp`.
+#[allow(unused_macros)]
+macro_rules! unchecked_slice {
+    ($feature:literal; $($name:ident: $from:ty => $to:ty;)*) => {$(
+        #[doc = concat!("Converts a slice of `", stringify!($from), "` to `", stringify!($to), "`.")]
+        ///
+        /// # Safety
+        ///
+        #[doc = concat!("The CPU must support ", $feature, "; see [`is_available`].")]
+        ///
+        /// # Panics
+        ///
+        /// Panics if `src` and `dst` have different lengths.
+        #[allow(unsafe_code)]
+        pub unsafe fn $name(src: &[$from], dst: &mut [$to]) {
+            crate::slice::check_len(src, dst);
+            imp::$name(src, dst)
+        }
+    )*};
+}
+
+/// The x86-64 F16C instructions, which only convert to and from `f32`.
+#[cfg(all(
+    feature = "asm",
+    target_arch = "x86_64",
+    any(target_feature = "f16c", feature = "runtime-detect")
+))]
+pub mod f16c {
+    use crate::f16;
+    use crate::half::x86_64::f16c as imp;
+
+    /// Returns whether the CPU supports F16C.
+    #[inline]
+    pub fn is_available() -> bool {
+        available!(is_x86_feature_detected, "f16c")
+    }
+
+    unchecked! { "F16C";
+        f32_from_f16: f16 => f32;
+        f16_from_f32: f32 => f16;
+    }
+
+    unchecked_slice! { "F16C";
+        f32_from_f16_slice: f16 => f32;
+        f16_from_f32_slice: f32 => f16;
+    }
+}
+
+/// The x86-64 AVX-512 FP16 instructions.
+#[cfg(all(
+    feature = "asm",
+    target_arch = "x86_64",
+    any(target_feature = "avx512fp16", feature = "runtime-detect")
+))]
+pub mod avx512fp16 {
+    use crate::f16;
+    use crate::half::x86_64::avx as imp;
+
+    /// Returns whether the CPU supports AVX-512 FP16.
+    #[inline]
+    pub fn is_available() -> bool {
+        available!(is_x86_feature_detected, "avx512fp16")
+    }
+
+    unchecked! { "AVX-512 FP16";
+        f32_from_f16: f16 => f32;
+        f16_from_f32: f32 => f16;
+        f64_from_f16: f16 => f64;
+        f16_from_f64: f64 => f16;
+        i32_from_f16: f16 => i32;
+        f16_from_i32: i32 => f16;
+        u32_from_f16: f16 => u32;
+        f16_from_u32: u32 => f16;
+        i64_from_f16: f16 => i64;
+        f16_from_i64: i64 => f16;
+        u64_from_f16: f16 => u64;
+        f16_from_u64: u64 => f16;
+    }
+
+    unchecked_slice! { "AVX-512 FP16";
+        f32_from_f16_slice: f16 => f32;
+        f16_from_f32_slice: f32 => f16;
+        f64_from_f16_slice: f16 => f64;
+        f16_from_f64_slice: f64 => f16;
+    }
+}
+
+/// The AArch64 FP16 instructions.
+#[cfg(all(
+    feature = "asm",
+    target_arch = "aarch64",
+    any(target_feature = "fp16", feature = "runtime-detect")
+))]
+pub mod fp16 {
+    use crate::f16;
+    use crate::half::aarch64 as imp;
+
+    /// Returns whether the CPU supports FP16.
+    #[inline]
+    pub fn is_available() -> bool {
+        available!(is_aarch64_feature_detected, "fp16")
+    }
+
+    unchecked! { "FP16";
+        f32_from_f16: f16 => f32;
+        f16_from_f32: f32 => f16;
+        f64_from_f16: f16 => f64;
+        f16_from_f64: f64 => f16;
+        i32_from_f16: f16 => i32;
+        f16_from_i32: i32 => f16;
+        u32_from_f16: f16 => u32;
+        f16_from_u32: u32 => f16;
+        i64_from_f16: f16 => i64;
+        f16_from_i64: i64 => f16;
+        u64_from_f16: f16 => u64;
+        f16_from_u64: u64 => f16;
+    }
+}
+
+/// Converts with the active backend, or returns `None`. Conversions that
+/// F16C provides are marked `f16c`.
+macro_rules! checked {
+    ($($name:ident: $from:ty => $to:ty $(, $f16c:ident)?;)*) => {$(
+        #[doc = concat!(
+            "Converts `", stringify!($from), "` to `", stringify!($to),
+            "` in hardware, or returns `None` if the active backend cannot."
+        )]
+        #[inline]
+        #[allow(unsafe_code)]
+        #[allow(unused_variables)] // Without a hardware backend
+        pub fn $name(value: $from) -> Option<$to> {
+            dispatch! {
+                avx512fp16: Some(unsafe { crate::half::x86_64::avx::$name(value) }),
+                $(f16c: Some(unsafe { crate::half::x86_64::$f16c::$name(value) }),)?
+                fp16: Some(unsafe { crate::half::aarch64::$name(value) }),
+                software: None,
+            }
+        }
+    )*};
+}
+
+checked! {
+    f32_from_f16: f16 => f32, f16c;
+    f16_from_f32: f32 => f16, f16c;
+    f64_from_f16: f16 => f64;
+    f16_from_f64: f64 => f16;
+    i32_from_f16: f16 => i32;
+    f16_from_i32: i32 => f16;
+    u32_from_f16: f16 => u32;
+    f16_from_u32: u32 => f16;
+    i64_from_f16: f16 => i64;
+    f16_from_i64: i64 => f16;
+    u64_from_f16: f16 => u64;
+    f16_from_u64: u64 => f16;
+}
@@ -0,0 +1,683 @@
+//! A trait over the IEEE 754 binary formats.
+//!
+//! [`IeeeFloat`] describes a format by its field widths and gives bit-level
+//! operations in terms of them, so that an algorithm can be written once for
+//! `f16`, `f32`, `f64` and `f128`, and for `bf16` and `f8e5m2`, which follow
+//! the same rules with other widths. It is implemented for this crate's types
+//! and for the primitives, including the core `f16` and `f128` that the
+//! `nightly` feature re-exports.
+
+use crate::rem;
+use crate::round::round_pack;
+use crate::{bf16, f128, f16, f8e5m2};
+use core::fmt::Debug;
+use core::hash::Hash;
+use core::num::FpCategory;
+use core::ops::{Add, BitAnd, BitOr, BitXor, Neg, Not, Shl, Shr, Sub};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// The unsigned integer that holds the bits of an [`IeeeFloat`].
+pub trait FloatBits:
+    sealed::Sealed
+    + Copy
+    + Debug
+    + Default
+    + Eq
+    + Ord
+    + Hash
+    + Not<Output = Self>
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + BitXor<Output = Self>
+    + Shl<u32, Output = Self>
+    + Shr<u32, Output = Self>
+    + Add<Output = Self>
+    + Sub<Output = Self>
+{
+    /// Zero.
+    const ZERO: Self;
+
+    /// One.
+    const ONE: Self;
+
+    /// Returns the low 32 bits.
+    fn low_u32(self) -> u32;
+
+    /// Returns the bits zero-extended to `u128`.
+    fn to_u128(self) -> u128;
+
+    /// Returns the low bits of `bits`.
+    fn from_u128(bits: u128) -> Self;
+}
+
+macro_rules! bits {
+    ($($bits:ty),*) => {$(
+        impl sealed::Sealed for $bits {}
+
+        impl FloatBits for $bits {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            #[inline]
+            fn low_u32(self) -> u32 {
+                self as u32
+            }
+
+            #[inline]
+            fn to_u128(self) -> u128 {
+                self as u128
+            }
+
+            #[inline]
+            fn from_u128(bits: u128) -> Self {
+                bits as Self
+            }
+        }
+    )*};
+}
+
+bits!(u8, u16, u32, u64, u128);
+
+/// An IEEE 754 binary floating-point format.
+///
+/// The provided methods work on the bits alone, so they behave identically
+/// for every implementation and never depend on the target's float support.
+/// The trait is sealed.
+pub trait IeeeFloat:
+    sealed::Sealed + Copy + Debug + Default + PartialEq + Neg<Output = Self>
+{
+    /// The unsigned integer of the same width.
+    type Bits: FloatBits;
+
+    /// The total width in bits.
+    const BITS: u32;
+
+    /// The width of the exponent field.
+    const EXP_BITS: u32;
+
+    /// The width of the stored mantissa field, excluding the implicit bit.
+    const MANT_BITS: u32;
+
+    /// The exponent bias: a biased exponent of `BIAS` is 2^0.
+    const BIAS: i32;
+
+    /// The sign bit.
+    const SIGN_MASK: Self::Bits;
+
+    /// The exponent field.
+    const EXP_MASK: Self::Bits;
+
+    /// The stored mantissa field.
+    const MANT_MASK: Self::Bits;
+
+    /// Positive infinity.
+    const INFINITY: Self;
+
+    /// Negative infinity.
+    const NEG_INFINITY: Self;
+
+    /// The default quiet NaN.
+    const NAN: Self;
+
+    /// The largest finite value.
+    const MAX: Self;
+
+    /// The smallest finite value.
+    const MIN: Self;
+
+    /// Returns the raw representation.
+    fn to_bits(self) -> Self::Bits;
+
+    /// Creates a value from its raw representation.
+    fn from_bits(bits: Self::Bits) -> Self;
+
+    /// Returns the biased exponent field.
+    #[inline]
+    fn raw_exponent(self) -> u32 {
+        ((self.to_bits() & Self::EXP_MASK) >> Self::MANT_BITS).low_u32()
+    }
+
+    /// Returns the stored mantissa field.
+    #[inline]
+    fn raw_mantissa(self) -> Self::Bits {
+        self.to_bits() & Self::MANT_MASK
+    }
+
+    /// Returns `true` if `self` is NaN.
+    #[inline]
+    fn is_nan(self) -> bool {
+        self.to_bits() & !Self::SIGN_MASK > Self::EXP_MASK
+    }
+
+    /// Returns `true` if `self` is positive or negative infinity.
+    #[inline]
+    fn is_infinite(self) -> bool {
+        self.to_bits() & !Self::SIGN_MASK == Self::EXP_MASK
+    }
+
+    /// Returns `true` if `self` is neither infinite nor NaN.
+    #[inline]
+    fn is_finite(self) -> bool {
+        self.to_bits() & Self::EXP_MASK != Self::EXP_MASK
+    }
+
+    /// Returns `true` if `self` is neither zero, subnormal, infinite nor NaN.
+    #[inline]
+    fn is_normal(self) -> bool {
+        self.classify() == FpCategory::Normal
+    }
+
+    /// Returns `true` if `self` is subnormal.
+    #[inline]
+    fn is_subnormal(self) -> bool {
+        self.classify() == FpCategory::Subnormal
+    }
+
+    /// Returns `true` if the sign bit is clear, including for `+0.0` and
+    /// NaNs with a positive sign.
+    #[inline]
+    fn is_sign_positive(self) -> bool {
+        !self.is_sign_negative()
+    }
+
+    /// Returns `true` if the sign bit is set, including for `-0.0` and NaNs
+    /// with a negative sign.
+    #[inline]
+    fn is_sign_negative(self) -> bool {
+        self.to_bits() & Self::SIGN_MASK != Self::Bits::ZERO
+    }
+
+    /// Returns the floating-point category.
+    #[inline]
+    fn classify(self) -> FpCategory {
+        let exp = self.to_bits() & Self::EXP_MASK;
+        let mant = self.raw_mantissa();
+
+        if exp == Self::EXP_MASK {
+            if mant == Self::Bits::ZERO {
+                FpCategory::Infinite
+            } else {
+                FpCategory::Nan
+            }
+        } else if exp != Self::Bits::ZERO {
+            FpCategory::Normal
+        } else if mant != Self::Bits::ZERO {
+            FpCategory::Subnormal
+        } else {
+            FpCategory::Zero
+        }
+    }
+
+    /// Returns `self` with the sign bit cleared.
+    #[inline]
+    fn abs(self) -> Self {
+        Self::from_bits(self.to_bits() & !Self::SIGN_MASK)
+    }
+
+    /// Returns `self` with the sign bit of `sign`.
+    #[inline]
+    fn copysign(self, sign: Self) -> Self {
+        Self::from_bits((self.to_bits() & !Self::SIGN_MASK) | (sign.to_bits() & Self::SIGN_MASK))
+    }
+
+    /// Returns the least number greater than `self` (IEEE 754 `nextUp`).
+    ///
+    /// NaN and `INFINITY` are returned unchanged, `NEG_INFINITY` becomes
+    /// `MIN`, and both zeros become the smallest positive subnormal.
+    #[inline]
+    fn next_up(self) -> Self {
+        let bits = self.to_bits();
+        let abs = bits & !Self::SIGN_MASK;
+
+        if self.is_nan() || bits == Self::EXP_MASK {
+            self
+        } else if abs == Self::Bits::ZERO {
+            Self::from_bits(Self::Bits::ONE)
+        } else if bits == abs {
+            Self::from_bits(bits + Self::Bits::ONE)
+        } else {
+            Self::from_bits(bits - Self::Bits::ONE)
+        }
+    }
+
+    /// Returns the greatest number less than `self` (IEEE 754 `nextDown`).
+    ///
+    /// NaN and `NEG_INFINITY` are returned unchanged, `INFINITY` becomes
+    /// `MAX`, and both zeros become the smallest negative subnormal.
+    #[inline]
+    fn next_down(self) -> Self {
+        let bits = self.to_bits();
+        let abs = bits & !Self::SIGN_MASK;
+
+        if self.is_nan() || bits == Self::SIGN_MASK | Self::EXP_MASK {
+            self
+        } else if abs == Self::Bits::ZERO {
+            Self::from_bits(Self::SIGN_MASK | Self::Bits::ONE)
+        } else if bits == abs {
+            Self::from_bits(bits - Self::Bits::ONE)
+        } else {
+            Self::from_bits(bits + Self::Bits::ONE)
+        }
+    }
+
+    /// Returns the next representable value after `self` in the direction of
+    /// `toward` (C `nextafter`).
+    ///
+    /// If either argument is NaN, that NaN is returned. If the arguments are
+    /// equal, `toward` is returned, so `0.0.next_after(-0.0)` is `-0.0`.
+    #[inline]
+    fn next_after(self, toward: Self) -> Self {
+        if self.is_nan() {
+            self
+        } else if toward.is_nan() || ordinal(self) == ordinal(toward) {
+            toward
+        } else if ordinal(self) < ordinal(toward) {
+            self.next_up()
+        } else {
+            self.next_down()
+        }
+    }
+
+    /// Returns the unit in the last place of `self`.
+    ///
+    /// This is the (positive) distance from `|self|` to the next larger
+    /// representable magnitude. The ULP of `MAX` is the spacing that `MAX`
+    /// would have if the exponent range were unbounded. Infinities have an
+    /// infinite ULP and NaN is returned unchanged.
+    #[inline]
+    fn ulp(self) -> Self {
+        let exp = self.raw_exponent();
+
+        if self.is_nan() {
+            self
+        } else if !self.is_finite() {
+            Self::INFINITY
+        } else if exp > Self::MANT_BITS {
+            // The spacing is a normal power of two
+            Self::from_bits(Self::Bits::from_u128(
+                ((exp - Self::MANT_BITS) as u128) << Self::MANT_BITS,
+            ))
+        } else if exp > 0 {
+            // The spacing is a subnormal power of two
+            Self::from_bits(Self::Bits::ONE << (exp - 1))
+        } else {
+            Self::from_bits(Self::Bits::ONE)
+        }
+    }
+
+    /// Returns the number of representable values between `self` and
+    /// `other`, counting one of the endpoints.
+    ///
+    /// Adjacent values are at distance `1`, `0.0` and `-0.0` are at distance
+    /// `0`, and the infinities are one step beyond `MAX` and `MIN`. If either
+    /// argument is NaN, `u128::MAX` is returned.
+    #[inline]
+    fn ulp_distance(self, other: Self) -> u128 {
+        if self.is_nan() || other.is_nan() {
+            return u128::MAX;
+        }
+
+        ordinal(self).abs_diff(ordinal(other))
+    }
+
+    /// Returns the raw fields of `self`: the sign, the biased exponent and
+    /// the stored (trailing) mantissa bits.
+    #[inline]
+    fn to_parts(self) -> (bool, u16, Self::Bits) {
+        (
+            self.is_sign_negative(),
+            self.raw_exponent() as u16,
+            self.raw_mantissa(),
+        )
+    }
+
+    /// Assembles a value from its raw fields.
+    ///
+    /// Returns `None` if `exponent` does not fit in `EXP_BITS` bits or
+    /// `mantissa` does not fit in `MANT_BITS` bits.
+    #[inline]
+    fn from_parts(sign: bool, exponent: u16, mantissa: Self::Bits) -> Option<Self> {
+        if (exponent as u32) >> Self::EXP_BITS != 0
+            || mantissa & !Self::MANT_MASK != Self::Bits::ZERO
+        {
+            return None;
+        }
+
+        let sign = if sign {
+            Self::SIGN_MASK
+        } else {
+            Self::Bits::ZERO
+        };
+        let exp = Self::Bits::from_u128((exponent as u128) << Self::MANT_BITS);
+        Some(Self::from_bits(sign | exp | mantissa))
+    }
+
+    /// Returns the mantissa, exponent and sign as integers.
+    ///
+    /// For finite values the original number equals
+    /// `sign * mantissa * 2^exponent` exactly. The result is not meaningful
+    /// for infinities and NaN.
+    #[inline]
+    fn integer_decode(self) -> (Self::Bits, i16, i8) {
+        let (sign, exp, mant) = self.to_parts();
+        let sign = if sign { -1 } else { 1 };
+        let min_exp = 1 - Self::BIAS - Self::MANT_BITS as i32;
+
+        if exp == 0 {
+            (mant, min_exp as i16, sign)
+        } else {
+            let mant = mant | Self::Bits::ONE << Self::MANT_BITS;
+            (mant, (min_exp + exp as i32 - 1) as i16, sign)
+        }
+    }
+
+    /// Returns the unbiased exponent of `self` (C `ilogb`).
+    ///
+    /// Subnormals are treated as if they were normalized. Zero and NaN
+    /// return `i32::MIN` and infinities return `i32::MAX`.
+    #[inline]
+    fn ilogb(self) -> i32 {
+        let (_, exp, mant) = self.to_parts();
+
+        if self.is_infinite() {
+            i32::MAX
+        } else if self.is_nan() {
+            i32::MIN
+        } else if exp != 0 {
+            exp as i32 - Self::BIAS
+        } else if mant != Self::Bits::ZERO {
+            let top = 127 - mant.to_u128().leading_zeros() as i32;
+            top - Self::BIAS - Self::MANT_BITS as i32 + 1
+        } else {
+            i32::MIN
+        }
+    }
+
+    /// Returns the unbiased exponent of `self` as a float (C `logb`).
+    ///
+    /// Zero returns `NEG_INFINITY`, infinities return `INFINITY` and NaN is
+    /// returned unchanged. The exponent is exact in every format but
+    /// `f8e5m2`, whose 3 bits of precision round it to nearest even.
+    #[inline]
+    fn logb(self) -> Self {
+        if self.is_nan() {
+            self
+        } else if self.is_infinite() {
+            Self::INFINITY
+        } else if is_zero(self) {
+            Self::NEG_INFINITY
+        } else {
+            let e = self.ilogb();
+            pack(e < 0, e.unsigned_abs() as u128, 0, false)
+        }
+    }
+
+    /// Multiplies `self` by `2^n` (C `scalbn`).
+    ///
+    /// The result is correctly rounded: it overflows to infinity and rounds
+    /// to nearest, ties to even, when it underflows into the subnormals.
+    #[inline]
+    fn scalbn(self, n: i32) -> Self {
+        // Zero, infinity and NaN are unchanged
+        if !self.is_finite() || is_zero(self) {
+            return self;
+        }
+
+        // Anything beyond this range overflows or underflows regardless
+        let n = n.clamp(-0x10000, 0x10000);
+
+        let (mant, exp, _) = self.integer_decode();
+        pack(
+            self.is_sign_negative(),
+            mant.to_u128(),
+            exp as i32 + n,
+            false,
+        )
+    }
+
+    /// Multiplies `self` by `2^n` (C `ldexp`).
+    ///
+    /// This is identical to [`scalbn`](Self::scalbn).
+    #[inline]
+    fn ldexp(self, n: i32) -> Self {
+        self.scalbn(n)
+    }
+
+    /// Breaks `self` into a normalized fraction and a power of two (C `frexp`).
+    ///
+    /// For finite nonzero values, returns `(m, e)` with `0.5 <= |m| < 1` and
+    /// `self == m * 2^e`. Zero, infinity and NaN are returned unchanged with
+    /// an exponent of `0`.
+    #[inline]
+    fn frexp(self) -> (Self, i32) {
+        if !self.is_finite() || is_zero(self) {
+            return (self, 0);
+        }
+
+        let e = self.ilogb() + 1;
+        (self.scalbn(-e), e)
+    }
+
+    /// Returns `true` if `self` is a signaling NaN.
+    ///
+    /// A signaling NaN has the most significant mantissa bit clear.
+    #[inline]
+    fn is_signaling_nan(self) -> bool {
+        self.is_nan() && self.to_bits() & quiet_bit::<Self>() == Self::Bits::ZERO
+    }
+
+    /// Returns `true` if `self` is a quiet NaN.
+    ///
+    /// A quiet NaN has the most significant mantissa bit set.
+    #[inline]
+    fn is_quiet_nan(self) -> bool {
+        self.is_nan() && self.to_bits() & quiet_bit::<Self>() != Self::Bits::ZERO
+    }
+
+    /// Creates a positive quiet NaN carrying `payload`.
+    ///
+    /// The payload occupies the `MANT_BITS - 1` mantissa bits below the
+    /// quiet bit. Returns `None` if `payload` does not fit.
+    #[inline]
+    fn nan_with_payload(payload: Self::Bits) -> Option<Self> {
+        if payload >= quiet_bit::<Self>() {
+            return None;
+        }
+
+        Some(Self::from_bits(
+            Self::EXP_MASK | quiet_bit::<Self>() | payload,
+        ))
+    }
+
+    /// Creates a positive signaling NaN carrying `payload`.
+    ///
+    /// Returns `None` if `payload` does not fit in `MANT_BITS - 1` bits or is
+    /// zero, since a signaling NaN with a zero payload would encode infinity.
+    #[inline]
+    fn signaling_nan_with_payload(payload: Self::Bits) -> Option<Self> {
+        if payload == Self::Bits::ZERO || payload >= quiet_bit::<Self>() {
+            return None;
+        }
+
+        Some(Self::from_bits(Self::EXP_MASK | payload))
+    }
+
+    /// Returns the payload of a NaN, or `None` if `self` is not NaN.
+    ///
+    /// The payload excludes the sign and the quiet bit, so quieting a
+    /// signaling NaN preserves its payload.
+    #[inline]
+    fn payload(self) -> Option<Self::Bits> {
+        if self.is_nan() {
+            Some(self.raw_mantissa() & (quiet_bit::<Self>() - Self::Bits::ONE))
+        } else {
+            None
+        }
+    }
+
+    /// Replaces every NaN with the canonical positive quiet NaN, `NAN`.
+    ///
+    /// Non-NaN values are returned unchanged. This is useful wherever NaN
+    /// bit patterns must not leak observable state, for example NaN-boxing.
+    #[inline]
+    fn canonicalize(self) -> Self {
+        if self.is_nan() {
+            Self::NAN
+        } else {
+            self
+        }
+    }
+
+    /// Computes the IEEE 754 remainder of `self` divided by `rhs`.
+    ///
+    /// The result is `self - n * rhs`, where `n` is the integer nearest
+    /// `self / rhs`, ties to even. It is always exact and its magnitude is
+    /// at most `|rhs| / 2`. A zero result has the sign of `self`. NaNs
+    /// propagate, and an infinite `self` or a zero `rhs` gives NaN.
+    #[inline]
+    fn remainder(self, rhs: Self) -> Self {
+        rem::remquo(self, rhs).0
+    }
+
+    /// Computes [`remainder`](Self::remainder) along with the low bits of
+    /// the quotient.
+    ///
+    /// The second value has the sign of `self / rhs` and the magnitude of
+    /// the rounded quotient `n` modulo `2^31`, which is enough to pick the
+    /// octant in argument reduction. It is `0` for the special cases.
+    #[inline]
+    fn remquo(self, rhs: Self) -> (Self, i32) {
+        rem::remquo(self, rhs)
+    }
+
+    /// Computes the least nonnegative remainder of `self` divided by `rhs`.
+    ///
+    /// This is the C `fmod` remainder, plus `|rhs|` if that is negative, and
+    /// lies in `[0, |rhs|)` unless it rounds up to `|rhs|` for a tiny
+    /// negative `self`. It is exact otherwise. NaNs propagate, an infinite
+    /// `self` or a zero `rhs` gives NaN, and an infinite `rhs` leaves a
+    /// finite nonnegative `self` unchanged.
+    #[inline]
+    fn rem_euclid(self, rhs: Self) -> Self {
+        rem::rem_euclid(self, rhs)
+    }
+
+    /// Computes the quotient of Euclidean division of `self` by `rhs`.
+    ///
+    /// This is the integer `n` for which `self - n * rhs` is
+    /// [`rem_euclid`](Self::rem_euclid), rounded once. A zero or infinite
+    /// operand gives the same result as `self / rhs` truncated, except that
+    /// a negative `self` divided by an infinity gives `-1` or `1`.
+    #[inline]
+    fn div_euclid(self, rhs: Self) -> Self {
+        rem::div_euclid(self, rhs)
+    }
+}
+
+/// Returns the quiet bit, the most significant mantissa bit.
+#[inline]
+pub(crate) fn quiet_bit<F: IeeeFloat>() -> F::Bits {
+    F::Bits::ONE << (F::MANT_BITS - 1)
+}
+
+/// Returns the first NaN operand, quieted, with its payload and sign, as
+/// the hardware of every major architecture does.
+#[inline]
+pub(crate) fn propagate_nan<F: IeeeFloat>(x: F, y: F) -> F {
+    let nan = if x.is_nan() { x } else { y };
+    F::from_bits(nan.to_bits() | quiet_bit::<F>())
+}
+
+/// Implements [`propagate_nan`] as a `const` method, for the formats whose
+/// arithmetic is `const` or that are not `IeeeFloat`. The type needs
+/// `const` methods `is_nan` and `quiet`.
+macro_rules! propagate_nan {
+    ($($vis:tt)*) => {
+        /// Returns the NaN result of an operation with a NaN operand.
+        ///
+        /// The first NaN operand is returned with its payload and sign
+        /// intact, quieted, as the hardware of every major architecture
+        /// does.
+        #[inline]
+        $($vis)* const fn propagate_nan(self, other: Self) -> Self {
+            if self.is_nan() {
+                self.quiet()
+            } else {
+                other.quiet()
+            }
+        }
+    };
+}
+
+/// Returns `true` if `x` is either zero.
+#[inline]
+pub(crate) fn is_zero<F: IeeeFloat>(x: F) -> bool {
+    x.to_bits() & !F::SIGN_MASK == F::Bits::ZERO
+}
+
+/// Rounds `(-1)^sign * (mant + sticky) * 2^exp` into `F`; see [`round_pack`].
+#[inline]
+pub(crate) fn pack<F: IeeeFloat>(sign: bool, mant: u128, exp: i32, sticky: bool) -> F {
+    let bits = round_pack(sign, mant, exp, sticky, F::EXP_BITS, F::MANT_BITS);
+    F::from_bits(F::Bits::from_u128(bits))
+}
+
+/// Maps a non-NaN value onto the integers so that adjacent values are
+/// adjacent integers and both zeros map to `0`.
+#[inline]
+fn ordinal<F: IeeeFloat>(x: F) -> i128 {
+    let abs = x.abs().to_bits().to_u128() as i128;
+
+    if x.is_sign_negative() {
+        -abs
+    } else {
+        abs
+    }
+}
+
+macro_rules! ieee {
+    ($($float:ty: $bits:ty, $exp_bits:literal, $mant_bits:literal;)*) => {$(
+        impl sealed::Sealed for $float {}
+
+        impl IeeeFloat for $float {
+            type Bits = $bits;
+
+            const BITS: u32 = 1 + $exp_bits + $mant_bits;
+            const EXP_BITS: u32 = $exp_bits;
+            const MANT_BITS: u32 = $mant_bits;
+            const BIAS: i32 = (1 << ($exp_bits - 1)) - 1;
+
+            const SIGN_MASK: $bits = 1 << ($exp_bits + $mant_bits);
+            const EXP_MASK: $bits = ((1 << $exp_bits) - 1) << $mant_bits;
+            const MANT_MASK: $bits = (1 << $mant_bits) - 1;
+
+            const INFINITY: Self = <$float>::INFINITY;
+            const NEG_INFINITY: Self = <$float>::NEG_INFINITY;
+            const NAN: Self = <$float>::NAN;
+            const MAX: Self = <$float>::MAX;
+            const MIN: Self = <$float>::MIN;
+
+            #[inline]
+            fn to_bits(self) -> $bits {
+                <$float>::to_bits(self)
+            }
+
+            #[inline]
+            fn from_bits(bits: $bits) -> Self {
+                <$float>::from_bits(bits)
+            }
+        }
+    )*};
+}
+
+ieee! {
+    f8e5m2: u8, 5, 2;
+    f16: u16, 5, 10;
+    bf16: u16, 8, 7;
+    f32: u32, 8, 23;
+    f64: u64, 11, 52;
+    f128: u128, 15, 112;
+}
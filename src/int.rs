@@ -0,0 +1,103 @@
+#![cfg(feature = "casting")]
+
+//! Conversions for the pointer-sized integers and the `core::num` wrappers.
+//!
+//! Each goes through the fixed-width conversion of the same width, so it
+//! rounds and saturates exactly like it and like `as`: `usize` converts as
+//! `u16`, `u32` or `u64`, depending on the target.
+
+use crate::{f128, f16};
+use casting::CastFrom;
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping,
+};
+
+#[cfg(feature = "saturating")]
+use core::num::Saturating;
+
+#[cfg(target_pointer_width = "16")]
+type Usize = u16;
+#[cfg(target_pointer_width = "16")]
+type Isize = i16;
+
+#[cfg(target_pointer_width = "32")]
+type Usize = u32;
+#[cfg(target_pointer_width = "32")]
+type Isize = i32;
+
+#[cfg(target_pointer_width = "64")]
+type Usize = u64;
+#[cfg(target_pointer_width = "64")]
+type Isize = i64;
+
+/// Converts between a float and `usize`/`isize` through their fixed-width
+/// equivalents.
+macro_rules! size {
+    ($float:ty: $($size:ty => $fixed:ty),*) => {$(
+        impl CastFrom<$size> for $float {
+            #[inline]
+            fn cast_from(value: $size) -> $float {
+                <$float>::cast_from(value as $fixed)
+            }
+        }
+
+        impl CastFrom<$float> for $size {
+            #[inline]
+            fn cast_from(value: $float) -> $size {
+                <$fixed>::cast_from(value) as $size
+            }
+        }
+    )*};
+}
+
+size!(f16: usize => Usize, isize => Isize);
+size!(f128: usize => Usize, isize => Isize);
+
+/// Converts between a float and a wrapper as its wrapped integer does.
+macro_rules! wrapper {
+    ($float:ty: $($wrapper:ident),*) => {$(
+        impl<T> CastFrom<$wrapper<T>> for $float
+        where
+            $float: CastFrom<T>,
+        {
+            #[inline]
+            fn cast_from(value: $wrapper<T>) -> $float {
+                <$float>::cast_from(value.0)
+            }
+        }
+
+        impl<T: CastFrom<$float>> CastFrom<$float> for $wrapper<T> {
+            #[inline]
+            fn cast_from(value: $float) -> $wrapper<T> {
+                $wrapper(T::cast_from(value))
+            }
+        }
+    )*};
+}
+
+wrapper!(f16: Wrapping);
+wrapper!(f128: Wrapping);
+
+#[cfg(feature = "saturating")]
+wrapper!(f16: Saturating);
+#[cfg(feature = "saturating")]
+wrapper!(f128: Saturating);
+
+/// Converts from a nonzero integer. There is no conversion back, since a
+/// float may convert to zero.
+macro_rules! nonzero {
+    ($float:ty: $($nonzero:ty),*) => {$(
+        impl CastFrom<$nonzero> for $float {
+            #[inline]
+            fn cast_from(value: $nonzero) -> $float {
+                <$float>::cast_from(value.get())
+            }
+        }
+    )*};
+}
+
+nonzero!(f16: NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize);
+nonzero!(f16: NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize);
+nonzero!(f128: NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize);
+nonzero!(f128: NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize);
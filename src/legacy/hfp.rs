@@ -0,0 +1,197 @@
+//! IBM System/360 hexadecimal floating point.
+//!
+//! A value is `(-1)^sign * 0.fraction * 16^(characteristic - 64)`, with a
+//! 7-bit characteristic and a fraction of 6, 14 or 28 hexadecimal digits.
+//! Normalized values have a nonzero leading digit, so they keep 21 to 24,
+//! 53 to 56 or 109 to 112 significant bits. Encoding normalizes, except
+//! below 16^-65, where the fraction is left unnormalized at the smallest
+//! characteristic.
+
+use super::{Legacy, Value};
+use crate::round::shift_round;
+
+/// Decodes a sign, characteristic and fraction of `frac_bits` bits.
+#[inline]
+fn decode(negative: bool, char: u32, frac: u128, frac_bits: u32) -> Value {
+    Value::Finite(negative, frac, 4 * (char as i32 - 64) - frac_bits as i32)
+}
+
+/// Rounds a value to a sign, characteristic and fraction of `frac_bits`
+/// bits.
+#[inline]
+fn encode(value: Value, frac_bits: u32) -> (bool, u32, u128) {
+    let max = (true, 127, (1 << frac_bits) - 1);
+
+    let (negative, mant, exp) = match value {
+        Value::Finite(negative, mant, exp) => (negative, mant, exp),
+        Value::Infinite(negative) | Value::Nan(negative) => return (negative, max.1, max.2),
+    };
+
+    if mant == 0 {
+        return (negative, 0, 0);
+    }
+
+    // The characteristic that puts the leading bit in the leading digit
+    let top = 127 - mant.leading_zeros() as i32 + exp;
+    let mut char = (top.div_euclid(4) + 65).max(0);
+
+    let shift = 4 * (char - 64) - frac_bits as i32 - exp;
+    let mut frac = if shift > 0 {
+        shift_round(mant, shift.min(129) as u32, false)
+    } else {
+        mant << -shift
+    };
+
+    // Rounding may carry into a new digit
+    if frac >> frac_bits != 0 {
+        frac >>= 4;
+        char += 1;
+    }
+
+    if char > 127 {
+        return (negative, max.1, max.2);
+    }
+
+    (negative, char as u32, frac)
+}
+
+macro_rules! hfp {
+    ($($(#[$doc:meta])* $name:ident: $bits:ty, $frac_bits:literal;)*) => {$(
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+        #[repr(transparent)]
+        pub struct $name($bits);
+
+        impl $name {
+            pub const MIN: Self = Self(<$bits>::MAX);
+            pub const MAX: Self = Self(<$bits>::MAX >> 1);
+
+            /// The smallest positive normalized value, 16^-65.
+            pub const MIN_POSITIVE: Self = Self(1 << ($frac_bits - 4));
+
+            /// Creates a value from its representation as an integer.
+            #[inline]
+            pub const fn from_bits(bits: $bits) -> Self {
+                Self(bits)
+            }
+
+            /// Creates a value from its representation as a byte array in
+            /// big endian, the byte order of System/360 and its successors.
+            #[inline]
+            pub const fn from_be_bytes(bytes: [u8; core::mem::size_of::<$bits>()]) -> Self {
+                Self(<$bits>::from_be_bytes(bytes))
+            }
+
+            /// Returns the raw representation of this value as an integer.
+            #[inline]
+            pub const fn to_bits(self) -> $bits {
+                self.0
+            }
+
+            /// Returns the memory representation of this value as a byte
+            /// array in big endian.
+            #[inline]
+            pub const fn to_be_bytes(self) -> [u8; core::mem::size_of::<$bits>()] {
+                self.0.to_be_bytes()
+            }
+        }
+
+        impl Legacy for $name {
+            #[inline]
+            fn decode(self) -> Value {
+                let bits = self.0 as u128;
+                let negative = (self.0 >> ($frac_bits + 7)) != 0;
+                let char = (bits >> $frac_bits) as u32 & 0x7f;
+                decode(negative, char, bits & ((1 << $frac_bits) - 1), $frac_bits)
+            }
+
+            #[inline]
+            fn encode(value: Value) -> Self {
+                let (negative, char, frac) = encode(value, $frac_bits);
+                let bits = (negative as u128) << ($frac_bits + 7) | (char as u128) << $frac_bits | frac;
+                Self(bits as $bits)
+            }
+        }
+    )*};
+}
+
+hfp! {
+    /// IBM hexadecimal floating point, short format: 6 hexadecimal digits.
+    HfpShort: u32, 24;
+
+    /// IBM hexadecimal floating point, long format: 14 hexadecimal digits.
+    HfpLong: u64, 56;
+}
+
+/// IBM hexadecimal floating point, extended format: 28 hexadecimal digits
+/// in two long-format halves.
+///
+/// The high half holds the sign, the characteristic and the leading 14
+/// digits, and the low half the trailing 14 digits under its own sign and
+/// characteristic, which are ignored when decoding. Encoding sets them as
+/// the hardware does: the same sign, and the characteristic less 14,
+/// modulo 128, or all zeros for a zero.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct HfpExtended(u128);
+
+/// The fraction digits of each half of an `HfpExtended`.
+const HALF: u128 = (1 << 56) - 1;
+
+impl HfpExtended {
+    pub const MIN: Self = Self(0xffff_ffff_ffff_ffff_f1ff_ffff_ffff_ffff);
+    pub const MAX: Self = Self(0x7fff_ffff_ffff_ffff_71ff_ffff_ffff_ffff);
+
+    /// The smallest positive normalized value, 16^-65.
+    pub const MIN_POSITIVE: Self = Self(0x0010_0000_0000_0000_7200_0000_0000_0000);
+
+    /// Creates a value from its representation as an integer, the high
+    /// half in the upper 64 bits.
+    #[inline]
+    pub const fn from_bits(bits: u128) -> Self {
+        Self(bits)
+    }
+
+    /// Creates a value from its representation as a byte array in big
+    /// endian, the byte order of System/360 and its successors.
+    #[inline]
+    pub const fn from_be_bytes(bytes: [u8; 16]) -> Self {
+        Self(u128::from_be_bytes(bytes))
+    }
+
+    /// Returns the raw representation of this value as an integer.
+    #[inline]
+    pub const fn to_bits(self) -> u128 {
+        self.0
+    }
+
+    /// Returns the memory representation of this value as a byte array in
+    /// big endian.
+    #[inline]
+    pub const fn to_be_bytes(self) -> [u8; 16] {
+        self.0.to_be_bytes()
+    }
+}
+
+impl Legacy for HfpExtended {
+    #[inline]
+    fn decode(self) -> Value {
+        let negative = self.0 >> 127 != 0;
+        let char = (self.0 >> 120) as u32 & 0x7f;
+        let frac = (self.0 >> 64 & HALF) << 56 | (self.0 & HALF);
+        decode(negative, char, frac, 112)
+    }
+
+    #[inline]
+    fn encode(value: Value) -> Self {
+        let (negative, char, frac) = encode(value, 112);
+        let sign = (negative as u128) << 7;
+        if frac == 0 {
+            return Self(sign << 120);
+        }
+
+        let high = (sign | char as u128) << 56 | frac >> 56;
+        let low = (sign | (char.wrapping_sub(14) & 0x7f) as u128) << 56 | (frac & HALF);
+        Self(high << 64 | low)
+    }
+}
@@ -0,0 +1,108 @@
+#![cfg(feature = "casting")]
+
+//! Legacy mainframe formats: IBM System/360 hexadecimal floating point and
+//! the VAX F, D, G and H formats.
+//!
+//! Each type stores the bits of its format and converts to and from `f32`,
+//! `f64` and `f128` with `CastFrom`. Every HFP value ([`HfpShort`],
+//! [`HfpLong`] and [`HfpExtended`]) and every VAX [`VaxF`], [`VaxD`] and
+//! [`VaxG`] value is exact in `f128`, and so is every [`VaxH`] value of at
+//! least 2^-16382; the two lowest binades of `VaxH` are below the normal
+//! range of `f128` and are rounded.
+//!
+//! Every other conversion rounds to nearest, ties to even. This includes
+//! encoding, where the original hardware often differed: S/360 arithmetic
+//! truncated its results, as much of the software written for it does, so
+//! data encoded by such software may be one unit lower in the last place
+//! than the values encoded here.
+//!
+//! Neither family has infinities. HFP has no NaN either, so infinities,
+//! NaNs and values too large saturate to the largest magnitude of their
+//! sign, and values too small for even an unnormalized fraction round to
+//! zero. VAX stores the reserved operand, which decodes as NaN, for NaNs,
+//! infinities and overflow, as its hardware does on overflow, and flushes
+//! values below its smallest normal to zero, as it has no subnormals or
+//! negative zero.
+
+mod hfp;
+mod vax;
+
+pub use self::hfp::*;
+pub use self::vax::*;
+use crate::f128;
+use crate::round::{decode, round_pack, Decoded};
+use casting::CastFrom;
+
+/// A decoded value.
+#[derive(Clone, Copy)]
+enum Value {
+    /// `(-1)^sign * mant * 2^exp`
+    Finite(bool, u128, i32),
+    Infinite(bool),
+    Nan(bool),
+}
+
+impl Value {
+    /// Rounds to the IEEE 754 format with `exp_bits` exponent bits and
+    /// `mant_bits` stored mantissa bits.
+    #[inline]
+    fn to_ieee(self, exp_bits: u32, mant_bits: u32) -> u128 {
+        let inf = ((1 << exp_bits) - 1) << mant_bits;
+        let sign = |negative: bool| (negative as u128) << (exp_bits + mant_bits);
+
+        match self {
+            Self::Finite(negative, mant, exp) => {
+                round_pack(negative, mant, exp, false, exp_bits, mant_bits)
+            }
+            Self::Infinite(negative) => sign(negative) | inf,
+            Self::Nan(negative) => sign(negative) | inf | 1 << (mant_bits - 1),
+        }
+    }
+
+    /// Decodes the IEEE 754 format with `exp_bits` exponent bits and
+    /// `mant_bits` stored mantissa bits.
+    #[inline]
+    fn from_ieee(bits: u128, exp_bits: u32, mant_bits: u32) -> Self {
+        match decode(bits, exp_bits, mant_bits) {
+            Decoded::Finite(negative, mant, exp) => Self::Finite(negative, mant, exp),
+            Decoded::Infinite(negative) => Self::Infinite(negative),
+            Decoded::Nan(negative, _) => Self::Nan(negative),
+        }
+    }
+}
+
+/// A legacy format, converted through a decoded value.
+trait Legacy: Copy {
+    /// Returns the exact value.
+    fn decode(self) -> Value;
+
+    /// Rounds a value into the format.
+    fn encode(value: Value) -> Self;
+}
+
+macro_rules! cast {
+    ($($name:ty),*) => {$(
+        cast!(@ $name: f32: u32, 8, 23);
+        cast!(@ $name: f64: u64, 11, 52);
+        cast!(@ $name: f128: u128, 15, 112);
+    )*};
+
+    (@ $name:ty: $float:ty: $bits:ty, $exp_bits:literal, $mant_bits:literal) => {
+        impl CastFrom<$float> for $name {
+            #[inline]
+            fn cast_from(value: $float) -> $name {
+                let value = Value::from_ieee(value.to_bits() as u128, $exp_bits, $mant_bits);
+                <$name>::encode(value)
+            }
+        }
+
+        impl CastFrom<$name> for $float {
+            #[inline]
+            fn cast_from(value: $name) -> $float {
+                <$float>::from_bits(value.decode().to_ieee($exp_bits, $mant_bits) as $bits)
+            }
+        }
+    };
+}
+
+cast!(HfpShort, HfpLong, HfpExtended, VaxF, VaxD, VaxG, VaxH);
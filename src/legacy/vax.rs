@@ -0,0 +1,194 @@
+//! VAX floating point.
+//!
+//! A value is `(-1)^sign * 0.1fraction * 2^(exponent - bias)`, with a
+//! hidden leading bit and a bias of half the exponent range: F and D have
+//! an 8-bit exponent with 23 and 55 fraction bits, G an 11-bit exponent
+//! with 52, and H a 15-bit exponent with 112. An exponent of zero is zero
+//! with a positive sign, whatever the fraction, and the reserved operand
+//! with a negative one.
+//!
+//! In memory, the formats are sequences of 16-bit little endian words, the
+//! word holding the sign and exponent first. `from_bits` takes the
+//! logical layout, with the sign in the most significant bit, and
+//! `from_vax_bytes` the memory layout.
+
+use super::{Legacy, Value};
+use crate::round::shift_round;
+
+/// Reads 16-bit little endian words, most significant first.
+#[inline]
+const fn from_words<const N: usize>(bytes: [u8; N]) -> u128 {
+    let mut bits = 0;
+    let mut i = 0;
+    while i < N {
+        bits = bits << 16 | (bytes[i + 1] as u128) << 8 | bytes[i] as u128;
+        i += 2;
+    }
+    bits
+}
+
+/// Writes 16-bit little endian words, most significant first.
+#[inline]
+const fn to_words<const N: usize>(bits: u128) -> [u8; N] {
+    let mut bytes = [0; N];
+    let mut i = 0;
+    while i < N {
+        let word = bits >> (8 * (N - 2 - i));
+        bytes[i] = word as u8;
+        bytes[i + 1] = (word >> 8) as u8;
+        i += 2;
+    }
+    bytes
+}
+
+/// Decodes the logical bits of a format with `exp_bits` exponent bits and
+/// `frac_bits` fraction bits.
+#[inline]
+fn decode(bits: u128, exp_bits: u32, frac_bits: u32) -> Value {
+    let negative = (bits >> (exp_bits + frac_bits)) & 1 != 0;
+    let exp = (bits >> frac_bits) as i32 & ((1 << exp_bits) - 1);
+    let frac = bits & ((1 << frac_bits) - 1);
+
+    if exp == 0 {
+        if negative {
+            return Value::Nan(false);
+        }
+
+        return Value::Finite(false, 0, 0);
+    }
+
+    let bias = 1 << (exp_bits - 1);
+    Value::Finite(
+        negative,
+        frac | 1 << frac_bits,
+        exp - bias - frac_bits as i32 - 1,
+    )
+}
+
+/// Rounds a value to the logical bits of a format with `exp_bits` exponent
+/// bits and `frac_bits` fraction bits.
+#[inline]
+fn encode(value: Value, exp_bits: u32, frac_bits: u32) -> u128 {
+    let reserved = 1 << (exp_bits + frac_bits);
+
+    let (negative, mant, exp) = match value {
+        Value::Finite(negative, mant, exp) => (negative, mant, exp),
+        Value::Infinite(_) | Value::Nan(_) => return reserved,
+    };
+
+    if mant == 0 {
+        return 0;
+    }
+
+    // Round to frac_bits + 1 significant bits
+    let len = 128 - mant.leading_zeros();
+    let (mut mant, mut exp) = if len > frac_bits + 1 {
+        let shift = len - frac_bits - 1;
+        (shift_round(mant, shift, false), exp + shift as i32)
+    } else {
+        let shift = frac_bits + 1 - len;
+        (mant << shift, exp - shift as i32)
+    };
+
+    // Rounding may carry into a new binade
+    if mant >> (frac_bits + 1) != 0 {
+        mant >>= 1;
+        exp += 1;
+    }
+
+    let biased = exp + frac_bits as i32 + 1 + (1 << (exp_bits - 1));
+    if biased >= 1 << exp_bits {
+        return reserved;
+    } else if biased <= 0 {
+        return 0;
+    }
+
+    (negative as u128) << (exp_bits + frac_bits)
+        | (biased as u128) << frac_bits
+        | (mant & ((1 << frac_bits) - 1))
+}
+
+macro_rules! vax {
+    ($($(#[$doc:meta])* $name:ident: $bits:ty, $exp_bits:literal, $frac_bits:literal;)*) => {$(
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+        #[repr(transparent)]
+        pub struct $name($bits);
+
+        impl $name {
+            /// The reserved operand, which decodes as NaN.
+            pub const RESERVED: Self = Self(1 << ($exp_bits + $frac_bits));
+
+            pub const MIN: Self = Self(<$bits>::MAX);
+            pub const MAX: Self = Self(<$bits>::MAX >> 1);
+
+            /// The smallest positive value, 2^-(bias).
+            pub const MIN_POSITIVE: Self = Self(1 << $frac_bits);
+
+            /// Returns `true` if `self` is the reserved operand, or any other
+            /// encoding with a negative sign and a zero exponent.
+            #[inline]
+            pub const fn is_reserved(self) -> bool {
+                self.0 >> $frac_bits == 1 << $exp_bits
+            }
+
+            /// Creates a value from its logical representation as an
+            /// integer, with the sign in the most significant bit.
+            #[inline]
+            pub const fn from_bits(bits: $bits) -> Self {
+                Self(bits)
+            }
+
+            /// Creates a value from its memory representation as a byte
+            /// array: 16-bit little endian words, most significant first.
+            #[inline]
+            pub const fn from_vax_bytes(bytes: [u8; core::mem::size_of::<$bits>()]) -> Self {
+                Self(from_words(bytes) as $bits)
+            }
+
+            /// Returns the logical representation of this value as an
+            /// integer.
+            #[inline]
+            pub const fn to_bits(self) -> $bits {
+                self.0
+            }
+
+            /// Returns the memory representation of this value as a byte
+            /// array: 16-bit little endian words, most significant first.
+            #[inline]
+            pub const fn to_vax_bytes(self) -> [u8; core::mem::size_of::<$bits>()] {
+                to_words(self.0 as u128)
+            }
+        }
+
+        impl Legacy for $name {
+            #[inline]
+            fn decode(self) -> Value {
+                decode(self.0 as u128, $exp_bits, $frac_bits)
+            }
+
+            #[inline]
+            fn encode(value: Value) -> Self {
+                Self(encode(value, $exp_bits, $frac_bits) as $bits)
+            }
+        }
+    )*};
+}
+
+vax! {
+    /// VAX F_floating: 32 bits, with an 8-bit exponent and 24 significant
+    /// bits.
+    VaxF: u32, 8, 23;
+
+    /// VAX D_floating: 64 bits, with an 8-bit exponent and 56 significant
+    /// bits.
+    VaxD: u64, 8, 55;
+
+    /// VAX G_floating: 64 bits, with an 11-bit exponent and 53 significant
+    /// bits.
+    VaxG: u64, 11, 52;
+
+    /// VAX H_floating: 128 bits, with a 15-bit exponent and 113 significant
+    /// bits.
+    VaxH: u128, 15, 112;
+}
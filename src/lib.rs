@@ -2,15 +2,66 @@
 #![cfg_attr(feature = "nightly", feature(f16, f128))]
 #![no_std]
 
+#[cfg(feature = "std")]
+extern crate std;
+
 #[cfg(feature = "casting")]
 pub use casting;
 
+mod ahp;
+mod backend;
+mod bfloat;
+mod big;
+mod decimal;
+mod double;
+mod extended;
+mod fp8;
+#[macro_use]
+mod ieee;
+pub mod legacy;
+mod minifloat;
+pub mod mx;
+mod octuple;
+pub mod packed;
+mod rem;
+mod round;
+mod slice;
+mod uint;
+
+pub use ahp::f16ahp;
+pub use backend::{backend, bf16_backend, Backend};
+pub use bfloat::bf16;
+pub use decimal::{d128, d32, d64, ParseDecimalError};
+pub use double::DoubleDouble;
+pub use extended::f80;
+pub use fp8::{f8e4m3, f8e4m3fnuz, f8e5m2, f8e5m2fnuz};
+pub use ieee::{FloatBits, IeeeFloat};
+pub use minifloat::{Encoding, FiniteNan, Fnuz, Ieee, Minifloat};
+pub use octuple::f256;
+
+#[cfg(feature = "casting")]
+pub use slice::ConvertSlice;
+
+#[cfg(not(feature = "nightly"))]
+#[macro_use]
+mod wide;
+
 #[cfg(not(feature = "nightly"))]
+#[macro_use]
 mod half;
 
+#[cfg(not(feature = "nightly"))]
+pub mod hw;
+
+#[cfg(not(feature = "nightly"))]
+mod int;
+
 #[cfg(not(feature = "nightly"))]
 mod quad;
 
+#[cfg(not(feature = "nightly"))]
+pub mod soft;
+
 #[cfg(not(feature = "nightly"))]
 pub use half::f16;
 
@@ -0,0 +1,36 @@
+#![cfg(feature = "casting")]
+
+//! Conversions to and from the primitive floats and `f128`, correctly
+//! rounded in both directions.
+
+use super::{Encoding, Minifloat};
+use crate::f128;
+use casting::CastFrom;
+
+macro_rules! cast {
+    ($($float:ty: $bits:ty, $exp_bits:literal, $mant_bits:literal;)*) => {$(
+        impl<const EXP: u32, const MANT: u32, E: Encoding> CastFrom<$float>
+            for Minifloat<EXP, MANT, E>
+        {
+            #[inline]
+            fn cast_from(value: $float) -> Self {
+                Self::from_ieee(value.to_bits() as u128, $exp_bits, $mant_bits)
+            }
+        }
+
+        impl<const EXP: u32, const MANT: u32, E: Encoding> CastFrom<Minifloat<EXP, MANT, E>>
+            for $float
+        {
+            #[inline]
+            fn cast_from(value: Minifloat<EXP, MANT, E>) -> Self {
+                <$float>::from_bits(value.to_ieee($exp_bits, $mant_bits) as $bits)
+            }
+        }
+    )*};
+}
+
+cast! {
+    f32: u32, 8, 23;
+    f64: u64, 11, 52;
+    f128: u128, 15, 112;
+}
@@ -0,0 +1,53 @@
+use super::{Encoding, Minifloat};
+use core::fmt::{self, Write};
+
+/// A stack buffer for formatting one `f64`.
+struct Buffer {
+    bytes: [u8; 32],
+    len: usize,
+}
+
+impl Write for Buffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        self.bytes
+            .get_mut(self.len..end)
+            .ok_or(fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+impl<const EXP: u32, const MANT: u32, E: Encoding> fmt::Display for Minifloat<EXP, MANT, E> {
+    /// Writes the shortest decimal that reads back, through `f64`, as
+    /// `self`, breaking ties away from zero as the primitives do. With a
+    /// precision, writes the exact value to that precision.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Every value is exact in f64
+        let exact = f64::from_bits(self.to_ieee(11, 52) as u64);
+        if f.precision().is_some() || !self.is_finite() || exact == 0.0 {
+            return fmt::Display::fmt(&exact, f);
+        }
+
+        // Formatting rounds decimal ties to even: nudging the value one f64
+        // ulp away from zero breaks them away instead
+        let away = f64::from_bits(exact.to_bits() + 1);
+
+        for digits in 0..17 {
+            let mut buffer = Buffer {
+                bytes: [0; 32],
+                len: 0,
+            };
+            write!(buffer, "{:.*e}", digits, away)?;
+
+            let text = core::str::from_utf8(&buffer.bytes[..buffer.len]).map_err(|_| fmt::Error)?;
+            let decimal: f64 = text.parse().map_err(|_| fmt::Error)?;
+            if Self::from_ieee(decimal.to_bits() as u128, 11, 52).0 == self.0 {
+                return fmt::Display::fmt(&decimal, f);
+            }
+        }
+
+        fmt::Display::fmt(&exact, f)
+    }
+}
@@ -0,0 +1,321 @@
+//! A binary floating-point format of any width up to `f64`.
+//!
+//! [`Minifloat`] takes its exponent and mantissa widths as const generics
+//! and its special values from an [`Encoding`], so one type covers TF32
+//! (`Minifloat<8, 10>`), E3M4 or a DSP's private layout. Conversions use the
+//! same rounding core as the dedicated types.
+
+use crate::round::{decode, rescale_payload, round_pack, unpack, Decoded};
+use core::fmt;
+use core::marker::PhantomData;
+use core::num::FpCategory;
+use core::ops::Neg;
+
+mod cast;
+mod display;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// How a [`Minifloat`] encodes infinities and NaN. The trait is sealed.
+pub trait Encoding: sealed::Sealed + Copy + Default + fmt::Debug + 'static {
+    /// Whether the largest exponent holds the infinities and NaNs, as in
+    /// IEEE 754.
+    const IEEE: bool;
+
+    /// Whether the negative zero encoding is the only NaN, with a bias one
+    /// larger than IEEE 754's.
+    const FNUZ: bool;
+}
+
+/// The IEEE 754 encoding: infinities and NaNs have the largest exponent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Ieee;
+
+/// No infinities, and a single NaN of each sign with every exponent and
+/// mantissa bit set, as in the OCP `f8e4m3` format. Overflow produces NaN.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct FiniteNan;
+
+/// No infinities and no negative zero: its encoding is the only NaN, and
+/// the bias is one larger, as in `f8e4m3fnuz`. Overflow produces NaN.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Fnuz;
+
+impl sealed::Sealed for Ieee {}
+impl sealed::Sealed for FiniteNan {}
+impl sealed::Sealed for Fnuz {}
+
+impl Encoding for Ieee {
+    const IEEE: bool = true;
+    const FNUZ: bool = false;
+}
+
+impl Encoding for FiniteNan {
+    const IEEE: bool = false;
+    const FNUZ: bool = false;
+}
+
+impl Encoding for Fnuz {
+    const IEEE: bool = false;
+    const FNUZ: bool = true;
+}
+
+/// Binary floating-point type with `EXP` exponent bits and `MANT` stored
+/// mantissa bits, and special values encoded as `E` says.
+///
+/// `EXP` may be 2 to 11 and `MANT` 1 to 52, so that every value is exact in
+/// an `f64`; other widths fail to compile when used. The bits are held in
+/// the low `1 + EXP + MANT` bits of a `u64`. It provides classification,
+/// `Display`, and correctly rounded conversions to and from `f32`, `f64`
+/// and `f128`.
+#[derive(Clone, Copy, Default)]
+#[repr(transparent)]
+pub struct Minifloat<const EXP: u32, const MANT: u32, E: Encoding = Ieee>(u64, PhantomData<E>);
+
+impl<const EXP: u32, const MANT: u32, E: Encoding> fmt::Debug for Minifloat<EXP, MANT, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Minifloat").field(&self.0).finish()
+    }
+}
+
+impl<const EXP: u32, const MANT: u32, E: Encoding> Neg for Minifloat<EXP, MANT, E> {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        // Zero and the NaN have no negative counterparts
+        if E::FNUZ && self.0 & Self::ABS == 0 {
+            self
+        } else {
+            Self(self.0 ^ Self::SIGN, PhantomData)
+        }
+    }
+}
+
+impl<const EXP: u32, const MANT: u32, E: Encoding> PartialEq for Minifloat<EXP, MANT, E> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        // IEEE 754 compliance: NaN != NaN and +0.0 == -0.0
+        if self.is_nan() || other.is_nan() {
+            return false;
+        }
+
+        if (self.0 | other.0) & Self::ABS == 0 {
+            return true;
+        }
+
+        self.0 == other.0
+    }
+}
+
+impl<const EXP: u32, const MANT: u32, E: Encoding> Minifloat<EXP, MANT, E> {
+    const VALID: () = assert!(
+        EXP >= 2 && EXP <= 11 && MANT >= 1 && MANT <= 52,
+        "Minifloat needs 2 to 11 exponent bits and 1 to 52 mantissa bits"
+    );
+
+    const SIGN: u64 = 1 << (EXP + MANT);
+    const ABS: u64 = Self::SIGN - 1;
+    const EXP_MASK: u64 = ((1 << EXP) - 1) << MANT;
+    const MANT_MASK: u64 = (1 << MANT) - 1;
+
+    /// The bits of the largest finite value.
+    const MAX_BITS: u64 = if E::IEEE {
+        Self::EXP_MASK - 1
+    } else if E::FNUZ {
+        Self::ABS
+    } else {
+        Self::ABS - 1
+    };
+
+    /// The total width in bits.
+    pub const BITS: u32 = 1 + EXP + MANT;
+
+    pub const MANTISSA_DIGITS: u32 = MANT + 1;
+
+    /// The NaN produced by conversions.
+    pub const NAN: Self = Self(
+        if E::IEEE {
+            Self::EXP_MASK | (1 << (MANT - 1))
+        } else if E::FNUZ {
+            Self::SIGN
+        } else {
+            Self::ABS
+        },
+        PhantomData,
+    );
+
+    pub const MIN: Self = Self(Self::SIGN | Self::MAX_BITS, PhantomData);
+    pub const MAX: Self = Self(Self::MAX_BITS, PhantomData);
+
+    /// The smallest positive normal value.
+    pub const MIN_POSITIVE: Self = Self(1 << MANT, PhantomData);
+
+    /// Positive infinity, or, in encodings without infinities, the NaN that
+    /// overflow produces.
+    pub const INFINITY: Self = if E::IEEE {
+        Self(Self::EXP_MASK, PhantomData)
+    } else {
+        Self::NAN
+    };
+
+    /// Negative infinity, or, in encodings without infinities, the NaN that
+    /// overflow produces.
+    pub const NEG_INFINITY: Self = if E::IEEE {
+        Self(Self::SIGN | Self::EXP_MASK, PhantomData)
+    } else if E::FNUZ {
+        Self::NAN
+    } else {
+        Self(Self::SIGN | Self::ABS, PhantomData)
+    };
+
+    /// Creates a `Minifloat` from its representation in the low bits of a
+    /// `u64`. Bits above the format's width are ignored.
+    #[inline]
+    pub fn from_bits(bits: u64) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID;
+        Self(bits & (Self::SIGN | Self::ABS), PhantomData)
+    }
+
+    /// Returns the raw representation of this `Minifloat` in the low bits of
+    /// a `u64`.
+    #[inline]
+    pub fn to_bits(self) -> u64 {
+        self.0
+    }
+
+    #[inline]
+    pub fn is_nan(self) -> bool {
+        if E::FNUZ {
+            self.0 == Self::SIGN
+        } else if E::IEEE {
+            self.0 & Self::ABS > Self::EXP_MASK
+        } else {
+            self.0 & Self::ABS == Self::ABS
+        }
+    }
+
+    #[inline]
+    pub fn is_infinite(self) -> bool {
+        E::IEEE && self.0 & Self::ABS == Self::EXP_MASK
+    }
+
+    #[inline]
+    pub fn is_finite(self) -> bool {
+        !self.is_nan() && !self.is_infinite()
+    }
+
+    /// Returns `true` if `self` is neither zero, subnormal, infinite nor NaN.
+    #[inline]
+    pub fn is_normal(self) -> bool {
+        matches!(self.classify(), FpCategory::Normal)
+    }
+
+    #[inline]
+    pub fn is_subnormal(self) -> bool {
+        matches!(self.classify(), FpCategory::Subnormal)
+    }
+
+    #[inline]
+    pub fn is_sign_positive(self) -> bool {
+        !self.is_sign_negative()
+    }
+
+    #[inline]
+    pub fn is_sign_negative(self) -> bool {
+        self.0 & Self::SIGN != 0
+    }
+
+    /// Returns the floating-point category.
+    #[inline]
+    pub fn classify(self) -> FpCategory {
+        if self.is_nan() {
+            FpCategory::Nan
+        } else if self.is_infinite() {
+            FpCategory::Infinite
+        } else if self.0 & Self::EXP_MASK != 0 {
+            FpCategory::Normal
+        } else if self.0 & Self::MANT_MASK != 0 {
+            FpCategory::Subnormal
+        } else {
+            FpCategory::Zero
+        }
+    }
+
+    /// Converts to the binary format with `exp_bits` exponent bits and
+    /// `mant_bits` stored mantissa bits, rounding to nearest even.
+    ///
+    /// NaNs are quieted and keep their sign and top payload bits.
+    #[inline]
+    pub(crate) fn to_ieee(self, exp_bits: u32, mant_bits: u32) -> u128 {
+        let sign = (self.is_sign_negative() as u128) << (exp_bits + mant_bits);
+        let inf = sign | (((1 << exp_bits) - 1) << mant_bits);
+
+        if self.is_nan() {
+            let mant = (self.0 & Self::MANT_MASK) as u128;
+            let payload = if E::IEEE {
+                rescale_payload(mant, MANT, mant_bits)
+            } else {
+                0
+            };
+            return inf | (1 << (mant_bits - 1)) | payload;
+        }
+
+        if self.is_infinite() {
+            return inf;
+        }
+
+        // The fields are IEEE 754's, but for the bias in FNUZ
+        let (sign, mant, exp) = unpack(self.0 as u128, EXP, MANT);
+        round_pack(sign, mant, exp - E::FNUZ as i32, false, exp_bits, mant_bits)
+    }
+
+    /// Converts from the binary format with `exp_bits` exponent bits and
+    /// `mant_bits` stored mantissa bits, rounding to nearest even.
+    ///
+    /// Values beyond `MAX` become `INFINITY` or `NEG_INFINITY`. NaNs stay
+    /// NaN, keeping their sign and top payload bits where the encoding has
+    /// room for them.
+    pub(crate) fn from_ieee(bits: u128, exp_bits: u32, mant_bits: u32) -> Self {
+        let sign = |negative: bool| if negative { Self::SIGN } else { 0 };
+        let overflow = |negative: bool| {
+            if negative {
+                Self::NEG_INFINITY
+            } else {
+                Self::INFINITY
+            }
+        };
+
+        let (negative, mant, exp) = match decode(bits, exp_bits, mant_bits) {
+            Decoded::Finite(negative, mant, exp) => (negative, mant, exp),
+            Decoded::Infinite(negative) => return overflow(negative),
+            Decoded::Nan(negative, mant) => {
+                return if E::IEEE {
+                    let payload = rescale_payload(mant, mant_bits, MANT) as u64;
+                    Self::from_bits(sign(negative) | Self::NAN.0 | payload)
+                } else if E::FNUZ {
+                    Self::NAN
+                } else {
+                    Self::from_bits(sign(negative) | Self::NAN.0)
+                };
+            }
+        };
+
+        // Round into a format with one more exponent bit, so that the top
+        // exponent is an ordinary one, then shift the bias to this format's:
+        // 2^EXP - 1 there, 2^(EXP - 1) - 1 here, plus one for FNUZ
+        let rebias = E::FNUZ as i32 - (1 << (EXP - 1));
+        let rounded = round_pack(false, mant, exp + rebias, false, EXP + 1, MANT) as u64;
+
+        if rounded > Self::MAX_BITS {
+            overflow(negative)
+        } else if rounded == 0 && E::FNUZ {
+            Self::from_bits(0)
+        } else {
+            Self::from_bits(sign(negative) | rounded)
+        }
+    }
+}
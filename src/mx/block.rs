@@ -0,0 +1,172 @@
+use super::{decode, encode, BLOCK_SIZE, E8M0};
+use super::{Fp4E2M1, Fp6E2M3, Fp6E3M2, Fp8E4M3, Fp8E5M2, Int8};
+use crate::f16;
+use casting::CastFrom;
+
+/// Stores one element per byte.
+#[inline]
+fn pack8(codes: [u8; BLOCK_SIZE]) -> [u8; 32] {
+    codes
+}
+
+#[inline]
+fn unpack8(bytes: [u8; 32]) -> [u8; BLOCK_SIZE] {
+    bytes
+}
+
+/// Stores four elements in every three bytes, little endian: element `i`
+/// is bits `6 * i` to `6 * i + 5` of the block.
+#[inline]
+fn pack6(codes: [u8; BLOCK_SIZE]) -> [u8; 24] {
+    let mut bytes = [0; 24];
+    for (dst, src) in bytes.chunks_exact_mut(3).zip(codes.chunks_exact(4)) {
+        let word = src
+            .iter()
+            .enumerate()
+            .fold(0u32, |word, (i, &code)| word | (code as u32) << (6 * i));
+        dst.copy_from_slice(&word.to_le_bytes()[..3]);
+    }
+    bytes
+}
+
+#[inline]
+fn unpack6(bytes: [u8; 24]) -> [u8; BLOCK_SIZE] {
+    let mut codes = [0; BLOCK_SIZE];
+    for (dst, src) in codes.chunks_exact_mut(4).zip(bytes.chunks_exact(3)) {
+        let word = u32::from_le_bytes([src[0], src[1], src[2], 0]);
+        for (i, code) in dst.iter_mut().enumerate() {
+            *code = (word >> (6 * i)) as u8 & 0x3f;
+        }
+    }
+    codes
+}
+
+/// Stores two elements per byte, the even one in the low nibble.
+#[inline]
+fn pack4(codes: [u8; BLOCK_SIZE]) -> [u8; 16] {
+    let mut bytes = [0; 16];
+    for (dst, src) in bytes.iter_mut().zip(codes.chunks_exact(2)) {
+        *dst = src[0] | src[1] << 4;
+    }
+    bytes
+}
+
+#[inline]
+fn unpack4(bytes: [u8; 16]) -> [u8; BLOCK_SIZE] {
+    let mut codes = [0; BLOCK_SIZE];
+    for (dst, src) in codes.chunks_exact_mut(2).zip(bytes) {
+        dst[0] = src & 0x0f;
+        dst[1] = src >> 4;
+    }
+    codes
+}
+
+macro_rules! block {
+    ($(
+        $(#[$attr:meta])*
+        $name:ident($element:ty): $bytes:literal, $pack:ident, $unpack:ident;
+    )*) => {$(
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+        pub struct $name {
+            scale: E8M0,
+            elements: [u8; $bytes],
+        }
+
+        impl $name {
+            /// Creates a block from its scale and packed elements.
+            #[inline]
+            pub const fn from_parts(scale: E8M0, elements: [u8; $bytes]) -> Self {
+                Self { scale, elements }
+            }
+
+            /// Returns the shared scale.
+            #[inline]
+            pub const fn scale(self) -> E8M0 {
+                self.scale
+            }
+
+            /// Returns the packed elements.
+            #[inline]
+            pub const fn elements(self) -> [u8; $bytes] {
+                self.elements
+            }
+
+            /// Quantizes 32 `f32` values into a block.
+            #[inline]
+            pub fn from_f32_array(values: [f32; BLOCK_SIZE]) -> Self {
+                Self::from_f64_array(values.map(f64::from))
+            }
+
+            /// Quantizes 32 `f16` values into a block.
+            #[inline]
+            pub fn from_f16_array(values: [f16; BLOCK_SIZE]) -> Self {
+                Self::from_f64_array(values.map(f64::cast_from))
+            }
+
+            /// Returns the values of the block, each rounded to nearest even.
+            #[inline]
+            pub fn to_f32_array(self) -> [f32; BLOCK_SIZE] {
+                self.to_f64_array().map(|value| {
+                    if value.is_nan() {
+                        f32::NAN
+                    } else {
+                        value as f32
+                    }
+                })
+            }
+
+            /// Returns the values of the block, each rounded to nearest even.
+            #[inline]
+            pub fn to_f16_array(self) -> [f16; BLOCK_SIZE] {
+                self.to_f64_array().map(f16::cast_from)
+            }
+
+            #[inline]
+            fn from_f64_array(values: [f64; BLOCK_SIZE]) -> Self {
+                let (scale, codes) = encode::<$element>(values);
+                Self::from_parts(scale, $pack(codes))
+            }
+
+            #[inline]
+            fn to_f64_array(self) -> [f64; BLOCK_SIZE] {
+                decode::<$element>(self.scale, $unpack(self.elements))
+            }
+        }
+    )*};
+}
+
+block! {
+    /// An MXFP8 block of [`f8e4m3`](crate::f8e4m3) elements, one per byte.
+    MxFp8E4M3(Fp8E4M3): 32, pack8, unpack8;
+
+    /// An MXFP8 block of [`f8e5m2`](crate::f8e5m2) elements, one per byte.
+    MxFp8E5M2(Fp8E5M2): 32, pack8, unpack8;
+
+    /// An MXFP6 block of E2M3 elements: 1 sign, 2 exponent and 3 mantissa
+    /// bits with bias 1, so magnitudes up to 7.5 and no infinity or NaN.
+    ///
+    /// Each three bytes hold four elements, little endian: element `i` is
+    /// bits `6 * i` to `6 * i + 5` of its group.
+    MxFp6E2M3(Fp6E2M3): 24, pack6, unpack6;
+
+    /// An MXFP6 block of E3M2 elements: 1 sign, 3 exponent and 2 mantissa
+    /// bits with bias 3, so magnitudes up to 28 and no infinity or NaN.
+    ///
+    /// Each three bytes hold four elements, little endian: element `i` is
+    /// bits `6 * i` to `6 * i + 5` of its group.
+    MxFp6E3M2(Fp6E3M2): 24, pack6, unpack6;
+
+    /// An MXFP4 block of E2M1 elements: 1 sign, 2 exponent and 1 mantissa
+    /// bit with bias 1, so magnitudes up to 6 and no infinity or NaN.
+    ///
+    /// Each byte holds two elements, the even-indexed one in the low nibble.
+    MxFp4(Fp4E2M1): 16, pack4, unpack4;
+
+    /// An MXINT8 block of two's complement elements with an implicit scale
+    /// of 2^-6, one per byte.
+    ///
+    /// Quantization keeps the range symmetric, from -127 to 127, but `-128`
+    /// decodes as -2.
+    MxInt8(Int8): 32, pack8, unpack8;
+}
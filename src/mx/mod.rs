@@ -0,0 +1,239 @@
+#![cfg(feature = "casting")]
+
+//! OCP Microscaling (MX) block formats.
+//!
+//! A block stores [`BLOCK_SIZE`] elements with one shared [`E8M0`] scale,
+//! a power of two, and each value is its element times the scale. The
+//! element formats are FP8 ([`MxFp8E4M3`], [`MxFp8E5M2`]), FP6
+//! ([`MxFp6E2M3`], [`MxFp6E3M2`]), FP4 ([`MxFp4`]) and 8-bit integers with
+//! an implicit scale of 2^-6 ([`MxInt8`]).
+//!
+//! Quantization follows the OCP MX specification: the shared exponent is
+//! that of the largest magnitude in the block, less the largest exponent of
+//! the element format, and each scaled value is rounded to nearest even,
+//! saturating at the element format's largest magnitude. A block containing
+//! an infinity or NaN gets the NaN scale, which decodes every element as
+//! NaN. Every step is exact but the one rounding, so results are bit-for-bit
+//! reproducible on every target.
+
+mod block;
+
+pub use self::block::*;
+use crate::fp8::sw::{narrow, widen};
+use crate::fp8::{f8e4m3, f8e5m2, Format};
+use casting::CastFrom;
+
+/// The number of elements in a block.
+pub const BLOCK_SIZE: usize = 32;
+
+/// The shared scale of an MX block: an 8-bit biased exponent with no sign
+/// or mantissa.
+///
+/// `bits` stands for 2^(`bits` - 127), from 2^-127 to 2^127, and `0xff` is
+/// NaN.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct E8M0(u8);
+
+impl E8M0 {
+    pub const NAN: Self = Self(0xff);
+
+    /// The scale 2^0.
+    pub const ONE: Self = Self(127);
+
+    pub const MIN: Self = Self(0);
+    pub const MAX: Self = Self(0xfe);
+
+    #[inline]
+    pub const fn is_nan(self) -> bool {
+        self.0 == 0xff
+    }
+
+    /// Returns the unbiased exponent, or `None` for NaN.
+    #[inline]
+    pub const fn exponent(self) -> Option<i32> {
+        if self.is_nan() {
+            None
+        } else {
+            Some(self.0 as i32 - 127)
+        }
+    }
+
+    /// Creates an `E8M0` from its representation as a `u8`.
+    #[inline]
+    pub const fn from_bits(bits: u8) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the raw representation of this `E8M0` as a `u8`.
+    #[inline]
+    pub const fn to_bits(self) -> u8 {
+        self.0
+    }
+}
+
+impl CastFrom<E8M0> for f32 {
+    #[inline]
+    fn cast_from(value: E8M0) -> f32 {
+        // Exact: the smallest scale is an f32 subnormal
+        match value.exponent() {
+            Some(exp) => pow2(exp) as f32,
+            None => f32::NAN,
+        }
+    }
+}
+
+impl CastFrom<E8M0> for f64 {
+    #[inline]
+    fn cast_from(value: E8M0) -> f64 {
+        match value.exponent() {
+            Some(exp) => pow2(exp),
+            None => f64::NAN,
+        }
+    }
+}
+
+/// Returns 2^`exp`, for `exp` within the normal range of f64.
+#[inline]
+fn pow2(exp: i32) -> f64 {
+    f64::from_bits(((exp + 1023) as u64) << 52)
+}
+
+/// An element format.
+trait Element {
+    /// The exponent of the largest power of two the format can hold.
+    const EMAX: i32;
+
+    /// Rounds a finite, already scaled value to its element code.
+    fn encode(value: f64) -> u8;
+
+    /// Returns the exact value of an element code.
+    fn decode(code: u8) -> f64;
+}
+
+/// A floating-point element `WIDTH` bits wide with the sign on top, rounded
+/// by the 8-bit machinery with its sign moved to bit 7.
+macro_rules! float {
+    ($($name:ident($format:ty): width $width:literal, emax $emax:literal;)*) => {$(
+        enum $name {}
+
+        impl Element for $name {
+            const EMAX: i32 = $emax;
+
+            #[inline]
+            fn encode(value: f64) -> u8 {
+                let bits = narrow::<$format>(value, true).to_bits();
+                (bits & 0x7f) | ((bits >> 7) << ($width - 1))
+            }
+
+            #[inline]
+            fn decode(code: u8) -> f64 {
+                let bits = (code & ((1 << ($width - 1)) - 1)) | ((code >> ($width - 1)) << 7);
+                f64::from(widen(<$format>::from_bits(bits)))
+            }
+        }
+    )*};
+}
+
+/// The layout of a 6- or 4-bit element format, which has no infinity or
+/// NaN: every encoding is finite.
+macro_rules! narrow_format {
+    ($($name:ident: mant $mant:literal, bias $bias:literal, max $max:literal;)*) => {$(
+        #[derive(Clone, Copy)]
+        struct $name(u8);
+
+        impl Format for $name {
+            const MANT: u32 = $mant;
+            const BIAS: i32 = $bias;
+            const MAX: u8 = $max;
+            const INF: bool = false;
+            const FNUZ: bool = false;
+
+            #[inline]
+            fn from_bits(bits: u8) -> Self {
+                Self(bits)
+            }
+
+            #[inline]
+            fn to_bits(self) -> u8 {
+                self.0
+            }
+        }
+    )*};
+}
+
+narrow_format! {
+    E2M3: mant 3, bias 1, max 0x1f;
+    E3M2: mant 2, bias 3, max 0x1f;
+    E2M1: mant 1, bias 1, max 0x07;
+}
+
+float! {
+    Fp8E4M3(f8e4m3): width 8, emax 8;
+    Fp8E5M2(f8e5m2): width 8, emax 15;
+    Fp6E2M3(E2M3): width 6, emax 2;
+    Fp6E3M2(E3M2): width 6, emax 4;
+    Fp4E2M1(E2M1): width 4, emax 2;
+}
+
+/// A two's complement 8-bit element with an implicit scale of 2^-6.
+enum Int8 {}
+
+impl Element for Int8 {
+    const EMAX: i32 = 0;
+
+    #[inline]
+    fn encode(value: f64) -> u8 {
+        // Adding 1.5 * 2^52 leaves the integer part in the low bits, rounded
+        // to nearest even by the addition itself. The range is symmetric,
+        // so -128 is never produced.
+        let magic = 6755399441055744.0;
+        let rounded = (value * 64.0 + magic) - magic;
+        (rounded.clamp(-127.0, 127.0) as i8) as u8
+    }
+
+    #[inline]
+    fn decode(code: u8) -> f64 {
+        code as i8 as f64 / 64.0
+    }
+}
+
+/// Chooses the shared scale of `values` and encodes each of them.
+fn encode<E: Element>(values: [f64; BLOCK_SIZE]) -> (E8M0, [u8; BLOCK_SIZE]) {
+    let mut max = 0;
+    for value in values {
+        max = max.max(value.to_bits() & 0x7FFF_FFFF_FFFF_FFFF);
+    }
+
+    if max >= 0x7FF0_0000_0000_0000 {
+        return (E8M0::NAN, [0; BLOCK_SIZE]);
+    }
+
+    // floor(log2(max)) less the element's own, clamped to the scale's
+    // range. The values come from f32 or f16, so none is an f64 subnormal,
+    // and an all-zero block takes the smallest scale.
+    let exp = if max == 0 {
+        -127
+    } else {
+        ((max >> 52) as i32 - 1023 - E::EMAX).clamp(-127, 127)
+    };
+
+    let mut codes = [0; BLOCK_SIZE];
+    for (code, value) in codes.iter_mut().zip(values) {
+        *code = E::encode(value * pow2(-exp));
+    }
+
+    (E8M0((exp + 127) as u8), codes)
+}
+
+/// Decodes each element of a block, exactly.
+fn decode<E: Element>(scale: E8M0, codes: [u8; BLOCK_SIZE]) -> [f64; BLOCK_SIZE] {
+    let scale = f64::cast_from(scale);
+
+    let mut values = [0.0; BLOCK_SIZE];
+    for (value, code) in values.iter_mut().zip(codes) {
+        *value = E::decode(code) * scale;
+    }
+
+    values
+}
@@ -0,0 +1,214 @@
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+
+use super::{f256, MANT_BITS};
+use crate::uint::U256;
+
+/// Guard bits kept below the mantissa while adding.
+const GUARD: u32 = 10;
+
+impl f256 {
+    propagate_nan!();
+
+    fn add_impl(self, rhs: Self) -> Self {
+        if self.is_nan() || rhs.is_nan() {
+            return self.propagate_nan(rhs);
+        }
+
+        if self.is_infinite() {
+            if rhs.is_infinite() && self.0 != rhs.0 {
+                return Self::NAN;
+            }
+            return self;
+        } else if rhs.is_infinite() {
+            return rhs;
+        }
+
+        // Order the operands by magnitude
+        let (a, b) = if self.abs_bits() >= rhs.abs_bits() {
+            (self, rhs)
+        } else {
+            (rhs, self)
+        };
+
+        let (sign, ma, ea) = a.unpack();
+        let (_, mb, eb) = b.unpack();
+
+        if mb.is_zero() {
+            // The sum of two zeros is negative only if both are
+            return if ma.is_zero() {
+                Self([0, a.0[1] & b.0[1]])
+            } else {
+                a
+            };
+        }
+
+        // Align the smaller operand, folding what falls off into a sticky bit
+        let shift = (ea - eb) as u32;
+        let ma = ma.shl(GUARD);
+        let (mb, sticky) = mb.shl(GUARD).shr(shift);
+
+        let mant = if a.is_sign_negative() == b.is_sign_negative() {
+            ma.add(mb)
+        } else {
+            // Borrow from the kept bits for a partially shifted out operand
+            ma.sub(mb).sub(U256::new(sticky as u128))
+        };
+
+        // Exact cancellation is positive when rounding to nearest
+        if mant.is_zero() {
+            return Self::default();
+        }
+
+        Self::round_pack(sign, mant, ea - GUARD as i32, sticky)
+    }
+
+    fn mul_impl(self, rhs: Self) -> Self {
+        if self.is_nan() || rhs.is_nan() {
+            return self.propagate_nan(rhs);
+        }
+
+        let sign = self.is_sign_negative() != rhs.is_sign_negative();
+        let zero = self.abs_bits().is_zero() || rhs.abs_bits().is_zero();
+
+        if self.is_infinite() || rhs.is_infinite() {
+            if zero {
+                return Self::NAN;
+            }
+            return Self::from_u256(sign, Self::INFINITY.abs_bits());
+        } else if zero {
+            return Self::from_u256(sign, U256::ZERO);
+        }
+
+        let (_, ma, ea) = self.unpack();
+        let (_, mb, eb) = rhs.unpack();
+        let (hi, lo) = ma.mul_wide(mb);
+
+        // Keep the top 256 bits of the product and a sticky bit
+        let (mant, exp, sticky) = if hi.is_zero() {
+            (lo, 0, false)
+        } else {
+            let zeros = hi.leading_zeros();
+            let mant = hi.shl(zeros).add(lo.shr(256 - zeros).0);
+            (mant, 256 - zeros as i32, !lo.shl(zeros).is_zero())
+        };
+
+        Self::round_pack(sign, mant, ea + eb + exp, sticky)
+    }
+
+    fn div_impl(self, rhs: Self) -> Self {
+        if self.is_nan() || rhs.is_nan() {
+            return self.propagate_nan(rhs);
+        }
+
+        let sign = self.is_sign_negative() != rhs.is_sign_negative();
+        let signed_inf = Self::from_u256(sign, Self::INFINITY.abs_bits());
+        let signed_zero = Self::from_u256(sign, U256::ZERO);
+        let a_zero = self.abs_bits().is_zero();
+        let b_zero = rhs.abs_bits().is_zero();
+
+        if self.is_infinite() {
+            return if rhs.is_infinite() {
+                Self::NAN
+            } else {
+                signed_inf
+            };
+        } else if rhs.is_infinite() {
+            return signed_zero;
+        } else if b_zero {
+            return if a_zero { Self::NAN } else { signed_inf };
+        } else if a_zero {
+            return signed_zero;
+        }
+
+        // Normalize both mantissas so that the quotient has a fixed width
+        let (_, ma, ea) = self.unpack();
+        let (_, mb, eb) = rhs.unpack();
+        let sa = ma.leading_zeros() - (255 - MANT_BITS);
+        let sb = mb.leading_zeros() - (255 - MANT_BITS);
+        let (ma, mb) = (ma.shl(sa), mb.shl(sb));
+
+        // Restoring division: three bits beyond the mantissa, then a sticky
+        let mut quot = U256::ZERO;
+        let mut rem = ma;
+        for _ in 0..MANT_BITS + 4 {
+            quot = quot.shl(1);
+            if rem >= mb {
+                rem = rem.sub(mb);
+                quot.lo |= 1;
+            }
+            rem = rem.shl(1);
+        }
+
+        let exp = ea - sa as i32 - (eb - sb as i32) - (MANT_BITS as i32 + 3);
+        Self::round_pack(sign, quot, exp, !rem.is_zero())
+    }
+}
+
+impl Add for f256 {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        self.add_impl(rhs)
+    }
+}
+
+impl Sub for f256 {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self::Output {
+        // Negating a NaN would flip the sign of a propagated payload
+        if rhs.is_nan() {
+            return self.propagate_nan(rhs);
+        }
+
+        self.add_impl(-rhs)
+    }
+}
+
+impl Mul for f256 {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.mul_impl(rhs)
+    }
+}
+
+impl Div for f256 {
+    type Output = Self;
+
+    #[inline]
+    fn div(self, rhs: Self) -> Self::Output {
+        self.div_impl(rhs)
+    }
+}
+
+impl AddAssign for f256 {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for f256 {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign for f256 {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl DivAssign for f256 {
+    #[inline]
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
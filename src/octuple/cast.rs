@@ -0,0 +1,97 @@
+#![cfg(feature = "casting")]
+
+//! Conversions to and from the other binary floats. Widening into `f256`
+//! is exact, and narrowing rounds to nearest even.
+
+use super::{f256, MANT_BITS};
+use crate::round::{round_pack, unpack};
+use crate::uint::U256;
+use crate::{f128, f16};
+use casting::CastFrom;
+
+impl f256 {
+    /// Converts to the binary format with `exp_bits` exponent bits and
+    /// `mant_bits` stored mantissa bits. NaNs are quieted and keep their
+    /// sign and top payload bits.
+    #[inline]
+    fn to_ieee(self, exp_bits: u32, mant_bits: u32) -> u128 {
+        let (negative, mant, exp) = self.unpack();
+        let inf =
+            ((negative as u128) << (exp_bits + mant_bits)) | (((1 << exp_bits) - 1) << mant_bits);
+
+        if self.is_nan() {
+            let payload = mant.shr(MANT_BITS - mant_bits).0.lo & ((1 << mant_bits) - 1);
+            return inf | (1 << (mant_bits - 1)) | payload;
+        }
+
+        if self.is_infinite() {
+            return inf;
+        }
+
+        // Keep the top 128 bits and a sticky bit, which is plenty to round
+        let shift = 128u32.saturating_sub(mant.leading_zeros());
+        let (mant, sticky) = mant.shr(shift);
+        round_pack(
+            negative,
+            mant.lo,
+            exp + shift as i32,
+            sticky,
+            exp_bits,
+            mant_bits,
+        )
+    }
+
+    /// Converts from the binary format with `exp_bits` exponent bits and
+    /// `mant_bits` stored mantissa bits. NaNs are quieted and keep their
+    /// sign and payload.
+    #[inline]
+    fn from_ieee(bits: u128, exp_bits: u32, mant_bits: u32) -> Self {
+        let (negative, mant, exp) = unpack(bits, exp_bits, mant_bits);
+        let abs = bits & ((1 << (exp_bits + mant_bits)) - 1);
+        let inf = ((1 << exp_bits) - 1) << mant_bits;
+
+        if abs > inf {
+            let payload = U256::new(mant & ((1 << mant_bits) - 1)).shl(MANT_BITS - mant_bits);
+            let nan = Self::NAN.0[1] | payload.hi;
+            return Self::from_u256(
+                negative,
+                U256 {
+                    hi: nan,
+                    lo: payload.lo,
+                },
+            );
+        }
+
+        if abs == inf {
+            return Self::from_u256(negative, Self::INFINITY.abs_bits());
+        }
+
+        // Every narrower value is exact
+        Self::round_pack(negative, U256::new(mant), exp, false)
+    }
+}
+
+macro_rules! cast {
+    ($($float:ty: $bits:ty, $exp_bits:literal, $mant_bits:literal;)*) => {$(
+        impl CastFrom<$float> for f256 {
+            #[inline]
+            fn cast_from(value: $float) -> f256 {
+                f256::from_ieee(value.to_bits() as u128, $exp_bits, $mant_bits)
+            }
+        }
+
+        impl CastFrom<f256> for $float {
+            #[inline]
+            fn cast_from(value: f256) -> $float {
+                <$float>::from_bits(value.to_ieee($exp_bits, $mant_bits) as $bits)
+            }
+        }
+    )*};
+}
+
+cast! {
+    f16: u16, 5, 10;
+    f32: u32, 8, 23;
+    f64: u64, 11, 52;
+    f128: u128, 15, 112;
+}
@@ -0,0 +1,817 @@
+//! Decimal strings for `f256`, converted exactly in both directions.
+//!
+//! Both directions work on the exact value with big integers: parsing
+//! rounds once, however many digits it is given, and formatting either
+//! finds the shortest digits that read back to the same value, or writes
+//! the exact value rounded to the requested precision.
+//!
+//! The big integers live on the stack, and each step holds one at a time,
+//! sized from the exponent: [`SMALL`] limbs for values from about `1e-500`
+//! to `1e500`, and [`LIMBS`] beyond. The documentation of `from_str` gives
+//! the stack this takes, for parsing and formatting alike.
+
+use core::cmp::Ordering;
+use core::fmt::{self, Write};
+use core::num::ParseFloatError;
+use core::str::FromStr;
+
+use super::{f256, EXP_BIAS, MANT_BITS, MIN_QUANTUM};
+use crate::big::{log10_pow2, Big};
+use crate::uint::{pow10, U256};
+
+/// Limbs for the exact values of the format: the integers up to `MAX`, and
+/// the fractions down to the least subnormal times a power of ten.
+const LIMBS: usize = 4200;
+
+/// Limbs for the values from about `1e-500` to `1e500`, whose exponents
+/// keep every step within `(SMALL - 2) * 64` bits; see [`sized!`].
+const SMALL: usize = 40;
+
+/// Significant digits kept while parsing. Any others are first folded
+/// into a sticky bit, and only read again if they decide a tie.
+const KEEP: u32 = 100;
+
+/// Limbs for a coefficient of `KEEP` digits.
+const COEFF: usize = 6;
+
+/// The digits of a chunk, and its modulus.
+const CHUNK_DIGITS: usize = 19;
+const CHUNK: u64 = pow10(CHUNK_DIGITS as u32) as u64;
+
+/// Calls the generic function `$f` with [`SMALL`] limbs if its values
+/// take at most `$bits` bits, and with [`LIMBS`] otherwise.
+macro_rules! sized {
+    ($bits:expr, $f:ident($($arg:expr),*)) => {
+        if $bits <= (SMALL as i64 - 2) * 64 {
+            $f::<SMALL>($($arg),*)
+        } else {
+            $f::<LIMBS>($($arg),*)
+        }
+    };
+}
+
+#[inline]
+fn empty() -> ParseFloatError {
+    "".parse::<f32>().unwrap_err()
+}
+
+#[inline]
+fn invalid() -> ParseFloatError {
+    "x".parse::<f32>().unwrap_err()
+}
+
+/// Parses `s` with the syntax of `f64`, rounding to nearest, ties to even.
+fn parse(s: &str) -> Result<f256, ParseFloatError> {
+    if s.is_empty() {
+        return Err(empty());
+    }
+
+    let (negative, rest) = match s.as_bytes() {
+        [b'-', rest @ ..] => (true, rest),
+        [b'+', rest @ ..] => (false, rest),
+        rest => (false, rest),
+    };
+    let signed = |x: f256| if negative { -x } else { x };
+
+    if rest.eq_ignore_ascii_case(b"inf") || rest.eq_ignore_ascii_case(b"infinity") {
+        return Ok(signed(f256::INFINITY));
+    } else if rest.eq_ignore_ascii_case(b"nan") {
+        return Ok(f256::NAN);
+    }
+
+    // Keep the leading significant digits, in chunks
+    let mut coeff = Big::<COEFF>::new(U256::ZERO);
+    let (mut chunk, mut chunk_len) = (0, 0);
+    let mut kept = 0;
+    let mut exp: i64 = 0;
+    let mut sticky = false;
+    let mut seen = false;
+    let mut point = false;
+    let mut i = 0;
+
+    while i < rest.len() {
+        match rest[i] {
+            b'.' if !point => point = true,
+            byte @ b'0'..=b'9' => {
+                seen = true;
+                let digit = (byte - b'0') as u64;
+                if kept < KEEP {
+                    if kept > 0 || digit != 0 {
+                        kept += 1;
+                        chunk = chunk * 10 + digit;
+                        chunk_len += 1;
+                        if chunk_len == CHUNK_DIGITS {
+                            coeff.mul_small(CHUNK);
+                            coeff.add_small(chunk);
+                            chunk = 0;
+                            chunk_len = 0;
+                        }
+                    }
+                    exp -= point as i64;
+                } else {
+                    sticky |= digit != 0;
+                    exp += !point as i64;
+                }
+            }
+            _ => break,
+        }
+        i += 1;
+    }
+    coeff.mul_small(pow10(chunk_len as u32) as u64);
+    coeff.add_small(chunk);
+
+    if !seen {
+        return Err(invalid());
+    }
+    let mantissa = &rest[..i];
+
+    if i < rest.len() {
+        let (first, digits) = rest[i + 1..].split_first().ok_or_else(invalid)?;
+        if !rest[i].eq_ignore_ascii_case(&b'e') {
+            return Err(invalid());
+        }
+
+        let (negative, digits) = match first {
+            b'-' => (true, digits),
+            b'+' => (false, digits),
+            _ => (false, &rest[i + 1..]),
+        };
+
+        // Exponents far beyond the format saturate
+        if digits.is_empty() || !digits.iter().all(u8::is_ascii_digit) {
+            return Err(invalid());
+        }
+        let value = digits
+            .iter()
+            .fold(0i64, |n, &b| (n * 10 + (b - b'0') as i64).min(1 << 40));
+        exp += if negative { -value } else { value };
+    }
+
+    // The value is below 10^top and at least a tenth of that
+    let exp = exp.clamp(-(1 << 30), 1 << 30) as i32;
+    let top = kept as i32 + exp;
+    if coeff.is_zero() || top < log10_pow2(MIN_QUANTUM) - 1 {
+        return Ok(signed(f256::default()));
+    } else if top > log10_pow2(EXP_BIAS + 1) + 2 {
+        return Ok(signed(f256::INFINITY));
+    }
+
+    let lower = approximate(&coeff, exp, sticky);
+    if !sticky {
+        return Ok(signed(lower));
+    }
+
+    // The dropped digits put the value strictly between two bounds, which
+    // can only round apart across the midpoint of two neighbours
+    coeff.add_small(1);
+    let upper = approximate(&coeff, exp, false);
+    if lower == upper {
+        return Ok(signed(lower));
+    }
+
+    let (_, mant, quantum) = lower.unpack();
+    let midpoint = mant.shl(1).add(U256::new(1));
+    Ok(signed(
+        match compare(mantissa, top, midpoint, quantum - 1) {
+            Ordering::Less => lower,
+            Ordering::Greater => upper,
+            Ordering::Equal if lower.0[0] & 1 == 0 => lower,
+            Ordering::Equal => upper,
+        },
+    ))
+}
+
+/// Rounds `coeff * 10^exp` to a positive `f256`, where `sticky` indicates
+/// that the exact value is slightly larger.
+fn approximate(coeff: &Big<COEFF>, exp: i32, sticky: bool) -> f256 {
+    let bits = exp.unsigned_abs() as i64 * 3322 / 1000 + 400;
+    sized!(bits, approximate_in(coeff, exp, sticky))
+}
+
+#[inline(never)]
+fn approximate_in<const N: usize>(coeff: &Big<COEFF>, exp: i32, sticky: bool) -> f256 {
+    let mut big = Big::<N>::new(U256::ZERO);
+    big.set(coeff);
+    let (mant, shift, inexact) = if exp >= 0 {
+        big.mul_pow10(exp as u32);
+        let (mant, shift, inexact) = big.top256();
+        (mant, shift as i32, inexact)
+    } else {
+        // Scale up so that the quotient keeps at least 256 bits
+        let k = exp.unsigned_abs();
+        let scale = (k as u64 * 3322 / 1000 + 260).saturating_sub(big.bits() as u64) as u32;
+        big.shl(scale);
+        let rem = big.div_pow10(k);
+        let (mant, shift, inexact) = big.top256();
+        (mant, shift as i32 - scale as i32, rem || inexact)
+    };
+
+    f256::round_pack(false, mant, shift, inexact || sticky)
+}
+
+/// Compares the digits of a parsed mantissa, whose value is below
+/// `10^top` and at least a tenth of that, to `mant * 2^exp`.
+fn compare(mantissa: &[u8], top: i32, mant: U256, exp: i32) -> Ordering {
+    sized!(exact_bits(exp), compare_in(mantissa, top, mant, exp))
+}
+
+#[inline(never)]
+fn compare_in<const N: usize>(mantissa: &[u8], top: i32, mant: U256, exp: i32) -> Ordering {
+    let mut exact = Digits::<N>::new();
+    exact.start(mant, exp);
+    let exact_top = exact.int_len as i64 - exact.skip_zeros() as i64;
+    if top as i64 != exact_top {
+        return (top as i64).cmp(&exact_top);
+    }
+
+    let digits = mantissa
+        .iter()
+        .filter(|byte| byte.is_ascii_digit())
+        .skip_while(|&&byte| byte == b'0');
+    for &byte in digits {
+        match (byte - b'0').cmp(&exact.next()) {
+            Ordering::Equal => continue,
+            order => return order,
+        }
+    }
+
+    if exact.rest_nonzero() {
+        Ordering::Less
+    } else {
+        Ordering::Equal
+    }
+}
+
+/// Returns a bound on the bits that [`Digits`] takes for `mant * 2^exp`.
+#[inline]
+fn exact_bits(exp: i32) -> i64 {
+    exp.unsigned_abs() as i64 + 256 + 64
+}
+
+/// The exact decimal digits of `mant * 2^exp`, most significant first,
+/// from its integer part (a lone zero if it is below one) on, and then
+/// zeros once the expansion ends.
+struct Digits<const N: usize> {
+    source: Source<N>,
+    bytes: [u8; 78],
+    pos: usize,
+    /// The length of the integer part.
+    int_len: usize,
+}
+
+enum Source<const N: usize> {
+    /// The chunks of an integer that are left, least significant first.
+    Integer([u64; N], usize),
+    /// The fraction that is left, as bits below the given point.
+    Fraction(Big<N>, u32),
+}
+
+impl<const N: usize> Digits<N> {
+    /// Returns no digits, until [`start`](Self::start).
+    #[inline]
+    fn new() -> Self {
+        Self {
+            source: Source::Integer([0; N], 0),
+            bytes: [b'0'; 78],
+            pos: 0,
+            int_len: 0,
+        }
+    }
+
+    /// Starts the digits of `mant * 2^exp` from the first, in place, which
+    /// keeps a single copy of them on the stack however often they are
+    /// read.
+    fn start(&mut self, mant: U256, exp: i32) {
+        self.bytes = [b'0'; 78];
+
+        if exp >= 0 {
+            self.source = Source::Integer([0; N], 0);
+            let (chunks, rest) = match &mut self.source {
+                Source::Integer(chunks, rest) => (chunks, rest),
+                Source::Fraction(..) => unreachable!(),
+            };
+
+            // Doubles the chunks of the mantissa in place, so the value
+            // never needs a binary copy as well
+            let mut len = 0;
+            let mut value = mant;
+            while !value.is_zero() {
+                let (quot, rem) = value.div_rem(CHUNK as u128);
+                chunks[len] = rem as u64;
+                len += 1;
+                value = quot;
+            }
+
+            let mut exp = exp as u32;
+            while exp > 0 {
+                let step = exp.min(64);
+                let mut carry = 0;
+                for chunk in &mut chunks[..len] {
+                    let wide = ((*chunk as u128) << step) + carry;
+                    *chunk = (wide % CHUNK as u128) as u64;
+                    carry = wide / CHUNK as u128;
+                }
+                while carry != 0 {
+                    chunks[len] = (carry % CHUNK as u128) as u64;
+                    carry /= CHUNK as u128;
+                    len += 1;
+                }
+                exp -= step;
+            }
+
+            let len = len.max(1);
+            *rest = len - 1;
+            self.pos = self.bytes.len()
+                - U256::new(chunks[len - 1] as u128)
+                    .to_ascii(&mut self.bytes)
+                    .len();
+            self.int_len = self.bytes.len() - self.pos + CHUNK_DIGITS * (len - 1);
+        } else {
+            let shift = exp.unsigned_abs();
+            let int = mant.shr(shift).0;
+            let frac = if shift >= 256 {
+                mant
+            } else {
+                mant.sub(int.shl(shift))
+            };
+
+            self.source = Source::Fraction(Big::new(frac), shift);
+            self.pos = self.bytes.len() - int.to_ascii(&mut self.bytes).len();
+            self.int_len = self.bytes.len() - self.pos;
+        }
+    }
+
+    #[inline]
+    fn next(&mut self) -> u8 {
+        if self.pos == self.bytes.len() {
+            self.refill();
+        }
+        self.pos += 1;
+        self.bytes[self.pos - 1] - b'0'
+    }
+
+    fn refill(&mut self) {
+        let mut chunk = match &mut self.source {
+            Source::Integer(_, 0) => 0,
+            Source::Integer(chunks, len) => {
+                *len -= 1;
+                chunks[*len]
+            }
+            Source::Fraction(frac, bits) => {
+                frac.mul_small(CHUNK);
+                frac.split_off(*bits)
+            }
+        };
+
+        self.pos = self.bytes.len() - CHUNK_DIGITS;
+        for byte in self.bytes[self.pos..].iter_mut().rev() {
+            *byte = b'0' + (chunk % 10) as u8;
+            chunk /= 10;
+        }
+    }
+
+    /// Returns whether any of the digits left is nonzero.
+    fn rest_nonzero(&self) -> bool {
+        self.bytes[self.pos..].iter().any(|&byte| byte != b'0')
+            || match &self.source {
+                Source::Integer(chunks, len) => chunks[..*len].iter().any(|&chunk| chunk != 0),
+                Source::Fraction(frac, _) => !frac.is_zero(),
+            }
+    }
+
+    /// Skips the leading zeros of a nonzero value, returning how many.
+    fn skip_zeros(&mut self) -> usize {
+        let mut count = 0;
+        loop {
+            if self.pos == self.bytes.len() {
+                self.refill();
+            }
+            if self.bytes[self.pos] != b'0' {
+                return count;
+            }
+            self.pos += 1;
+            count += 1;
+        }
+    }
+
+    /// Reads `count` digits, returning whether they round up, half to
+    /// even, and the position of the last one below 9, which takes a carry.
+    fn plan(&mut self, count: usize) -> (bool, Option<usize>) {
+        let mut last = None;
+        let mut digit = 0;
+        for i in 0..count {
+            digit = self.next();
+            if digit != 9 {
+                last = Some(i);
+            }
+        }
+
+        let next = self.next();
+        let up = next > 5 || (next == 5 && (digit & 1 == 1 || self.rest_nonzero()));
+        (up, last)
+    }
+
+    /// Returns the digit at `i` of the rounding that [`plan`](Self::plan)
+    /// found, reading the digits again in order. A carry out of all of
+    /// them makes a leading one and zeros.
+    #[inline]
+    fn rounded(&mut self, i: usize, (up, last): (bool, Option<usize>)) -> u8 {
+        match last {
+            None if up => (i == 0) as u8,
+            Some(last) if up && i == last => self.next() + 1,
+            Some(last) if up && i > last => {
+                self.next();
+                0
+            }
+            _ => self.next(),
+        }
+    }
+}
+
+/// Returns `floor(mant * 2^exp / 10^scale)`, which must fit in 256 bits,
+/// and whether it is inexact.
+fn window(mant: U256, exp: i32, scale: i32) -> (U256, bool) {
+    let bits = 259 + exp.max(0) as i64 + (-scale).max(0) as i64 * 3322 / 1000 + 64;
+    sized!(bits, window_in(mant, exp, scale))
+}
+
+#[inline(never)]
+fn window_in<const N: usize>(mant: U256, exp: i32, scale: i32) -> (U256, bool) {
+    let mut big = Big::<N>::new(mant);
+    let mut sticky = false;
+
+    if scale < 0 {
+        big.mul_pow10(scale.unsigned_abs());
+    }
+    if exp >= 0 {
+        big.shl(exp as u32);
+    } else {
+        sticky |= big.shr(exp.unsigned_abs());
+    }
+    if scale > 0 {
+        sticky |= big.div_pow10(scale as u32);
+    }
+
+    (big.to_u256(), sticky)
+}
+
+/// Returns the shortest digits that read back as the nonzero
+/// `mant * 2^exp`, the nearest such if there are several, and their
+/// exponent.
+fn shortest(mant: U256, exp: i32) -> (U256, i32) {
+    // A window of 74 to 77 digits, and the midpoints to the neighbours
+    let bits = 256 - mant.leading_zeros() as i32;
+    let scale = log10_pow2(bits - 1 + exp) - 74;
+    let binade = mant == U256::new(1).shl(MANT_BITS) && exp > MIN_QUANTUM;
+    let four = mant.shl(2);
+
+    let (value, sticky) = window(four, exp - 2, scale);
+    let (lo, lo_sticky) = window(
+        four.sub(U256::new(if binade { 1 } else { 2 })),
+        exp - 2,
+        scale,
+    );
+    let (hi, hi_sticky) = window(four.add(U256::new(2)), exp - 2, scale);
+
+    // Ties at the midpoints round back to an even mantissa
+    let even = mant.lo & 1 == 0;
+    let inside = |d: U256| {
+        (d > lo || (d == lo && !lo_sticky && even)) && (d < hi || (d == hi && (hi_sticky || even)))
+    };
+
+    // 73 digits always suffice, so the window has one to spare
+    for drop in (1..value.digits()).rev() {
+        let (quot, rem) = value.div_rem_pow10(drop);
+        let unit = U256::pow10(drop);
+        let half = unit.shr(1).0;
+        let down = value.sub(rem);
+        let up = down.add(unit);
+
+        let nearer_up = rem > half || (rem == half && (sticky || quot.lo & 1 == 1));
+        let (first, second) = if nearer_up { (up, down) } else { (down, up) };
+        for digits in [first, second] {
+            if inside(digits) {
+                let mut digits = digits.div_rem_pow10(drop).0;
+                let mut exp = scale + drop as i32;
+                while digits.div_rem(10).1 == 0 {
+                    digits = digits.div_rem(10).0;
+                    exp += 1;
+                }
+                return (digits, exp);
+            }
+        }
+    }
+
+    (value, scale)
+}
+
+/// Buffers the output of a formatter, for numbers of any length.
+struct Out<'a, 'b> {
+    f: &'a mut fmt::Formatter<'b>,
+    bytes: [u8; 64],
+    len: usize,
+}
+
+impl<'a, 'b> Out<'a, 'b> {
+    #[inline]
+    fn new(f: &'a mut fmt::Formatter<'b>) -> Self {
+        Self {
+            f,
+            bytes: [0; 64],
+            len: 0,
+        }
+    }
+
+    #[inline]
+    fn push(&mut self, byte: u8) -> fmt::Result {
+        if self.len == self.bytes.len() {
+            self.flush()?;
+        }
+        self.bytes[self.len] = byte;
+        self.len += 1;
+        Ok(())
+    }
+
+    #[inline]
+    fn push_str(&mut self, s: &str) -> fmt::Result {
+        s.bytes().try_for_each(|byte| self.push(byte))
+    }
+
+    fn flush(&mut self) -> fmt::Result {
+        let text = core::str::from_utf8(&self.bytes[..self.len]).map_err(|_| fmt::Error)?;
+        self.f.write_str(text)?;
+        self.len = 0;
+        Ok(())
+    }
+}
+
+/// Returns the sign to write, which NaN never has.
+#[inline]
+fn sign(f: &fmt::Formatter<'_>, negative: bool) -> &'static str {
+    if negative {
+        "-"
+    } else if f.sign_plus() {
+        "+"
+    } else {
+        ""
+    }
+}
+
+/// Pads `sign` and the `len` bytes written by `body` like the standard
+/// floats do.
+fn pad<F>(f: &mut fmt::Formatter<'_>, sign: &str, len: usize, body: F) -> fmt::Result
+where
+    F: FnOnce(&mut Out<'_, '_>) -> fmt::Result,
+{
+    let pad = f
+        .width()
+        .map_or(0, |width| width.saturating_sub(len + sign.len()));
+    let (before, after, fill) = if f.sign_aware_zero_pad() {
+        (0, 0, '0')
+    } else {
+        match f.align() {
+            Some(fmt::Alignment::Left) => (0, pad, f.fill()),
+            Some(fmt::Alignment::Center) => (pad / 2, pad - pad / 2, f.fill()),
+            _ => (pad, 0, f.fill()),
+        }
+    };
+
+    for _ in 0..before {
+        f.write_char(fill)?;
+    }
+    let zeros = if f.sign_aware_zero_pad() { pad } else { 0 };
+    let mut out = Out::new(f);
+    out.push_str(sign)?;
+    (0..zeros).try_for_each(|_| out.push(b'0'))?;
+    body(&mut out)?;
+    out.flush()?;
+    for _ in 0..after {
+        f.write_char(fill)?;
+    }
+    Ok(())
+}
+
+/// Writes `inf` or `NaN`, which takes no sign.
+fn special(f: &mut fmt::Formatter<'_>, x: f256) -> fmt::Result {
+    if x.is_nan() {
+        pad(f, "", 3, |out| out.push_str("NaN"))
+    } else {
+        pad(f, sign(f, x.is_sign_negative()), 3, |out| {
+            out.push_str("inf")
+        })
+    }
+}
+
+/// Writes the shortest digits in plain notation.
+fn write_plain(f: &mut fmt::Formatter<'_>, x: f256) -> fmt::Result {
+    let (negative, mant, exp) = x.unpack();
+    if mant.is_zero() {
+        return pad(f, sign(f, negative), 1, |out| out.push(b'0'));
+    }
+
+    let (digits, exp) = shortest(mant, exp);
+    let mut bytes = [0; 78];
+    let digits = digits.to_ascii(&mut bytes);
+    let point = digits.len() as i64 + exp as i64;
+
+    if exp >= 0 {
+        pad(f, sign(f, negative), point as usize, |out| {
+            digits.iter().try_for_each(|&byte| out.push(byte))?;
+            (0..exp).try_for_each(|_| out.push(b'0'))
+        })
+    } else if point > 0 {
+        let (int, frac) = digits.split_at(point as usize);
+        pad(f, sign(f, negative), digits.len() + 1, |out| {
+            int.iter().try_for_each(|&byte| out.push(byte))?;
+            out.push(b'.')?;
+            frac.iter().try_for_each(|&byte| out.push(byte))
+        })
+    } else {
+        let zeros = point.unsigned_abs() as usize;
+        pad(f, sign(f, negative), 2 + zeros + digits.len(), |out| {
+            out.push_str("0.")?;
+            (0..zeros).try_for_each(|_| out.push(b'0'))?;
+            digits.iter().try_for_each(|&byte| out.push(byte))
+        })
+    }
+}
+
+/// Writes the exact value with `precision` digits after the point,
+/// rounded half to even.
+fn write_fixed(f: &mut fmt::Formatter<'_>, x: f256, precision: usize) -> fmt::Result {
+    let (negative, mant, exp) = x.unpack();
+    sized!(
+        exact_bits(exp),
+        write_fixed_in(f, negative, mant, exp, precision)
+    )
+}
+
+#[inline(never)]
+fn write_fixed_in<const N: usize>(
+    f: &mut fmt::Formatter<'_>,
+    negative: bool,
+    mant: U256,
+    exp: i32,
+    precision: usize,
+) -> fmt::Result {
+    let mut digits = Digits::<N>::new();
+    digits.start(mant, exp);
+    let count = digits.int_len + precision;
+    let plan = digits.plan(count);
+
+    // A carry out of every digit lengthens the integer part
+    let carry = plan.0 && plan.1.is_none();
+    let int_len = digits.int_len + carry as usize;
+    let len = int_len + (precision > 0) as usize + precision;
+
+    pad(f, sign(f, negative), len, |out| {
+        digits.start(mant, exp);
+        for i in 0..count + carry as usize {
+            out.push(b'0' + digits.rounded(i, plan))?;
+            if i + 1 == int_len && precision > 0 {
+                out.push(b'.')?;
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Writes one digit, a point and the rest of `digits`, then the exponent.
+fn write_exp<F>(
+    f: &mut fmt::Formatter<'_>,
+    negative: bool,
+    count: usize,
+    exp: i64,
+    upper: bool,
+    mut digit: F,
+) -> fmt::Result
+where
+    F: FnMut(usize) -> u8,
+{
+    let mut bytes = [0; 78];
+    let magnitude = U256::new(exp.unsigned_abs() as u128).to_ascii(&mut bytes);
+    let len = count + (count > 1) as usize + 1 + (exp < 0) as usize + magnitude.len();
+
+    pad(f, sign(f, negative), len, |out| {
+        for i in 0..count {
+            out.push(b'0' + digit(i))?;
+            if i == 0 && count > 1 {
+                out.push(b'.')?;
+            }
+        }
+        out.push(if upper { b'E' } else { b'e' })?;
+        if exp < 0 {
+            out.push(b'-')?;
+        }
+        magnitude.iter().try_for_each(|&byte| out.push(byte))
+    })
+}
+
+/// Writes the shortest digits, or the exact value rounded half to even to
+/// `precision` digits after the point, in scientific notation.
+fn write_sci(f: &mut fmt::Formatter<'_>, x: f256, upper: bool) -> fmt::Result {
+    let (negative, mant, exp) = x.unpack();
+
+    let precision = match f.precision() {
+        Some(precision) => precision,
+        None if mant.is_zero() => return write_exp(f, negative, 1, 0, upper, |_| 0),
+        None => {
+            let (digits, exp) = shortest(mant, exp);
+            let mut bytes = [0; 78];
+            let digits = digits.to_ascii(&mut bytes);
+            let exp = exp as i64 + digits.len() as i64 - 1;
+            return write_exp(f, negative, digits.len(), exp, upper, |i| digits[i] - b'0');
+        }
+    };
+
+    sized!(
+        exact_bits(exp),
+        write_sci_in(f, negative, mant, exp, precision, upper)
+    )
+}
+
+#[inline(never)]
+fn write_sci_in<const N: usize>(
+    f: &mut fmt::Formatter<'_>,
+    negative: bool,
+    mant: U256,
+    exp: i32,
+    precision: usize,
+    upper: bool,
+) -> fmt::Result {
+    let mut digits = Digits::<N>::new();
+    digits.start(mant, exp);
+    let skip = |digits: &mut Digits<N>| {
+        if mant.is_zero() {
+            0
+        } else {
+            digits.skip_zeros()
+        }
+    };
+
+    let exp10 = digits.int_len as i64 - 1 - skip(&mut digits) as i64;
+    let plan = digits.plan(precision + 1);
+
+    let exp10 = exp10 + (plan.0 && plan.1.is_none()) as i64;
+    digits.start(mant, exp);
+    skip(&mut digits);
+    write_exp(f, negative, precision + 1, exp10, upper, |i| {
+        digits.rounded(i, plan)
+    })
+}
+
+impl FromStr for f256 {
+    type Err = ParseFloatError;
+
+    /// Parses a number with the syntax of [`f64`], such as `-1.25`,
+    /// `1.25e-3`, `inf` or `NaN`, and rounds it to nearest, ties to even.
+    ///
+    /// The result is correctly rounded however many digits are given.
+    ///
+    /// Parsing and formatting work on the stack, without allocating. Values
+    /// from about `1e-500` to `1e500` take a 320-byte big integer, and run
+    /// on a thread with a 16 KiB stack. Beyond that range the big integer
+    /// is 33 KiB, and a thread needs about 40 KiB.
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s)
+    }
+}
+
+impl fmt::Display for f256 {
+    /// Writes the shortest digits that read back as the same value, in
+    /// plain notation, or with a precision, the exact value rounded half
+    /// to even. It takes the stack that [`from_str`](Self::from_str) does.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.is_finite() {
+            special(f, *self)
+        } else if let Some(precision) = f.precision() {
+            write_fixed(f, *self, precision)
+        } else {
+            write_plain(f, *self)
+        }
+    }
+}
+
+impl fmt::LowerExp for f256 {
+    /// Writes the value like [`Display`](fmt::Display) does, in scientific
+    /// notation.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_finite() {
+            write_sci(f, *self, false)
+        } else {
+            special(f, *self)
+        }
+    }
+}
+
+impl fmt::UpperExp for f256 {
+    /// Writes the value like [`LowerExp`](fmt::LowerExp) does, with `E`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_finite() {
+            write_sci(f, *self, true)
+        } else {
+            special(f, *self)
+        }
+    }
+}
@@ -0,0 +1,376 @@
+use core::cmp::Ordering;
+use core::ops::Neg;
+
+use crate::uint::U256;
+
+mod arith;
+mod cast;
+mod fmt;
+
+const EXP_BITS: u32 = 19;
+const MANT_BITS: u32 = 236;
+const EXP_BIAS: i32 = (1 << (EXP_BITS - 1)) - 1;
+const MAX_EXP: u128 = (1 << EXP_BITS) - 1;
+
+/// The weight of the last mantissa bit of the subnormals.
+const MIN_QUANTUM: i32 = 1 - EXP_BIAS - MANT_BITS as i32;
+
+/// The mantissa bits in the high word.
+const HI_MANT_BITS: u32 = MANT_BITS - 128;
+const HI_MANT_MASK: u128 = (1 << HI_MANT_BITS) - 1;
+const HI_ABS_MASK: u128 = u128::MAX >> 1;
+const SIGN: u128 = 1 << 127;
+
+/// 256-bit floating point type (IEEE 754 octuple-precision, binary256).
+///
+/// A reference format for checking the error of `f128` algorithms, with
+/// 237 bits of precision and exponents up to ±262143. Everything is
+/// implemented in software, in every mode: classification, correctly
+/// rounded arithmetic, conversions, and parsing and formatting.
+///
+/// The bits are stored as `[u128; 2]`, the low word first. Parsing and
+/// formatting work with exact big integers on the stack; the `from_str`
+/// documentation under [`FromStr`](#impl-FromStr-for-f256) gives how much.
+#[derive(Debug, Clone, Copy, Default)]
+#[allow(non_camel_case_types)]
+#[repr(transparent)]
+pub struct f256([u128; 2]);
+
+impl Neg for f256 {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Self([self.0[0], self.0[1] ^ SIGN])
+    }
+}
+
+impl PartialEq for f256 {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for f256 {
+    /// Compares by value: NaN is unordered and `-0.0 == 0.0`.
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.is_nan() || other.is_nan() {
+            return None;
+        }
+
+        let (a, b) = (self.abs_bits(), other.abs_bits());
+        let negative = |x: &Self, abs: U256| x.is_sign_negative() && !abs.is_zero();
+
+        Some(match (negative(self, a), negative(other, b)) {
+            (false, false) => a.cmp(&b),
+            (true, true) => b.cmp(&a),
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+        })
+    }
+}
+
+impl f256 {
+    pub const NAN: Self = Self([0, MAX_EXP << HI_MANT_BITS | 1 << (HI_MANT_BITS - 1)]);
+
+    pub const MIN: Self = Self([
+        u128::MAX,
+        SIGN | (MAX_EXP - 1) << HI_MANT_BITS | HI_MANT_MASK,
+    ]);
+    pub const MAX: Self = Self([u128::MAX, (MAX_EXP - 1) << HI_MANT_BITS | HI_MANT_MASK]);
+
+    pub const INFINITY: Self = Self([0, MAX_EXP << HI_MANT_BITS]);
+    pub const NEG_INFINITY: Self = Self([0, SIGN | MAX_EXP << HI_MANT_BITS]);
+
+    pub const MANTISSA_DIGITS: u32 = 237;
+
+    #[inline]
+    pub const fn is_nan(self) -> bool {
+        let hi = self.0[1] & HI_ABS_MASK;
+        hi > Self::INFINITY.0[1] || (hi == Self::INFINITY.0[1] && self.0[0] != 0)
+    }
+
+    #[inline]
+    pub const fn is_infinite(self) -> bool {
+        self.0[1] & HI_ABS_MASK == Self::INFINITY.0[1] && self.0[0] == 0
+    }
+
+    #[inline]
+    pub const fn is_finite(self) -> bool {
+        self.0[1] & HI_ABS_MASK < Self::INFINITY.0[1]
+    }
+
+    #[inline]
+    pub const fn is_sign_positive(self) -> bool {
+        (self.0[1] & SIGN) == 0
+    }
+
+    #[inline]
+    pub const fn is_sign_negative(self) -> bool {
+        (self.0[1] & SIGN) != 0
+    }
+
+    /// Returns the least number greater than `self` (IEEE 754 `nextUp`).
+    ///
+    /// NaN and `INFINITY` are returned unchanged, `NEG_INFINITY` becomes
+    /// `MIN`, and both zeros become the smallest positive subnormal.
+    #[inline]
+    pub const fn next_up(self) -> Self {
+        let abs = self.abs_bits();
+
+        if self.is_nan() || (self.0[1] == Self::INFINITY.0[1] && self.0[0] == 0) {
+            self
+        } else if abs.is_zero() {
+            Self([1, 0])
+        } else if self.is_sign_positive() {
+            Self::from_u256(false, abs.add(U256::new(1)))
+        } else {
+            Self::from_u256(true, abs.sub(U256::new(1)))
+        }
+    }
+
+    /// Returns the greatest number less than `self` (IEEE 754 `nextDown`).
+    ///
+    /// NaN and `NEG_INFINITY` are returned unchanged, `INFINITY` becomes
+    /// `MAX`, and both zeros become the smallest negative subnormal.
+    #[inline]
+    pub const fn next_down(self) -> Self {
+        let abs = self.abs_bits();
+
+        if self.is_nan() || (self.0[1] == Self::NEG_INFINITY.0[1] && self.0[0] == 0) {
+            self
+        } else if abs.is_zero() {
+            Self([1, SIGN])
+        } else if self.is_sign_positive() {
+            Self::from_u256(false, abs.sub(U256::new(1)))
+        } else {
+            Self::from_u256(true, abs.add(U256::new(1)))
+        }
+    }
+
+    /// Returns the unit in the last place of `self`.
+    ///
+    /// This is the (positive) distance from `|self|` to the next larger
+    /// representable magnitude. The ULP of `MAX` is the spacing that `MAX`
+    /// would have if the exponent range were unbounded. Infinities have an
+    /// infinite ULP and NaN is returned unchanged.
+    #[inline]
+    pub fn ulp(self) -> Self {
+        let exp = (self.0[1] >> HI_MANT_BITS) & MAX_EXP;
+
+        if self.is_nan() {
+            self
+        } else if exp == MAX_EXP {
+            Self::INFINITY
+        } else {
+            let quantum = (exp.max(1) as i32 - EXP_BIAS - MANT_BITS as i32).max(MIN_QUANTUM);
+            Self::round_pack(false, U256::new(1), quantum, false)
+        }
+    }
+
+    /// Creates an `f256` from its representation as two `u128`, the low
+    /// word first.
+    #[inline]
+    pub const fn from_bits(bits: [u128; 2]) -> Self {
+        Self(bits)
+    }
+
+    /// Creates an `f256` from its representation as a byte array in big endian.
+    #[inline]
+    pub const fn from_be_bytes(bytes: [u8; 32]) -> Self {
+        let (hi, lo) = split(bytes);
+        Self([u128::from_be_bytes(lo), u128::from_be_bytes(hi)])
+    }
+
+    /// Creates an `f256` from its representation as a byte array in little endian.
+    #[inline]
+    pub const fn from_le_bytes(bytes: [u8; 32]) -> Self {
+        let (lo, hi) = split(bytes);
+        Self([u128::from_le_bytes(lo), u128::from_le_bytes(hi)])
+    }
+
+    /// Creates an `f256` from its representation as a byte array in native endian.
+    #[inline]
+    pub const fn from_ne_bytes(bytes: [u8; 32]) -> Self {
+        if cfg!(target_endian = "big") {
+            Self::from_be_bytes(bytes)
+        } else {
+            Self::from_le_bytes(bytes)
+        }
+    }
+
+    /// Returns the raw representation of this `f256` as two `u128`, the low
+    /// word first.
+    #[inline]
+    pub const fn to_bits(self) -> [u128; 2] {
+        self.0
+    }
+
+    /// Returns the memory representation of this `f256` as a byte array in big endian.
+    #[inline]
+    pub const fn to_be_bytes(self) -> [u8; 32] {
+        join(self.0[1].to_be_bytes(), self.0[0].to_be_bytes())
+    }
+
+    /// Returns the memory representation of this `f256` as a byte array in little endian.
+    #[inline]
+    pub const fn to_le_bytes(self) -> [u8; 32] {
+        join(self.0[0].to_le_bytes(), self.0[1].to_le_bytes())
+    }
+
+    /// Returns the memory representation of this `f256` as a byte array in native endian.
+    #[inline]
+    pub const fn to_ne_bytes(self) -> [u8; 32] {
+        if cfg!(target_endian = "big") {
+            self.to_be_bytes()
+        } else {
+            self.to_le_bytes()
+        }
+    }
+
+    /// Returns the magnitude bits.
+    #[inline]
+    const fn abs_bits(self) -> U256 {
+        U256 {
+            hi: self.0[1] & HI_ABS_MASK,
+            lo: self.0[0],
+        }
+    }
+
+    #[inline]
+    const fn from_u256(negative: bool, abs: U256) -> Self {
+        let sign = if negative { SIGN } else { 0 };
+        Self([abs.lo, sign | abs.hi])
+    }
+
+    /// Returns the NaN with the quiet bit set.
+    #[inline]
+    const fn quiet(self) -> Self {
+        Self([self.0[0], self.0[1] | 1 << (HI_MANT_BITS - 1)])
+    }
+
+    /// Splits a finite value into its sign, its integer mantissa and the
+    /// weight of the mantissa's last bit, so that the value is
+    /// `(-1)^sign * mant * 2^exp`.
+    #[inline]
+    pub(crate) const fn unpack(self) -> (bool, U256, i32) {
+        let exp = ((self.0[1] >> HI_MANT_BITS) & MAX_EXP) as i32;
+        let mant = U256 {
+            hi: self.0[1] & HI_MANT_MASK,
+            lo: self.0[0],
+        };
+
+        if exp == 0 {
+            (self.is_sign_negative(), mant, MIN_QUANTUM)
+        } else {
+            let mant = U256 {
+                hi: mant.hi | 1 << HI_MANT_BITS,
+                lo: mant.lo,
+            };
+            (
+                self.is_sign_negative(),
+                mant,
+                exp - EXP_BIAS - MANT_BITS as i32,
+            )
+        }
+    }
+
+    /// Rounds `(-1)^sign * mant * 2^exp` to nearest, ties to even.
+    ///
+    /// `sticky` indicates that `mant` was truncated and that the exact value
+    /// is slightly larger in magnitude than `mant * 2^exp`. Overflow
+    /// produces an infinity and underflow produces subnormals or a signed
+    /// zero.
+    #[inline]
+    pub(crate) fn round_pack(sign: bool, mant: U256, exp: i32, sticky: bool) -> Self {
+        if mant.is_zero() {
+            return Self::from_u256(sign, U256::ZERO);
+        }
+
+        // Pick the weight of the last mantissa bit for the result
+        let top = 255 - mant.leading_zeros() as i32;
+        let mut quantum = (exp + top - MANT_BITS as i32).max(MIN_QUANTUM);
+
+        let mut sig = if quantum >= exp {
+            shift_round(mant, (quantum - exp) as u32, sticky)
+        } else {
+            mant.shl((exp - quantum) as u32)
+        };
+
+        // Rounding may carry into a new binade
+        if sig.hi >> (HI_MANT_BITS + 1) != 0 {
+            sig = sig.shr(1).0;
+            quantum += 1;
+        }
+
+        // Subnormal or zero
+        if sig.hi >> HI_MANT_BITS == 0 {
+            return Self::from_u256(sign, sig);
+        }
+
+        let biased = quantum + MANT_BITS as i32 + EXP_BIAS;
+        if biased >= MAX_EXP as i32 {
+            return Self::from_u256(sign, Self::INFINITY.abs_bits());
+        }
+
+        let hi = (biased as u128) << HI_MANT_BITS | sig.hi & HI_MANT_MASK;
+        Self::from_u256(sign, U256 { hi, lo: sig.lo })
+    }
+}
+
+/// Rounds `mant >> shift` to nearest, ties to even.
+///
+/// `sticky` indicates that nonzero bits below `mant` were already discarded.
+#[inline]
+fn shift_round(mant: U256, shift: u32, sticky: bool) -> U256 {
+    if shift == 0 {
+        return mant;
+    }
+
+    if shift > 256 {
+        return U256::ZERO;
+    }
+
+    let (kept, rem) = if shift == 256 {
+        (U256::ZERO, mant)
+    } else {
+        let kept = mant.shr(shift).0;
+        (kept, mant.sub(kept.shl(shift)))
+    };
+    let half = U256::new(1).shl(shift - 1);
+
+    if rem > half || (rem == half && (sticky || kept.lo & 1 == 1)) {
+        kept.add(U256::new(1))
+    } else {
+        kept
+    }
+}
+
+/// Splits 32 bytes into their halves.
+#[inline]
+const fn split(bytes: [u8; 32]) -> ([u8; 16], [u8; 16]) {
+    let mut first = [0; 16];
+    let mut second = [0; 16];
+    let mut i = 0;
+    while i < 16 {
+        first[i] = bytes[i];
+        second[i] = bytes[i + 16];
+        i += 1;
+    }
+    (first, second)
+}
+
+/// Joins two halves into 32 bytes.
+#[inline]
+const fn join(first: [u8; 16], second: [u8; 16]) -> [u8; 32] {
+    let mut bytes = [0; 32];
+    let mut i = 0;
+    while i < 16 {
+        bytes[i] = first[i];
+        bytes[i + 16] = second[i];
+        i += 1;
+    }
+    bytes
+}
@@ -0,0 +1,172 @@
+#![cfg(feature = "casting")]
+
+//! Packed small-float formats of GPU textures and shading languages.
+//!
+//! The unsigned 11- and 10-bit floats of `R11G11B10F` ([`pack_uf11`],
+//! [`pack_uf10`]) have five exponent bits with a bias of 15, like `f16`, six
+//! or five mantissa bits and no sign. `RGB9E5` ([`pack_rgb9e5`]) stores three
+//! 9-bit mantissas that share one 5-bit exponent. [`pack_half_2x16`] is
+//! GLSL's `packHalf2x16`, two `f16` in one word.
+//!
+//! Encoding follows the Vulkan and Direct3D rules. The unsigned floats round
+//! to nearest even and clamp negative values and negative infinity to zero
+//! and finite values above the largest to the largest; positive infinity
+//! stays infinite, and NaNs of either sign become positive NaNs, quieted
+//! and keeping their top payload bits. `RGB9E5` clamps each component to
+//! `[0, 65408]`, with NaN as zero, then computes the shared exponent and the
+//! mantissas with the formulas of the specifications, which round halves
+//! up. Decoding is exact.
+
+use crate::f16;
+use crate::round::{decode, rescale_payload, round_pack, unpack, Decoded};
+use casting::CastFrom;
+
+/// Encodes a non-negative float with a 5-bit exponent and `mant_bits`
+/// stored mantissa bits.
+#[inline]
+fn narrow(value: f32, mant_bits: u32) -> u32 {
+    let inf = 0x1f << mant_bits;
+
+    match decode(value.to_bits() as u128, 8, 23) {
+        Decoded::Nan(_, mant) => {
+            inf | 1 << (mant_bits - 1) | rescale_payload(mant, 23, mant_bits) as u32
+        }
+        Decoded::Infinite(true) | Decoded::Finite(true, ..) => 0,
+        Decoded::Infinite(false) => inf,
+        Decoded::Finite(false, mant, exp) => {
+            (round_pack(false, mant, exp, false, 5, mant_bits) as u32).min(inf - 1)
+        }
+    }
+}
+
+/// Decodes a non-negative float with a 5-bit exponent and `mant_bits` stored
+/// mantissa bits, which has the bits of an `f16` with the low mantissa bits
+/// cleared.
+#[inline]
+fn widen(bits: u32, mant_bits: u32) -> f32 {
+    f32::cast_from(f16::from_bits((bits << (10 - mant_bits)) as u16))
+}
+
+/// Encodes `value` as an unsigned 11-bit float, in the low bits.
+#[inline]
+pub fn pack_uf11(value: f32) -> u16 {
+    narrow(value, 6) as u16
+}
+
+/// Decodes the unsigned 11-bit float in the low bits of `bits`.
+#[inline]
+pub fn unpack_uf11(bits: u16) -> f32 {
+    widen(bits as u32 & 0x7ff, 6)
+}
+
+/// Encodes `value` as an unsigned 10-bit float, in the low bits.
+#[inline]
+pub fn pack_uf10(value: f32) -> u16 {
+    narrow(value, 5) as u16
+}
+
+/// Decodes the unsigned 10-bit float in the low bits of `bits`.
+#[inline]
+pub fn unpack_uf10(bits: u16) -> f32 {
+    widen(bits as u32 & 0x3ff, 5)
+}
+
+/// Encodes red, green and blue as `R11G11B10F`: red in bits 0 to 10, green
+/// in bits 11 to 21 and blue in bits 22 to 31.
+#[inline]
+pub fn pack_r11g11b10f(rgb: [f32; 3]) -> u32 {
+    narrow(rgb[0], 6) | narrow(rgb[1], 6) << 11 | narrow(rgb[2], 5) << 22
+}
+
+/// Decodes `R11G11B10F` into red, green and blue.
+#[inline]
+pub fn unpack_r11g11b10f(bits: u32) -> [f32; 3] {
+    [
+        widen(bits & 0x7ff, 6),
+        widen(bits >> 11 & 0x7ff, 6),
+        widen(bits >> 22, 5),
+    ]
+}
+
+/// The mantissa bits of `RGB9E5`.
+const N: i32 = 9;
+
+/// The exponent bias of `RGB9E5`.
+const B: i32 = 15;
+
+/// The largest component of `RGB9E5`, 511/512 * 2^16.
+const SHAREDEXP_MAX: f32 = 65408.0;
+
+/// Returns `floor(value / 2^exp + 0.5)` exactly, for a finite non-negative
+/// `value` below `2^(exp + 32)`.
+#[inline]
+fn round_half_up(value: f32, exp: i32) -> u32 {
+    let (_, mant, weight) = unpack(value.to_bits() as u128, 8, 23);
+    let shift = exp - weight;
+    if shift <= 0 {
+        (mant << -shift) as u32
+    } else if shift > 25 {
+        0
+    } else {
+        (((mant >> (shift - 1)) + 1) >> 1) as u32
+    }
+}
+
+/// Encodes red, green and blue as `RGB9E5`: the mantissas of red, green and
+/// blue in bits 0 to 8, 9 to 17 and 18 to 26, and the shared exponent in
+/// bits 27 to 31.
+pub fn pack_rgb9e5(rgb: [f32; 3]) -> u32 {
+    // NaN compares false, so it clamps to zero like -0.0
+    let clamp = |x: f32| if x > 0.0 { x.min(SHAREDEXP_MAX) } else { 0.0 };
+    let [r, g, b] = [clamp(rgb[0]), clamp(rgb[1]), clamp(rgb[2])];
+    let max = r.max(g).max(b);
+
+    // floor(log2(max)), or -B - 1 for zero and values below 2^(-B - 1)
+    let log2 = if max == 0.0 {
+        -B - 1
+    } else {
+        let (_, mant, exp) = unpack(max.to_bits() as u128, 8, 23);
+        (exp + 127 - mant.leading_zeros() as i32).max(-B - 1)
+    };
+
+    // A largest mantissa that rounds up to 2^N takes the next exponent
+    let mut exp = log2 + 1 + B;
+    if round_half_up(max, exp - B - N) == 1 << N {
+        exp += 1;
+    }
+
+    let scale = exp - B - N;
+    round_half_up(r, scale)
+        | round_half_up(g, scale) << 9
+        | round_half_up(b, scale) << 18
+        | (exp as u32) << 27
+}
+
+/// Decodes `RGB9E5` into red, green and blue.
+#[inline]
+pub fn unpack_rgb9e5(bits: u32) -> [f32; 3] {
+    // 2^(exp - B - N), which is normal for every exponent
+    let scale = f32::from_bits(((bits >> 27) + 127 - (B + N) as u32) << 23);
+    [
+        (bits & 0x1ff) as f32 * scale,
+        (bits >> 9 & 0x1ff) as f32 * scale,
+        (bits >> 18 & 0x1ff) as f32 * scale,
+    ]
+}
+
+/// Converts two floats to `f16`, rounding to nearest even, and packs them
+/// with the first in the low 16 bits, as GLSL's `packHalf2x16`.
+#[inline]
+pub fn pack_half_2x16(v: [f32; 2]) -> u32 {
+    f16::cast_from(v[0]).to_bits() as u32 | (f16::cast_from(v[1]).to_bits() as u32) << 16
+}
+
+/// Unpacks two `f16` from the low and high 16 bits of `bits` and converts
+/// them to `f32`, as GLSL's `unpackHalf2x16`.
+#[inline]
+pub fn unpack_half_2x16(bits: u32) -> [f32; 2] {
+    [
+        f32::cast_from(f16::from_bits(bits as u16)),
+        f32::cast_from(f16::from_bits((bits >> 16) as u16)),
+    ]
+}
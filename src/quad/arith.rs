@@ -0,0 +1,247 @@
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+
+use super::f128;
+use crate::round::{round_pack, unpack};
+
+const MANT_BITS: u32 = 112;
+const ABS_MASK: u128 = u128::MAX >> 1;
+
+/// Guard bits kept below the mantissa while adding.
+const GUARD: u32 = 10;
+
+/// Returns the full 256-bit product of `a` and `b` as `(hi, lo)`.
+#[inline]
+const fn widening_mul(a: u128, b: u128) -> (u128, u128) {
+    let (a1, a0) = (a >> 64, a as u64 as u128);
+    let (b1, b0) = (b >> 64, b as u64 as u128);
+
+    let lo = a0 * b0;
+    let mid1 = a1 * b0;
+    let mid2 = a0 * b1;
+    let hi = a1 * b1;
+
+    let (mid, carry) = mid1.overflowing_add(mid2);
+    let (lo, c) = lo.overflowing_add(mid << 64);
+    let hi = hi + (mid >> 64) + ((carry as u128) << 64) + c as u128;
+
+    (hi, lo)
+}
+
+impl f128 {
+    propagate_nan!(pub(crate));
+
+    const fn add_impl(self, rhs: Self) -> Self {
+        if self.is_nan() || rhs.is_nan() {
+            return self.propagate_nan(rhs);
+        }
+
+        if self.is_infinite() {
+            if rhs.is_infinite() && self.0 != rhs.0 {
+                return Self::NAN;
+            }
+            return self;
+        } else if rhs.is_infinite() {
+            return rhs;
+        }
+
+        // Order the operands by magnitude
+        let (a, b) = if self.0 & ABS_MASK >= rhs.0 & ABS_MASK {
+            (self, rhs)
+        } else {
+            (rhs, self)
+        };
+
+        let (_, ma, ea) = unpack(a.0, 15, MANT_BITS);
+        let (_, mb, eb) = unpack(b.0, 15, MANT_BITS);
+
+        if mb == 0 {
+            // The sum of two zeros is negative only if both are
+            return if ma == 0 { Self(a.0 & b.0) } else { a };
+        }
+
+        // Align the smaller operand, folding what falls off into a sticky bit
+        let shift = (ea - eb) as u32;
+        let ma = ma << GUARD;
+        let mb = mb << GUARD;
+        let (mb, sticky) = if shift >= 128 {
+            (0, true)
+        } else {
+            (mb >> shift, mb & ((1 << shift) - 1) != 0)
+        };
+
+        let mant = if a.is_sign_negative() == b.is_sign_negative() {
+            ma + mb
+        } else {
+            // Borrow from the kept bits for a partially shifted out operand
+            ma - mb - sticky as u128
+        };
+
+        // Exact cancellation is positive when rounding to nearest
+        if mant == 0 {
+            return Self(0);
+        }
+
+        let exp = ea - GUARD as i32;
+        Self(round_pack(
+            a.is_sign_negative(),
+            mant,
+            exp,
+            sticky,
+            15,
+            MANT_BITS,
+        ))
+    }
+
+    const fn mul_impl(self, rhs: Self) -> Self {
+        if self.is_nan() || rhs.is_nan() {
+            return self.propagate_nan(rhs);
+        }
+
+        let sign = self.is_sign_negative() != rhs.is_sign_negative();
+        let zero = self.0 & ABS_MASK == 0 || rhs.0 & ABS_MASK == 0;
+
+        if self.is_infinite() || rhs.is_infinite() {
+            if zero {
+                return Self::NAN;
+            }
+            return Self(Self::INFINITY.0 | ((sign as u128) << 127));
+        } else if zero {
+            return Self((sign as u128) << 127);
+        }
+
+        let (_, ma, ea) = unpack(self.0, 15, MANT_BITS);
+        let (_, mb, eb) = unpack(rhs.0, 15, MANT_BITS);
+        let (hi, lo) = widening_mul(ma, mb);
+
+        // Keep the top 128 bits of the product and a sticky bit
+        let (mant, exp, sticky) = if hi == 0 {
+            (lo, 0, false)
+        } else {
+            let zeros = hi.leading_zeros();
+            let mant = (hi << zeros) | (lo >> (128 - zeros));
+            (mant, 128 - zeros as i32, lo << zeros != 0)
+        };
+
+        let exp = ea + eb + exp;
+        Self(round_pack(sign, mant, exp, sticky, 15, MANT_BITS))
+    }
+
+    const fn div_impl(self, rhs: Self) -> Self {
+        if self.is_nan() || rhs.is_nan() {
+            return self.propagate_nan(rhs);
+        }
+
+        let sign = self.is_sign_negative() != rhs.is_sign_negative();
+        let signed_inf = Self(Self::INFINITY.0 | ((sign as u128) << 127));
+        let signed_zero = Self((sign as u128) << 127);
+        let a_zero = self.0 & ABS_MASK == 0;
+        let b_zero = rhs.0 & ABS_MASK == 0;
+
+        if self.is_infinite() {
+            return if rhs.is_infinite() {
+                Self::NAN
+            } else {
+                signed_inf
+            };
+        } else if rhs.is_infinite() {
+            return signed_zero;
+        } else if b_zero {
+            return if a_zero { Self::NAN } else { signed_inf };
+        } else if a_zero {
+            return signed_zero;
+        }
+
+        // Normalize both mantissas so that the quotient has a fixed width
+        let (_, ma, ea) = unpack(self.0, 15, MANT_BITS);
+        let (_, mb, eb) = unpack(rhs.0, 15, MANT_BITS);
+        let sa = ma.leading_zeros() - (127 - MANT_BITS);
+        let sb = mb.leading_zeros() - (127 - MANT_BITS);
+        let (ma, mb) = (ma << sa, mb << sb);
+
+        // Restoring division: three bits beyond the mantissa, then a sticky
+        let mut quot = 0u128;
+        let mut rem = ma;
+        let mut i = 0;
+        while i < MANT_BITS + 4 {
+            quot <<= 1;
+            if rem >= mb {
+                rem -= mb;
+                quot |= 1;
+            }
+            rem <<= 1;
+            i += 1;
+        }
+
+        let exp = ea - sa as i32 - (eb - sb as i32) - (MANT_BITS as i32 + 3);
+        Self(round_pack(sign, quot, exp, rem != 0, 15, MANT_BITS))
+    }
+}
+
+impl Add for f128 {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        self.add_impl(rhs)
+    }
+}
+
+impl Sub for f128 {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self::Output {
+        // Negating a NaN would flip the sign of a propagated payload
+        if rhs.is_nan() {
+            return self.propagate_nan(rhs);
+        }
+
+        self.add_impl(-rhs)
+    }
+}
+
+impl Mul for f128 {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.mul_impl(rhs)
+    }
+}
+
+impl Div for f128 {
+    type Output = Self;
+
+    #[inline]
+    fn div(self, rhs: Self) -> Self::Output {
+        self.div_impl(rhs)
+    }
+}
+
+impl AddAssign for f128 {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for f128 {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign for f128 {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl DivAssign for f128 {
+    #[inline]
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
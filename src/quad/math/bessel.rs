@@ -0,0 +1,88 @@
+use super::f128;
+use crate::wide::Wide;
+
+impl f128 {
+    /// Computes the Bessel function of the first kind of order zero.
+    ///
+    /// `j0(±0)` is `1` and `j0(±∞)` is `+0`.
+    #[inline]
+    pub fn j0(self) -> Self {
+        self.jn(0)
+    }
+
+    /// Computes the Bessel function of the first kind of order one.
+    ///
+    /// `j1(±0)` is `±0` and `j1(±∞)` is `±0`.
+    #[inline]
+    pub fn j1(self) -> Self {
+        self.jn(1)
+    }
+
+    /// Computes the Bessel function of the first kind of order `n`.
+    ///
+    /// `J_n` is even or odd with `n`, and `J_-n = (-1)^n J_n`. Infinite
+    /// arguments give zero. The running time grows linearly with `|n|`.
+    #[inline]
+    pub fn jn(self, n: i32) -> Self {
+        if self.is_nan() {
+            return self.quiet();
+        }
+
+        let order = n.unsigned_abs();
+        let neg = order & 1 == 1 && self.is_sign_negative() != (n < 0);
+        if order == 0 && self.is_zero() {
+            return Self::from_wide(Wide::ONE);
+        } else if self.is_zero() || self.is_infinite() {
+            return Self((neg as u128) << 127);
+        }
+
+        // J_n(x) < (e x / 2n)^n, which is below 2^-n once n >= 3|x| and so
+        // far below the smallest subnormal for large n
+        let x = self.to_wide().abs();
+        let n = Wide::from_i64(order as i64);
+        if order > 16500 && x.mul(Wide::from_i64(3)).cmp(n).is_le() {
+            return Self((neg as u128) << 127);
+        }
+
+        Self::from_wide(Wide::from_sign(neg, x.bessel_j(order)))
+    }
+
+    /// Computes the Bessel function of the second kind of order zero.
+    ///
+    /// `y0(±0)` is `-∞`, `y0(+∞)` is `+0` and negative arguments give NaN.
+    #[inline]
+    pub fn y0(self) -> Self {
+        self.yn(0)
+    }
+
+    /// Computes the Bessel function of the second kind of order one.
+    ///
+    /// `y1(±0)` is `-∞`, `y1(+∞)` is `+0` and negative arguments give NaN.
+    #[inline]
+    pub fn y1(self) -> Self {
+        self.yn(1)
+    }
+
+    /// Computes the Bessel function of the second kind of order `n`.
+    ///
+    /// `Y_-n = (-1)^n Y_n`. `yn(n, ±0)` is `-∞`, `yn(n, +∞)` is `+0` and
+    /// negative arguments give NaN. The running time grows linearly with
+    /// `|n|`.
+    #[inline]
+    pub fn yn(self, n: i32) -> Self {
+        if self.is_nan() {
+            return self.quiet();
+        } else if self.is_zero() {
+            return Self::NEG_INFINITY;
+        } else if self.is_sign_negative() {
+            return Self::NAN;
+        } else if self.is_infinite() {
+            return Self(0);
+        }
+
+        let order = n.unsigned_abs();
+        let neg = order & 1 == 1 && n < 0;
+        let y = self.to_wide().bessel_y(order);
+        Self::from_wide(Wide::from_sign(neg, y))
+    }
+}
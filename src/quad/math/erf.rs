@@ -0,0 +1,40 @@
+use super::f128;
+use crate::wide::Wide;
+use crate::IeeeFloat;
+
+impl f128 {
+    /// Computes the error function of `self`.
+    ///
+    /// `erf(±0)` is `±0` and `erf(±∞)` is `±1`.
+    #[inline]
+    pub fn erf(self) -> Self {
+        if self.is_nan() {
+            self.quiet()
+        } else if self.is_zero() {
+            self
+        } else if self.ilogb() >= 4 {
+            // 1 - erf(16) is far below half an ULP of 1
+            Self::from_wide(Wide::from_sign(self.is_sign_negative(), Wide::ONE))
+        } else {
+            Self::from_wide(self.to_wide().erf())
+        }
+    }
+
+    /// Computes the complementary error function of `self`, `1 - erf(self)`,
+    /// without losing precision when the result is small.
+    ///
+    /// `erfc(-∞)` is `2` and `erfc(+∞)` is `+0`.
+    #[inline]
+    pub fn erfc(self) -> Self {
+        if self.is_nan() {
+            self.quiet()
+        } else if self.is_sign_negative() && self.ilogb() >= 4 {
+            Self::from_wide(Wide::ONE.scale(1))
+        } else if self.ilogb() >= 7 {
+            // erfc(128) is far below the smallest subnormal
+            Self(0)
+        } else {
+            Self::from_wide(self.to_wide().erfc())
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use super::f128;
+use crate::wide::{Wide, FRAC_1_PI, PI};
+use crate::IeeeFloat;
+
+/// Arguments of at least `2^11` in magnitude overflow or underflow `gamma`.
+const RANGE: i32 = 11;
+
+impl f128 {
+    /// Computes the gamma function of `self`.
+    ///
+    /// `gamma(±0)` is `±∞` and `gamma(+∞)` is `+∞`. Negative integers and
+    /// `-∞` give NaN. Positive integers give exact factorials whenever they
+    /// are representable.
+    #[inline]
+    pub fn gamma(self) -> Self {
+        if self.is_nan() {
+            return self.quiet();
+        } else if self.is_zero() {
+            return Self(Self::INFINITY.0 | (self.0 & (1 << 127)));
+        } else if self.is_infinite() {
+            return if self.is_sign_negative() {
+                Self::NAN
+            } else {
+                self
+            };
+        }
+
+        let x = self.to_wide();
+        if !x.neg {
+            return if self.ilogb() >= RANGE {
+                Self::INFINITY
+            } else {
+                Self::from_wide(x.gamma())
+            };
+        }
+
+        // Reflection: gamma(x) = pi / (sin(pi x) gamma(1 - x))
+        let sin = x.sin_pi();
+        if sin.is_zero() {
+            Self::NAN
+        } else if self.ilogb() >= RANGE {
+            Self((sin.neg as u128) << 127)
+        } else {
+            let gamma = Wide::ONE.sub(x).gamma();
+            Self::from_wide(PI.div(sin.mul(gamma)))
+        }
+    }
+
+    /// Computes the natural logarithm of the absolute value of the gamma
+    /// function of `self`, along with the sign of the gamma function as `1`
+    /// or `-1`.
+    ///
+    /// `ln_gamma(1)` and `ln_gamma(2)` are `+0`, keeping full relative
+    /// precision nearby. Zeros, negative integers and infinities give `+∞`.
+    #[inline]
+    pub fn ln_gamma(self) -> (Self, i32) {
+        if self.is_nan() {
+            return (self.quiet(), 1);
+        } else if self.is_infinite() {
+            return (Self::INFINITY, 1);
+        } else if self.is_zero() {
+            let sign = if self.is_sign_negative() { -1 } else { 1 };
+            return (Self::INFINITY, sign);
+        }
+
+        let x = self.to_wide();
+        if !x.neg {
+            return (Self::from_wide(x.ln_gamma()), 1);
+        }
+
+        // Reflection: ln|gamma(x)| = -ln(|sin(pi x)| / pi * gamma(1 - x))
+        let sin = x.sin_pi();
+        if sin.is_zero() {
+            return (Self::INFINITY, 1);
+        }
+
+        let ln = sin.abs().mul(FRAC_1_PI).ln();
+        let ln = ln.add(Wide::ONE.sub(x).ln_gamma()).neg();
+        (Self::from_wide(ln), if sin.neg { -1 } else { 1 })
+    }
+}
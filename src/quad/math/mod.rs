@@ -0,0 +1,15 @@
+//! Elementary and special functions.
+//!
+//! Each function handles the special cases of C23 Annex F directly and
+//! evaluates everything else in 256-bit `Wide` arithmetic, rounding once
+//! at the end. The functions shared with `f16` are written once, in
+//! `crate::wide::float`.
+
+use super::f128;
+
+mod bessel;
+mod erf;
+mod gamma;
+mod pow;
+
+elementary!(f128: bits u128, exp 15, mant 112, range 15, overflow 15, saturate 6);
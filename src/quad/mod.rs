@@ -1,11 +1,15 @@
 use core::ops::Neg;
 
+mod arith;
+mod math;
+mod nan;
+mod rem;
 mod sw;
 
 /// 128-bit floating point type (IEEE 754 quadruple-precision).
 ///
-/// This provides a minimal implementation with construction methods and
-/// conversions through f64.
+/// This provides construction methods, conversions through f64, correctly
+/// rounded arithmetic and software math functions.
 #[derive(Debug, Clone, Copy, Default)]
 #[allow(non_camel_case_types)]
 #[repr(transparent)]
@@ -55,19 +59,19 @@ impl PartialEq for f128 {
 }
 
 impl f128 {
-    pub const NAN: Self = Self(u128::MAX);
+    pub const NAN: Self = Self(0x7fff8000000000000000000000000000);
 
     pub const MIN: Self = Self(0xfffeffffffffffffffffffffffffffff);
     pub const MAX: Self = Self(0x7ffeffffffffffffffffffffffffffff);
 
-    pub const INFINITY: Self = Self(u128::MAX - 1);
-    pub const NEG_INFINITY: Self = Self(u128::MAX - 2);
+    pub const INFINITY: Self = Self(0x7fff0000000000000000000000000000);
+    pub const NEG_INFINITY: Self = Self(0xffff0000000000000000000000000000);
 
     pub const MANTISSA_DIGITS: u32 = 113;
 
     #[inline]
     pub const fn is_nan(self) -> bool {
-        self.0 == Self::NAN.0
+        (self.0 & (u128::MAX >> 1)) > Self::INFINITY.0
     }
 
     #[inline]
@@ -90,6 +94,44 @@ impl f128 {
         (self.0 & (1 << 127)) != 0
     }
 
+    /// Returns the least number greater than `self` (IEEE 754 `nextUp`).
+    ///
+    /// NaN and `INFINITY` are returned unchanged, `NEG_INFINITY` becomes
+    /// `MIN`, and both zeros become the smallest positive subnormal.
+    #[inline]
+    pub const fn next_up(self) -> Self {
+        let abs = self.0 & (u128::MAX >> 1);
+
+        if self.is_nan() || self.0 == Self::INFINITY.0 {
+            self
+        } else if abs == 0 {
+            Self(1)
+        } else if self.0 == abs {
+            Self(self.0 + 1)
+        } else {
+            Self(self.0 - 1)
+        }
+    }
+
+    /// Returns the greatest number less than `self` (IEEE 754 `nextDown`).
+    ///
+    /// NaN and `NEG_INFINITY` are returned unchanged, `INFINITY` becomes
+    /// `MAX`, and both zeros become the smallest negative subnormal.
+    #[inline]
+    pub const fn next_down(self) -> Self {
+        let abs = self.0 & (u128::MAX >> 1);
+
+        if self.is_nan() || self.0 == Self::NEG_INFINITY.0 {
+            self
+        } else if abs == 0 {
+            Self((1 << 127) | 1)
+        } else if self.0 == abs {
+            Self(self.0 - 1)
+        } else {
+            Self(self.0 + 1)
+        }
+    }
+
     /// Creates an `f128` from its representation as a `u128`.
     #[inline]
     pub const fn from_bits(bits: u128) -> Self {
@@ -0,0 +1,11 @@
+use super::f128;
+
+const QUIET_BIT: u128 = 1 << 111;
+
+impl f128 {
+    /// Sets the quiet bit, turning a signaling NaN into a quiet NaN.
+    #[inline]
+    pub(crate) const fn quiet(self) -> Self {
+        Self(self.0 | QUIET_BIT)
+    }
+}
@@ -1,15 +1,19 @@
 #![cfg(feature = "casting")]
 
+use crate::round::{decode, rescale_payload, round_pack, Decoded};
+
 // Common f128 bit layout constants used across all conversion modules
 /// f128 infinity/NaN representation (exponent = 32767, mantissa = 0)
 pub const F128_INF: u128 = 0x7FFF_0000_0000_0000_0000_0000_0000_0000;
 
-/// f128 exponent bias
-pub const F128_EXP_BIAS: i32 = 16383;
-
 /// Masks
 pub const F128_MANT_MASK: u128 = (1u128 << 112) - 1;
-pub const F128_ABS_MASK: u128 = (1u128 << 127) - 1;
+
+/// The exponent and stored mantissa widths of the binary formats.
+const F16: (u32, u32) = (5, 10);
+const F32: (u32, u32) = (8, 23);
+const F64: (u32, u32) = (11, 52);
+const F128: (u32, u32) = (15, 112);
 
 mod fl16;
 mod fl32;
@@ -19,3 +23,28 @@ mod int16;
 mod int32;
 mod int64;
 mod int8;
+
+/// Converts the bits of a value between two binary formats, given as
+/// `(exp_bits, mant_bits)`, rounding to nearest even.
+///
+/// Infinities stay infinite, and NaNs are quieted and keep the top payload
+/// bits.
+#[inline]
+const fn convert(bits: u128, from: (u32, u32), to: (u32, u32)) -> u128 {
+    let (from_exp, from_mant) = from;
+    let (to_exp, to_mant) = to;
+
+    let sign = to_exp + to_mant;
+    let inf = ((1 << to_exp) - 1) << to_mant;
+
+    match decode(bits, from_exp, from_mant) {
+        Decoded::Nan(negative, mant) => {
+            let payload = rescale_payload(mant, from_mant, to_mant);
+            (negative as u128) << sign | inf | (1 << (to_mant - 1)) | payload
+        }
+        Decoded::Infinite(negative) => (negative as u128) << sign | inf,
+        Decoded::Finite(negative, mant, exp) => {
+            round_pack(negative, mant, exp, false, to_exp, to_mant)
+        }
+    }
+}
@@ -0,0 +1,355 @@
+//! Exact division with an integral quotient, for every remainder function.
+//!
+//! Operands are finite nonzero magnitudes `mant * 2^exp` whose mantissas are
+//! normalized to `[2^112, 2^113)`, so that every format up to `binary128`
+//! shares the same code. The remainder is always exact. For exponent gaps
+//! too wide to divide out bit by bit it is found with modular
+//! exponentiation, `mx * 2^d mod my`, in a number of steps logarithmic in
+//! the gap, and the low bits of the quotient follow from the remainder.
+
+use core::cmp::Ordering;
+
+use crate::ieee::{is_zero, pack, propagate_nan};
+use crate::{FloatBits, IeeeFloat};
+
+/// The position of the leading bit of a normalized mantissa.
+const TOP: u32 = 112;
+
+/// Free bits above a normalized mantissa, less one for a carry.
+const HEADROOM: u32 = 127 - TOP - 1;
+
+/// Quotient bits found by long division before switching to modular
+/// arithmetic.
+const STEPS: u32 = 126;
+
+/// Bits of the multiplier consumed per step of [`mul_mod`]. The partial
+/// remainder has at most 113 bits, so a step fits in 128.
+const CHUNK: u32 = 14;
+
+/// Returns `a * b mod m` for `a, b < m < 2^113`.
+#[inline]
+fn mul_mod(a: u128, b: u128, m: u128) -> u128 {
+    let mut r = 0;
+    let mut left = 128 - b.leading_zeros();
+
+    while left > 0 {
+        let n = if left % CHUNK == 0 {
+            CHUNK
+        } else {
+            left % CHUNK
+        };
+        left -= n;
+        let digit = (b >> left) & ((1 << n) - 1);
+        r = ((r << n) % m + a * digit) % m;
+    }
+
+    r
+}
+
+/// Returns `2^d mod m` for `m < 2^113` by square and multiply.
+#[inline]
+fn pow2_mod(d: u32, m: u128) -> u128 {
+    let mut r = 1 % m;
+    let mut base = 2 % m;
+    let mut d = d;
+
+    while d > 0 {
+        if d & 1 == 1 {
+            r = mul_mod(r, base, m);
+        }
+        base = mul_mod(base, base, m);
+        d >>= 1;
+    }
+
+    r
+}
+
+/// Returns the inverse of an odd `x` modulo `2^128`.
+#[inline]
+fn inverse(x: u128) -> u128 {
+    // Every odd x is its own inverse modulo 8, and each Newton step
+    // doubles the number of correct bits
+    let mut y = x;
+    for _ in 0..6 {
+        y = y.wrapping_mul(2u128.wrapping_sub(x.wrapping_mul(y)));
+    }
+
+    y
+}
+
+/// The exact result of dividing `|x|` by `|y|` with a truncated integral
+/// quotient `q`.
+#[derive(Clone, Copy)]
+pub(crate) struct Division {
+    /// The remainder `|x| - q |y|` is `rem * 2^exp`.
+    pub rem: u128,
+    pub exp: i32,
+
+    /// The quotient is `top * 2^shift + low`, where `low` holds the bits of
+    /// `q` below `2^shift`, up to 127 of them.
+    top: u128,
+    shift: u32,
+    low: u128,
+
+    /// The divisor.
+    y: (u128, i32),
+}
+
+impl Division {
+    /// Divides `x` by `y`, both given as normalized `(mant, exp)`.
+    pub fn new(x: (u128, i32), y: (u128, i32)) -> Self {
+        let (mx, ex) = x;
+        let (my, ey) = y;
+
+        if (ex, mx) < (ey, my) {
+            return Self {
+                rem: mx,
+                exp: ex,
+                top: 0,
+                shift: 0,
+                low: 0,
+                y,
+            };
+        }
+
+        // Long division of the leading quotient bits
+        let d = (ex - ey) as u32;
+        let steps = d.min(STEPS);
+        let mut top = mx / my;
+        let mut rem = mx % my;
+        for _ in 0..steps {
+            top <<= 1;
+            rem <<= 1;
+            if rem >= my {
+                rem -= my;
+                top |= 1;
+            }
+        }
+
+        if d == steps {
+            return Self {
+                rem,
+                exp: ey,
+                top,
+                shift: 0,
+                low: 0,
+                y,
+            };
+        }
+
+        // The rest of the quotient lies below 2^shift. With x = mx * 2^d
+        // divisible by 2^(shift + t), where my = o * 2^t, the remainder
+        // gives its low bits as q = -(rem >> t) / o modulo 2^shift.
+        let shift = d - STEPS;
+        let rem = mul_mod(mx % my, pow2_mod(d, my), my);
+        let t = my.trailing_zeros();
+        let bits = shift.min(127);
+        let q = (rem >> t).wrapping_neg().wrapping_mul(inverse(my >> t));
+
+        Self {
+            rem,
+            exp: ey,
+            top,
+            shift,
+            low: q & ((1 << bits) - 1),
+            y,
+        }
+    }
+
+    /// Returns `true` if the remainder is zero.
+    #[inline]
+    pub fn is_exact(self) -> bool {
+        self.rem == 0
+    }
+
+    /// Returns the low 32 bits of `q`.
+    #[inline]
+    pub fn quotient_bits(self) -> u32 {
+        (self.top.checked_shl(self.shift).unwrap_or(0) | self.low) as u32
+    }
+
+    /// Compares twice the remainder with `|y|`.
+    #[inline]
+    pub fn cmp_half(self) -> Ordering {
+        let (my, ey) = self.y;
+
+        if self.exp == ey {
+            (self.rem << 1).cmp(&my)
+        } else {
+            // The quotient is zero and the remainder is x itself
+            (self.exp + 1, self.rem).cmp(&(ey, my))
+        }
+    }
+
+    /// Returns `|y|` less the remainder as `(mant, exp, sticky)` for
+    /// [`round_pack`](crate::round::round_pack).
+    pub fn complement(self) -> (u128, i32, bool) {
+        let (my, ey) = self.y;
+        let gap = (ey - self.exp) as u32;
+
+        if gap <= HEADROOM {
+            return ((my << gap) - self.rem, self.exp, false);
+        }
+
+        // Only a zero quotient leaves x this far below y. Fold what falls
+        // off into a sticky bit, borrowing from the kept bits.
+        let s = gap - HEADROOM;
+        let (kept, lost) = if s >= 128 {
+            (0, true)
+        } else {
+            (self.rem >> s, self.rem & ((1 << s) - 1) != 0)
+        };
+
+        let mant = (my << HEADROOM) - kept - lost as u128;
+        (mant, ey - HEADROOM as i32, lost)
+    }
+
+    /// Returns `q`, or `q + 1` if `up`, as `(mant, exp, sticky)` for
+    /// [`round_pack`](crate::round::round_pack).
+    pub fn quotient(self, up: bool) -> (u128, i32, bool) {
+        if !up {
+            return (self.top, self.shift as i32, self.low != 0);
+        } else if self.shift == 0 {
+            return (self.top + 1, 0, false);
+        }
+
+        // The increment carries into the top bits only if every low bit is
+        // set, which needs all of them to be known
+        let low = self.low + 1;
+        if self.shift <= 127 && low == 1 << self.shift {
+            (self.top + 1, self.shift as i32, false)
+        } else {
+            (self.top, self.shift as i32, true)
+        }
+    }
+}
+
+/// Returns the magnitude of a finite nonzero value as a normalized mantissa
+/// and exponent.
+#[inline]
+fn normalized<F: IeeeFloat>(x: F) -> (u128, i32) {
+    let (mant, exp, _) = x.integer_decode();
+    let mant = mant.to_u128();
+    let shift = mant.leading_zeros() - (127 - TOP);
+    (mant << shift, exp as i32 - shift as i32)
+}
+
+/// Divides finite nonzero values exactly.
+#[inline]
+fn division<F: IeeeFloat>(x: F, y: F) -> Division {
+    Division::new(normalized(x), normalized(y))
+}
+
+/// Returns the result of a remainder if either operand is a special case.
+///
+/// NaNs propagate, an infinite dividend or a zero divisor gives NaN, and
+/// otherwise a zero dividend or an infinite divisor leaves `x` as the
+/// remainder.
+#[inline]
+fn special<F: IeeeFloat>(x: F, y: F) -> Option<F> {
+    if x.is_nan() || y.is_nan() {
+        Some(propagate_nan(x, y))
+    } else if x.is_infinite() || is_zero(y) {
+        Some(F::NAN)
+    } else if is_zero(x) || y.is_infinite() {
+        Some(x)
+    } else {
+        None
+    }
+}
+
+/// Encodes a remainder with the sign of `x`, flipped if `flip`.
+#[inline]
+fn pack_rem<F: IeeeFloat>(x: F, flip: bool, (mant, exp, sticky): (u128, i32, bool)) -> F {
+    pack(x.is_sign_negative() != flip, mant, exp, sticky)
+}
+
+/// Computes the IEEE 754 remainder of `x` divided by `y` and the low bits of
+/// the rounded quotient; see [`IeeeFloat::remquo`].
+pub(crate) fn remquo<F: IeeeFloat>(x: F, y: F) -> (F, i32) {
+    if let Some(special) = special(x, y) {
+        return (special, 0);
+    }
+
+    let div = division(x, y);
+    let q = div.quotient_bits();
+
+    // Round the quotient to nearest, ties to even
+    let up = match div.cmp_half() {
+        Ordering::Greater => true,
+        Ordering::Equal => q & 1 == 1,
+        Ordering::Less => false,
+    };
+
+    let rem = if up {
+        pack_rem(x, true, div.complement())
+    } else {
+        pack_rem(x, false, (div.rem, div.exp, false))
+    };
+
+    let n = (q.wrapping_add(up as u32) & 0x7fff_ffff) as i32;
+    if x.is_sign_negative() != y.is_sign_negative() {
+        (rem, -n)
+    } else {
+        (rem, n)
+    }
+}
+
+/// Computes the least nonnegative remainder of `x` divided by `y`; see
+/// [`IeeeFloat::rem_euclid`].
+pub(crate) fn rem_euclid<F: IeeeFloat>(x: F, y: F) -> F {
+    // A finite negative dividend is offset by an infinite divisor
+    if y.is_infinite() && x.is_finite() && x.is_sign_negative() && !is_zero(x) {
+        return F::INFINITY;
+    } else if let Some(special) = special(x, y) {
+        return special;
+    }
+
+    let div = division(x, y);
+    if x.is_sign_positive() || div.is_exact() {
+        pack_rem(x, false, (div.rem, div.exp, false))
+    } else {
+        pack_rem(x, true, div.complement())
+    }
+}
+
+/// Computes the quotient of Euclidean division of `x` by `y`; see
+/// [`IeeeFloat::div_euclid`].
+pub(crate) fn div_euclid<F: IeeeFloat>(x: F, y: F) -> F {
+    let sign = x.is_sign_negative() != y.is_sign_negative();
+
+    if x.is_nan() || y.is_nan() {
+        return propagate_nan(x, y);
+    } else if x.is_infinite() || is_zero(y) {
+        return if y.is_infinite() || is_zero(x) {
+            F::NAN
+        } else if sign {
+            F::NEG_INFINITY
+        } else {
+            F::INFINITY
+        };
+    } else if is_zero(x) {
+        return pack(sign, 0, 0, false);
+    } else if y.is_infinite() {
+        // The remainder is x, plus |y| if x is negative
+        return pack(sign, x.is_sign_negative() as u128, 0, false);
+    }
+
+    let div = division(x, y);
+    let up = x.is_sign_negative() && !div.is_exact();
+    let (mant, exp, sticky) = div.quotient(up);
+    pack(sign, mant, exp, sticky)
+}
+
+/// Computes the C `fmod` remainder: `x - n * y` with `n` the quotient
+/// truncated toward zero. Core's `f16` and `f128` have their own `%`.
+#[cfg(not(feature = "nightly"))]
+#[inline]
+pub(crate) fn fmod<F: IeeeFloat>(x: F, y: F) -> F {
+    if let Some(special) = special(x, y) {
+        return special;
+    }
+
+    let div = division(x, y);
+    pack_rem(x, false, (div.rem, div.exp, false))
+}
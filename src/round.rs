@@ -0,0 +1,158 @@
+//! Shared rounding core for the binary interchange formats.
+//!
+//! Every narrowing operation in this crate eventually has an exact value of
+//! the form `(-1)^sign * mant * 2^exp` that needs to be rounded into a format
+//! with a given number of exponent and stored mantissa bits. This module
+//! does that once, correctly, for every format up to `binary128`.
+
+/// Splits the bits of a finite value in a binary format with `exp_bits`
+/// exponent bits and `mant_bits` stored mantissa bits into its sign, its
+/// integer mantissa and the weight of the mantissa's last bit, so that the
+/// value is `(-1)^sign * mant * 2^exp`. This is the inverse of
+/// [`round_pack`].
+#[inline]
+pub(crate) const fn unpack(bits: u128, exp_bits: u32, mant_bits: u32) -> (bool, u128, i32) {
+    let bias = (1i32 << (exp_bits - 1)) - 1;
+    let sign = (bits >> (exp_bits + mant_bits)) & 1 == 1;
+    let exp = ((bits >> mant_bits) & ((1 << exp_bits) - 1)) as i32;
+    let mant = bits & ((1u128 << mant_bits) - 1);
+
+    if exp == 0 {
+        (sign, mant, 1 - bias - mant_bits as i32)
+    } else {
+        (sign, mant | (1 << mant_bits), exp - bias - mant_bits as i32)
+    }
+}
+
+/// A value of a binary format, classified.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum Decoded {
+    /// `(-1)^sign * mant * 2^exp`, as from [`unpack`].
+    Finite(bool, u128, i32),
+    /// An infinity of the sign.
+    Infinite(bool),
+    /// A NaN of the sign, with its stored mantissa field, quiet bit included.
+    Nan(bool, u128),
+}
+
+/// Classifies the bits of a value in a binary format with `exp_bits`
+/// exponent bits and `mant_bits` stored mantissa bits, splitting a finite
+/// one as [`unpack`] does.
+#[inline]
+pub(crate) const fn decode(bits: u128, exp_bits: u32, mant_bits: u32) -> Decoded {
+    let negative = (bits >> (exp_bits + mant_bits)) & 1 == 1;
+    let abs = bits & ((1 << (exp_bits + mant_bits)) - 1);
+    let inf = ((1 << exp_bits) - 1) << mant_bits;
+
+    if abs > inf {
+        Decoded::Nan(negative, abs & ((1 << mant_bits) - 1))
+    } else if abs == inf {
+        Decoded::Infinite(negative)
+    } else {
+        let (negative, mant, exp) = unpack(bits, exp_bits, mant_bits);
+        Decoded::Finite(negative, mant, exp)
+    }
+}
+
+/// Moves a NaN payload from a mantissa field of `from_bits` bits to one of
+/// `to_bits` bits, keeping its top bits aligned: the low bits are zero when
+/// widening and dropped when narrowing.
+#[inline]
+pub(crate) const fn rescale_payload(payload: u128, from_bits: u32, to_bits: u32) -> u128 {
+    if to_bits > from_bits {
+        payload << (to_bits - from_bits)
+    } else {
+        payload >> (from_bits - to_bits)
+    }
+}
+
+/// Rounds `mant >> shift` to nearest, ties to even.
+///
+/// `sticky` indicates that nonzero bits below `mant` were already discarded.
+#[inline]
+pub(crate) const fn shift_round(mant: u128, shift: u32, sticky: bool) -> u128 {
+    if shift == 0 {
+        return mant;
+    }
+
+    if shift > 128 {
+        return 0;
+    }
+
+    let (kept, rem, half) = if shift == 128 {
+        (0, mant, 1u128 << 127)
+    } else {
+        (
+            mant >> shift,
+            mant & ((1u128 << shift) - 1),
+            1u128 << (shift - 1),
+        )
+    };
+
+    if rem > half || (rem == half && (sticky || kept & 1 == 1)) {
+        kept + 1
+    } else {
+        kept
+    }
+}
+
+/// Rounds `(-1)^sign * mant * 2^exp` to nearest, ties to even, and encodes
+/// it in a binary format with `exp_bits` exponent bits and `mant_bits`
+/// stored mantissa bits.
+///
+/// `sticky` indicates that `mant` was truncated and that the exact value is
+/// slightly larger in magnitude than `mant * 2^exp`. Overflow produces an
+/// infinity and underflow produces subnormals or a signed zero. `mant_bits`
+/// may be at most 126 and `exp` must be within a few hundred thousand of the
+/// format's exponent range so that intermediate exponents cannot overflow.
+#[inline]
+pub(crate) const fn round_pack(
+    sign: bool,
+    mant: u128,
+    exp: i32,
+    sticky: bool,
+    exp_bits: u32,
+    mant_bits: u32,
+) -> u128 {
+    let sign = (sign as u128) << (exp_bits + mant_bits);
+
+    if mant == 0 {
+        return sign;
+    }
+
+    let bias = (1i32 << (exp_bits - 1)) - 1;
+    let max_exp = (1i32 << exp_bits) - 1;
+    let min_quantum = 1 - bias - mant_bits as i32;
+
+    // Pick the weight of the last mantissa bit for the result
+    let top = 127 - mant.leading_zeros() as i32;
+    let mut quantum = exp + top - mant_bits as i32;
+    if quantum < min_quantum {
+        quantum = min_quantum;
+    }
+
+    let mut sig = if quantum >= exp {
+        let shift = quantum - exp;
+        shift_round(mant, if shift > 129 { 129 } else { shift as u32 }, sticky)
+    } else {
+        mant << (exp - quantum) as u32
+    };
+
+    // Rounding may carry into a new binade
+    if sig >> (mant_bits + 1) != 0 {
+        sig >>= 1;
+        quantum += 1;
+    }
+
+    // Subnormal or zero
+    if sig >> mant_bits == 0 {
+        return sign | sig;
+    }
+
+    let biased = quantum + mant_bits as i32 + bias;
+    if biased >= max_exp {
+        return sign | ((max_exp as u128) << mant_bits);
+    }
+
+    sign | ((biased as u128) << mant_bits) | (sig & ((1u128 << mant_bits) - 1))
+}
@@ -0,0 +1,131 @@
+#![cfg(feature = "casting")]
+
+//! Bulk conversions between slices of floats.
+
+use crate::{f128, IeeeFloat};
+use casting::CastFrom;
+
+#[cfg(feature = "nightly")]
+use crate::f16;
+
+/// Applies a scalar conversion to every element.
+#[inline]
+pub(crate) fn each<S: Copy, D>(src: &[S], dst: &mut [D], f: impl Fn(S) -> D) {
+    for (d, s) in dst.iter_mut().zip(src) {
+        *d = f(*s);
+    }
+}
+
+#[inline]
+pub(crate) fn check_len<S, D>(src: &[S], dst: &[D]) {
+    assert_eq!(
+        src.len(),
+        dst.len(),
+        "source and destination slices have different lengths"
+    );
+}
+
+/// Converts whole slices and arrays to and from `f32` and `f64`.
+///
+/// Each element converts exactly as by `CastFrom`; the bulk forms only let an
+/// implementation choose its backend once and convert several elements per
+/// instruction. It is implemented for `f16`, `bf16` and `f128`, including the
+/// core types that the `nightly` feature re-exports, which convert one
+/// element at a time.
+pub trait ConvertSlice: IeeeFloat {
+    /// Converts a slice of `f32`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` and `dst` have different lengths.
+    fn convert_from_f32_slice(src: &[f32], dst: &mut [Self]);
+
+    /// Converts a slice to `f32`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` and `dst` have different lengths.
+    fn convert_to_f32_slice(src: &[Self], dst: &mut [f32]);
+
+    /// Converts a slice of `f64`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` and `dst` have different lengths.
+    fn convert_from_f64_slice(src: &[f64], dst: &mut [Self]);
+
+    /// Converts a slice to `f64`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` and `dst` have different lengths.
+    fn convert_to_f64_slice(src: &[Self], dst: &mut [f64]);
+
+    /// Converts an array of `f32`; see
+    /// [`convert_from_f32_slice`](Self::convert_from_f32_slice).
+    #[inline]
+    fn convert_from_f32_array<const N: usize>(src: [f32; N]) -> [Self; N] {
+        let mut dst = [Self::default(); N];
+        Self::convert_from_f32_slice(&src, &mut dst);
+        dst
+    }
+
+    /// Converts an array to `f32`; see
+    /// [`convert_to_f32_slice`](Self::convert_to_f32_slice).
+    #[inline]
+    fn convert_to_f32_array<const N: usize>(src: [Self; N]) -> [f32; N] {
+        let mut dst = [0.0; N];
+        Self::convert_to_f32_slice(&src, &mut dst);
+        dst
+    }
+
+    /// Converts an array of `f64`; see
+    /// [`convert_from_f64_slice`](Self::convert_from_f64_slice).
+    #[inline]
+    fn convert_from_f64_array<const N: usize>(src: [f64; N]) -> [Self; N] {
+        let mut dst = [Self::default(); N];
+        Self::convert_from_f64_slice(&src, &mut dst);
+        dst
+    }
+
+    /// Converts an array to `f64`; see
+    /// [`convert_to_f64_slice`](Self::convert_to_f64_slice).
+    #[inline]
+    fn convert_to_f64_array<const N: usize>(src: [Self; N]) -> [f64; N] {
+        let mut dst = [0.0; N];
+        Self::convert_to_f64_slice(&src, &mut dst);
+        dst
+    }
+}
+
+/// Implements [`ConvertSlice`] through the scalar casts.
+macro_rules! scalar {
+    ($($float:ident),*) => {$(
+        impl ConvertSlice for $float {
+            fn convert_from_f32_slice(src: &[f32], dst: &mut [Self]) {
+                check_len(src, dst);
+                each(src, dst, Self::cast_from);
+            }
+
+            fn convert_to_f32_slice(src: &[Self], dst: &mut [f32]) {
+                check_len(src, dst);
+                each(src, dst, f32::cast_from);
+            }
+
+            fn convert_from_f64_slice(src: &[f64], dst: &mut [Self]) {
+                check_len(src, dst);
+                each(src, dst, Self::cast_from);
+            }
+
+            fn convert_to_f64_slice(src: &[Self], dst: &mut [f64]) {
+                check_len(src, dst);
+                each(src, dst, f64::cast_from);
+            }
+        }
+    )*};
+}
+
+scalar!(f128);
+
+#[cfg(feature = "nightly")]
+scalar!(f16);
@@ -0,0 +1,60 @@
+#![cfg(feature = "casting")]
+
+//! Portable software conversions.
+//!
+//! These are the conversions `CastFrom` falls back to when no hardware
+//! [`Backend`](crate::Backend) is active. They never use target-specific
+//! instructions, so they give the same bits on every target and with every
+//! feature combination, which makes them suitable for code whose results
+//! must not depend on the build. Like every backend, they round exactly as
+//! the standard library casts do.
+
+use crate::f16;
+use crate::half::sw;
+use crate::slice;
+
+macro_rules! soft {
+    ($($name:ident: $from:ty => $to:ty;)*) => {$(
+        #[doc = concat!("Converts `", stringify!($from), "` to `", stringify!($to), "`.")]
+        #[inline]
+        pub fn $name(value: $from) -> $to {
+            sw::$name(value)
+        }
+    )*};
+}
+
+soft! {
+    f32_from_f16: f16 => f32;
+    f16_from_f32: f32 => f16;
+    f64_from_f16: f16 => f64;
+    f16_from_f64: f64 => f16;
+    i32_from_f16: f16 => i32;
+    f16_from_i32: i32 => f16;
+    u32_from_f16: f16 => u32;
+    f16_from_u32: u32 => f16;
+    i64_from_f16: f16 => i64;
+    f16_from_i64: i64 => f16;
+    u64_from_f16: f16 => u64;
+    f16_from_u64: u64 => f16;
+}
+
+macro_rules! soft_slice {
+    ($($name:ident: $scalar:ident, $from:ty => $to:ty;)*) => {$(
+        #[doc = concat!("Converts a slice of `", stringify!($from), "` to `", stringify!($to), "`.")]
+        ///
+        /// # Panics
+        ///
+        /// Panics if `src` and `dst` have different lengths.
+        pub fn $name(src: &[$from], dst: &mut [$to]) {
+            slice::check_len(src, dst);
+            slice::each(src, dst, sw::$scalar);
+        }
+    )*};
+}
+
+soft_slice! {
+    f32_from_f16_slice: f32_from_f16, f16 => f32;
+    f16_from_f32_slice: f16_from_f32, f32 => f16;
+    f64_from_f16_slice: f64_from_f16, f16 => f64;
+    f16_from_f64_slice: f16_from_f64, f64 => f16;
+}
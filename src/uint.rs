@@ -0,0 +1,336 @@
+//! A 256-bit unsigned integer, wide enough for the exact product of two
+//! decimal128 coefficients and for the significands of binary256.
+
+use core::cmp::Ordering;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct U256 {
+    pub hi: u128,
+    pub lo: u128,
+}
+
+impl PartialOrd for U256 {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for U256 {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.hi, self.lo).cmp(&(other.hi, other.lo))
+    }
+}
+
+/// Returns 10^`exp`, for `exp` of at most 38.
+#[inline]
+pub(crate) const fn pow10(exp: u32) -> u128 {
+    10u128.pow(exp)
+}
+
+/// Returns the number of decimal digits of `x`, counting zero as one.
+#[inline]
+pub(crate) const fn digits(x: u128) -> u32 {
+    let mut n = 1;
+    while n < 39 && x >= pow10(n) {
+        n += 1;
+    }
+    n
+}
+
+/// The powers of ten that fit in a `U256`.
+const POW10: [U256; 78] = {
+    let mut table = [U256::ZERO; 78];
+    let mut i = 0;
+    while i < 78 {
+        table[i] = if i <= 38 {
+            U256::new(pow10(i as u32))
+        } else {
+            table[i - 1].mul_u128(10)
+        };
+        i += 1;
+    }
+    table
+};
+
+impl U256 {
+    pub const ZERO: Self = Self { hi: 0, lo: 0 };
+
+    #[inline]
+    pub const fn new(lo: u128) -> Self {
+        Self { hi: 0, lo }
+    }
+
+    /// Returns 10^`exp`, for `exp` of at most 77.
+    #[inline]
+    pub const fn pow10(exp: u32) -> Self {
+        POW10[exp as usize]
+    }
+
+    /// Returns the full product of `a` and `b`.
+    #[inline]
+    pub const fn mul(a: u128, b: u128) -> Self {
+        let (a1, a0) = (a >> 64, a as u64 as u128);
+        let (b1, b0) = (b >> 64, b as u64 as u128);
+
+        let lo = a0 * b0;
+        let mid1 = a1 * b0;
+        let mid2 = a0 * b1;
+        let hi = a1 * b1;
+
+        let (mid, carry) = mid1.overflowing_add(mid2);
+        let (lo, c) = lo.overflowing_add(mid << 64);
+        let hi = hi + (mid >> 64) + ((carry as u128) << 64) + c as u128;
+
+        Self { hi, lo }
+    }
+
+    /// Returns `self * m`, which must fit.
+    #[inline]
+    pub const fn mul_u128(self, m: u128) -> Self {
+        let low = Self::mul(self.lo, m);
+        Self {
+            hi: self.hi * m + low.hi,
+            lo: low.lo,
+        }
+    }
+
+    /// Returns `self * 10^exp`, which must fit.
+    #[inline]
+    pub fn mul_pow10(self, exp: u32) -> Self {
+        if self.hi == 0 && exp <= 77 {
+            let p = Self::pow10(exp);
+            if p.hi == 0 {
+                return Self::mul(self.lo, p.lo);
+            }
+            return p.mul_u128(self.lo);
+        }
+
+        let mut x = self;
+        let mut exp = exp;
+        while exp > 0 {
+            let step = exp.min(38);
+            x = x.mul_u128(pow10(step));
+            exp -= step;
+        }
+        x
+    }
+
+    #[inline]
+    pub const fn add(self, other: Self) -> Self {
+        let (lo, carry) = self.lo.overflowing_add(other.lo);
+        Self {
+            hi: self.hi + other.hi + carry as u128,
+            lo,
+        }
+    }
+
+    /// Returns `self - other`, which must not be negative.
+    #[inline]
+    pub const fn sub(self, other: Self) -> Self {
+        let (lo, borrow) = self.lo.overflowing_sub(other.lo);
+        Self {
+            hi: self.hi - other.hi - borrow as u128,
+            lo,
+        }
+    }
+
+    #[inline]
+    pub const fn is_zero(self) -> bool {
+        self.hi == 0 && self.lo == 0
+    }
+
+    #[inline]
+    pub const fn leading_zeros(self) -> u32 {
+        if self.hi == 0 {
+            128 + self.lo.leading_zeros()
+        } else {
+            self.hi.leading_zeros()
+        }
+    }
+
+    /// Returns `self << n`, for `n` below 256.
+    #[inline]
+    pub const fn shl(self, n: u32) -> Self {
+        if n == 0 {
+            self
+        } else if n < 128 {
+            Self {
+                hi: self.hi << n | self.lo >> (128 - n),
+                lo: self.lo << n,
+            }
+        } else {
+            Self {
+                hi: self.lo << (n - 128),
+                lo: 0,
+            }
+        }
+    }
+
+    /// Returns `self >> n`, and whether any ones were shifted out.
+    #[inline]
+    pub const fn shr(self, n: u32) -> (Self, bool) {
+        if n == 0 {
+            (self, false)
+        } else if n < 128 {
+            let shifted = Self {
+                hi: self.hi >> n,
+                lo: self.lo >> n | self.hi << (128 - n),
+            };
+            (shifted, self.lo << (128 - n) != 0)
+        } else if n < 256 {
+            let sticky = self.lo != 0 || (n > 128 && self.hi << (256 - n) != 0);
+            (Self::new(self.hi >> (n - 128)), sticky)
+        } else {
+            (Self::ZERO, !self.is_zero())
+        }
+    }
+
+    /// Returns the full 512-bit product of `self` and `other` as `(hi, lo)`.
+    #[inline]
+    pub const fn mul_wide(self, other: Self) -> (Self, Self) {
+        let ll = Self::mul(self.lo, other.lo);
+        let lh = Self::mul(self.lo, other.hi);
+        let hl = Self::mul(self.hi, other.lo);
+        let hh = Self::mul(self.hi, other.hi);
+
+        // The middle terms straddle the two halves
+        let (mid, c1) = lh.overflowing_add(hl);
+        let (lo_hi, c2) = ll.hi.overflowing_add(mid.lo);
+        let lo = Self {
+            hi: lo_hi,
+            lo: ll.lo,
+        };
+
+        let hi = hh
+            .add(Self::new(mid.hi))
+            .add(Self::new(c2 as u128))
+            .add(Self {
+                hi: c1 as u128,
+                lo: 0,
+            });
+
+        (hi, lo)
+    }
+
+    #[inline]
+    const fn overflowing_add(self, other: Self) -> (Self, bool) {
+        let (lo, carry) = self.lo.overflowing_add(other.lo);
+        let (hi, c1) = self.hi.overflowing_add(other.hi);
+        let (hi, c2) = hi.overflowing_add(carry as u128);
+        (Self { hi, lo }, c1 || c2)
+    }
+
+    /// Returns the number of decimal digits, counting zero as one.
+    #[inline]
+    pub fn digits(self) -> u32 {
+        if self.hi == 0 {
+            return digits(self.lo);
+        }
+
+        let mut n = 39;
+        while n < 78 && self >= Self::pow10(n) {
+            n += 1;
+        }
+        n
+    }
+
+    /// Divides by a 64-bit divisor, returning the quotient and remainder.
+    #[inline]
+    fn div_u64(self, d: u64) -> (Self, u64) {
+        let d = d as u128;
+        let mut rem = 0;
+        let mut limbs = [
+            (self.hi >> 64) as u64,
+            self.hi as u64,
+            (self.lo >> 64) as u64,
+            self.lo as u64,
+        ];
+
+        for limb in limbs.iter_mut() {
+            let cur = rem << 64 | *limb as u128;
+            *limb = (cur / d) as u64;
+            rem = cur % d;
+        }
+
+        let hi = (limbs[0] as u128) << 64 | limbs[1] as u128;
+        let lo = (limbs[2] as u128) << 64 | limbs[3] as u128;
+        (Self { hi, lo }, rem as u64)
+    }
+
+    /// Writes the decimal digits into the end of `bytes`, returning them.
+    #[inline]
+    pub fn to_ascii(self, bytes: &mut [u8; 78]) -> &[u8] {
+        let mut x = self;
+        let mut start = bytes.len();
+        loop {
+            let (q, r) = x.div_u64(10);
+            start -= 1;
+            bytes[start] = b'0' + r as u8;
+            x = q;
+            if x.is_zero() {
+                return &bytes[start..];
+            }
+        }
+    }
+
+    /// Returns the quotient and remainder of division by 10^`exp`, for
+    /// `exp` of at most 77.
+    #[inline]
+    pub fn div_rem_pow10(self, exp: u32) -> (Self, Self) {
+        if self.hi == 0 && exp <= 38 {
+            let d = pow10(exp);
+            return (Self::new(self.lo / d), Self::new(self.lo % d));
+        }
+
+        let mut q = self;
+        let mut left = exp;
+        while left > 0 {
+            let step = left.min(19);
+            q = q.div_u64(pow10(step) as u64).0;
+            left -= step;
+        }
+
+        // The quotient of each step is floored, so the composition is exact
+        (q, self.sub(q.mul_pow10(exp)))
+    }
+
+    /// Returns the quotient and remainder of division by `d`, which must
+    /// not be zero.
+    #[inline]
+    pub fn div_rem(self, d: u128) -> (Self, u128) {
+        if self.hi == 0 {
+            return (Self::new(self.lo / d), self.lo % d);
+        }
+
+        if d >> 64 == 0 {
+            let (q, r) = self.div_u64(d as u64);
+            return (q, r as u128);
+        }
+
+        // Long division one bit at a time; the remainder stays below d, so
+        // below 2^128, and its shift carries at most one bit out
+        let mut q = Self::ZERO;
+        let mut rem: u128 = 0;
+        for i in (0..256).rev() {
+            let bit = if i >= 128 {
+                self.hi >> (i - 128) & 1
+            } else {
+                self.lo >> i & 1
+            };
+            let carry = rem >> 127;
+            rem = rem << 1 | bit;
+            if carry != 0 || rem >= d {
+                rem = rem.wrapping_sub(d);
+                if i >= 128 {
+                    q.hi |= 1 << (i - 128);
+                } else {
+                    q.lo |= 1 << i;
+                }
+            }
+        }
+
+        (q, rem)
+    }
+}
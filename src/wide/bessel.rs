@@ -0,0 +1,183 @@
+use super::consts::{EULER_GAMMA, FRAC_1_PI};
+use super::Wide;
+
+/// Arguments from which Hankel's asymptotic expansions reach full
+/// precision.
+const HANKEL: i64 = 96;
+
+impl Wide {
+    /// Returns `(J_nu(x), Y_nu(x))` for `nu` of 0 or 1 and `x >= 96` from
+    /// Hankel's asymptotic expansions.
+    ///
+    /// `J_nu(x) = sqrt(2 / (pi x)) (P cos(w) - Q sin(w))` and
+    /// `Y_nu(x) = sqrt(2 / (pi x)) (P sin(w) + Q cos(w))`, where
+    /// `w = x - (2 nu + 1) pi/4` is reduced exactly.
+    fn hankel(self, nu: u32) -> (Self, Self) {
+        let mu = 4 * (nu * nu) as i64;
+        let inv = self.recip();
+
+        // The terms a_k / x^k alternate between P and Q
+        let mut p = Self::ONE;
+        let mut q = Self::ZERO;
+        let mut term = Self::ONE;
+        for k in 1i64.. {
+            let odd = 2 * k - 1;
+            term = term.mul(inv).mul(Self::from_i64(mu - odd * odd));
+            term = term.div_u64(8 * k as u64);
+            match k % 4 {
+                1 => q = q.add(term),
+                2 => p = p.sub(term),
+                3 => q = q.sub(term),
+                _ => p = p.add(term),
+            }
+            if Self::negligible(term, Self::ONE) {
+                break;
+            }
+        }
+
+        // sqrt(2) sin(w) and sqrt(2) cos(w): turn the reduced argument back
+        // by pi/4, then by whole quadrants
+        let (quadrant, r) = self.rem_frac_pi_2();
+        let (sin, cos) = r.sin_cos();
+        let (s, c) = (sin.sub(cos), cos.add(sin));
+        let (s, c) = match quadrant.wrapping_sub(nu) & 3 {
+            0 => (s, c),
+            1 => (c, s.neg()),
+            2 => (s.neg(), c.neg()),
+            _ => (c.neg(), s),
+        };
+
+        let scale = FRAC_1_PI.mul(inv).sqrt();
+        let j = p.mul(c).sub(q.mul(s)).mul(scale);
+        let y = p.mul(s).add(q.mul(c)).mul(scale);
+        (j, y)
+    }
+
+    /// Runs Miller's backward recurrence `J_(k-1) = 2k/x J_k - J_(k+1)`
+    /// from far above orders `n` and `x`, normalized by
+    /// `J_0 + 2 (J_2 + J_4 + ...) = 1`.
+    ///
+    /// Returns `(J_n(x), Y_0(x), Y_1(x))`, the last two from the Neumann
+    /// series `Y_0 = 2/pi ((ln(x/2) + γ) J_0 - 2 sum (-1)^k J_2k / k)` and its
+    /// derivative. `x` must be positive and below `2^32`.
+    fn miller(self, n: u32) -> (Self, Self, Self) {
+        // J_k decays like the Airy function past k = x, becoming negligible
+        // about 36 x^(1/3) orders later
+        let x = self.trunc_u64() + 1;
+        let start = (n as u64).max(x) + 36 * (icbrt(x) + 1) + 50;
+        let two_inv = self.recip().scale(1);
+
+        let mut next = Self::ZERO;
+        let mut cur = Self::ONE;
+        let mut jn = Self::ZERO;
+        let mut j1 = Self::ZERO;
+        let mut norm = Self::ZERO;
+        let mut sum0 = Self::ZERO;
+        let mut sum1 = Self::ZERO;
+        for k in (1..=start).rev() {
+            let prev = two_inv.mul(Self::from_u128(false, k as u128, 0)).mul(cur);
+            next = core::mem::replace(&mut cur, prev.sub(next));
+
+            // cur is now J_m
+            let m = k - 1;
+            if m == n as u64 {
+                jn = cur;
+            }
+
+            let h = (m + 1) / 2;
+            if m == 1 {
+                j1 = cur;
+                sum1 = sum1.sub(cur);
+            } else if m % 2 == 1 {
+                // The derivative of sum0 collects (-1)^h (2h - 1) / (h (h - 1))
+                // of each J_(2h - 1)
+                let t = cur.mul(Self::from_i64(2 * h as i64 - 1));
+                let t = t.div_u64(h * (h - 1));
+                sum1 = if h % 2 == 0 { sum1.add(t) } else { sum1.sub(t) };
+            } else if m > 0 {
+                norm = norm.add(cur.scale(1));
+                let t = cur.div_u64(h);
+                sum0 = if h % 2 == 0 { sum0.add(t) } else { sum0.sub(t) };
+            }
+        }
+
+        let norm = norm.add(cur).recip();
+        let (jn, j0, j1) = (jn.mul(norm), cur.mul(norm), j1.mul(norm));
+        let (sum0, sum1) = (sum0.mul(norm), sum1.mul(norm));
+
+        let frac_2_pi = FRAC_1_PI.scale(1);
+        let l = self.scale(-1).ln().add(EULER_GAMMA);
+        let y0 = frac_2_pi.mul(l.mul(j0).sub(sum0.scale(1)));
+        let y1 = frac_2_pi.mul(l.mul(j1).sub(j0.div(self)).add(sum1));
+        (jn, y0, y1)
+    }
+
+    /// Steps the recurrence `C_(k+1) = 2k/x C_k - C_(k-1)` that Bessel
+    /// functions of both kinds satisfy from orders 0 and 1 up to `n`.
+    fn recur_up(self, n: u32, c0: Self, c1: Self) -> Self {
+        if n == 0 {
+            return c0;
+        }
+
+        let two_inv = self.recip().scale(1);
+        let mut prev = c0;
+        let mut cur = c1;
+        for k in 1..n {
+            let next = two_inv.mul(Self::from_u128(false, k as u128, 0)).mul(cur);
+            prev = core::mem::replace(&mut cur, next.sub(prev));
+
+            // Past the turning point Y_k grows without bound; stop once it is
+            // far beyond any format
+            if cur.ilogb() > 1 << 20 {
+                break;
+            }
+        }
+
+        cur
+    }
+
+    /// Returns the Bessel function of the first kind `J_n(x)` for positive
+    /// `x`.
+    pub fn bessel_j(self, n: u32) -> Self {
+        if self.cmp(Self::from_i64(HANKEL)).is_lt() {
+            return self.miller(n).0;
+        }
+
+        // The forward recurrence is only stable below the turning point
+        if Self::from_i64(n as i64).cmp(self).is_ge() {
+            return self.miller(n).0;
+        }
+
+        let (j0, _) = self.hankel(0);
+        if n == 0 {
+            return j0;
+        }
+
+        let (j1, _) = self.hankel(1);
+        self.recur_up(n, j0, j1)
+    }
+
+    /// Returns the Bessel function of the second kind `Y_n(x)` for positive
+    /// `x`.
+    pub fn bessel_y(self, n: u32) -> Self {
+        let (y0, y1) = if self.cmp(Self::from_i64(HANKEL)).is_lt() {
+            let (_, y0, y1) = self.miller(0);
+            (y0, y1)
+        } else {
+            (self.hankel(0).1, self.hankel(1).1)
+        };
+
+        // Y_n grows with n, so the forward recurrence is always stable
+        self.recur_up(n, y0, y1)
+    }
+}
+
+/// Returns the integer cube root of `x`, rounded down.
+#[inline]
+fn icbrt(x: u64) -> u64 {
+    let mut r = 0;
+    while (r + 1) * (r + 1) * (r + 1) <= x {
+        r += 1;
+    }
+    r
+}
@@ -0,0 +1,91 @@
+//! 256-bit mathematical constants.
+
+use super::Wide;
+
+/// ln(2)
+pub(crate) const LN_2: Wide = Wide::from_const(
+    false,
+    0xb17217f7d1cf79abc9e3b39803f2f6af,
+    0x40f343267298b62d8a0d175b8baafa2c,
+    -256,
+);
+
+/// log2(e)
+pub(crate) const LOG2_E: Wide = Wide::from_const(
+    false,
+    0xb8aa3b295c17f0bbbe87fed0691d3e88,
+    0xeb577aa8dd695a588b25166cd1a13248,
+    -255,
+);
+
+/// log10(2)
+pub(crate) const LOG10_2: Wide = Wide::from_const(
+    false,
+    0x9a209a84fbcff7988f8959ac0b7c9178,
+    0x26ad30c543d1f3498a5e6f26b7cc63cb,
+    -257,
+);
+
+/// log10(e)
+pub(crate) const LOG10_E: Wide = Wide::from_const(
+    false,
+    0xde5bd8a937287195355baaafad33dc32,
+    0x3ee3460245c9a2023a3f2d44f78ea53c,
+    -257,
+);
+
+/// sqrt(2)
+pub(crate) const SQRT_2: Wide = Wide::from_const(
+    false,
+    0xb504f333f9de6484597d89b3754abe9f,
+    0x1d6f60ba893ba84ced17ac8583339915,
+    -255,
+);
+
+/// pi
+pub(crate) const PI: Wide = Wide::from_const(
+    false,
+    0xc90fdaa22168c234c4c6628b80dc1cd1,
+    0x29024e088a67cc74020bbea63b139b22,
+    -254,
+);
+
+/// pi/2
+pub(crate) const FRAC_PI_2: Wide = Wide::from_const(
+    false,
+    0xc90fdaa22168c234c4c6628b80dc1cd1,
+    0x29024e088a67cc74020bbea63b139b22,
+    -255,
+);
+
+/// 1/pi
+pub(crate) const FRAC_1_PI: Wide = Wide::from_const(
+    false,
+    0xa2f9836e4e441529fc2757d1f534ddc0,
+    0xdb6295993c439041fe5163abdebbc562,
+    -257,
+);
+
+/// 2/sqrt(pi)
+pub(crate) const FRAC_2_SQRT_PI: Wide = Wide::from_const(
+    false,
+    0x906eba8214db688d71d48a7f6bfec344,
+    0x1409a0ebac3e751739a15830cce620b1,
+    -255,
+);
+
+/// Euler-Mascheroni constant
+pub(crate) const EULER_GAMMA: Wide = Wide::from_const(
+    false,
+    0x93c467e37db0c7a4d1be3f810152cb56,
+    0xa1cecc3af65cc0190c03df34709affbe,
+    -256,
+);
+
+/// ln(sqrt(2*pi))
+pub(crate) const LN_SQRT_2PI: Wide = Wide::from_const(
+    false,
+    0xeb3f8e4325f5a53494bc900144192023,
+    0xcfb08f8d13458b4ddec6a3133daa155d,
+    -256,
+);
@@ -0,0 +1,65 @@
+use super::consts::FRAC_2_SQRT_PI;
+use super::Wide;
+
+/// Below this magnitude `erfc(x)` is `1 - erf(x)`, which loses at most
+/// `log2(e^9)` bits to cancellation.
+const SERIES: i64 = 3;
+
+impl Wide {
+    /// Returns `erf(x)` for `|x| < 3` from the series
+    /// `2/sqrt(pi) e^(-x^2) sum 2^n x^(2n + 1) / (1 * 3 * ... * (2n + 1))`,
+    /// whose terms all share the sign of `x`.
+    fn erf_series(self) -> Self {
+        let x2 = self.mul(self);
+
+        let mut sum = self;
+        let mut term = self;
+        for n in 1.. {
+            term = term.mul(x2).scale(1).div_u64(2 * n + 1);
+            sum = sum.add(term);
+            if Self::negligible(term, sum) {
+                break;
+            }
+        }
+
+        FRAC_2_SQRT_PI.mul(sum).mul(x2.neg().exp())
+    }
+
+    /// Returns `erfc(x)` for `x >= 3` from the continued fraction
+    /// `2/sqrt(pi) x e^(-x^2) / (2x^2 + 1 - 1*2 / (2x^2 + 5 - 3*4 / (2x^2 + 9 - ...)))`.
+    fn erfc_fraction(self) -> Self {
+        let x2 = self.mul(self);
+        let t = x2.scale(1);
+
+        // Enough terms for full precision, which converge faster as x grows
+        let n = 2600 / x2.trunc_u64() + 30;
+        let mut v = t.add(Self::from_i64(4 * n as i64 + 1));
+        for k in (1..=n).rev() {
+            let a = Self::from_i64(((2 * k - 1) * (2 * k)) as i64);
+            v = t.add(Self::from_i64(4 * k as i64 - 3)).sub(a.div(v));
+        }
+
+        FRAC_2_SQRT_PI.mul(self).mul(x2.neg().exp()).div(v)
+    }
+
+    /// Returns the error function `erf(x)` for `|x| < 128`.
+    pub fn erf(self) -> Self {
+        if self.cmp_abs(Self::from_i64(SERIES)).is_lt() {
+            self.erf_series()
+        } else {
+            Self::from_sign(self.neg, Self::ONE.sub(self.abs().erfc_fraction()))
+        }
+    }
+
+    /// Returns the complementary error function `erfc(x) = 1 - erf(x)` for
+    /// `|x| < 128`, with full relative precision as it tends to zero.
+    pub fn erfc(self) -> Self {
+        if self.cmp_abs(Self::from_i64(SERIES)).is_lt() {
+            Self::ONE.sub(self.erf_series())
+        } else if self.neg {
+            Self::ONE.scale(1).sub(self.abs().erfc_fraction())
+        } else {
+            self.erfc_fraction()
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use super::consts::{LN_2, LOG2_E};
+use super::Wide;
+
+/// Halvings applied to the argument before summing the Taylor series.
+const HALVINGS: i32 = 8;
+
+impl Wide {
+    /// Returns `true` once `term` no longer affects a sum of magnitude `sum`.
+    #[inline]
+    pub(crate) fn negligible(term: Self, sum: Self) -> bool {
+        term.is_zero() || (!sum.is_zero() && term.ilogb() < sum.ilogb() - 260)
+    }
+
+    /// Returns `e^x - 1` for `|x| <= 1/2` with full relative precision.
+    ///
+    /// The argument is halved before summing the Taylor series, then the
+    /// result is doubled back with `e^2x - 1 = (e^x - 1) * (e^x + 1)`, which
+    /// never cancels.
+    fn exp_m1_small(self) -> Self {
+        let x = self.scale(-HALVINGS);
+
+        let mut sum = x;
+        let mut term = x;
+        for n in 2.. {
+            term = term.mul(x).div_u64(n);
+            sum = sum.add(term);
+            if Self::negligible(term, sum) {
+                break;
+            }
+        }
+
+        let two = Self::ONE.scale(1);
+        for _ in 0..HALVINGS {
+            sum = sum.mul(sum.add(two));
+        }
+
+        sum
+    }
+
+    /// Splits `e^x` into `2^k * (1 + m)` with `|m| < 1/2`.
+    ///
+    /// `|x|` must be below `2^20`.
+    fn exp_split(self) -> (i32, Self) {
+        let k = self.mul(LOG2_E).round_i64();
+        let r = self.sub(LN_2.mul(Self::from_i64(k)));
+        (k as i32, r.exp_m1_small())
+    }
+
+    /// Returns `e^x`. `|x|` must be below `2^20`.
+    pub fn exp(self) -> Self {
+        let (k, m) = self.exp_split();
+        Self::ONE.add(m).scale(k)
+    }
+
+    /// Returns `2^x`. `|x|` must be below `2^20`.
+    pub fn exp2(self) -> Self {
+        // The fraction is exact, so integral arguments give exact powers
+        let k = self.round_i64();
+        let f = self.sub(Self::from_i64(k));
+        let m = f.mul(LN_2).exp_m1_small();
+        Self::ONE.add(m).scale(k as i32)
+    }
+
+    /// Returns `e^x - 1` with full relative precision near zero.
+    ///
+    /// `|x|` must be below `2^20`.
+    pub fn exp_m1(self) -> Self {
+        if self.cmp_abs(Self::ONE.scale(-1)).is_le() {
+            return self.exp_m1_small();
+        }
+
+        // Away from zero there is no cancellation to worry about
+        let (k, m) = self.exp_split();
+        Self::ONE.add(m).scale(k).sub(Self::ONE)
+    }
+}
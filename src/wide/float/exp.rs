@@ -0,0 +1,74 @@
+/// Implements `exp`, `exp2` and `exp_m1` for `$float`, whose arguments
+/// of at least `2^$range` in magnitude overflow or underflow all three.
+macro_rules! exp {
+    ($float:ident, range $range:literal) => {
+        mod exp {
+            use super::$float;
+            use $crate::wide::Wide;
+            use $crate::IeeeFloat;
+
+            const RANGE: i32 = $range;
+
+            impl $float {
+                /// Returns `e^(self)`, the exponential function.
+                ///
+                /// `exp(±0)` is `1`, `exp(-∞)` is `+0` and `exp(+∞)` is `+∞`.
+                #[inline]
+                pub fn exp(self) -> Self {
+                    if self.is_nan() {
+                        self.quiet()
+                    } else if self.ilogb() >= RANGE {
+                        Self::exp_limit(self)
+                    } else {
+                        Self::from_wide(self.to_wide().exp())
+                    }
+                }
+
+                /// Returns `2^(self)`.
+                ///
+                /// `exp2(±0)` is `1`, `exp2(-∞)` is `+0` and `exp2(+∞)` is `+∞`. Integral
+                /// arguments in range give exact powers of two.
+                #[inline]
+                pub fn exp2(self) -> Self {
+                    if self.is_nan() {
+                        self.quiet()
+                    } else if self.ilogb() >= RANGE {
+                        Self::exp_limit(self)
+                    } else {
+                        Self::from_wide(self.to_wide().exp2())
+                    }
+                }
+
+                /// Returns `e^(self) - 1`, accurate even when `self` is close to zero.
+                ///
+                /// `exp_m1(±0)` is `±0`, `exp_m1(-∞)` is `-1` and `exp_m1(+∞)` is `+∞`.
+                #[inline]
+                pub fn exp_m1(self) -> Self {
+                    if self.is_nan() {
+                        self.quiet()
+                    } else if self.is_zero() {
+                        self
+                    } else if self.ilogb() >= RANGE {
+                        if self.is_sign_negative() {
+                            Self::from_wide(Wide::ONE.neg())
+                        } else {
+                            Self::INFINITY
+                        }
+                    } else {
+                        Self::from_wide(self.to_wide().exp_m1())
+                    }
+                }
+
+                /// The result of `exp` or `exp2` for arguments beyond the finite range.
+                #[inline]
+                fn exp_limit(x: Self) -> Self {
+                    if x.is_sign_negative() {
+                        Self(0)
+                    } else {
+                        Self::INFINITY
+                    }
+                }
+            }
+        }
+    };
+}
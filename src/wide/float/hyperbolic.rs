@@ -0,0 +1,147 @@
+/// Implements the hyperbolic functions and their inverses for `$float`.
+///
+/// `sinh` and `cosh` overflow from an exponent of `$overflow` on, and
+/// `tanh` is within half an ULP of ±1 from an exponent of `$saturate` on.
+macro_rules! hyperbolic {
+    ($float:ident, overflow $overflow:literal, saturate $saturate:literal) => {
+        mod hyperbolic {
+            use core::cmp::Ordering;
+
+            use super::$float;
+            use $crate::wide::Wide;
+            use $crate::IeeeFloat;
+
+            impl $float {
+                /// Computes the hyperbolic sine of `self`.
+                ///
+                /// `sinh(±0)` is `±0` and `sinh(±∞)` is `±∞`.
+                #[inline]
+                pub fn sinh(self) -> Self {
+                    if self.is_nan() {
+                        self.quiet()
+                    } else if self.is_zero() || self.is_infinite() {
+                        self
+                    } else if self.ilogb() >= $overflow {
+                        Self(Self::INFINITY.0 | (self.0 & Self::SIGN_MASK))
+                    } else {
+                        // sinh(x) = (m + m / (m + 1)) / 2 with m = e^|x| - 1, exact near zero
+                        let x = self.to_wide();
+                        let m = x.abs().exp_m1();
+                        let sinh = m.add(m.div(m.add(Wide::ONE))).scale(-1);
+                        Self::from_wide(Wide::from_sign(x.neg, sinh))
+                    }
+                }
+
+                /// Computes the hyperbolic cosine of `self`.
+                ///
+                /// `cosh(±0)` is `1` and `cosh(±∞)` is `+∞`.
+                #[inline]
+                pub fn cosh(self) -> Self {
+                    if self.is_nan() {
+                        self.quiet()
+                    } else if self.is_infinite() || self.ilogb() >= $overflow {
+                        Self::INFINITY
+                    } else {
+                        let e = self.to_wide().abs().exp();
+                        Self::from_wide(e.add(e.recip()).scale(-1))
+                    }
+                }
+
+                /// Computes the hyperbolic tangent of `self`.
+                ///
+                /// `tanh(±0)` is `±0` and `tanh(±∞)` is `±1`.
+                #[inline]
+                pub fn tanh(self) -> Self {
+                    if self.is_nan() {
+                        return self.quiet();
+                    } else if self.is_zero() {
+                        return self;
+                    }
+
+                    let neg = self.is_sign_negative();
+                    let tanh = if self.ilogb() >= $saturate {
+                        // Within half an ULP of ±1
+                        Wide::ONE
+                    } else {
+                        // tanh(x) = m / (m + 2) with m = e^(2|x|) - 1
+                        let m = self.to_wide().abs().scale(1).exp_m1();
+                        m.div(m.add(Wide::ONE.scale(1)))
+                    };
+
+                    Self::from_wide(Wide::from_sign(neg, tanh))
+                }
+
+                /// Computes the inverse hyperbolic sine of `self`.
+                ///
+                /// `asinh(±0)` is `±0` and `asinh(±∞)` is `±∞`.
+                #[inline]
+                pub fn asinh(self) -> Self {
+                    if self.is_nan() {
+                        return self.quiet();
+                    } else if self.is_zero() || self.is_infinite() {
+                        return self;
+                    }
+
+                    // asinh(x) = ln_1p(|x| + x^2 / (1 + sqrt(1 + x^2)))
+                    let one = Wide::ONE;
+                    let x = self.to_wide();
+                    let x2 = x.mul(x);
+                    let t = x.abs().add(x2.div(one.add(one.add(x2).sqrt())));
+                    Self::from_wide(Wide::from_sign(x.neg, t.ln_1p()))
+                }
+
+                /// Computes the inverse hyperbolic cosine of `self`.
+                ///
+                /// `acosh(1)` is `+0`, `acosh(+∞)` is `+∞` and arguments below `1` give
+                /// NaN.
+                #[inline]
+                pub fn acosh(self) -> Self {
+                    if self.is_nan() {
+                        return self.quiet();
+                    } else if self.0 == Self::INFINITY.0 {
+                        return self;
+                    } else if self.is_infinite() {
+                        return Self::NAN;
+                    }
+
+                    // acosh(x) = ln_1p(t + sqrt(t * (t + 2))) with t = x - 1
+                    let t = self.to_wide().sub(Wide::ONE);
+                    if t.neg && !t.is_zero() {
+                        return Self::NAN;
+                    }
+
+                    let root = t.mul(t.add(Wide::ONE.scale(1))).sqrt();
+                    Self::from_wide(t.add(root).ln_1p())
+                }
+
+                /// Computes the inverse hyperbolic tangent of `self`.
+                ///
+                /// `atanh(±0)` is `±0`, `atanh(±1)` is `±∞` and arguments outside
+                /// `[-1, 1]` give NaN.
+                #[inline]
+                pub fn atanh(self) -> Self {
+                    if self.is_nan() {
+                        return self.quiet();
+                    } else if self.is_zero() {
+                        return self;
+                    } else if self.is_infinite() {
+                        return Self::NAN;
+                    }
+
+                    let one = Wide::ONE;
+                    let x = self.to_wide();
+                    match x.cmp_abs(one) {
+                        Ordering::Greater => Self::NAN,
+                        Ordering::Equal => Self(Self::INFINITY.0 | (self.0 & Self::SIGN_MASK)),
+                        Ordering::Less => {
+                            // atanh(x) = ln_1p(2|x| / (1 - |x|)) / 2
+                            let a = x.abs();
+                            let t = a.scale(1).div(one.sub(a)).ln_1p().scale(-1);
+                            Self::from_wide(Wide::from_sign(x.neg, t))
+                        }
+                    }
+                }
+            }
+        }
+    };
+}
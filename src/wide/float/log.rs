@@ -0,0 +1,97 @@
+/// Implements the logarithms for `$float`.
+macro_rules! log {
+    ($float:ident) => {
+        mod log {
+            use core::cmp::Ordering;
+
+            use super::$float;
+            use $crate::wide::{Wide, LOG10_2, LOG10_E, LOG2_E};
+
+            impl $float {
+                /// Returns the result of a logarithm if `self` is a special case.
+                ///
+                /// `log(±0)` is `-∞`, `log(+∞)` is `+∞`, and negative arguments give
+                /// NaN. NaN arguments are returned quieted.
+                #[inline]
+                fn log_special(self) -> Option<Self> {
+                    if self.is_nan() {
+                        Some(self.quiet())
+                    } else if self.is_zero() {
+                        Some(Self::NEG_INFINITY)
+                    } else if self.is_sign_negative() {
+                        Some(Self::NAN)
+                    } else if self.is_infinite() {
+                        Some(self)
+                    } else {
+                        None
+                    }
+                }
+
+                /// Returns the natural logarithm of `self`.
+                ///
+                /// `ln(1)` is `+0`, `ln(±0)` is `-∞`, `ln(+∞)` is `+∞`, and negative
+                /// arguments give NaN.
+                #[inline]
+                pub fn ln(self) -> Self {
+                    match self.log_special() {
+                        Some(special) => special,
+                        None => Self::from_wide(self.to_wide().ln()),
+                    }
+                }
+
+                /// Returns the base 2 logarithm of `self`.
+                ///
+                /// Special cases are as for [`Self::ln`]. Exact powers of two give exact
+                /// results.
+                #[inline]
+                pub fn log2(self) -> Self {
+                    match self.log_special() {
+                        Some(special) => special,
+                        None => {
+                            let (k, l) = self.to_wide().ln_split();
+                            Self::from_wide(Wide::from_i64(k as i64).add(l.mul(LOG2_E)))
+                        }
+                    }
+                }
+
+                /// Returns the base 10 logarithm of `self`.
+                ///
+                /// Special cases are as for [`Self::ln`]. Exact powers of ten give exact
+                /// results.
+                #[inline]
+                pub fn log10(self) -> Self {
+                    match self.log_special() {
+                        Some(special) => special,
+                        None => {
+                            let (k, l) = self.to_wide().ln_split();
+                            let k = Wide::from_i64(k as i64).mul(LOG10_2);
+                            Self::from_wide(k.add(l.mul(LOG10_E)))
+                        }
+                    }
+                }
+
+                /// Returns `ln(1 + self)`, accurate even when `self` is close to zero.
+                ///
+                /// `ln_1p(±0)` is `±0`, `ln_1p(-1)` is `-∞`, `ln_1p(+∞)` is `+∞`, and
+                /// arguments below `-1` give NaN.
+                #[inline]
+                pub fn ln_1p(self) -> Self {
+                    if self.is_nan() {
+                        self.quiet()
+                    } else if self.is_zero() || self.0 == Self::INFINITY.0 {
+                        self
+                    } else if self.is_infinite() {
+                        Self::NAN
+                    } else {
+                        let x = self.to_wide();
+                        match x.cmp(Wide::ONE.neg()) {
+                            Ordering::Less => Self::NAN,
+                            Ordering::Equal => Self::NEG_INFINITY,
+                            Ordering::Greater => Self::from_wide(x.ln_1p()),
+                        }
+                    }
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,54 @@
+//! The elementary functions of the binary formats, written once.
+//!
+//! The math module of each format invokes [`elementary!`] with its layout
+//! and thresholds. Each function handles the special cases of C23 Annex F
+//! directly and evaluates everything else in 256-bit [`Wide`](super::Wide)
+//! arithmetic, rounding once at the end.
+
+#[macro_use]
+mod exp;
+#[macro_use]
+mod hyperbolic;
+#[macro_use]
+mod log;
+#[macro_use]
+mod trig;
+
+/// Implements the conversions to and from `Wide` and the elementary
+/// functions for `$float`, stored in `$bits` with `$exp` exponent and
+/// `$mant` mantissa bits. `range`, `overflow` and `saturate` are the
+/// exponents beyond which [`exp!`] and [`hyperbolic!`] return their limits.
+macro_rules! elementary {
+    (
+        $float:ident: bits $bits:ty, exp $exp:literal, mant $mant:literal,
+            range $range:literal, overflow $overflow:literal, saturate $saturate:literal
+    ) => {
+        const EXP_BITS: u32 = $exp;
+        const MANT_BITS: u32 = $mant;
+
+        impl $float {
+            /// Converts a finite value to `Wide` exactly.
+            #[inline]
+            fn to_wide(self) -> $crate::wide::Wide {
+                $crate::wide::Wide::from_bits(self.0 as u128, EXP_BITS, MANT_BITS)
+            }
+
+            /// Rounds a `Wide` to nearest, ties to even.
+            #[inline]
+            fn from_wide(w: $crate::wide::Wide) -> Self {
+                Self(w.to_bits(EXP_BITS, MANT_BITS) as $bits)
+            }
+
+            /// Returns `true` for both zeros.
+            #[inline]
+            pub(super) const fn is_zero(self) -> bool {
+                self.0 << 1 == 0
+            }
+        }
+
+        exp!($float, range $range);
+        hyperbolic!($float, overflow $overflow, saturate $saturate);
+        log!($float);
+        trig!($float);
+    };
+}
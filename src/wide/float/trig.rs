@@ -0,0 +1,169 @@
+/// Implements the trigonometric functions and their inverses for `$float`.
+macro_rules! trig {
+    ($float:ident) => {
+        mod trig {
+            use core::cmp::Ordering;
+
+            use super::$float;
+            use $crate::wide::{Wide, FRAC_PI_2, PI};
+
+            impl $float {
+                /// Computes the sine of `self` (in radians).
+                ///
+                /// `sin(±0)` is `±0` and `sin(±∞)` is NaN. Arguments of any magnitude are
+                /// reduced exactly.
+                #[inline]
+                pub fn sin(self) -> Self {
+                    self.sin_cos().0
+                }
+
+                /// Computes the cosine of `self` (in radians).
+                ///
+                /// `cos(±0)` is `1` and `cos(±∞)` is NaN.
+                #[inline]
+                pub fn cos(self) -> Self {
+                    self.sin_cos().1
+                }
+
+                /// Computes the sine and cosine of `self` at once.
+                #[inline]
+                pub fn sin_cos(self) -> (Self, Self) {
+                    if self.is_nan() {
+                        (self.quiet(), self.quiet())
+                    } else if self.is_infinite() {
+                        (Self::NAN, Self::NAN)
+                    } else if self.is_zero() {
+                        (self, Self::from_wide(Wide::ONE))
+                    } else {
+                        let (sin, cos) = self.to_wide().sin_cos();
+                        (Self::from_wide(sin), Self::from_wide(cos))
+                    }
+                }
+
+                /// Computes the tangent of `self` (in radians).
+                ///
+                /// `tan(±0)` is `±0` and `tan(±∞)` is NaN.
+                #[inline]
+                pub fn tan(self) -> Self {
+                    if self.is_nan() {
+                        self.quiet()
+                    } else if self.is_infinite() {
+                        Self::NAN
+                    } else if self.is_zero() {
+                        self
+                    } else {
+                        let (sin, cos) = self.to_wide().sin_cos();
+                        Self::from_wide(sin.div(cos))
+                    }
+                }
+
+                /// Computes the arcsine of `self`, in radians in `[-pi/2, pi/2]`.
+                ///
+                /// `asin(±0)` is `±0` and arguments outside `[-1, 1]` give NaN.
+                #[inline]
+                pub fn asin(self) -> Self {
+                    if self.is_nan() {
+                        return self.quiet();
+                    } else if self.is_zero() {
+                        return self;
+                    } else if self.is_infinite() {
+                        return Self::NAN;
+                    }
+
+                    let one = Wide::ONE;
+                    let x = self.to_wide();
+                    match x.cmp_abs(one) {
+                        Ordering::Greater => Self::NAN,
+                        Ordering::Equal => Self::from_wide(Wide::from_sign(x.neg, FRAC_PI_2)),
+                        Ordering::Less => {
+                            // asin(x) = atan(x / sqrt((1 - x) * (1 + x))), exact near ±1
+                            let cos = one.sub(x).mul(one.add(x)).sqrt();
+                            Self::from_wide(x.div(cos).atan())
+                        }
+                    }
+                }
+
+                /// Computes the arccosine of `self`, in radians in `[0, pi]`.
+                ///
+                /// `acos(1)` is `+0` and arguments outside `[-1, 1]` give NaN.
+                #[inline]
+                pub fn acos(self) -> Self {
+                    if self.is_nan() {
+                        return self.quiet();
+                    } else if self.is_infinite() {
+                        return Self::NAN;
+                    }
+
+                    let one = Wide::ONE;
+                    let x = self.to_wide();
+                    match x.cmp_abs(one) {
+                        Ordering::Greater => Self::NAN,
+                        Ordering::Equal if x.neg => Self::from_wide(PI),
+                        _ => {
+                            // acos(x) = 2 * atan(sqrt((1 - x) / (1 + x)))
+                            let t = one.sub(x).div(one.add(x)).sqrt();
+                            Self::from_wide(t.atan().scale(1))
+                        }
+                    }
+                }
+
+                /// Computes the arctangent of `self`, in radians in `[-pi/2, pi/2]`.
+                ///
+                /// `atan(±0)` is `±0` and `atan(±∞)` is `±pi/2`.
+                #[inline]
+                pub fn atan(self) -> Self {
+                    if self.is_nan() {
+                        self.quiet()
+                    } else if self.is_zero() {
+                        self
+                    } else if self.is_infinite() {
+                        Self::from_wide(Wide::from_sign(self.is_sign_negative(), FRAC_PI_2))
+                    } else {
+                        Self::from_wide(self.to_wide().atan())
+                    }
+                }
+
+                /// Computes the four quadrant arctangent of `self` (`y`) and `other`
+                /// (`x`), in radians in `[-pi, pi]`.
+                ///
+                /// Special cases follow C23 Annex F: zeros and infinities select the
+                /// angle by their signs, for example `atan2(±0, -0)` is `±pi` and
+                /// `atan2(±∞, +∞)` is `±pi/4`.
+                pub fn atan2(self, other: Self) -> Self {
+                    if self.is_nan() || other.is_nan() {
+                        return self.propagate_nan(other);
+                    }
+
+                    let neg = self.is_sign_negative();
+                    let quarter = FRAC_PI_2.scale(-1);
+
+                    let angle = if self.is_infinite() {
+                        if other.is_infinite() {
+                            // pi/4 or 3pi/4
+                            if other.is_sign_negative() {
+                                PI.sub(quarter)
+                            } else {
+                                quarter
+                            }
+                        } else {
+                            FRAC_PI_2
+                        }
+                    } else if self.is_zero() || other.is_infinite() {
+                        // The angle is that of the x axis in the direction of x
+                        if other.is_sign_negative() {
+                            PI
+                        } else {
+                            Wide::ZERO
+                        }
+                    } else if other.is_zero() {
+                        FRAC_PI_2
+                    } else {
+                        self.to_wide().abs().atan2(other.to_wide())
+                    };
+
+                    Self::from_wide(Wide::from_sign(neg, angle))
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,172 @@
+use super::consts::LN_SQRT_2PI;
+use super::Wide;
+
+/// Magnitudes of the Bernoulli numbers `B_2`, `B_4`, ..., `B_58` as
+/// numerator and denominator. Their signs alternate, starting positive.
+const BERNOULLI: [(u128, u64); 29] = [
+    (1, 6),
+    (1, 30),
+    (1, 42),
+    (1, 30),
+    (5, 66),
+    (691, 2730),
+    (7, 6),
+    (3617, 510),
+    (43867, 798),
+    (174611, 330),
+    (854513, 138),
+    (236364091, 2730),
+    (8553103, 6),
+    (23749461029, 870),
+    (8615841276005, 14322),
+    (7709321041217, 510),
+    (2577687858367, 6),
+    (26315271553053477373, 1919190),
+    (2929993913841559, 6),
+    (261082718496449122051, 13530),
+    (1520097643918070802691, 1806),
+    (27833269579301024235023, 690),
+    (596451111593912163277961, 282),
+    (5609403368997817686249127547, 46410),
+    (495057205241079648212477525, 66),
+    (801165718135489957347924991853, 1590),
+    (29149963634884862421418123812691, 798),
+    (2479392929313226753685415739663229, 870),
+    (84483613348880041862046775994036021, 354),
+];
+
+/// Arguments from which Stirling's series reaches full precision within
+/// the terms of [`BERNOULLI`].
+const STIRLING: i64 = 80;
+
+/// Returns the `k`th correction term of Stirling's series,
+/// `B_2k / (2k (2k - 1)) * power`.
+#[inline]
+fn stirling_term(k: usize, power: Wide) -> Wide {
+    let (num, den) = BERNOULLI[k - 1];
+    let k = k as u64;
+    Wide::from_u128(k % 2 == 0, num, 0)
+        .mul(power)
+        .div_u64(den * (2 * k) * (2 * k - 1))
+}
+
+impl Wide {
+    /// Returns `ln Γ(y)` for `y >= 80` from Stirling's series,
+    /// `(y - 1/2) ln(y) - y + ln(sqrt(2 pi)) + sum B_2k / (2k (2k - 1) y^(2k - 1))`.
+    fn ln_gamma_stirling(self) -> Self {
+        let half = Self::ONE.scale(-1);
+        let mut sum = self.sub(half).mul(self.ln()).sub(self).add(LN_SQRT_2PI);
+
+        let inv = self.recip();
+        let inv2 = inv.mul(inv);
+        let mut power = inv;
+        for k in 1..=BERNOULLI.len() {
+            let term = stirling_term(k, power);
+            sum = sum.add(term);
+            if Self::negligible(term, sum) {
+                break;
+            }
+            power = power.mul(inv2);
+        }
+
+        sum
+    }
+
+    /// Returns `ln Γ(a + eps)` for `a` of 1 or 2, where `Γ(a) = 1`, with full
+    /// relative precision however small `eps` is.
+    ///
+    /// With `y = 80`, `ln Γ(a + eps)` is `ln Γ(y + eps) - ln Γ(y)` less
+    /// `ln(1 + eps/j)` for `j` from `a` to `y - 1`. Both parts are expanded in
+    /// `eps` so that `ln Γ(y)` cancels exactly rather than numerically.
+    fn ln_gamma_offset(a: i64, eps: Self) -> Self {
+        let one = Self::ONE;
+        let y = Self::from_i64(STIRLING);
+        let u = eps.div(y);
+
+        // The leading terms: (y - 1/2) ln(1 + u) + eps (ln(y + eps) - 1)
+        let half = one.scale(-1);
+        let mut diff = y.sub(half).mul(u.ln_1p());
+        diff = diff.add(eps.mul(y.add(eps).ln().sub(one)));
+
+        // Each correction term changes by y^-m ((1 + u)^-m - 1) for odd m.
+        // With v = (1 + u)^-2, r_m = (1 + u)^-m - 1 steps as
+        // r_(m + 2) = r_m v + (v - 1), where every product has the same sign.
+        let w = one.add(u).recip();
+        let v = w.mul(w);
+        let v1 = u.mul(u.add(one.scale(1))).mul(v).neg();
+        let mut r = u.mul(w).neg();
+
+        let inv = y.recip();
+        let inv2 = inv.mul(inv);
+        let mut power = inv;
+        for k in 1..=BERNOULLI.len() {
+            let term = stirling_term(k, power.mul(r));
+            diff = diff.add(term);
+            if Self::negligible(term, diff) {
+                break;
+            }
+            power = power.mul(inv2);
+            r = r.mul(v).add(v1);
+        }
+
+        // prod (1 + eps/j) - 1, accumulated as q + w + q w so that it never
+        // cancels
+        let mut q = Self::ZERO;
+        for j in a..STIRLING {
+            let w = eps.div_u64(j as u64);
+            q = q.add(w).add(q.mul(w));
+        }
+
+        diff.sub(q.ln_1p())
+    }
+
+    /// Shifts `x` up to `y = x + n >= 80` for Stirling's series.
+    ///
+    /// Returns `y` and `p = x (x + 1) ... (x + n - 1)`, so that
+    /// `Γ(x) = Γ(y) / p`.
+    fn gamma_shift(self) -> (Self, Self) {
+        let limit = Self::from_i64(STIRLING);
+        let mut y = self;
+        let mut p = Self::ONE;
+        while y.cmp(limit).is_lt() {
+            p = p.mul(y);
+            y = y.add(Self::ONE);
+        }
+
+        (y, p)
+    }
+
+    /// Returns `ln Γ(x)` for positive `x`.
+    pub fn ln_gamma(self) -> Self {
+        // Near the zeros at 1 and 2 the result needs its own expansion to
+        // keep its relative precision
+        for a in [1, 2] {
+            let eps = self.sub(Self::from_i64(a));
+            if eps.is_zero() {
+                return Self::ZERO;
+            }
+            if eps.ilogb() < -2 {
+                return Self::ln_gamma_offset(a, eps);
+            }
+        }
+
+        let (y, p) = self.gamma_shift();
+        y.ln_gamma_stirling().sub(p.ln())
+    }
+
+    /// Returns `Γ(x)` for `x` in `(0, 4096)`.
+    pub fn gamma(self) -> Self {
+        // Factorials up to 59! are exact in 256 bits, so multiplying them
+        // out also rounds the ones that fall on a midpoint correctly
+        if self.parity().is_some() && self.cmp(Self::from_i64(60)).is_le() {
+            let mut p = Self::ONE;
+            for k in 2..self.trunc_u64() {
+                p = p.mul(Self::from_i64(k as i64));
+            }
+            return p;
+        }
+
+        let (y, p) = self.gamma_shift();
+        y.ln_gamma_stirling().exp().div(p)
+    }
+}
@@ -0,0 +1,60 @@
+use super::consts::{LN_2, SQRT_2};
+use super::Wide;
+
+impl Wide {
+    /// Returns `2 * atanh(z) = ln((1 + z) / (1 - z))` for small `|z|`.
+    fn atanh2(self) -> Self {
+        let z2 = self.mul(self);
+
+        let mut sum = self;
+        let mut power = self;
+        for n in 1.. {
+            power = power.mul(z2);
+            let term = power.div_u64(2 * n + 1);
+            sum = sum.add(term);
+            if Self::negligible(term, sum) {
+                break;
+            }
+        }
+
+        sum.scale(1)
+    }
+
+    /// Splits `ln(x)` into `k * ln(2) + l`, returning `(k, l)`.
+    ///
+    /// `x` must be positive. The reduced logarithm `l` lies within
+    /// `±ln(2) / 2`, and has full relative precision even when `x` is
+    /// close to one.
+    pub fn ln_split(self) -> (i32, Self) {
+        let mut k = self.ilogb();
+        let mut m = self.scale(-k);
+        if m.cmp_abs(SQRT_2).is_gt() {
+            m = m.scale(-1);
+            k += 1;
+        }
+
+        // m - 1 is exact since both operands are within a factor of two
+        let z = m.sub(Self::ONE).div(m.add(Self::ONE));
+        (k, z.atanh2())
+    }
+
+    /// Returns the natural logarithm of a positive value.
+    pub fn ln(self) -> Self {
+        let (k, l) = self.ln_split();
+        LN_2.mul(Self::from_i64(k as i64)).add(l)
+    }
+
+    /// Returns `ln(1 + x)` for `x > -1`, with full relative precision near
+    /// zero.
+    pub fn ln_1p(self) -> Self {
+        let one = Self::ONE;
+
+        // Near zero, 1 + x would round away the low bits of x
+        if self.cmp(SQRT_2.sub(one)).is_lt() && self.cmp(SQRT_2.scale(-1).sub(one)).is_gt() {
+            let z = self.div(self.add(one.scale(1)));
+            return z.atanh2();
+        }
+
+        one.add(self).ln()
+    }
+}
@@ -0,0 +1,522 @@
+//! Extended precision arithmetic for evaluating math functions.
+//!
+//! A [`Wide`] is a binary floating point number with a 256-bit significand
+//! and an `i32` exponent. Math functions are evaluated in `Wide` and rounded
+//! once into the destination format. With more than twice the precision of
+//! `binary128`, the result is correctly rounded unless the exact value lies
+//! extraordinarily close to a rounding boundary, and is always within one ULP.
+
+mod bessel;
+mod consts;
+mod erf;
+mod exp;
+#[macro_use]
+mod float;
+mod gamma;
+mod log;
+mod trig;
+
+pub(crate) use consts::*;
+
+use core::cmp::Ordering;
+
+use crate::round::round_pack;
+
+/// A 256-bit significand as little endian 64-bit limbs.
+type Limbs = [u64; 4];
+
+/// Returns the number of leading zero bits of a little endian limb array.
+#[inline]
+fn leading_zeros<const N: usize>(x: &[u64; N]) -> u32 {
+    let mut zeros = 0;
+
+    for limb in x.iter().rev() {
+        zeros += limb.leading_zeros();
+        if *limb != 0 {
+            break;
+        }
+    }
+
+    zeros
+}
+
+/// Shifts a little endian limb array left by `s` bits, discarding overflow.
+#[inline]
+fn shl<const N: usize>(x: [u64; N], s: u32) -> [u64; N] {
+    let words = (s / 64) as usize;
+    let bits = s % 64;
+    let mut out = [0u64; N];
+
+    for i in (words..N).rev() {
+        out[i] = x[i - words] << bits;
+        if bits != 0 && i > words {
+            out[i] |= x[i - words - 1] >> (64 - bits);
+        }
+    }
+
+    out
+}
+
+/// Shifts a little endian limb array right by `s` bits.
+#[inline]
+fn shr<const N: usize>(x: [u64; N], s: u32) -> [u64; N] {
+    let words = (s / 64) as usize;
+    let bits = s % 64;
+    let mut out = [0u64; N];
+
+    for i in 0..N.saturating_sub(words) {
+        out[i] = x[i + words] >> bits;
+        if bits != 0 && i + words + 1 < N {
+            out[i] |= x[i + words + 1] << (64 - bits);
+        }
+    }
+
+    out
+}
+
+/// Shifts a little endian limb array right by `s` bits, OR-ing every bit
+/// shifted out into the least significant bit.
+#[inline]
+fn shr_sticky<const N: usize>(x: [u64; N], s: u32) -> [u64; N] {
+    let mut out = shr(x, s);
+
+    let lost = if s as usize >= 64 * N {
+        x.iter().any(|&limb| limb != 0)
+    } else {
+        shl(out, s) != x
+    };
+
+    out[0] |= lost as u64;
+    out
+}
+
+/// Adds two little endian limb arrays, returning the carry out.
+#[inline]
+fn add<const N: usize>(a: [u64; N], b: [u64; N]) -> ([u64; N], bool) {
+    let mut out = [0u64; N];
+    let mut carry = false;
+
+    for i in 0..N {
+        let (sum, c1) = a[i].overflowing_add(b[i]);
+        let (sum, c2) = sum.overflowing_add(carry as u64);
+        out[i] = sum;
+        carry = c1 || c2;
+    }
+
+    (out, carry)
+}
+
+/// Subtracts `b` from `a`, which must not be smaller.
+#[inline]
+fn sub<const N: usize>(a: [u64; N], b: [u64; N]) -> [u64; N] {
+    let mut out = [0u64; N];
+    let mut borrow = false;
+
+    for i in 0..N {
+        let (diff, b1) = a[i].overflowing_sub(b[i]);
+        let (diff, b2) = diff.overflowing_sub(borrow as u64);
+        out[i] = diff;
+        borrow = b1 || b2;
+    }
+
+    out
+}
+
+/// Compares two little endian limb arrays.
+#[inline]
+fn cmp<const N: usize>(a: &[u64; N], b: &[u64; N]) -> Ordering {
+    a.iter().rev().cmp(b.iter().rev())
+}
+
+/// An extended precision binary floating point number.
+///
+/// The value is `(-1)^neg * mant * 2^exp`, where `mant` is a 256-bit
+/// integer with its most significant bit set unless the value is zero.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Wide {
+    pub neg: bool,
+    pub exp: i32,
+    pub mant: Limbs,
+}
+
+impl Wide {
+    pub const ZERO: Self = Self {
+        neg: false,
+        exp: 0,
+        mant: [0; 4],
+    };
+
+    pub const ONE: Self = Self {
+        neg: false,
+        exp: -255,
+        mant: [0, 0, 0, 1 << 63],
+    };
+
+    /// Creates a value from a 256-bit constant given as `(hi, lo)` halves
+    /// and the exponent of its least significant bit.
+    pub const fn from_const(neg: bool, hi: u128, lo: u128, exp: i32) -> Self {
+        Self {
+            neg,
+            exp,
+            mant: [lo as u64, (lo >> 64) as u64, hi as u64, (hi >> 64) as u64],
+        }
+    }
+
+    /// Creates `(-1)^neg * mant * 2^exp`.
+    #[inline]
+    pub fn from_u128(neg: bool, mant: u128, exp: i32) -> Self {
+        Self::normalize(neg, exp, [0, mant as u64, (mant >> 64) as u64, 0, 0])
+    }
+
+    /// Creates a value from an integer.
+    #[inline]
+    pub fn from_i64(value: i64) -> Self {
+        Self::from_u128(value < 0, value.unsigned_abs() as u128, 0)
+    }
+
+    /// Decodes a finite value of a binary format with `exp_bits` exponent
+    /// bits and `mant_bits` stored mantissa bits.
+    #[inline]
+    pub fn from_bits(bits: u128, exp_bits: u32, mant_bits: u32) -> Self {
+        let neg = (bits >> (exp_bits + mant_bits)) & 1 != 0;
+        let bias = (1i32 << (exp_bits - 1)) - 1;
+        let biased = ((bits >> mant_bits) & ((1 << exp_bits) - 1)) as i32;
+        let mant = bits & ((1 << mant_bits) - 1);
+
+        if biased == 0 {
+            Self::from_u128(neg, mant, 1 - bias - mant_bits as i32)
+        } else {
+            let mant = mant | (1 << mant_bits);
+            Self::from_u128(neg, mant, biased - bias - mant_bits as i32)
+        }
+    }
+
+    /// Rounds to nearest, ties to even, into a binary format with `exp_bits`
+    /// exponent bits and `mant_bits` stored mantissa bits.
+    #[inline]
+    pub fn to_bits(self, exp_bits: u32, mant_bits: u32) -> u128 {
+        let hi = ((self.mant[3] as u128) << 64) | self.mant[2] as u128;
+        let sticky = self.mant[0] != 0 || self.mant[1] != 0;
+
+        // Far beyond any format: saturate so the exponent cannot overflow
+        let exp = self.exp.clamp(-0x40000, 0x40000) + 128;
+        round_pack(self.neg, hi, exp, sticky, exp_bits, mant_bits)
+    }
+
+    /// Rounds like [`Wide::to_bits`], but treats values within a relative
+    /// `2^-tolerance` of a rounding boundary as lying exactly on it.
+    ///
+    /// This is for functions such as `pow` whose exact result may be a
+    /// midpoint between two representable values: the approximation cannot
+    /// tell which side of the midpoint it is on, but the exact value can
+    /// only be the midpoint itself.
+    pub fn to_bits_snapped(self, exp_bits: u32, mant_bits: u32, tolerance: u32) -> u128 {
+        if self.is_zero() {
+            return self.to_bits(exp_bits, mant_bits);
+        }
+
+        let eps = Self::from_u128(false, 1, self.ilogb() - tolerance as i32);
+        let below = self.abs().sub(eps).to_bits(exp_bits, mant_bits);
+        let above = self.abs().add(eps).to_bits(exp_bits, mant_bits);
+
+        let bits = if below == above || below & 1 == 0 {
+            below
+        } else {
+            above
+        };
+
+        bits | ((self.neg as u128) << (exp_bits + mant_bits))
+    }
+
+    /// Normalizes and rounds a wider significand to 256 bits.
+    ///
+    /// The value is `(-1)^neg * x * 2^(exp - 64)`, so the lowest limb of `x`
+    /// lies below the unit in the last place of `exp`.
+    #[inline]
+    fn normalize<const N: usize>(neg: bool, exp: i32, x: [u64; N]) -> Self {
+        let zeros = leading_zeros(&x);
+        if zeros as usize == 64 * N {
+            return Self::ZERO;
+        }
+
+        // Place the most significant bit at the top of the top limb
+        let x = shl(x, zeros);
+        let mut exp = exp - zeros as i32 + 64 * (N as i32 - 5);
+
+        // Round the low limbs into the top four
+        let mut mant = [x[N - 4], x[N - 3], x[N - 2], x[N - 1]];
+        let guard = x[N - 5];
+        let sticky = x[..N - 5].iter().any(|&limb| limb != 0);
+        let half = 1 << 63;
+
+        if guard > half || (guard == half && (sticky || mant[0] & 1 == 1)) {
+            let (sum, carry) = add(mant, [1, 0, 0, 0]);
+            mant = sum;
+            if carry {
+                mant = [0, 0, 0, 1 << 63];
+                exp += 1;
+            }
+        }
+
+        Self { neg, exp, mant }
+    }
+
+    #[inline]
+    pub fn is_zero(self) -> bool {
+        self.mant[3] == 0
+    }
+
+    #[inline]
+    pub fn neg(self) -> Self {
+        Self {
+            neg: !self.neg,
+            ..self
+        }
+    }
+
+    /// Returns `x` with the sign `neg` applied to it.
+    #[inline]
+    pub fn from_sign(neg: bool, x: Self) -> Self {
+        if neg {
+            x.neg()
+        } else {
+            x
+        }
+    }
+
+    #[inline]
+    pub fn abs(self) -> Self {
+        Self { neg: false, ..self }
+    }
+
+    /// Multiplies by `2^k`.
+    #[inline]
+    pub fn scale(self, k: i32) -> Self {
+        if self.is_zero() {
+            return self;
+        }
+
+        Self {
+            exp: self.exp + k,
+            ..self
+        }
+    }
+
+    /// Returns the exponent of the most significant bit.
+    #[inline]
+    pub fn ilogb(self) -> i32 {
+        self.exp + 255
+    }
+
+    /// Compares magnitudes.
+    #[inline]
+    pub fn cmp_abs(self, other: Self) -> Ordering {
+        match (self.is_zero(), other.is_zero()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            (false, false) => self
+                .exp
+                .cmp(&other.exp)
+                .then_with(|| cmp(&self.mant, &other.mant)),
+        }
+    }
+
+    /// Compares values.
+    #[inline]
+    pub fn cmp(self, other: Self) -> Ordering {
+        match (self.is_zero() || !self.neg, other.is_zero() || !other.neg) {
+            (true, true) => self.cmp_abs(other),
+            (false, false) => other.cmp_abs(self),
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+        }
+    }
+
+    pub fn add(self, other: Self) -> Self {
+        if other.is_zero() {
+            return self;
+        }
+        if self.is_zero() {
+            return other;
+        }
+
+        let (a, b) = if self.cmp_abs(other) == Ordering::Less {
+            (other, self)
+        } else {
+            (self, other)
+        };
+
+        // Align with one guard limb and a sticky bit
+        let shift = (a.exp - b.exp) as u32;
+        let x = [0, a.mant[0], a.mant[1], a.mant[2], a.mant[3], 0];
+        let y = [0, b.mant[0], b.mant[1], b.mant[2], b.mant[3], 0];
+        let y = shr_sticky(y, shift.min(64 * 6));
+
+        let sum = if a.neg == b.neg {
+            add(x, y).0
+        } else {
+            sub(x, y)
+        };
+
+        Self::normalize(a.neg, a.exp, sum)
+    }
+
+    #[inline]
+    pub fn sub(self, other: Self) -> Self {
+        self.add(other.neg())
+    }
+
+    pub fn mul(self, other: Self) -> Self {
+        if self.is_zero() || other.is_zero() {
+            return Self::ZERO;
+        }
+
+        let mut p = [0u64; 8];
+        for i in 0..4 {
+            let mut carry = 0u128;
+            for j in 0..4 {
+                let t = self.mant[i] as u128 * other.mant[j] as u128 + p[i + j] as u128 + carry;
+                p[i + j] = t as u64;
+                carry = t >> 64;
+            }
+            p[i + 4] = carry as u64;
+        }
+
+        Self::normalize(self.neg != other.neg, self.exp + other.exp + 64, p)
+    }
+
+    /// Divides by a small nonzero integer.
+    #[inline]
+    pub fn div_u64(self, n: u64) -> Self {
+        if self.is_zero() {
+            return self;
+        }
+
+        // Long division with one extra limb below the significand, plus a
+        // sticky limb for any remainder
+        let n = n as u128;
+        let mut q = [0u64; 6];
+        let mut rem = 0u128;
+        for i in (1..6).rev() {
+            let limb = if i > 1 { self.mant[i - 2] } else { 0 };
+            let cur = (rem << 64) | limb as u128;
+            q[i] = (cur / n) as u64;
+            rem = cur % n;
+        }
+        q[0] = (rem != 0) as u64;
+
+        Self::normalize(self.neg, self.exp - 64, q)
+    }
+
+    /// Returns `1 / self`, which must not be zero.
+    pub fn recip(self) -> Self {
+        // Estimate from the top 64 bits, then refine with Newton's method,
+        // r' = r + r * (1 - x * r), doubling the correct bits each time.
+        let top = self.mant[3];
+        let mut r = Self::from_u128(self.neg, u128::MAX / top as u128, -self.exp - 320);
+
+        for _ in 0..4 {
+            let e = Self::ONE.sub(self.mul(r));
+            r = r.add(r.mul(e));
+        }
+
+        r
+    }
+
+    #[inline]
+    pub fn div(self, other: Self) -> Self {
+        self.mul(other.recip())
+    }
+
+    /// Returns the square root of a nonnegative value.
+    pub fn sqrt(self) -> Self {
+        if self.is_zero() {
+            return self;
+        }
+
+        // Estimate 1/sqrt from the top bits with an even exponent
+        let top = self.mant[3] as u128;
+        let (t, e) = if (self.exp + 192) % 2 == 0 {
+            (top << 64, self.exp + 192 - 64)
+        } else {
+            (top << 63, self.exp + 192 - 63)
+        };
+        let s = isqrt(t);
+        let mut y = Self::from_u128(false, u128::MAX / s, -128 - e / 2);
+
+        // Newton's method for 1/sqrt: y' = y + y * (1 - x * y^2) / 2
+        for _ in 0..4 {
+            let e = Self::ONE.sub(self.mul(y.mul(y)));
+            y = y.add(y.mul(e).scale(-1));
+        }
+
+        // sqrt(x) = x * y, with one final correction
+        let s = self.mul(y);
+        s.add(y.mul(self.sub(s.mul(s))).scale(-1))
+    }
+
+    /// Rounds to the nearest integer, ties away from zero.
+    ///
+    /// The magnitude must be below `2^62`.
+    #[inline]
+    pub fn round_i64(self) -> i64 {
+        let half = Self::ONE.scale(-1);
+        let t = self.abs().add(half).trunc_u64() as i64;
+        if self.neg {
+            -t
+        } else {
+            t
+        }
+    }
+
+    /// Returns the integer part of the magnitude, which must be below `2^64`.
+    #[inline]
+    pub fn trunc_u64(self) -> u64 {
+        if self.is_zero() || self.exp <= -256 {
+            return 0;
+        }
+
+        shr(self.mant, (-self.exp) as u32)[0]
+    }
+
+    /// Classifies an integral value by parity.
+    ///
+    /// Returns `None` if the value is not an integer, otherwise whether it
+    /// is odd.
+    #[inline]
+    pub fn parity(self) -> Option<bool> {
+        if self.is_zero() || self.exp > 0 {
+            return Some(false);
+        }
+        if self.exp <= -256 {
+            return None;
+        }
+
+        let frac = (-self.exp) as u32;
+        let int = shr(self.mant, frac);
+        if shl(int, frac) != self.mant {
+            return None;
+        }
+
+        Some(int[0] & 1 == 1)
+    }
+}
+
+/// Returns the integer square root of `x`.
+#[inline]
+fn isqrt(x: u128) -> u128 {
+    if x == 0 {
+        return 0;
+    }
+
+    // Start above the root so Newton's method descends monotonically
+    let mut r = 1u128 << ((129 - x.leading_zeros()) / 2);
+    loop {
+        let next = (r + x / r) / 2;
+        if next >= r {
+            return r;
+        }
+        r = next;
+    }
+}
@@ -0,0 +1,176 @@
+//! f16ahp tests
+//!
+//! Widening is checked against the encoding decoded by hand over every
+//! encoding, narrowing against a reference rounding in f64, with ARM's
+//! saturation of infinities, NaNs and out-of-range values.
+
+#![cfg(feature = "casting")]
+#![feature(f16, f128)]
+
+#[path = "common/rng.rs"]
+mod rng;
+
+use floats::casting::CastFrom;
+use floats::{f128 as F128, f16 as F16, f16ahp};
+use rng::XorShift;
+
+const MAX: f64 = 131008.0;
+
+/// Decodes an f16ahp by hand: f16's fields, with every exponent finite.
+fn value(bits: u16) -> f64 {
+    let exp = (bits >> 10 & 0x1f) as i32;
+    let mant = (bits & 0x3ff) as f64;
+    let abs = if exp == 0 {
+        mant * 2f64.powi(-24)
+    } else {
+        (mant + 1024.0) * 2f64.powi(exp - 25)
+    };
+    if bits >> 15 != 0 {
+        -abs
+    } else {
+        abs
+    }
+}
+
+/// Rounds a finite f64 to the nearest f16ahp value, ties to even, and
+/// saturates what rounds beyond `MAX`.
+fn reference(x: f64) -> f64 {
+    let exp = ((x.to_bits() >> 52 & 0x7ff) as i32 - 1023).max(-14);
+    let quantum = 2f64.powi(exp - 10);
+    let scaled = x.abs() / quantum;
+    let mut int = scaled.floor();
+    if scaled - int > 0.5 || (scaled - int == 0.5 && int % 2.0 == 1.0) {
+        int += 1.0;
+    }
+    let abs = (int * quantum).min(MAX);
+    abs.copysign(x)
+}
+
+fn narrow(x: f64) -> f16ahp {
+    f16ahp::cast_from(x)
+}
+
+#[test]
+fn widen() {
+    for bits in 0..=u16::MAX {
+        let x = f16ahp::from_bits(bits);
+        let v = value(bits);
+
+        assert_eq!(f64::cast_from(x).to_bits(), v.to_bits(), "{bits:#06x}");
+        assert_eq!(f32::cast_from(x).to_bits(), (v as f32).to_bits());
+        let wide = f128::from_bits(F128::cast_from(x).to_bits());
+        assert_eq!(wide.to_bits(), (v as f128).to_bits());
+
+        // Every encoding round trips
+        assert_eq!(narrow(v).to_bits(), bits);
+        assert_eq!(f16ahp::cast_from(v as f32).to_bits(), bits);
+        assert_eq!(f16ahp::cast_from(F128::cast_from(x)).to_bits(), bits);
+
+        // Below the top exponent, the encoding is f16's
+        assert_eq!(F16::cast_from(x).to_bits(), (v as f16).to_bits());
+        if bits & 0x7c00 != 0x7c00 {
+            assert_eq!(F16::cast_from(x).to_bits(), bits);
+            assert_eq!(f16ahp::cast_from(F16::from_bits(bits)).to_bits(), bits);
+        } else {
+            assert!(F16::cast_from(x).is_infinite());
+        }
+    }
+}
+
+#[test]
+fn round() {
+    let mut rng = XorShift(0x5851_f42d_4c95_7f2d);
+
+    for _ in 0..1_000_000 {
+        let x = f32::from_bits(rng.next() as u32);
+        if x.is_nan() || x.is_infinite() {
+            continue;
+        }
+        let r = reference(x as f64);
+        assert_eq!(
+            f64::cast_from(f16ahp::cast_from(x)).to_bits(),
+            r.to_bits(),
+            "{x:?}"
+        );
+    }
+
+    // Exponents around the format's range, where rounding matters most
+    for _ in 0..1_000_000 {
+        let exp = 1023 - 27 + rng.next() % 46;
+        let x = f64::from_bits((rng.next() & 1) << 63 | exp << 52 | rng.next() >> 12);
+        let r = reference(x);
+        assert_eq!(f64::cast_from(narrow(x)).to_bits(), r.to_bits(), "{x:?}");
+        let wide = F128::from_bits((x as f128).to_bits());
+        assert_eq!(
+            f64::cast_from(f16ahp::cast_from(wide)).to_bits(),
+            r.to_bits()
+        );
+    }
+
+    // Ties go to even, also across the binades
+    assert_eq!(narrow(1.0 + 2f64.powi(-11)).to_bits(), 0x3c00);
+    assert_eq!(narrow(1.0 + 3.0 * 2f64.powi(-11)).to_bits(), 0x3c02);
+    assert_eq!(narrow(65520.0).to_bits(), 0x7c00);
+    assert_eq!(narrow(2f64.powi(-25)).to_bits(), 0);
+    assert_eq!(narrow(-3.0 * 2f64.powi(-25)).to_bits(), 0x8002);
+}
+
+#[test]
+fn saturate() {
+    assert_eq!(narrow(MAX).to_bits(), 0x7fff);
+    assert_eq!(narrow(131039.99).to_bits(), 0x7fff);
+    assert_eq!(f64::cast_from(narrow(131039.99)), MAX);
+
+    // The midpoint to 2^17 would round up to even, which saturates
+    assert_eq!(narrow(131040.0).to_bits(), 0x7fff);
+    assert_eq!(narrow(-131040.0).to_bits(), 0xffff);
+    assert_eq!(narrow(f64::MAX).to_bits(), 0x7fff);
+    assert_eq!(f16ahp::cast_from(f32::MIN).to_bits(), 0xffff);
+    assert_eq!(narrow(f64::INFINITY).to_bits(), 0x7fff);
+    assert_eq!(narrow(f64::NEG_INFINITY).to_bits(), 0xffff);
+    assert_eq!(f16ahp::cast_from(F16::INFINITY).to_bits(), 0x7fff);
+    assert_eq!(f16ahp::cast_from(F128::NEG_INFINITY).to_bits(), 0xffff);
+
+    // NaN becomes a zero of its sign, whatever the payload
+    assert_eq!(narrow(f64::NAN).to_bits(), 0);
+    assert_eq!(narrow(-f64::NAN).to_bits(), 0x8000);
+    assert_eq!(
+        f16ahp::cast_from(f32::from_bits(0xff80_0001)).to_bits(),
+        0x8000
+    );
+    assert_eq!(f16ahp::cast_from(F16::from_bits(0x7e00)).to_bits(), 0);
+    assert_eq!(f16ahp::cast_from(F128::NAN).to_bits(), 0);
+
+    // Signed zeros and the smallest subnormals
+    assert_eq!(narrow(-0.0).to_bits(), 0x8000);
+    assert_eq!(narrow(f64::from_bits(1)).to_bits(), 0);
+    assert_eq!(narrow(-f64::from_bits(1)).to_bits(), 0x8000);
+    assert_eq!(f64::cast_from(f16ahp::from_bits(1)), 2f64.powi(-24));
+}
+
+#[test]
+fn api() {
+    assert_eq!(f64::cast_from(f16ahp::MAX), MAX);
+    assert_eq!(f64::cast_from(f16ahp::MIN), -MAX);
+    assert_eq!(f64::cast_from(f16ahp::MIN_POSITIVE), 2f64.powi(-14));
+    assert_eq!(f16ahp::MANTISSA_DIGITS, 11);
+    assert_eq!(f16ahp::default().to_bits(), 0);
+
+    for bits in [0, 0x7c00, 0x7fff, 0xfe00] {
+        let x = f16ahp::from_bits(bits);
+        assert!(x.is_finite() && !x.is_nan() && !x.is_infinite());
+        assert_eq!(x, x);
+    }
+
+    assert_eq!(f16ahp::from_bits(0), f16ahp::from_bits(0x8000));
+    assert_ne!(f16ahp::MAX, f16ahp::MIN);
+    assert_eq!(-f16ahp::MAX, f16ahp::MIN);
+    assert!(f16ahp::MIN.is_sign_negative() && f16ahp::MAX.is_sign_positive());
+
+    let x = f16ahp::from_bits(0x7bcd);
+    assert_eq!(x.to_be_bytes(), [0x7b, 0xcd]);
+    assert_eq!(x.to_le_bytes(), [0xcd, 0x7b]);
+    assert_eq!(f16ahp::from_be_bytes([0x7b, 0xcd]).to_bits(), 0x7bcd);
+    assert_eq!(f16ahp::from_le_bytes([0xcd, 0x7b]).to_bits(), 0x7bcd);
+    assert_eq!(f16ahp::from_ne_bytes(x.to_ne_bytes()).to_bits(), 0x7bcd);
+}
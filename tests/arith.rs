@@ -0,0 +1,115 @@
+//! Arithmetic tests for f128
+//!
+//! The nightly std `f128` operations are correctly rounded, so results must
+//! match them bit for bit.
+
+#![feature(f128)]
+
+#[path = "common/rng.rs"]
+mod rng;
+
+use floats::f128 as F128;
+use rng::XorShift;
+
+const SPECIAL: [f128; 14] = [
+    0.0,
+    -0.0,
+    1.0,
+    -1.0,
+    1.5,
+    f128::MAX,
+    f128::MIN,
+    f128::MIN_POSITIVE,
+    f128::EPSILON,
+    f128::from_bits(1),
+    f128::from_bits((1 << 112) - 1),
+    f128::INFINITY,
+    f128::NEG_INFINITY,
+    f128::NAN,
+];
+
+fn check(a: u128, b: u128) {
+    let (x, y) = (f128::from_bits(a), f128::from_bits(b));
+    let (p, q) = (F128::from_bits(a), F128::from_bits(b));
+
+    let cases = [
+        ("+", (p + q).to_bits(), x + y),
+        ("-", (p - q).to_bits(), x - y),
+        ("*", (p * q).to_bits(), x * y),
+        ("/", (p / q).to_bits(), x / y),
+    ];
+
+    for (op, our, std) in cases {
+        if std.is_nan() {
+            assert!(f128::from_bits(our).is_nan(), "{a:#x} {op} {b:#x}");
+        } else {
+            assert_eq!(our, std.to_bits(), "{a:#x} {op} {b:#x}");
+        }
+    }
+}
+
+#[test]
+fn special() {
+    for x in SPECIAL {
+        for y in SPECIAL {
+            check(x.to_bits(), y.to_bits());
+        }
+    }
+}
+
+#[rstest::rstest]
+#[case::full_range(0x3fff, 0x3fff)]
+#[case::near_one(0x3fff, 4)]
+#[case::cancellation(0x3fff, 0)]
+#[case::overflow(0x7ffe, 8)]
+#[case::underflow(0x0000, 120)]
+#[case::subnormal(0x0000, 0)]
+fn random(#[case] center: i32, #[case] spread: i32) {
+    let mut rng = XorShift(0x9e37_79b9_7f4a_7c15 ^ center as u64 ^ (spread as u64) << 32);
+
+    for _ in 0..20_000 {
+        let a = rng.f128(center, spread);
+        let b = rng.f128(center, spread);
+        check(a, b);
+
+        // Nearby operands exercise cancellation and exact results
+        check(a, a ^ (rng.next() & 0xff) as u128);
+        check(a, b ^ 1 << 127);
+    }
+}
+
+#[cfg(not(feature = "nightly"))]
+#[test]
+fn nan_propagation() {
+    use floats::IeeeFloat;
+
+    let nan = F128::nan_with_payload(0x1234).unwrap();
+    let snan = -F128::signaling_nan_with_payload(0x5678).unwrap();
+    let one = F128::from_bits(1.0f128.to_bits());
+
+    // The first NaN operand wins and is quieted
+    assert_eq!((nan + snan).to_bits(), nan.to_bits());
+    assert_eq!((snan * nan).to_bits(), snan.to_bits() | 1 << 111);
+    assert_eq!((one / snan).to_bits(), snan.to_bits() | 1 << 111);
+    assert_eq!((one - snan).to_bits(), snan.to_bits() | 1 << 111);
+
+    // Invalid operations produce the default NaN
+    let inf = F128::INFINITY;
+    assert_eq!((inf - inf).to_bits(), F128::NAN.to_bits());
+    assert_eq!((inf * F128::from_bits(0)).to_bits(), F128::NAN.to_bits());
+}
+
+#[test]
+fn assign() {
+    let mut x = F128::from_bits(3.0f128.to_bits());
+    let two = F128::from_bits(2.0f128.to_bits());
+
+    x += two;
+    assert_eq!(x.to_bits(), 5.0f128.to_bits());
+    x -= two;
+    assert_eq!(x.to_bits(), 3.0f128.to_bits());
+    x *= two;
+    assert_eq!(x.to_bits(), 6.0f128.to_bits());
+    x /= two;
+    assert_eq!(x.to_bits(), 3.0f128.to_bits());
+}
@@ -0,0 +1,119 @@
+//! Backend tests
+//!
+//! Whichever conversion backend is active, every `f16` must convert exactly
+//! as the nightly standard library does. Run with `runtime-detect` or with
+//! target features enabled to cover the hardware backends.
+
+#![cfg(feature = "casting")]
+#![feature(f16, f128)]
+
+#[path = "common/rng.rs"]
+mod rng;
+
+use casting::CastFrom;
+use floats::{backend, f16 as F16, Backend};
+use rng::XorShift;
+
+#[test]
+fn selected() {
+    let expected = if cfg!(feature = "nightly") {
+        Backend::Native
+    } else {
+        detected()
+    };
+
+    assert_eq!(backend(), expected);
+    assert_eq!(backend(), expected);
+}
+
+#[cfg(all(feature = "runtime-detect", target_arch = "x86_64"))]
+fn detected() -> Backend {
+    if is_x86_feature_detected!("avx512fp16") {
+        Backend::Avx512Fp16
+    } else if is_x86_feature_detected!("f16c") {
+        Backend::F16c
+    } else {
+        Backend::Software
+    }
+}
+
+#[cfg(all(feature = "runtime-detect", target_arch = "aarch64"))]
+fn detected() -> Backend {
+    if std::arch::is_aarch64_feature_detected!("fp16") {
+        Backend::Fp16
+    } else {
+        Backend::Software
+    }
+}
+
+#[cfg(not(all(
+    feature = "runtime-detect",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+)))]
+fn detected() -> Backend {
+    if !cfg!(feature = "asm") {
+        Backend::Software
+    } else if cfg!(all(target_arch = "x86_64", target_feature = "avx512fp16")) {
+        Backend::Avx512Fp16
+    } else if cfg!(all(target_arch = "aarch64", target_feature = "fp16")) {
+        Backend::Fp16
+    } else if cfg!(all(target_arch = "x86_64", target_feature = "f16c")) {
+        Backend::F16c
+    } else {
+        Backend::Software
+    }
+}
+
+#[test]
+fn from_f16() {
+    for bits in 0..=u16::MAX {
+        let (our, std) = (F16::from_bits(bits), f16::from_bits(bits));
+
+        let floats = [
+            (f64::from(f32::cast_from(our)), f64::from(std as f32)),
+            (f64::cast_from(our), std as f64),
+        ];
+
+        for (our, std) in floats {
+            if std.is_nan() {
+                assert!(our.is_nan(), "{bits:#06x}");
+            } else {
+                assert_eq!(our.to_bits(), std.to_bits(), "{bits:#06x}");
+            }
+        }
+
+        assert_eq!(i32::cast_from(our), std as i32, "{bits:#06x}");
+        assert_eq!(u32::cast_from(our), std as u32, "{bits:#06x}");
+        assert_eq!(i64::cast_from(our), std as i64, "{bits:#06x}");
+        assert_eq!(u64::cast_from(our), std as u64, "{bits:#06x}");
+    }
+}
+
+#[test]
+fn into_f16() {
+    let mut rng = XorShift(0x9e37_79b9_7f4a_7c15);
+
+    for _ in 0..200_000 {
+        let n = rng.next();
+
+        // Concentrate on the f16 range, where rounding matters
+        let sign = (n >> 32) as u32 & (1 << 31);
+        let x = f32::from_bits((0x3000_0000 + n as u32 % 0x1800_0000) | sign);
+        let y = f64::from(x) * (1.0 + f64::from_bits(0x3e00_0000_0000_0000 | n >> 12));
+        let i = (n >> 40) as i32 - (1 << 23);
+        let u = n >> (n & 63);
+
+        let cases = [
+            (F16::cast_from(x), x as f16),
+            (F16::cast_from(y), y as f16),
+            (F16::cast_from(i), i as f16),
+            (F16::cast_from(u as u32), u as u32 as f16),
+            (F16::cast_from(i as i64 * 3), (i as i64 * 3) as f16),
+            (F16::cast_from(u), u as f16),
+        ];
+
+        for (our, std) in cases {
+            assert_eq!(our.to_bits(), std.to_bits(), "{n:#x}");
+        }
+    }
+}
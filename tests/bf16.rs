@@ -0,0 +1,310 @@
+//! bf16 tests
+//!
+//! There is no std bfloat16, so conversions into it are checked against the
+//! nearest value found by exact f128 arithmetic, and conversions out of it
+//! against std conversions from the equal f32.
+
+#![cfg(feature = "casting")]
+#![feature(f16, f128)]
+
+#[path = "common/rng.rs"]
+mod rng;
+
+use floats::casting::CastFrom;
+use floats::{bf16, bf16_backend, f128 as F128, f16 as F16, Backend, ConvertSlice, IeeeFloat};
+use rng::XorShift;
+
+/// The value of a non-negative bf16, with the infinity standing for 2^128,
+/// where the next binade would start.
+fn value(bits: u16) -> f128 {
+    if bits == 0x7f80 {
+        f128::from_bits((16383 + 128) << 112)
+    } else {
+        f32::from_bits((bits as u32) << 16) as f128
+    }
+}
+
+/// Rounds a non-NaN `x` to the nearest bf16, ties to even.
+fn reference(x: f128) -> u16 {
+    let sign = if x.is_sign_negative() { 0x8000 } else { 0 };
+    let x = x.abs();
+
+    if x >= value(0x7f80) {
+        return sign | 0x7f80;
+    }
+
+    // The greatest bf16 not above x
+    let (mut lo, mut hi) = (0u16, 0x7f80);
+    while hi - lo > 1 {
+        let mid = (lo + hi) / 2;
+        if value(mid) <= x {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+
+    let below = x - value(lo);
+    let above = value(lo + 1) - x;
+    if below < above || (below == above && lo % 2 == 0) {
+        sign | lo
+    } else {
+        sign | (lo + 1)
+    }
+}
+
+/// Every bf16 as the equal f32, or `None` for NaNs.
+fn halves() -> impl Iterator<Item = (bf16, Option<f32>)> {
+    (0..=u16::MAX).map(|bits| {
+        let x = f32::from_bits((bits as u32) << 16);
+        (bf16::from_bits(bits), Some(x).filter(|x| !x.is_nan()))
+    })
+}
+
+#[test]
+fn backend() {
+    match bf16_backend() {
+        Backend::Software | Backend::Avx512Bf16 | Backend::Bf16 => (),
+        other => panic!("{other:?}"),
+    }
+}
+
+#[test]
+fn from_f32() {
+    let mut rng = XorShift(0x9e37_79b9_7f4a_7c15);
+    let mut src = Vec::new();
+
+    for top in 0..=u16::MAX as u32 {
+        let random = rng.next() as u32 & 0xffff;
+        for low in [0, 1, 0x7fff, 0x8000, 0x8001, 0xffff, random] {
+            src.push(f32::from_bits(top << 16 | low));
+        }
+    }
+
+    let mut dst = vec![bf16::from_bits(0); src.len()];
+    bf16::convert_from_f32_slice(&src, &mut dst);
+
+    for (x, slice) in src.iter().zip(&dst) {
+        let ours = bf16::cast_from(*x);
+        let bits = x.to_bits();
+
+        if x.is_nan() {
+            assert_eq!(ours.to_bits(), (bits >> 16) as u16 | 0x40, "{bits:#x}");
+        } else {
+            assert_eq!(ours.to_bits(), reference(*x as f128), "{bits:#x}");
+        }
+        assert_eq!(slice.to_bits(), ours.to_bits(), "{bits:#x}");
+    }
+}
+
+#[test]
+fn from_f64() {
+    let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+
+    for _ in 0..200_000 {
+        let n = rng.next();
+
+        // Midpoints between adjacent bf16, and their neighbours
+        let lo = value((n >> 48) as u16 % 0x7f80);
+        let hi = value((n >> 48) as u16 % 0x7f80 + 1);
+        let mid = (lo as f64 + hi as f64) / 2.0;
+        let sign = if n & 1 == 0 { 1.0 } else { -1.0 };
+
+        // Random magnitudes across the bf16 range and beyond
+        let random = f64::from_bits((n & ((1 << 52) - 1)) | (((n >> 52) % 320 + 1023 - 160) << 52));
+
+        for x in [mid, mid.next_up(), mid.next_down(), random] {
+            let x = sign * x;
+            assert_eq!(
+                bf16::cast_from(x).to_bits(),
+                reference(x as f128),
+                "{:#x}",
+                x.to_bits()
+            );
+        }
+    }
+
+    let nan = f64::from_bits(0xfff4_2000_0000_0001);
+    assert_eq!(bf16::cast_from(nan).to_bits(), 0xffe1);
+}
+
+#[test]
+fn from_f128() {
+    let mut rng = XorShift(0x1234_5678_9abc_def1);
+
+    for _ in 0..200_000 {
+        let center = [16383 - 133, 16383 - 126, 16383, 16383 + 127][rng.next() as usize % 4];
+        let x = f128::from_bits(rng.f128(center, 12));
+        let ours = bf16::cast_from(F128::from_bits(x.to_bits()));
+        assert_eq!(ours.to_bits(), reference(x), "{:#x}", x.to_bits());
+    }
+
+    // Midpoints, including ones only f128 can hold
+    for bits in [0x0001u16, 0x0080, 0x3f80, 0x7f7e] {
+        let mid = (value(bits) + value(bits + 1)) / 2.0;
+        for x in [mid, mid.next_up(), mid.next_down(), f128::from_bits(1)] {
+            let ours = bf16::cast_from(F128::from_bits(x.to_bits()));
+            assert_eq!(ours.to_bits(), reference(x), "{:#x}", x.to_bits());
+        }
+    }
+
+    let nan = F128::from_bits(0x7fff_4000_0000_0000_0000_0000_0000_0001);
+    assert_eq!(bf16::cast_from(nan).to_bits(), 0x7fe0);
+}
+
+#[test]
+fn from_f16() {
+    for bits in 0..=u16::MAX {
+        let x = f16::from_bits(bits);
+        let ours = bf16::cast_from(F16::from_bits(bits));
+
+        if x.is_nan() {
+            assert!(ours.is_nan(), "{bits:#x}");
+        } else {
+            assert_eq!(ours.to_bits(), reference(x as f128), "{bits:#x}");
+        }
+    }
+}
+
+#[test]
+fn from_int() {
+    let mut rng = XorShift(0x0123_4567_89ab_cdef);
+
+    for _ in 0..200_000 {
+        let n = rng.next() >> (rng.next() % 64);
+        let i = n as i64;
+
+        assert_eq!(bf16::cast_from(n).to_bits(), reference(n as f128));
+        assert_eq!(bf16::cast_from(i).to_bits(), reference(i as f128));
+        assert_eq!(
+            bf16::cast_from(n as u32).to_bits(),
+            reference(n as u32 as f128)
+        );
+        assert_eq!(
+            bf16::cast_from(i as i32).to_bits(),
+            reference(i as i32 as f128)
+        );
+        assert_eq!(bf16::cast_from(n as usize).to_bits(), reference(n as f128));
+        assert_eq!(bf16::cast_from(n as u128).to_bits(), reference(n as f128));
+        assert_eq!(
+            bf16::cast_from((i as i128) << 60).to_bits(),
+            reference(((i as i128) << 60) as f128)
+        );
+        assert_eq!(
+            bf16::cast_from(n as u16).to_bits(),
+            reference(n as u16 as f128)
+        );
+        assert_eq!(
+            bf16::cast_from(i as i8).to_bits(),
+            reference(i as i8 as f128)
+        );
+    }
+
+    // Ties, and values just above them that only the low bits tell apart
+    let tie = 1u128 << 127 | 1 << 119;
+    assert_eq!(bf16::cast_from(tie).to_bits(), 0x7f00);
+    assert_eq!(bf16::cast_from(tie + 1).to_bits(), 0x7f01);
+    assert_eq!(bf16::cast_from(u128::MAX).to_bits(), 0x7f80);
+    assert_eq!(bf16::cast_from(i128::MIN).to_bits(), 0xff00);
+    assert_eq!(bf16::cast_from(-(1i64 << 40 | 1 << 32)).to_bits(), 0xd380);
+    assert_eq!(
+        bf16::cast_from(-(1i64 << 40 | 1 << 32 | 1)).to_bits(),
+        0xd381
+    );
+}
+
+#[test]
+fn into_others() {
+    for (h, x) in halves() {
+        let bits = h.to_bits();
+
+        let Some(x) = x else {
+            assert!(f32::cast_from(h).is_nan());
+            assert!(f64::cast_from(h).is_nan());
+            assert!(F128::cast_from(h).is_nan());
+            assert!(F16::cast_from(h).is_nan());
+            assert_eq!(f32::cast_from(h).to_bits() & 0x0040_0000, 0x0040_0000);
+            continue;
+        };
+
+        assert_eq!(f32::cast_from(h).to_bits(), x.to_bits(), "{bits:#x}");
+        assert_eq!(
+            f64::cast_from(h).to_bits(),
+            (x as f64).to_bits(),
+            "{bits:#x}"
+        );
+        assert_eq!(
+            F128::cast_from(h).to_bits(),
+            (x as f128).to_bits(),
+            "{bits:#x}"
+        );
+        assert_eq!(
+            F16::cast_from(h).to_bits(),
+            (x as f16).to_bits(),
+            "{bits:#x}"
+        );
+
+        assert_eq!(u8::cast_from(h), x as u8, "{bits:#x}");
+        assert_eq!(i16::cast_from(h), x as i16, "{bits:#x}");
+        assert_eq!(u32::cast_from(h), x as u32, "{bits:#x}");
+        assert_eq!(i64::cast_from(h), x as i64, "{bits:#x}");
+        assert_eq!(u128::cast_from(h), x as u128, "{bits:#x}");
+        assert_eq!(isize::cast_from(h), x as isize, "{bits:#x}");
+    }
+
+    let src: Vec<bf16> = halves().map(|(h, _)| h).collect();
+    let mut wide = vec![0.0f32; src.len()];
+    let mut wider = vec![0.0f64; src.len()];
+    let mut back = vec![bf16::from_bits(0); src.len()];
+
+    bf16::convert_to_f32_slice(&src, &mut wide);
+    bf16::convert_to_f64_slice(&src, &mut wider);
+    for (h, (w, d)) in src.iter().zip(wide.iter().zip(&wider)) {
+        assert_eq!(w.to_bits(), f32::cast_from(*h).to_bits());
+        assert_eq!(d.to_bits(), f64::cast_from(*h).to_bits());
+    }
+
+    bf16::convert_from_f64_slice(&wider, &mut back);
+    for (h, b) in src.iter().zip(&back) {
+        assert_eq!(b.to_bits(), bf16::cast_from(f64::cast_from(*h)).to_bits());
+    }
+
+    let exact = [1.0, -2.5, f32::from_bits(0x0002_0000)];
+    let array = bf16::convert_from_f32_array(exact);
+    assert_eq!(bf16::convert_to_f32_array(array), exact);
+}
+
+#[test]
+fn api() {
+    for (h, x) in halves() {
+        let bits = h.to_bits();
+
+        assert_eq!(h.is_nan(), x.is_none(), "{bits:#x}");
+        assert_eq!(h.is_nan(), IeeeFloat::is_nan(h), "{bits:#x}");
+        assert_eq!(h.is_infinite(), IeeeFloat::is_infinite(h), "{bits:#x}");
+        assert_eq!(h.is_finite(), IeeeFloat::is_finite(h), "{bits:#x}");
+        assert_eq!(h.is_sign_negative(), IeeeFloat::is_sign_negative(h));
+        assert_eq!(h.next_up().to_bits(), IeeeFloat::next_up(h).to_bits());
+        assert_eq!(h.next_down().to_bits(), IeeeFloat::next_down(h).to_bits());
+        assert_eq!((-h).to_bits(), bits ^ 0x8000);
+        assert_eq!(h == h, x.is_some(), "{bits:#x}");
+        assert_eq!(bf16::from_be_bytes(h.to_be_bytes()).to_bits(), bits);
+        assert_eq!(bf16::from_le_bytes(h.to_le_bytes()).to_bits(), bits);
+        assert_eq!(bf16::from_ne_bytes(h.to_ne_bytes()).to_bits(), bits);
+
+        // The spacing to the next larger magnitude, short of the infinity
+        if let Some(x) = x.filter(|_| bits & 0x7fff < 0x7f7f) {
+            let ulp = f32::from_bits((h.ulp().to_bits() as u32) << 16);
+            let next = f32::from_bits(((bits & 0x7fff) as u32 + 1) << 16);
+            assert_eq!(ulp, next - x.abs(), "{bits:#x}");
+        }
+    }
+
+    assert_eq!(bf16::from_bits(0), bf16::from_bits(0x8000));
+    assert_eq!(f32::cast_from(bf16::MAX), f32::from_bits(0x7f7f_0000));
+    assert_eq!(f32::cast_from(bf16::MIN), -f32::cast_from(bf16::MAX));
+    assert_eq!(bf16::INFINITY.next_down(), bf16::MAX);
+    assert_eq!(bf16::MANTISSA_DIGITS, <bf16 as IeeeFloat>::MANT_BITS + 1);
+    assert_eq!(bf16::from_bits(1).ulp_distance(-bf16::from_bits(1)), 2);
+    assert_eq!(bf16::NAN.to_bits(), <bf16 as IeeeFloat>::NAN.to_bits());
+}
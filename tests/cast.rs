@@ -22,11 +22,15 @@ pub extern "C" fn __floatuntihf(a: u128) -> f16 {
 
 mod common;
 
+#[path = "common/rng.rs"]
+mod rng;
+
 use common::Bitable;
 
 use std::fmt::Debug;
 
 use casting::CastInto;
+use floats::IeeeFloat;
 
 use crate::common::Customized;
 
@@ -112,6 +116,16 @@ use crate::common::Customized;
 #[case::f16_i128_max(i128::MAX as f16, i128::MAX)] // breaks when optimization is off
 #[case::f16_i128_default(i128::default() as f16, i128::default())]
 #[case::f16_i128_one(1.0f16, 1i128)]
+// f16 -> usize cases
+#[case::f16_usize_min(usize::MIN as f16, usize::MIN)]
+#[case::f16_usize_max(usize::MAX as f16, usize::MAX)]
+#[case::f16_usize_default(usize::default() as f16, usize::default())]
+#[case::f16_usize_one(1.0f16, 1usize)]
+// f16 -> isize cases
+#[case::f16_isize_min(isize::MIN as f16, isize::MIN)]
+#[case::f16_isize_max(isize::MAX as f16, isize::MAX)]
+#[case::f16_isize_default(isize::default() as f16, isize::default())]
+#[case::f16_isize_one(1.0f16, 1isize)]
 // f128 -> f64 cases
 #[case::f128_f64_neg_nan(-f128::NAN, -f64::NAN)]
 #[case::f128_f64_neg_inf(f128::NEG_INFINITY, f64::NEG_INFINITY)]
@@ -188,11 +202,21 @@ use crate::common::Customized;
 #[case::f128_i128_max(i128::MAX as f128, i128::MAX)] // breaks when optimization is off
 #[case::f128_i128_default(i128::default() as f128, i128::default())]
 #[case::f128_i128_one(1.0f128, 1i128)]
+// f128 -> usize cases
+#[case::f128_usize_min(usize::MIN as f128, usize::MIN)]
+#[case::f128_usize_max(usize::MAX as f128, usize::MAX)]
+#[case::f128_usize_default(usize::default() as f128, usize::default())]
+#[case::f128_usize_one(1.0f128, 1usize)]
+// f128 -> isize cases
+#[case::f128_isize_min(isize::MIN as f128, isize::MIN)]
+#[case::f128_isize_max(isize::MAX as f128, isize::MAX)]
+#[case::f128_isize_default(isize::default() as f128, isize::default())]
+#[case::f128_isize_one(1.0f128, 1isize)]
 fn test_cast_from<F, I>(#[case] from: F, #[case] into: I)
 where
     F: Copy + Bitable + CastInto<I> + Customized,
     I: Debug + Copy + Bitable<Bits: Debug + PartialEq>,
-    F::Custom: Copy + Bitable<Bits = F::Bits> + CastInto<I>,
+    F::Custom: IeeeFloat<Bits = F::Bits> + CastInto<I>,
 {
     let cust = F::Custom::from_bits(from.to_bits());
 
@@ -276,6 +300,16 @@ where
 #[case::i128_f16_max(i128::MAX, i128::MAX as f16)]
 #[case::i128_f16_default(i128::default(), i128::default() as f16)]
 #[case::i128_f16_one(1i128, 1.0f16)]
+// usize -> f16 cases
+#[case::usize_f16_min(usize::MIN, usize::MIN as f16)]
+#[case::usize_f16_max(usize::MAX, usize::MAX as f16)]
+#[case::usize_f16_default(usize::default(), usize::default() as f16)]
+#[case::usize_f16_one(1usize, 1.0f16)]
+// isize -> f16 cases
+#[case::isize_f16_min(isize::MIN, isize::MIN as f16)]
+#[case::isize_f16_max(isize::MAX, isize::MAX as f16)]
+#[case::isize_f16_default(isize::default(), isize::default() as f16)]
+#[case::isize_f16_one(1isize, 1.0f16)]
 // f64 -> f128 cases
 #[case::f64_f128_neg_nan(-f64::NAN, -f128::NAN)]
 #[case::f64_f128_neg_inf(f64::NEG_INFINITY, f128::NEG_INFINITY)]
@@ -352,11 +386,21 @@ where
 #[case::i128_f128_max(i128::MAX, i128::MAX as f128)] // breaks when optimization is off
 #[case::i128_f128_default(i128::default(), i128::default() as f128)]
 #[case::i128_f128_one(1i128, 1.0f128)]
+// usize -> f128 cases
+#[case::usize_f128_min(usize::MIN, usize::MIN as f128)]
+#[case::usize_f128_max(usize::MAX, usize::MAX as f128)]
+#[case::usize_f128_default(usize::default(), usize::default() as f128)]
+#[case::usize_f128_one(1usize, 1.0f128)]
+// isize -> f128 cases
+#[case::isize_f128_min(isize::MIN, isize::MIN as f128)]
+#[case::isize_f128_max(isize::MAX, isize::MAX as f128)]
+#[case::isize_f128_default(isize::default(), isize::default() as f128)]
+#[case::isize_f128_one(1isize, 1.0f128)]
 fn test_cast_into<F, I>(#[case] from: F, #[case] into: I)
 where
-    F: Copy + Bitable + CastInto<I> + CastInto<I::Custom>,
+    F: Copy + CastInto<I> + CastInto<I::Custom>,
     I: Debug + Copy + Bitable<Bits: Debug + PartialEq> + Customized,
-    I::Custom: Debug + Copy + Bitable<Bits = I::Bits>,
+    I::Custom: IeeeFloat<Bits = I::Bits>,
 {
     let test: I = from.cast_into();
     let cust: I::Custom = from.cast_into();
@@ -400,8 +444,8 @@ fn test_custom_f16_f128<F, I>(#[case] from: F, #[case] into: I)
 where
     F: Copy + Bitable + Customized + CastInto<I>,
     I: Copy + Bitable<Bits: Debug + PartialEq> + Customized,
-    F::Custom: Copy + Bitable<Bits = F::Bits> + CastInto<I::Custom>,
-    I::Custom: Copy + Bitable<Bits = I::Bits>,
+    F::Custom: IeeeFloat<Bits = F::Bits> + CastInto<I::Custom>,
+    I::Custom: IeeeFloat<Bits = I::Bits>,
 {
     let test: I = from.cast_into();
     let cust: I::Custom = F::Custom::from_bits(from.to_bits()).cast_into();
@@ -412,3 +456,119 @@ where
     // Fails when we have an implementation bug.
     assert_eq!(cust.to_bits(), into.to_bits());
 }
+
+/// Test the `core::num` wrappers, which convert as their integers do
+#[cfg(not(feature = "nightly"))]
+#[test]
+fn test_wrappers() {
+    use casting::CastFrom;
+    use floats::{f128 as F128, f16 as F16};
+    use std::num::{NonZeroI64, NonZeroU8, NonZeroUsize, Wrapping};
+
+    let h = F16::from_bits(300.5f16.to_bits());
+    let q = F128::from_bits((-2.5f128).to_bits());
+
+    assert_eq!(Wrapping::<u8>::cast_from(h), Wrapping(255));
+    assert_eq!(Wrapping::<i64>::cast_from(q), Wrapping(-2));
+    assert_eq!(
+        F16::cast_from(Wrapping(70000u32)).to_bits(),
+        f16::INFINITY.to_bits()
+    );
+    assert_eq!(
+        F128::cast_from(Wrapping(u128::MAX)).to_bits(),
+        (u128::MAX as f128).to_bits()
+    );
+
+    let n = NonZeroU8::new(200).unwrap();
+    assert_eq!(F16::cast_from(n).to_bits(), 200.0f16.to_bits());
+    assert_eq!(
+        F128::cast_from(NonZeroI64::MIN).to_bits(),
+        (i64::MIN as f128).to_bits()
+    );
+    assert_eq!(
+        F16::cast_from(NonZeroUsize::MAX).to_bits(),
+        (usize::MAX as f16).to_bits()
+    );
+}
+
+#[cfg(all(feature = "saturating", not(feature = "nightly")))]
+#[test]
+fn test_saturating() {
+    use casting::CastFrom;
+    use floats::{f128 as F128, f16 as F16};
+    use std::num::Saturating;
+
+    let h = F16::from_bits((-1.5f16).to_bits());
+    assert_eq!(Saturating::<u32>::cast_from(h), Saturating(0));
+    assert_eq!(Saturating::<i16>::cast_from(h), Saturating(-1));
+    assert_eq!(
+        F128::cast_from(Saturating(i128::MIN)).to_bits(),
+        (i128::MIN as f128).to_bits()
+    );
+}
+
+/// Test f128 to and from the other floats across every binade, including
+/// their subnormals
+#[test]
+fn test_f128_floats_random() {
+    use casting::CastFrom;
+    use floats::{f128 as F128, f16 as F16};
+
+    let mut rng = rng::XorShift(0x9e37_79b9_7f4a_7c15);
+
+    for _ in 0..100_000 {
+        // Around the subnormal and overflow thresholds of each format
+        let center = [
+            16383 - 14,
+            16383 - 126,
+            16383 - 1022,
+            16383 + 16,
+            16383 + 128,
+            16383 + 1024,
+        ];
+        let center = center[rng.next() as usize % 6];
+        let bits = rng.f128(center, 60);
+        let (x, y) = (F128::from_bits(bits), f128::from_bits(bits));
+
+        assert_eq!(
+            F16::cast_from(x).to_bits(),
+            (y as f16).to_bits(),
+            "{bits:#x}"
+        );
+        assert_eq!(
+            f32::cast_from(x).to_bits(),
+            (y as f32).to_bits(),
+            "{bits:#x}"
+        );
+        assert_eq!(
+            f64::cast_from(x).to_bits(),
+            (y as f64).to_bits(),
+            "{bits:#x}"
+        );
+
+        let n = rng.next();
+        let (h, s, d) = (
+            f16::from_bits(n as u16),
+            f32::from_bits(n as u32),
+            f64::from_bits(n),
+        );
+        if !h.is_nan() {
+            let wide = F128::cast_from(F16::from_bits(n as u16));
+            assert_eq!(wide.to_bits(), (h as f128).to_bits(), "{n:#x}");
+        }
+        if !s.is_nan() {
+            assert_eq!(
+                F128::cast_from(s).to_bits(),
+                (s as f128).to_bits(),
+                "{n:#x}"
+            );
+        }
+        if !d.is_nan() {
+            assert_eq!(
+                F128::cast_from(d).to_bits(),
+                (d as f128).to_bits(),
+                "{n:#x}"
+            );
+        }
+    }
+}
@@ -0,0 +1,30 @@
+//! Conversions through std's f128, shared by tests that check results in
+//! exact f128 arithmetic
+
+// Not every test uses every helper
+#![allow(dead_code)]
+
+use floats::casting::CastFrom;
+use floats::f128 as F128;
+
+/// Converts to std's f128 through ours, which is std's with `nightly`.
+pub fn wide<T>(x: T) -> f128
+where
+    F128: CastFrom<T>,
+{
+    f128::from_bits(F128::cast_from(x).to_bits())
+}
+
+/// Converts from std's f128 through ours.
+pub fn narrow<T: CastFrom<F128>>(x: f128) -> T {
+    T::cast_from(F128::from_bits(x.to_bits()))
+}
+
+/// Returns 2^exp exactly, including the subnormal powers.
+pub fn pow2(exp: i32) -> f128 {
+    if exp >= -16382 {
+        f128::from_bits(((exp + 16383) as u128) << 112)
+    } else {
+        f128::from_bits(1 << (exp + 16494))
+    }
+}
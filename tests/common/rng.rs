@@ -0,0 +1,55 @@
+//! Deterministic random inputs, shared by tests that sweep value ranges
+
+// Not every test uses every helper
+#![allow(dead_code)]
+
+/// A small deterministic generator for test inputs
+pub struct XorShift(pub u64);
+
+impl XorShift {
+    pub fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A random f128 bit pattern with a biased exponent within `spread` of
+    /// `center`
+    pub fn f128(&mut self, center: i32, spread: i32) -> u128 {
+        let sign = (self.next() & 1) as u128;
+        let exp = center - spread + (self.next() % (2 * spread as u64 + 1)) as i32;
+        let exp = exp.clamp(0, 0x7ffe) as u128;
+        let mant = ((self.next() as u128) << 64 | self.next() as u128) & ((1 << 112) - 1);
+        sign << 127 | exp << 112 | mant
+    }
+
+    /// A random f64 with an exponent within `spread` of 2^`center`, clamped
+    /// to the finite range
+    pub fn f64(&mut self, center: i32, spread: i32) -> f64 {
+        let sign = self.next() & 1;
+        let exp = center - spread + (self.next() % (2 * spread as u64 + 1)) as i32;
+        let exp = (exp + 1023).clamp(0, 2046) as u64;
+        f64::from_bits(sign << 63 | exp << 52 | self.next() >> 12)
+    }
+
+    /// A random f128 between `lo` and `hi`
+    pub fn range(&mut self, lo: f128, hi: f128) -> f128 {
+        let t = (self.next() >> 11) as f128 / (1u64 << 53) as f128;
+        lo + (hi - lo) * t
+    }
+}
+
+/// Returns the distance between two f128 values in ULPs
+pub fn ulps(a: f128, b: f128) -> u128 {
+    let ordinal = |x: f128| {
+        let bits = x.to_bits() as i128;
+        if bits < 0 {
+            i128::MIN - bits
+        } else {
+            bits
+        }
+    };
+
+    ordinal(a).abs_diff(ordinal(b))
+}
@@ -0,0 +1,558 @@
+//! Decimal floating point tests
+//!
+//! The arithmetic and the f128 conversions are checked against vectors
+//! from an independent implementation of the General Decimal Arithmetic
+//! specification, and the f64 conversions against the standard library's
+//! parsing and formatting.
+
+#![cfg(feature = "casting")]
+#![feature(f128)]
+
+#[path = "common/rng.rs"]
+mod rng;
+
+use floats::casting::CastFrom;
+use floats::{d128, d32, d64, f128 as F128};
+use rng::XorShift;
+
+/// Returns a random coefficient of 1 to `digits` digits.
+fn coefficient(rng: &mut XorShift, digits: u32) -> u128 {
+    let n = 1 + (rng.next() % digits as u64) as u32;
+    let wide = (rng.next() as u128) << 64 | rng.next() as u128;
+    wide % 10u128.pow(n)
+}
+
+/// Rounds `x` to `digits` digits, ties to even, returning the coefficient
+/// and the number of digits dropped.
+fn round_even(x: u128, digits: u32) -> (u128, i32) {
+    let mut n = 1;
+    while n < 39 && x >= 10u128.pow(n) {
+        n += 1;
+    }
+    if n <= digits {
+        return (x, 0);
+    }
+
+    let drop = n - digits;
+    let (q, r) = (x / 10u128.pow(drop), x % 10u128.pow(drop));
+    let half = 5 * 10u128.pow(drop - 1);
+    let q = if r > half || (r == half && q & 1 == 1) {
+        q + 1
+    } else {
+        q
+    };
+
+    if q == 10u128.pow(digits) {
+        (q / 10, drop as i32 + 1)
+    } else {
+        (q, drop as i32)
+    }
+}
+
+#[test]
+fn encodings() {
+    let one = 0x3040_0000_0000_0000_0000_0000_0000_0001;
+    assert_eq!("1".parse::<d32>().unwrap().to_bits(), 0x3280_0001);
+    assert_eq!("1".parse::<d64>().unwrap().to_bits(), 0x31c0_0000_0000_0001);
+    assert_eq!("1".parse::<d128>().unwrap().to_bits(), one);
+
+    assert_eq!(d32::from_bits(0x3280_0001).to_dpd(), 0x2250_0001);
+    assert_eq!(d64::from_parts(false, 1, 0).to_dpd(), 0x2238_0000_0000_0001);
+    assert_eq!(
+        d128::from_bits(one).to_dpd(),
+        0x2208_0000_0000_0000_0000_0000_0000_0001
+    );
+
+    // The largest values take the second coefficient layout in BID
+    assert_eq!(d32::MAX.to_bits(), 0x77f8_967f);
+    assert_eq!(d32::MAX.to_dpd(), 0x77f3_fcff);
+    assert_eq!(d32::MIN.to_bits(), 0xf7f8_967f);
+    assert_eq!(d64::MAX.to_bits(), 0x77fb_86f2_6fc0_ffff);
+    assert_eq!(d64::MAX.to_dpd(), 0x77fc_ff3f_cff3_fcff);
+    assert_eq!(d32::MIN_POSITIVE.to_bits(), 0x0300_0001);
+    assert_eq!(d32::MIN_POSITIVE.to_string(), "1E-95");
+
+    assert_eq!(d32::INFINITY.to_bits(), 0x7800_0000);
+    assert_eq!(d32::NEG_INFINITY.to_bits(), 0xf800_0000);
+    assert_eq!(d32::NAN.to_bits(), 0x7c00_0000);
+    assert!(d32::from_bits(0x7e00_0000).is_signaling_nan());
+    assert_eq!(d32::from_dpd(0x7e00_0000).to_bits(), 0x7e00_0000);
+    assert_eq!(d32::from_dpd(0xf800_0000), d32::NEG_INFINITY);
+
+    // Coefficients beyond the precision are non-canonical and read as zero
+    let noncanonical = d32::from_bits(0x6cbf_ffff);
+    assert_eq!(noncanonical, d32::default());
+    assert_eq!(noncanonical.to_parts(), Some((false, 0, 0)));
+    assert_eq!(noncanonical.to_dpd(), 0x2250_0000);
+
+    // Every declet reads back from its canonical form, and only the 24
+    // redundant ones differ from it
+    let mut redundant = 0;
+    for declet in 0..1024 {
+        let x = d32::from_dpd(0x2250_0000 | declet);
+        let canonical = x.to_dpd();
+        assert_eq!(d32::from_dpd(canonical).to_bits(), x.to_bits());
+        redundant += (canonical != 0x2250_0000 | declet) as u32;
+    }
+    assert_eq!(redundant, 24);
+
+    for value in 0..1000 {
+        let x = d64::from_parts(false, value * 1_000_001_001_001, 0);
+        assert_eq!(d64::from_dpd(x.to_dpd()).to_bits(), x.to_bits(), "{x}");
+    }
+
+    let mut rng = XorShift(0x0123_4567_89ab_cdef);
+    for _ in 0..100_000 {
+        let negative = rng.next() & 1 == 1;
+        let exp = (rng.next() % 12_288) as i32 - 6176;
+        let x = d128::from_parts(negative, coefficient(&mut rng, 34), exp);
+        assert_eq!(d128::from_dpd(x.to_dpd()).to_bits(), x.to_bits(), "{x}");
+    }
+
+    let x = d64::from_bits(0x31c0_0000_0000_0001);
+    assert_eq!(x.to_be_bytes(), [0x31, 0xc0, 0, 0, 0, 0, 0, 1]);
+    assert_eq!(d64::from_le_bytes(x.to_le_bytes()).to_bits(), x.to_bits());
+    assert_eq!(d64::from_ne_bytes(x.to_ne_bytes()).to_bits(), x.to_bits());
+}
+
+#[test]
+fn strings() {
+    // Each string reads back as the same coefficient and exponent
+    for &(input, output) in &[
+        ("0", "0"),
+        ("-0", "-0"),
+        ("0.00", "0.00"),
+        ("123", "123"),
+        ("-123", "-123"),
+        ("1.23E3", "1.23E+3"),
+        ("1.23E+5", "1.23E+5"),
+        ("12.3", "12.3"),
+        ("0.00123", "0.00123"),
+        ("1.23E-8", "1.23E-8"),
+        ("-1.23E-10", "-1.23E-10"),
+        ("0E+2", "0E+2"),
+        ("0.000001", "0.000001"),
+        ("0.0000001", "1E-7"),
+        ("+.5", "0.5"),
+        ("5.", "5"),
+        ("1e-7", "1E-7"),
+        ("inf", "Infinity"),
+        ("-Infinity", "-Infinity"),
+        ("NaN", "NaN"),
+        ("-nan", "-NaN"),
+        ("sNaN", "sNaN"),
+        ("NaN123", "NaN123"),
+        ("1234567890123456", "1234567890123456"),
+        ("12345678901234567", "1.234567890123457E+16"),
+        ("12345678901234565", "1.234567890123456E+16"),
+        (
+            "1.2345678901234565000000000000000000000000000000000000000000000000000000000000001",
+            "1.234567890123457",
+        ),
+        ("1E+385", "Infinity"),
+        ("9.9999999999999995E+384", "Infinity"),
+        ("9.999999999999999E+384", "9.999999999999999E+384"),
+        ("1E+384", "1.000000000000000E+384"),
+        ("1E-398", "1E-398"),
+        ("5E-399", "0E-398"),
+        ("5.1E-399", "1E-398"),
+        ("0E+1000", "0E+369"),
+        ("1E-99999999999999", "0E-398"),
+    ] {
+        let x: d64 = input.parse().unwrap();
+        assert_eq!(x.to_string(), output, "{input}");
+        assert_eq!(
+            output.parse::<d64>().unwrap().to_bits(),
+            x.to_bits(),
+            "{output}"
+        );
+    }
+
+    for input in &[
+        "",
+        ".",
+        "e5",
+        "1e",
+        "1e+",
+        "1.2.3",
+        "--1",
+        "1_0",
+        " 1",
+        "nan1x",
+        "NaN1234567890123456",
+        "Infinit",
+    ] {
+        assert!(input.parse::<d64>().is_err(), "{input}");
+    }
+
+    assert_eq!(format!("{:>8}", d32::from_parts(true, 15, -1)), "    -1.5");
+    assert_eq!(format!("{:+}", d32::from_parts(false, 15, -1)), "+1.5");
+    let error = "1.5x".parse::<d32>().unwrap_err();
+    assert_eq!(error.to_string(), "invalid decimal literal");
+}
+
+#[test]
+fn arithmetic() {
+    for &(a, op, b, expected) in &[
+        ("1.20", '+', "1.3", "2.50"),
+        ("1E+2", '+', "1E+0", "101"),
+        (
+            "9999999999999999999999999999999999",
+            '+',
+            "1",
+            "1.000000000000000000000000000000000E+34",
+        ),
+        ("1", '+', "-1", "0"),
+        ("-0", '+', "-0", "-0"),
+        ("0E-5", '+', "12.3", "12.30000"),
+        (
+            "9.999999999999999999999999999999999E+6144",
+            '+',
+            "1E+6111",
+            "Infinity",
+        ),
+        (
+            "1234567890123456789012345678901234",
+            '+',
+            "0.5",
+            "1234567890123456789012345678901234",
+        ),
+        (
+            "1234567890123456789012345678901235",
+            '+',
+            "0.5",
+            "1234567890123456789012345678901236",
+        ),
+        (
+            "1234567890123456789012345678901234",
+            '+',
+            "0.5000000000000000000000000000000001",
+            "1234567890123456789012345678901235",
+        ),
+        (
+            "1E+100",
+            '+',
+            "-1E-100",
+            "1.000000000000000000000000000000000E+100",
+        ),
+        ("1E-6176", '+', "1E-6176", "2E-6176"),
+        (
+            "1E-6143",
+            '+',
+            "-1E-6176",
+            "9.99999999999999999999999999999999E-6144",
+        ),
+        ("1E+34", '-', "1", "9999999999999999999999999999999999"),
+        ("1.000", '-', "0.001", "0.999"),
+        ("5", '-', "5", "0"),
+        ("1.10", '*', "2.0", "2.200"),
+        (
+            "99999999999999999999999999999999",
+            '*',
+            "1.01",
+            "100999999999999999999999999999999.0",
+        ),
+        ("1E-3100", '*', "1E-3100", "0E-6176"),
+        ("-2", '*', "0E+3", "-0E+3"),
+        ("1E+3072", '*', "1E+3073", "Infinity"),
+        (
+            "3.333333333333333333333333333333333",
+            '*',
+            "3",
+            "9.999999999999999999999999999999999",
+        ),
+        ("1.5E-6176", '*', "1", "2E-6176"),
+        ("2.5E-6176", '*', "1", "2E-6176"),
+        (
+            "1234567890123456789.5",
+            '*',
+            "1234567890123456789",
+            "1.524157875323883675636335943811919E+36",
+        ),
+        ("1", '/', "3", "0.3333333333333333333333333333333333"),
+        ("2", '/', "3", "0.6666666666666666666666666666666667"),
+        ("1", '/', "4", "0.25"),
+        ("1.00", '/', "4", "0.25"),
+        ("2.400", '/', "2", "1.200"),
+        ("1000", '/', "10", "100"),
+        ("1E+5", '/', "1E-6140", "Infinity"),
+        ("1E-6000", '/', "1E+200", "0E-6176"),
+        ("-7", '/', "0.7", "-1E+1"),
+        ("0E+5", '/', "3E+2", "0E+3"),
+        ("5", '/', "0", "Infinity"),
+    ] {
+        let (x, y): (d128, d128) = (a.parse().unwrap(), b.parse().unwrap());
+        let result = match op {
+            '+' => x + y,
+            '-' => x - y,
+            '*' => x * y,
+            _ => x / y,
+        };
+        assert_eq!(result.to_string(), expected, "{a} {op} {b}");
+    }
+
+    // NaNs propagate quieted, signaling ones first, with their sign
+    let snan: d64 = "-sNaN7".parse().unwrap();
+    let qnan: d64 = "NaN3".parse().unwrap();
+    let one = d64::from_parts(false, 1, 0);
+    assert_eq!((qnan + snan).to_string(), "-NaN7");
+    assert_eq!((one - qnan).to_string(), "NaN3");
+    assert_eq!((qnan * one).to_string(), "NaN3");
+    assert!(!(snan / one).is_signaling_nan());
+
+    // Invalid operations give the default NaN
+    let zero = d64::default();
+    assert_eq!(
+        (d64::INFINITY - d64::INFINITY).to_bits(),
+        d64::NAN.to_bits()
+    );
+    assert_eq!((d64::INFINITY * zero).to_bits(), d64::NAN.to_bits());
+    assert_eq!((zero / zero).to_bits(), d64::NAN.to_bits());
+    assert_eq!(
+        (d64::INFINITY / d64::INFINITY).to_bits(),
+        d64::NAN.to_bits()
+    );
+    assert_eq!(-one / zero, d64::NEG_INFINITY);
+    assert_eq!((one / d64::NEG_INFINITY).to_string(), "-0E-398");
+    assert_eq!(d64::INFINITY + one, d64::INFINITY);
+
+    let mut x = one;
+    x += one;
+    x *= x;
+    x -= one;
+    x /= "0.3".parse().unwrap();
+    assert_eq!(x.to_string(), "1E+1");
+}
+
+#[test]
+fn exact() {
+    let mut rng = XorShift(0x9e37_79b9_7f4a_7c15);
+
+    for _ in 0..200_000 {
+        let (ca, cb) = (coefficient(&mut rng, 16), coefficient(&mut rng, 16));
+        let (ea, eb) = ((rng.next() % 17) as i32 - 8, (rng.next() % 17) as i32 - 8);
+        let (x, y) = (
+            d128::from_parts(false, ca, ea),
+            d128::from_parts(false, cb, eb),
+        );
+
+        // Exact results take the exponent IEEE 754 prefers
+        let exp = ea.min(eb);
+        let sum = ca * 10u128.pow((ea - exp) as u32) + cb * 10u128.pow((eb - exp) as u32);
+        assert_eq!((x + y).to_parts(), Some((false, sum, exp)), "{x} + {y}");
+        assert_eq!(
+            (x * y).to_parts(),
+            Some((false, ca * cb, ea + eb)),
+            "{x} * {y}"
+        );
+
+        if cb != 0 {
+            let product = x * y;
+            assert_eq!(
+                (product / y).to_parts(),
+                Some((false, ca, ea)),
+                "{product} / {y}"
+            );
+        }
+
+        // Rounding to 16 digits, against integer arithmetic
+        let (x, y) = (
+            d64::from_parts(false, ca, ea),
+            d64::from_parts(true, cb, eb),
+        );
+        let (coeff, drop) = round_even(ca * cb, 16);
+        assert_eq!(
+            (x * y).to_parts(),
+            Some((true, coeff, ea + eb + drop)),
+            "{x} * {y}"
+        );
+
+        // Comparisons agree with those of the exact values
+        let order = (ca * 10u128.pow((ea - exp) as u32)).cmp(&(cb * 10u128.pow((eb - exp) as u32)));
+        let (x, y) = (
+            d128::from_parts(false, ca, ea),
+            d128::from_parts(false, cb, eb),
+        );
+        assert_eq!(x.partial_cmp(&y), Some(order), "{x} <=> {y}");
+        assert_eq!((-x).partial_cmp(&-y), Some(order.reverse()), "{x} <=> {y}");
+    }
+
+    // Members of a cohort are equal, and zeros of any sign and exponent
+    assert_eq!(d32::from_parts(false, 10, 0), d32::from_parts(false, 1, 1));
+    assert_eq!(d32::from_parts(true, 0, 5), d32::from_parts(false, 0, -5));
+    assert!(d32::NAN != d32::NAN);
+    assert!(d32::NEG_INFINITY < d32::MIN && d32::MAX < d32::INFINITY);
+}
+
+#[test]
+fn binary() {
+    let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+
+    for _ in 0..100_000 {
+        let negative = rng.next() & 1 == 1;
+        let x = d64::from_parts(
+            negative,
+            coefficient(&mut rng, 16),
+            (rng.next() % 700) as i32 - 350,
+        );
+        let y = d128::from_parts(
+            negative,
+            coefficient(&mut rng, 34),
+            (rng.next() % 700) as i32 - 370,
+        );
+
+        // Decimal to binary, against the standard library's parsing
+        for text in [x.to_string(), y.to_string()] {
+            let expected: f64 = text.parse().unwrap();
+            let z: d128 = text.parse().unwrap();
+            assert_eq!(f64::cast_from(z).to_bits(), expected.to_bits(), "{text}");
+            assert_eq!(
+                f32::cast_from(z).to_bits(),
+                text.parse::<f32>().unwrap().to_bits(),
+                "{text}"
+            );
+        }
+
+        // Binary to decimal, against its formatting to 16 or 34 digits
+        let f = f64::from_bits(rng.next());
+        if f.is_finite() {
+            let expected: d64 = format!("{:.15e}", f).parse().unwrap();
+            assert_eq!(d64::cast_from(f), expected, "{f:e}");
+            let expected: d128 = format!("{:.33e}", f).parse().unwrap();
+            assert_eq!(d128::cast_from(f), expected, "{f:e}");
+
+            // 17 digits are enough for an f64 to round trip
+            assert_eq!(f64::cast_from(d128::cast_from(f)).to_bits(), f.to_bits());
+            let g = f as f32;
+            assert_eq!(f32::cast_from(d64::cast_from(g)).to_bits(), g.to_bits());
+        }
+    }
+
+    // Exact values keep the exponent nearest zero
+    assert_eq!(d64::cast_from(0.5f64).to_string(), "0.5");
+    assert_eq!(d64::cast_from(1024.0f32).to_string(), "1024");
+    assert_eq!(d32::cast_from(0.1f64).to_string(), "0.1000000");
+    assert_eq!(d32::cast_from(1e20f64).to_string(), "1.000000E+20");
+
+    assert_eq!(d32::cast_from(f64::INFINITY), d32::INFINITY);
+    assert!(d32::cast_from(-0.0f64).is_sign_negative());
+    assert!(f64::cast_from(d32::from_parts(true, 0, 50)).is_sign_negative());
+    assert!(f64::cast_from(d32::NAN).is_nan());
+    assert!(d64::cast_from(f32::NAN).is_nan());
+    assert_eq!(f32::cast_from(d128::MAX), f32::INFINITY);
+    assert_eq!(d32::cast_from(f64::MAX), d32::INFINITY);
+    assert_eq!(f64::cast_from(d128::from_parts(false, 1, -400)), 0.0);
+    assert_eq!(d32::cast_from(f64::MIN_POSITIVE).to_string(), "0E-101");
+}
+
+#[test]
+fn quad() {
+    for &(bits, expected) in &[
+        (0x3fff_0000_0000_0000_0000_0000_0000_0000, "1"),
+        (
+            0x3ffb_9999_9999_9999_9999_9999_9999_999a,
+            "0.1000000000000000000000000000000000",
+        ),
+        (
+            0x4000_921f_b544_42d1_8469_898c_c517_01b8,
+            "3.141592653589793238462643383279503",
+        ),
+        (
+            0x7ffe_ffff_ffff_ffff_ffff_ffff_ffff_ffff,
+            "1.189731495357231765085759326628007E+4932",
+        ),
+        (
+            0x0000_0000_0000_0000_0000_0000_0000_0001,
+            "6.475175119438025110924438958227647E-4966",
+        ),
+        (
+            0x0001_0000_0000_0000_0000_0000_0000_0000,
+            "3.362103143112093506262677817321753E-4932",
+        ),
+        (
+            0x4070_0000_0000_0000_0000_0000_0000_0001,
+            "1.038459371706965525706099265844019E+34",
+        ),
+    ] {
+        let x = d128::cast_from(F128::from_bits(bits));
+        assert_eq!(x.to_string(), expected, "{bits:#x}");
+        assert_eq!(
+            d64::cast_from(F128::from_bits(bits)),
+            d64::cast_from(x),
+            "{bits:#x}"
+        );
+    }
+
+    for &(text, expected) in &[
+        ("0.1", 0x3ffb_9999_9999_9999_9999_9999_9999_999a),
+        ("1E+4932", 0x7ffe_ae59_6552_b8fd_ed99_d037_e3d0_4b75),
+        (
+            "-2.718281828459045235360287471352662",
+            0xc000_5bf0_a8b1_4576_9535_5fb8_ac40_4e79,
+        ),
+        (
+            "1.189731495357231765085759326628007E+4932",
+            0x7ffe_ffff_ffff_ffff_ffff_ffff_ffff_ffff,
+        ),
+        (
+            "1.189731495357231765085759326628008E+4932",
+            0x7fff_0000_0000_0000_0000_0000_0000_0000,
+        ),
+        (
+            "9.999999999999999999999999999999999E+6144",
+            0x7fff_0000_0000_0000_0000_0000_0000_0000,
+        ),
+        (
+            "6.475175119438025110924438958227646E-4966",
+            0x0000_0000_0000_0000_0000_0000_0000_0001,
+        ),
+        (
+            "3.237587559719012555462219479113824E-4966",
+            0x0000_0000_0000_0000_0000_0000_0000_0001,
+        ),
+        ("3.237587559719012555462219479113823E-4966", 0),
+        ("1E-6176", 0),
+    ] {
+        let x: d128 = text.parse().unwrap();
+        assert_eq!(F128::cast_from(x).to_bits(), expected, "{text}");
+    }
+}
+
+#[test]
+fn decimal() {
+    let mut rng = XorShift(0x1234_5678_9abc_def1);
+
+    for _ in 0..100_000 {
+        let negative = rng.next() & 1 == 1;
+        let x = d128::from_parts(
+            negative,
+            coefficient(&mut rng, 34),
+            (rng.next() % 1000) as i32 - 500,
+        );
+        let text = x.to_string();
+
+        // Narrowing rounds as parsing does
+        assert_eq!(
+            d64::cast_from(x).to_bits(),
+            text.parse::<d64>().unwrap().to_bits(),
+            "{text}"
+        );
+        assert_eq!(
+            d32::cast_from(x).to_bits(),
+            text.parse::<d32>().unwrap().to_bits(),
+            "{text}"
+        );
+
+        // Widening is exact
+        let y = d64::cast_from(x);
+        assert_eq!(d128::cast_from(y).to_parts(), y.to_parts());
+        let z = d32::cast_from(x);
+        assert_eq!(d64::cast_from(z).to_parts(), z.to_parts());
+    }
+
+    let nan = d64::cast_from("-sNaN12".parse::<d128>().unwrap());
+    assert_eq!(nan.to_string(), "-NaN12");
+    let nan = d32::cast_from("NaN12345678".parse::<d128>().unwrap());
+    assert_eq!(nan.to_string(), "NaN");
+    assert_eq!(d32::cast_from(d64::MAX), d32::INFINITY);
+}
@@ -0,0 +1,229 @@
+//! DoubleDouble tests
+//!
+//! The error-free transformations and the f128 conversions must be exact,
+//! and the arithmetic must stay within its published error bounds of the
+//! exact result, computed in f128.
+
+#![cfg(feature = "casting")]
+#![feature(f128)]
+
+#[path = "common/quad.rs"]
+mod quad;
+#[path = "common/rng.rs"]
+mod rng;
+
+use floats::casting::CastFrom;
+use floats::{f128 as F128, DoubleDouble};
+use quad::wide;
+use rng::XorShift;
+
+/// Splits std's f128 through ours.
+fn split(x: f128) -> DoubleDouble {
+    DoubleDouble::cast_from(F128::from_bits(x.to_bits()))
+}
+
+/// Returns a random normalized pair near 2^`center`.
+fn pair(rng: &mut XorShift, center: i32) -> DoubleDouble {
+    let hi = rng.f64(center, 0);
+    DoubleDouble::two_sum(hi, rng.f64(center - 54, 2))
+}
+
+/// Asserts that `x` is normalized and within `ulps` units of 2^-106 of
+/// `exact`, relative to `exact`.
+fn assert_close(x: DoubleDouble, exact: f128, ulps: f128) {
+    assert_eq!(x.hi + x.lo, x.hi, "{x:?} is not normalized");
+    let err = (wide(x) - exact).abs();
+    assert!(
+        err <= ulps * 2f128.powi(-106) * exact.abs(),
+        "{x:?} vs {exact:?}"
+    );
+}
+
+#[test]
+fn error_free() {
+    let mut rng = XorShift(0x9e37_79b9_7f4a_7c15);
+
+    for _ in 0..200_000 {
+        let a = rng.f64(0, 300);
+        let b = rng.f64(0, 300);
+        let (x, y) = (a as f128, b as f128);
+
+        // Each sum and product of two f64 is exact in f128
+        let s = DoubleDouble::two_sum(a, b);
+        assert_eq!(s.hi, a + b);
+        assert_eq!(s.hi as f128 + s.lo as f128, x + y, "{a:?} + {b:?}");
+
+        let (big, small) = if a.abs() >= b.abs() { (a, b) } else { (b, a) };
+        let f = DoubleDouble::fast_two_sum(big, small);
+        assert_eq!((f.hi, f.lo), (s.hi, s.lo), "{a:?} + {b:?}");
+
+        let p = DoubleDouble::two_prod(a, b);
+        assert_eq!(p.hi, a * b);
+        assert_eq!(p.hi as f128 + p.lo as f128, x * y, "{a:?} * {b:?}");
+    }
+}
+
+#[test]
+fn arithmetic() {
+    let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+
+    for _ in 0..200_000 {
+        let (cx, cy) = (rng.next() % 200, rng.next() % 200);
+        let x = pair(&mut rng, cx as i32 - 100);
+        let y = pair(&mut rng, cy as i32 - 100);
+        let (a, b) = (wide(x), wide(y));
+
+        assert_close(x + y, a + b, 3.0);
+        assert_close(x - y, a - b, 3.0);
+        assert_close(x * y, a * b, 7.0);
+        assert_close(x / y, a / b, 15.0);
+
+        let mut z = x;
+        z += y;
+        assert_eq!(z, x + y);
+        z -= y;
+        assert_eq!(z, x + y - y);
+        z *= y;
+        assert_eq!(z, (x + y - y) * y);
+        z /= y;
+        assert_eq!(z, (x + y - y) * y / y);
+        assert_eq!(wide(-x), -a);
+    }
+
+    // Cancellation leaves the low parts
+    let x = DoubleDouble::two_sum(1.0, 1e-20);
+    let y = DoubleDouble::two_sum(1.0, -1e-20);
+    assert_eq!((x - y).hi, 2e-20);
+}
+
+#[test]
+fn special() {
+    let one = DoubleDouble::ONE;
+    let zero = DoubleDouble::ZERO;
+
+    assert_eq!(one / zero, DoubleDouble::INFINITY);
+    assert_eq!(-one / zero, DoubleDouble::NEG_INFINITY);
+    assert!((zero / zero).is_nan());
+    assert_eq!(DoubleDouble::INFINITY + one, DoubleDouble::INFINITY);
+    assert_eq!(DoubleDouble::INFINITY * one, DoubleDouble::INFINITY);
+    assert!((DoubleDouble::INFINITY - DoubleDouble::INFINITY).is_nan());
+    assert!(DoubleDouble::NAN.is_nan() && !DoubleDouble::NAN.is_infinite());
+    assert!(DoubleDouble::INFINITY.is_infinite() && !DoubleDouble::INFINITY.is_finite());
+    assert!(DoubleDouble::NEG_INFINITY.is_sign_negative());
+    assert!(one.is_sign_positive() && one.is_finite());
+
+    // Overflow goes to infinity rather than NaN
+    let max = DoubleDouble::from(f64::MAX);
+    assert_eq!(max + max, DoubleDouble::INFINITY);
+    assert_eq!(max * max, DoubleDouble::INFINITY);
+    assert_eq!(max / DoubleDouble::from(0.5), DoubleDouble::INFINITY);
+
+    assert!(DoubleDouble::new(1.0, 0.0) < DoubleDouble::new(1.0, 1e-20));
+    assert_eq!(DoubleDouble::from(2.5).to_f64(), 2.5);
+    assert_eq!(DoubleDouble::default(), zero);
+}
+
+#[test]
+fn f128_split() {
+    let mut rng = XorShift(0x1234_5678_9abc_def1);
+
+    for &(center, spread) in &[(16383, 1000), (16383 - 1022, 60), (16383 + 1023, 2)] {
+        for _ in 0..100_000 {
+            let x = f128::from_bits(rng.f128(center, spread));
+            let d = split(x);
+
+            // hi is the nearest f64, and lo the nearest to the exact rest
+            assert_eq!(d.hi.to_bits(), (x as f64).to_bits(), "{x:?}");
+            if d.hi.is_infinite() {
+                assert_eq!(d.lo, 0.0);
+                continue;
+            }
+            assert_eq!(
+                d.lo.to_bits(),
+                ((x - d.hi as f128) as f64).to_bits(),
+                "{x:?}"
+            );
+
+            // Joining is the correctly rounded sum
+            assert_eq!(wide(d).to_bits(), (d.hi as f128 + d.lo as f128).to_bits());
+        }
+    }
+
+    // Every pair of f64 whose sum fits round trips exactly
+    for _ in 0..100_000 {
+        let a = rng.f64(0, 1000);
+        let b = rng.f64(a.abs().log2() as i32 - 55, 4);
+        let d = DoubleDouble::two_sum(a, b);
+        let x = wide(d);
+
+        assert_eq!(x, a as f128 + b as f128);
+        let e = split(x);
+        assert_eq!(
+            (e.hi.to_bits(), e.lo.to_bits()),
+            (d.hi.to_bits(), d.lo.to_bits())
+        );
+    }
+
+    // Arbitrary finite pairs, with any gap between the parts
+    for _ in 0..100_000 {
+        let d = DoubleDouble::new(rng.f64(0, 1000), rng.f64(0, 1000));
+        assert_eq!(
+            wide(d).to_bits(),
+            (d.hi as f128 + d.lo as f128).to_bits(),
+            "{d:?}"
+        );
+    }
+}
+
+#[test]
+fn f128_special() {
+    assert_eq!(wide(DoubleDouble::INFINITY), f128::INFINITY);
+    assert_eq!(
+        wide(DoubleDouble::new(1.0, f64::NEG_INFINITY)),
+        f128::NEG_INFINITY
+    );
+    assert_eq!(split(f128::NEG_INFINITY), DoubleDouble::NEG_INFINITY);
+    assert_eq!(split(f128::MAX), DoubleDouble::INFINITY);
+
+    // Signed zeros add as f64 zeros do
+    assert!(wide(DoubleDouble::new(-0.0, -0.0)).is_sign_negative());
+    assert!(wide(DoubleDouble::new(-0.0, 0.0)).is_sign_positive());
+    assert!(wide(DoubleDouble::new(1.0, -1.0)).is_sign_positive());
+    assert!(split(-0.0).hi.is_sign_negative());
+
+    // Values too small for f64 flush to zero with their sign
+    let tiny = split(-f128::from_bits(1));
+    assert_eq!(tiny.hi.to_bits(), (-0.0f64).to_bits());
+
+    // NaNs are quieted, keeping their sign and top payload bits
+    let nan = wide(DoubleDouble::new(
+        f64::from_bits(0xfff4_0000_0000_0001),
+        0.0,
+    ));
+    assert_eq!(nan.to_bits(), 0xffff_c000_0000_0000_1000_0000_0000_0000);
+    let nan = split(f128::from_bits(0x7fff_4000_0000_0000_0000_0000_0000_0000));
+    assert_eq!(nan.hi.to_bits(), 0x7ffc_0000_0000_0000);
+}
+
+#[test]
+fn ibm_long_double() {
+    // 0.1L as stored on big endian PowerPC
+    let bytes = [
+        0x3f, 0xb9, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9a, //
+        0xbc, 0x59, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9a,
+    ];
+    let tenth = DoubleDouble::from_be_bytes(bytes);
+    assert_eq!(tenth.hi, 0.1);
+    assert_eq!(tenth.lo.to_bits(), 0xbc59_9999_9999_999a);
+    assert_eq!(tenth.to_be_bytes(), bytes);
+    assert_eq!(split(wide(tenth)), tenth);
+    assert!((wide(tenth) - 0.1).abs() < 2f128.powi(-110));
+
+    // On little endian PowerPC, hi still comes first
+    let mut le = bytes;
+    le[..8].reverse();
+    le[8..].reverse();
+    assert_eq!(DoubleDouble::from_le_bytes(le), tenth);
+    assert_eq!(tenth.to_le_bytes(), le);
+    assert_eq!(DoubleDouble::from_ne_bytes(tenth.to_ne_bytes()), tenth);
+}
@@ -8,6 +8,7 @@
 mod common;
 
 use common::{Bitable, Customized};
+use floats::IeeeFloat;
 
 /// Generic PartialEq test
 #[rstest::rstest]
@@ -85,7 +86,7 @@ use common::{Bitable, Customized};
 fn test_partial_eq<T>(#[case] a: T, #[case] b: T)
 where
     T: PartialEq + Copy + core::fmt::Debug + Bitable + Customized,
-    T::Custom: PartialEq + Bitable<Bits = T::Bits>,
+    T::Custom: IeeeFloat<Bits = T::Bits>,
 {
     let ca = T::Custom::from_bits(a.to_bits());
     let cb = T::Custom::from_bits(b.to_bits());
@@ -0,0 +1,337 @@
+//! f256 tests
+//!
+//! Widening must be exact, narrowing and arithmetic on operands from f128
+//! must agree with std's correctly rounded f128 results, and strings are
+//! checked against std's exact f64 formatting and by round trips.
+
+#![cfg(feature = "casting")]
+#![feature(f128)]
+
+#[path = "common/rng.rs"]
+mod rng;
+
+use floats::casting::CastFrom;
+use floats::{f128 as F128, f256};
+use rng::XorShift;
+use std::thread;
+
+/// Widens std's f128 through ours.
+fn wide(x: f128) -> f256 {
+    f256::cast_from(F128::from_bits(x.to_bits()))
+}
+
+/// Narrows to std's f128 through ours.
+fn narrow(x: f256) -> f128 {
+    f128::from_bits(F128::cast_from(x).to_bits())
+}
+
+/// Returns the small integer `n`.
+fn int(n: u8) -> f256 {
+    f256::cast_from(n as f64)
+}
+
+#[test]
+fn encodings() {
+    let one = int(1);
+    assert_eq!(one.to_bits(), [0, 0x3ffff << 108]);
+    assert_eq!(f256::INFINITY.to_bits(), [0, 0x7ffff << 108]);
+    assert_eq!(f256::MAX.to_bits()[0], u128::MAX);
+    assert_eq!((-f256::MAX).to_bits(), f256::MIN.to_bits());
+    assert_eq!(f256::MANTISSA_DIGITS, 237);
+
+    assert!(f256::NAN.is_nan() && !f256::NAN.is_finite());
+    assert!(f256::from_bits([1, 0x7ffff << 108]).is_nan());
+    assert!(f256::INFINITY.is_infinite() && !f256::INFINITY.is_nan());
+    assert!(f256::NEG_INFINITY.is_sign_negative());
+    assert!(f256::MAX.is_finite() && f256::default().is_sign_positive());
+
+    // The low word comes first in memory on little endian targets
+    let x = f256::from_bits([0x0102, 0x0304 << 112]);
+    let be = x.to_be_bytes();
+    assert_eq!((be[0], be[1], be[30], be[31]), (3, 4, 1, 2));
+    let mut le = be;
+    le.reverse();
+    assert_eq!(x.to_le_bytes(), le);
+    assert_eq!(f256::from_be_bytes(be).to_bits(), x.to_bits());
+    assert_eq!(f256::from_le_bytes(le).to_bits(), x.to_bits());
+    assert_eq!(f256::from_ne_bytes(x.to_ne_bytes()).to_bits(), x.to_bits());
+
+    // Comparisons are by value
+    assert_eq!(f256::default(), -f256::default());
+    assert_ne!(f256::NAN, f256::NAN);
+    assert!(f256::NAN.partial_cmp(&one).is_none());
+    assert!(f256::MIN < -one && -one < f256::default() && one < f256::MAX);
+    assert!(f256::NEG_INFINITY < f256::MIN && f256::MAX < f256::INFINITY);
+
+    // Neighbours and the unit in the last place
+    let tiny = f256::from_bits([1, 0]);
+    assert_eq!(f256::default().next_up().to_bits(), tiny.to_bits());
+    assert_eq!((-f256::default()).next_down().to_bits(), (-tiny).to_bits());
+    assert_eq!(f256::MAX.next_up(), f256::INFINITY);
+    assert_eq!(f256::INFINITY.next_down(), f256::MAX);
+    assert_eq!(f256::NEG_INFINITY.next_up(), f256::MIN);
+    assert_eq!(f256::MIN.next_down(), f256::NEG_INFINITY);
+    assert_eq!(f256::NEG_INFINITY.next_down(), f256::NEG_INFINITY);
+    assert_eq!(f256::default().next_down().to_bits(), (-tiny).to_bits());
+    assert_eq!(one.next_down().next_up(), one);
+    assert_eq!((-one).next_up().next_down(), -one);
+    assert_eq!(one.ulp(), one.next_up() - one);
+    assert_eq!(one.next_down().ulp(), one - one.next_down());
+    assert_eq!(tiny.ulp(), tiny);
+    assert!(f256::NAN.next_up().is_nan());
+    assert!(f256::NAN.next_down().is_nan());
+
+    const ABOVE: f256 = f256::INFINITY.next_down().next_up();
+    assert_eq!(ABOVE, f256::INFINITY);
+}
+
+#[test]
+fn conversions() {
+    let mut rng = XorShift(0x5851_f42d_4c95_7f2d);
+
+    for &(center, spread) in &[(16383, 16383), (16383, 100), (0, 120)] {
+        for _ in 0..100_000 {
+            let x = f128::from_bits(rng.f128(center, spread));
+            let y = wide(x);
+
+            // Every f128 and f64 widens exactly and narrows back
+            assert_eq!(narrow(y).to_bits(), x.to_bits(), "{x:?}");
+            let d = x as f64;
+            assert_eq!(f64::cast_from(f256::cast_from(d)).to_bits(), d.to_bits());
+
+            // Narrowing rounds once
+            assert_eq!(f64::cast_from(y).to_bits(), d.to_bits(), "{x:?}");
+            assert_eq!(f32::cast_from(y).to_bits(), (x as f32).to_bits(), "{x:?}");
+        }
+    }
+
+    // Between the f128 neighbours, just above and below the midpoint
+    let x = 1.0f128 + f128::EPSILON;
+    let half = wide(f128::EPSILON) / int(2);
+    let above = wide(x) + half + half * half;
+    let below = wide(x) + half - half * half;
+    assert_eq!(narrow(wide(x) + half), x + f128::EPSILON);
+    assert_eq!(narrow(above), x + f128::EPSILON);
+    assert_eq!(narrow(below), x);
+
+    // NaNs are quieted, keeping their sign and top payload bits
+    let nan = wide(f128::from_bits(0xffff_4000_0000_0000_0000_0000_0000_0001));
+    assert_eq!(
+        nan.to_bits(),
+        [1 << 124, 0xffff_fc00_0000_0000_0000_0000_0000_0000]
+    );
+    assert_eq!(
+        narrow(nan).to_bits(),
+        0xffff_c000_0000_0000_0000_0000_0000_0001
+    );
+    assert_eq!(narrow(wide(f128::NEG_INFINITY)), f128::NEG_INFINITY);
+    assert_eq!(narrow(f256::MAX), f128::INFINITY);
+    assert_eq!(
+        narrow(-f256::from_bits([1, 0])).to_bits(),
+        (-0.0f128).to_bits()
+    );
+}
+
+#[test]
+fn arithmetic() {
+    let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+
+    for _ in 0..100_000 {
+        let a = f128::from_bits(rng.f128(16383, 200));
+        let b = f128::from_bits(rng.f128(16383, 200));
+        let (x, y) = (wide(a), wide(b));
+
+        // Products of f128 are exact, and round once on the way back
+        let p = x * y;
+        assert_eq!(narrow(p).to_bits(), (a * b).to_bits(), "{a:?} * {b:?}");
+        assert_eq!(p / y, x, "{a:?} * {b:?}");
+        assert_eq!((p / x).to_bits(), y.to_bits(), "{a:?} * {b:?}");
+
+        // So are sums of nearby f128
+        let c = f128::from_bits(rng.f128(((a.to_bits() >> 112) & 0x7fff) as i32, 100));
+        let z = wide(c);
+        assert_eq!(narrow(x + z).to_bits(), (a + c).to_bits(), "{a:?} + {c:?}");
+        assert_eq!(narrow(x - z).to_bits(), (a - c).to_bits(), "{a:?} - {c:?}");
+        assert_eq!(x + z - z, x);
+
+        let mut w = x;
+        w += z;
+        assert_eq!(w, x + z);
+        w -= z;
+        assert_eq!(w, x + z - z);
+        w *= y;
+        assert_eq!(w, (x + z - z) * y);
+        w /= y;
+        assert_eq!(w, (x + z - z) * y / y);
+    }
+
+    // 1/3 is 0.0101... in binary, cut off before a zero bit
+    let third = int(1) / int(3);
+    let pattern = u128::MAX / 3;
+    assert_eq!(third.to_bits(), [pattern, 0x3fffd << 108 | pattern >> 20]);
+    assert_eq!(narrow(third), 1.0 / 3.0);
+}
+
+#[test]
+fn special() {
+    let one = int(1);
+    let zero = f256::default();
+    let tiny = f256::from_bits([1, 0]);
+
+    assert_eq!(one / zero, f256::INFINITY);
+    assert_eq!(-one / zero, f256::NEG_INFINITY);
+    assert!((zero / zero).is_nan());
+    assert!((f256::INFINITY - f256::INFINITY).is_nan());
+    assert!((f256::INFINITY * zero).is_nan());
+    assert_eq!(f256::INFINITY + one, f256::INFINITY);
+    assert_eq!(one / f256::INFINITY, zero);
+
+    // Signed zeros
+    assert!((-zero + -zero).is_sign_negative());
+    assert!((-zero + zero).is_sign_positive());
+    assert!((one - one).is_sign_positive());
+    assert!((-one * zero).is_sign_negative());
+
+    // Overflow and gradual underflow
+    assert_eq!(f256::MAX + f256::MAX, f256::INFINITY);
+    assert_eq!(f256::MAX * f256::MIN, f256::NEG_INFINITY);
+    assert_eq!(tiny + tiny, tiny * int(2));
+    assert_eq!(tiny / int(2), zero);
+    assert_eq!((tiny * int(3)) / int(2), tiny * int(2));
+
+    // The first NaN propagates, quieted, and subtraction keeps its sign
+    let snan = f256::from_bits([7, 0x7ffff << 108]);
+    let nan = -f256::NAN;
+    assert_eq!((snan + nan).to_bits(), [7, 0x7ffff << 108 | 1 << 107]);
+    assert_eq!((one - nan).to_bits(), nan.to_bits());
+    assert_eq!((nan * snan).to_bits(), nan.to_bits());
+}
+
+#[test]
+fn strings() {
+    let mut rng = XorShift(0x1234_5678_9abc_def1);
+
+    for _ in 0..20_000 {
+        let x = rng.f64(0, 1100);
+        let y = f256::cast_from(x);
+
+        // The exact value rounds like std's
+        let precision = (rng.next() % 40) as usize;
+        assert_eq!(format!("{y:.precision$e}"), format!("{x:.precision$e}"));
+        assert_eq!(format!("{y:+.precision$}"), format!("{x:+.precision$}"));
+
+        // The shortest digits read back, and so do std's exact digits
+        assert_eq!(y.to_string().parse::<f256>(), Ok(y), "{x:?}");
+        assert_eq!(format!("{y:e}").parse::<f256>(), Ok(y), "{x:?}");
+        assert_eq!(format!("{x:.800e}").parse::<f256>(), Ok(y), "{x:?}");
+        assert_eq!(f64::cast_from(format!("{x:e}").parse::<f256>().unwrap()), x);
+    }
+
+    for _ in 0..2_000 {
+        let x = f128::from_bits(rng.f128(16383, 16383));
+        let y = wide(x) * wide(f128::from_bits(rng.f128(16383, 16383)));
+        assert_eq!(y.to_string().parse::<f256>(), Ok(y), "{x:?}");
+        assert_eq!(format!("{y:E}").parse::<f256>(), Ok(y), "{x:?}");
+    }
+
+    // Short values and the flags, as std writes them
+    for &x in &[0.0, -0.0, 0.5, -1.25, 1e21, 3.0517578125e-5, 100.0] {
+        let y = f256::cast_from(x);
+        assert_eq!(format!("{y}"), format!("{x}"));
+        assert_eq!(format!("{y:e} {y:E}"), format!("{x:e} {x:E}"));
+        assert_eq!(
+            format!("{y:+012.3}|{y:<9}|{y:^9}|{y:*>9}"),
+            format!("{x:+012.3}|{x:<9}|{x:^9}|{x:*>9}")
+        );
+        assert_eq!(format!("{y:08.2e}"), format!("{x:08.2e}"));
+    }
+    for &x in &[f64::INFINITY, f64::NEG_INFINITY, f64::NAN] {
+        let y = f256::cast_from(x);
+        assert_eq!(
+            format!("{y}|{y:e}|{y:+08}|{y:<6}"),
+            format!("{x}|{x:e}|{x:+08}|{x:<6}")
+        );
+    }
+
+    // Rounding half to even, with carries through every digit
+    let y = f256::cast_from(0.125);
+    assert_eq!(format!("{y:.2} {y:.1e}"), "0.12 1.2e-1");
+    let y = f256::cast_from(9.96);
+    assert_eq!(format!("{y:.1} {y:.1e}"), "10.0 1.0e1");
+
+    // The extremes
+    let tiny = f256::from_bits([1, 0]);
+    assert_eq!(format!("{:.5e}", f256::MAX), "1.61133e78913");
+    assert_eq!(format!("{:e}", tiny).parse::<f256>(), Ok(tiny));
+    assert_eq!(format!("{:.3e}", tiny), "2.248e-78984");
+    assert_eq!(f256::MAX.to_string().len(), 78914);
+    assert_eq!(f256::MAX.to_string().parse::<f256>(), Ok(f256::MAX));
+    assert_eq!("1e78914".parse::<f256>(), Ok(f256::INFINITY));
+    assert_eq!(
+        "-1e-78985".parse::<f256>().map(f256::is_sign_negative),
+        Ok(true)
+    );
+    assert_eq!("1e-78985".parse::<f256>(), Ok(f256::default()));
+
+    // Syntax and errors, as std's
+    assert_eq!("+InFiNiTy".parse::<f256>(), Ok(f256::INFINITY));
+    assert!("nan".parse::<f256>().unwrap().is_nan());
+    assert_eq!("1.".parse::<f256>(), ".1e1".parse::<f256>());
+    for s in &["", "-", ".", "1e", "e5", "1.5x", "0x10", "1e+", "--1"] {
+        assert_eq!(
+            s.parse::<f256>().unwrap_err().to_string(),
+            s.parse::<f64>().unwrap_err().to_string(),
+            "{s:?}"
+        );
+    }
+}
+
+#[test]
+fn ties() {
+    // 1 + 2^-237 lies halfway between 1 and its neighbour, and its exact
+    // digits decide the rounding however many there are
+    let one = int(1);
+    let half = (one.next_up() - one) / int(2);
+    let exact = format!("1{}", &format!("{half:.237}")[1..]);
+
+    assert_eq!(exact.parse::<f256>(), Ok(one));
+    assert_eq!(format!("{exact}000000").parse::<f256>(), Ok(one));
+    assert_eq!(format!("{exact}000001").parse::<f256>(), Ok(one.next_up()));
+
+    let below = format!("{}4{}", &exact[..exact.len() - 1], "9".repeat(300));
+    assert_eq!(below.parse::<f256>(), Ok(one));
+
+    // Just above the odd neighbour's midpoint, and exactly on it
+    let odd = one.next_up();
+    let digits = format!("1{}", &format!("{:.237}", half * int(3))[1..]);
+    assert_eq!(digits.parse::<f256>(), Ok(odd.next_up()));
+    assert_eq!(
+        format!("{}e0", &digits[..digits.len() - 1]).parse::<f256>(),
+        Ok(odd)
+    );
+}
+
+#[test]
+fn small_stack() {
+    // Values of ordinary magnitude, ties included, never need the big
+    // integers of the extremes of the range
+    let run = || {
+        let one = int(1);
+        let half = (one.next_up() - one) / int(2);
+        let tie = format!("1{}000001", &format!("{half:.237}")[1..]);
+        assert_eq!(tie.parse::<f256>(), Ok(one.next_up()));
+
+        for s in ["1.5", "-2.5e-300", "1e500", "3e-500"] {
+            let x = s.parse::<f256>().unwrap();
+            assert_eq!(x.to_string().parse::<f256>(), Ok(x));
+            assert_eq!(format!("{x:.40e}").parse::<f256>(), Ok(x));
+            assert!(format!("{x:.40}").len() > 40);
+        }
+    };
+    thread::Builder::new()
+        .stack_size(16 << 10)
+        .spawn(run)
+        .unwrap()
+        .join()
+        .unwrap();
+}
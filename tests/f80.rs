@@ -0,0 +1,360 @@
+//! f80 tests
+//!
+//! Widening is checked against the encoding decoded by hand, narrowing
+//! against std's correctly rounded f128 casts and, on x86-64, against the
+//! x87 itself.
+
+#![cfg(feature = "casting")]
+#![feature(f16, f128)]
+
+#[path = "common/quad.rs"]
+mod quad;
+#[path = "common/rng.rs"]
+mod rng;
+
+use floats::casting::CastFrom;
+use floats::{f16 as F16, f80};
+use quad::{narrow, pow2, wide};
+use rng::XorShift;
+
+/// Decodes an f80 by hand, or returns `None` for the NaNs and the
+/// encodings the x87 rejects.
+fn decode(bits: u128) -> Option<f128> {
+    let negative = bits >> 79 != 0;
+    let exp = (bits >> 64) as i32 & 0x7fff;
+    let int = bits >> 63 & 1 != 0;
+    let mant = bits as u64;
+
+    let abs = match (exp, int) {
+        (0x7fff, true) if mant << 1 == 0 => f128::INFINITY,
+        (0, _) => mant as f128 * pow2(1 - 16383 - 63),
+        (_, true) if exp < 0x7fff => mant as f128 * pow2(exp - 16383 - 63),
+        _ => return None,
+    };
+
+    Some(if negative { -abs } else { abs })
+}
+
+/// Returns a random f80 with a biased exponent within `spread` of `center`,
+/// with the integer bit set as the x87 would write it.
+fn random(rng: &mut XorShift, center: i32, spread: i32) -> u128 {
+    let bits = rng.f128(center, spread);
+    let exp = bits >> 112 & 0x7fff;
+    let int = (exp != 0) as u128;
+    (bits >> 127) << 79 | exp << 64 | int << 63 | (bits >> 49) & ((1 << 63) - 1)
+}
+
+/// Returns a random bit pattern, often in one of the unusual encodings.
+fn unusual(rng: &mut XorShift) -> u128 {
+    let n = rng.next();
+    let mant = rng.next() as u128;
+    let exp: u128 = match n % 4 {
+        0 => 0,
+        1 => 0x7fff,
+        2 => 1 + (n as u128 >> 8) % 0x7ffe,
+        _ => 0x3fff,
+    };
+    (n as u128 >> 2 & 1) << 79 | exp << 64 | mant
+}
+
+#[test]
+fn widen() {
+    let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+
+    for i in 0..200_000 {
+        let bits = if i % 2 == 0 {
+            unusual(&mut rng)
+        } else {
+            random(&mut rng, 16383, 16383)
+        };
+        let x = f80::from_bits(bits);
+
+        match decode(bits) {
+            Some(y) => {
+                assert!(!x.is_nan(), "{bits:#x}");
+                assert_eq!(x.is_infinite(), y.is_infinite(), "{bits:#x}");
+                assert_eq!(x.is_sign_negative(), y.is_sign_negative(), "{bits:#x}");
+                assert_eq!(wide(x).to_bits(), y.to_bits(), "{bits:#x}");
+            }
+
+            None => {
+                assert!(x.is_nan(), "{bits:#x}");
+                assert!(wide(x).is_nan(), "{bits:#x}");
+                assert!(f64::cast_from(x).is_nan(), "{bits:#x}");
+                assert!(f32::cast_from(x).is_nan(), "{bits:#x}");
+                assert!(F16::cast_from(x).is_nan(), "{bits:#x}");
+            }
+        }
+    }
+}
+
+#[test]
+fn narrow_to_ieee() {
+    let mut rng = XorShift(0x9e37_79b9_7f4a_7c15);
+
+    // Around the subnormal and overflow thresholds of each format, where
+    // the widening to f128 is exact and std's casts round once
+    for &(center, spread) in &[
+        (16383, 16383),
+        (16383 - 1022, 60),
+        (16383 + 1023, 8),
+        (16383 - 126, 30),
+        (16383 + 127, 8),
+        (16383 - 14, 16),
+        (16383 + 15, 8),
+        (0, 4),
+    ] {
+        for i in 0..50_000 {
+            let bits = if i % 8 == 0 {
+                unusual(&mut rng)
+            } else {
+                random(&mut rng, center, spread)
+            };
+            let x = f80::from_bits(bits);
+            let y = match decode(bits) {
+                Some(y) => y,
+                None => continue,
+            };
+
+            assert_eq!(
+                f64::cast_from(x).to_bits(),
+                (y as f64).to_bits(),
+                "{bits:#x}"
+            );
+            assert_eq!(
+                f32::cast_from(x).to_bits(),
+                (y as f32).to_bits(),
+                "{bits:#x}"
+            );
+            assert_eq!(
+                F16::cast_from(x).to_bits(),
+                (y as f16).to_bits(),
+                "{bits:#x}"
+            );
+        }
+    }
+}
+
+#[test]
+fn from_ieee() {
+    let mut rng = XorShift(0x1234_5678_9abc_def1);
+
+    for _ in 0..200_000 {
+        let n = rng.next();
+
+        // Every narrower value is exact, and round trips
+        let d = f64::from_bits(n);
+        let s = f32::from_bits(n as u32);
+        let h = F16::from_bits(n as u16);
+
+        if !d.is_nan() {
+            assert_eq!(wide(f80::cast_from(d)).to_bits(), (d as f128).to_bits());
+            assert_eq!(f64::cast_from(f80::cast_from(d)).to_bits(), d.to_bits());
+        }
+        if !s.is_nan() {
+            assert_eq!(wide(f80::cast_from(s)).to_bits(), (s as f128).to_bits());
+            assert_eq!(f32::cast_from(f80::cast_from(s)).to_bits(), s.to_bits());
+        }
+        if !h.is_nan() {
+            assert_eq!(wide(f80::cast_from(h)).to_bits(), wide(h).to_bits());
+            assert_eq!(F16::cast_from(f80::cast_from(h)).to_bits(), h.to_bits());
+        }
+    }
+}
+
+#[test]
+fn from_f128() {
+    let mut rng = XorShift(0x0123_4567_89ab_cdef);
+
+    for &(center, spread) in &[(16383, 16383), (0, 70), (0x7ffe, 2)] {
+        for _ in 0..100_000 {
+            let x = f128::from_bits(rng.f128(center, spread));
+            let r = narrow::<f80>(x);
+            let bits = r.to_bits();
+
+            // The integer bit is set exactly when the exponent is not zero
+            assert_eq!(bits >> 63 & 1, (bits >> 64 & 0x7fff != 0) as u128, "{x:?}");
+
+            let w = wide(r);
+            let exp = (bits >> 64 & 0x7fff).max(1) as i32;
+            let ulp = pow2(exp - 16383 - 63);
+
+            if w.is_infinite() {
+                assert!(x.abs() >= wide(f80::MAX) + ulp / 2.0, "{x:?}");
+                continue;
+            }
+
+            // The difference is exact, as both are within a binade
+            let diff = (x - w).abs();
+            assert!(diff * 2.0 <= ulp, "{x:?}");
+            if diff * 2.0 == ulp {
+                assert_eq!(bits & 1, 0, "{x:?}");
+            }
+            assert_eq!(w.is_sign_negative(), x.is_sign_negative(), "{x:?}");
+        }
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x87 {
+    use super::*;
+    use core::arch::asm;
+
+    fn to_f64(x: f80) -> f64 {
+        let bytes = x.to_le_bytes();
+        let mut out = 0f64;
+        unsafe {
+            asm!(
+                "fld tbyte ptr [{0}]",
+                "fstp qword ptr [{1}]",
+                in(reg) bytes.as_ptr(),
+                in(reg) &mut out,
+            );
+        }
+        out
+    }
+
+    fn to_f32(x: f80) -> f32 {
+        let bytes = x.to_le_bytes();
+        let mut out = 0f32;
+        unsafe {
+            asm!(
+                "fld tbyte ptr [{0}]",
+                "fstp dword ptr [{1}]",
+                in(reg) bytes.as_ptr(),
+                in(reg) &mut out,
+            );
+        }
+        out
+    }
+
+    fn from_f64(x: f64) -> f80 {
+        let mut bytes = [0u8; 10];
+        unsafe {
+            asm!(
+                "fld qword ptr [{0}]",
+                "fstp tbyte ptr [{1}]",
+                in(reg) &x,
+                in(reg) bytes.as_mut_ptr(),
+            );
+        }
+        f80::from_le_bytes(bytes)
+    }
+
+    #[test]
+    fn hardware() {
+        let mut rng = XorShift(0xdead_beef_cafe_f00d);
+
+        for i in 0..400_000 {
+            let bits = match i % 4 {
+                0 => unusual(&mut rng),
+                1 => random(&mut rng, 16383, 16383),
+                2 => random(&mut rng, 16383 - 1022, 60),
+                _ => random(&mut rng, 16383 - 126, 30),
+            };
+            let x = f80::from_bits(bits);
+
+            // NaNs agree in sign and payload except for the encodings the
+            // x87 rejects, which it replaces with its default NaN
+            let (d, s) = (f64::cast_from(x), f32::cast_from(x));
+            if x.is_nan() {
+                assert!(to_f64(x).is_nan() && d.is_nan(), "{bits:#x}");
+                assert!(to_f32(x).is_nan() && s.is_nan(), "{bits:#x}");
+                if bits >> 63 & 1 != 0 {
+                    assert_eq!(d.to_bits(), to_f64(x).to_bits(), "{bits:#x}");
+                    assert_eq!(s.to_bits(), to_f32(x).to_bits(), "{bits:#x}");
+                }
+            } else {
+                assert_eq!(d.to_bits(), to_f64(x).to_bits(), "{bits:#x}");
+                assert_eq!(s.to_bits(), to_f32(x).to_bits(), "{bits:#x}");
+            }
+
+            let d = f64::from_bits(rng.next());
+            assert_eq!(f80::cast_from(d).to_bits(), from_f64(d).to_bits(), "{d:?}");
+        }
+    }
+}
+
+#[test]
+fn encodings() {
+    // 1.0 and pi as stored by a C long double on x86
+    let one = f80::from_le_bytes([0, 0, 0, 0, 0, 0, 0, 0x80, 0xff, 0x3f]);
+    assert_eq!(one.to_bits(), 0x3fff_8000_0000_0000_0000);
+    assert_eq!(f64::cast_from(one), 1.0);
+    assert_eq!(f80::cast_from(1.0f64), one);
+
+    let pi = f80::from_bits(0x4000_c90f_daa2_2168_c235);
+    assert_eq!(
+        pi.to_le_bytes(),
+        [0x35, 0xc2, 0x68, 0x21, 0xa2, 0xda, 0x0f, 0xc9, 0x00, 0x40]
+    );
+    assert_eq!(
+        pi.to_be_bytes(),
+        [0x40, 0x00, 0xc9, 0x0f, 0xda, 0xa2, 0x21, 0x68, 0xc2, 0x35]
+    );
+    assert_eq!(f80::from_be_bytes(pi.to_be_bytes()).to_bits(), pi.to_bits());
+    assert_eq!(f80::from_ne_bytes(pi.to_ne_bytes()).to_bits(), pi.to_bits());
+    assert_eq!(f64::cast_from(pi), core::f64::consts::PI);
+    assert_eq!(f32::cast_from(pi), core::f32::consts::PI);
+    assert_eq!(wide(pi), 0xc90f_daa2_2168_c235u64 as f128 * pow2(-62));
+
+    // The upper bits are ignored
+    assert_eq!(
+        f80::from_bits(u128::MAX << 80 | 0x3fff << 64 | 1 << 63),
+        one
+    );
+    assert_eq!(f80::from_bits(u128::MAX).to_bits(), (1 << 80) - 1);
+
+    // A pseudo-denormal has the value of the same mantissa with exponent 1
+    let pseudo = f80::from_bits(0x0000_8000_0000_0000_0001);
+    let normal = f80::from_bits(0x0001_8000_0000_0000_0001);
+    assert!(pseudo.is_finite());
+    assert_eq!(pseudo, normal);
+    assert_eq!(wide(pseudo).to_bits(), wide(normal).to_bits());
+    assert_eq!(wide(pseudo), pow2(-16382) + pow2(-16445));
+
+    // Unnormals, pseudo-infinities and pseudo-NaNs are NaN
+    for bits in [
+        0x3fff_0000_0000_0000_0000,
+        0x0001_7fff_ffff_ffff_ffff,
+        0x7fff_0000_0000_0000_0000,
+        0xffff_0000_0000_0000_0000,
+        0x7fff_4000_0000_0000_0000,
+    ] {
+        let x = f80::from_bits(bits);
+        assert!(
+            x.is_nan() && !x.is_infinite() && !x.is_finite(),
+            "{bits:#x}"
+        );
+        assert_ne!(x, x);
+        assert!(wide(x).is_nan());
+    }
+
+    // Zeros and infinities
+    assert_eq!(f80::from_bits(0), -f80::from_bits(0));
+    assert!((-f80::from_bits(0)).is_sign_negative());
+    assert!(f80::INFINITY.is_infinite() && f80::NEG_INFINITY.is_infinite());
+    assert_eq!(-f80::INFINITY, f80::NEG_INFINITY);
+    assert_eq!(wide(f80::NEG_INFINITY), f128::NEG_INFINITY);
+    assert_eq!(f80::cast_from(f64::INFINITY), f80::INFINITY);
+    assert_eq!(f64::cast_from(f80::MAX), f64::INFINITY);
+    assert_eq!(f64::cast_from(f80::MIN), f64::NEG_INFINITY);
+    assert_eq!(narrow::<f80>(f128::MAX), f80::INFINITY);
+    assert_eq!(wide(f80::MAX), (2.0 - pow2(-63)) * pow2(16383));
+    assert_eq!(f80::MANTISSA_DIGITS, 64);
+
+    // NaN payloads move to the top of the mantissa, and signaling NaNs
+    // are quieted
+    let snan = f80::from_bits(0xffff_a000_0000_0000_0000);
+    assert!(snan.is_nan() && snan.is_sign_negative());
+    assert_eq!(
+        wide(snan).to_bits(),
+        0xffff_c000_0000_0000_0000_0000_0000_0000
+    );
+    assert_eq!(f64::cast_from(snan).to_bits(), 0xfffc_0000_0000_0000);
+    assert_eq!(
+        f80::cast_from(f64::from_bits(0x7ff4_0000_0000_0000)).to_bits(),
+        0x7fff_e000_0000_0000_0000
+    );
+    assert_eq!(f80::NAN.to_bits(), 0x7fff_c000_0000_0000_0000);
+}
@@ -0,0 +1,322 @@
+//! 8-bit float tests
+//!
+//! Each format is described by its parameters alone, and conversions into it
+//! are checked against the nearest of its values found by a linear search,
+//! for every f16 and for random and boundary f32 and f64 values.
+
+#![cfg(feature = "casting")]
+#![feature(f16, f128)]
+
+#[path = "common/rng.rs"]
+mod rng;
+
+use floats::casting::CastFrom;
+use floats::{f16 as F16, f8e4m3, f8e4m3fnuz, f8e5m2, f8e5m2fnuz, IeeeFloat};
+use rng::XorShift;
+
+/// The parameters of a format.
+#[derive(Clone, Copy)]
+struct Spec {
+    mant: u32,
+    bias: i32,
+    max: u8,
+    inf: bool,
+    fnuz: bool,
+}
+
+const E4M3: Spec = Spec {
+    mant: 3,
+    bias: 7,
+    max: 0x7e,
+    inf: false,
+    fnuz: false,
+};
+const E5M2: Spec = Spec {
+    mant: 2,
+    bias: 15,
+    max: 0x7b,
+    inf: true,
+    fnuz: false,
+};
+const E4M3FNUZ: Spec = Spec {
+    mant: 3,
+    bias: 8,
+    max: 0x7f,
+    inf: false,
+    fnuz: true,
+};
+const E5M2FNUZ: Spec = Spec {
+    mant: 2,
+    bias: 16,
+    max: 0x7f,
+    inf: false,
+    fnuz: true,
+};
+
+impl Spec {
+    /// The magnitude of `bits`, continuing past `MAX` as if the exponent
+    /// range were unbounded.
+    fn value(self, bits: u8) -> f64 {
+        let exp = (bits >> self.mant) as i32;
+        let mant = (bits & ((1 << self.mant) - 1)) as f64;
+
+        if exp == 0 {
+            mant * 2f64.powi(1 - self.bias - self.mant as i32)
+        } else {
+            (mant + (1 << self.mant) as f64) * 2f64.powi(exp - self.bias - self.mant as i32)
+        }
+    }
+
+    fn is_nan(self, bits: u8) -> bool {
+        if self.fnuz {
+            bits == 0x80
+        } else {
+            bits & 0x7f > self.max + self.inf as u8
+        }
+    }
+
+    /// The encoding of a non-NaN `x`, rounded to nearest even.
+    fn reference(self, x: f64, saturate: bool) -> u8 {
+        let sign = if x.is_sign_negative() { 0x80 } else { 0 };
+        let x = x.abs();
+
+        // The greatest encoding not above x, up to one past MAX
+        let mut lo = 0;
+        while lo <= self.max && self.value(lo + 1) <= x {
+            lo += 1;
+        }
+
+        let mag = if lo > self.max {
+            lo
+        } else {
+            let below = x - self.value(lo);
+            let above = self.value(lo + 1) - x;
+            if below < above || (below == above && lo % 2 == 0) {
+                lo
+            } else {
+                lo + 1
+            }
+        };
+
+        if mag > self.max {
+            if saturate {
+                sign | self.max
+            } else if self.inf {
+                sign | (self.max + 1)
+            } else if self.fnuz {
+                0x80
+            } else {
+                sign | 0x7f
+            }
+        } else if mag == 0 && self.fnuz {
+            0
+        } else {
+            sign | mag
+        }
+    }
+}
+
+/// Checks both modes of every conversion into `$fp8` for `$x`.
+macro_rules! narrow {
+    ($fp8:ty, $spec:expr, $x:expr, $float:ty, $saturating:ident) => {{
+        let x: $float = $x;
+        let wide = f64::cast_from(x);
+        let ieee = <$fp8>::cast_from(x).to_bits();
+        let sat = <$fp8>::$saturating(x).to_bits();
+
+        if wide.is_nan() {
+            assert!($spec.is_nan(ieee), "{wide:e}");
+            assert!($spec.is_nan(sat), "{wide:e}");
+        } else {
+            assert_eq!(ieee, $spec.reference(wide, false), "{wide:e}");
+            assert_eq!(sat, $spec.reference(wide, true), "{wide:e}");
+        }
+    }};
+}
+
+/// Checks every conversion into and out of `$fp8`.
+macro_rules! check {
+    ($fp8:ty, $spec:expr, $seed:literal) => {{
+        let spec: Spec = $spec;
+
+        // Widening is exact, and NaN stays NaN
+        for bits in 0..=u8::MAX {
+            let x = <$fp8>::from_bits(bits);
+            let y = f64::cast_from(x);
+
+            assert_eq!(x.is_nan(), spec.is_nan(bits), "{bits:#x}");
+            assert_eq!(y.is_nan(), spec.is_nan(bits), "{bits:#x}");
+            assert_eq!(f32::cast_from(x).is_nan(), y.is_nan());
+            assert_eq!(F16::cast_from(x).is_nan(), y.is_nan());
+            assert_eq!(x.is_infinite(), y.is_infinite(), "{bits:#x}");
+            assert_eq!(x.is_finite(), y.is_finite(), "{bits:#x}");
+
+            if !y.is_nan() {
+                let magnitude = if y.is_infinite() {
+                    f64::INFINITY
+                } else {
+                    spec.value(bits & 0x7f)
+                };
+                assert_eq!(y.abs(), magnitude, "{bits:#x}");
+                assert_eq!(y.is_sign_negative(), x.is_sign_negative(), "{bits:#x}");
+                assert_eq!(f32::cast_from(x) as f64, y);
+                assert_eq!(f64::cast_from(F16::cast_from(x)), y);
+
+                // And narrowing it back is the identity, but for -0.0 in
+                // the formats without one, and infinities when saturating
+                if !spec.fnuz || bits != 0x80 {
+                    assert_eq!(<$fp8>::cast_from(y).to_bits(), bits);
+                }
+                if y.is_finite() && (!spec.fnuz || bits != 0x80) {
+                    assert_eq!(<$fp8>::from_f64_saturating(y).to_bits(), bits);
+                }
+            }
+        }
+
+        for bits in 0..=u16::MAX {
+            narrow!($fp8, spec, F16::from_bits(bits), F16, from_f16_saturating);
+        }
+
+        // Every midpoint, with the values either side of it
+        for bits in 0..=spec.max {
+            let mid = (spec.value(bits) + spec.value(bits + 1)) / 2.0;
+            for x in [mid, -mid] {
+                narrow!($fp8, spec, x, f64, from_f64_saturating);
+                narrow!($fp8, spec, x.next_up(), f64, from_f64_saturating);
+                narrow!($fp8, spec, x.next_down(), f64, from_f64_saturating);
+                narrow!($fp8, spec, x as f32, f32, from_f32_saturating);
+                narrow!($fp8, spec, (x as f32).next_up(), f32, from_f32_saturating);
+                narrow!($fp8, spec, (x as f32).next_down(), f32, from_f32_saturating);
+            }
+        }
+
+        let mut rng = XorShift($seed);
+        for _ in 0..100_000 {
+            let n = rng.next();
+
+            // Exponents within and around the range
+            let exp = (n >> 52) % 64 + 1023 - 32;
+            let x = f64::from_bits((n & 0x800f_ffff_ffff_ffff) | exp << 52);
+            narrow!($fp8, spec, x, f64, from_f64_saturating);
+            narrow!($fp8, spec, x as f32, f32, from_f32_saturating);
+            narrow!($fp8, spec, f64::from_bits(n), f64, from_f64_saturating);
+            narrow!(
+                $fp8,
+                spec,
+                f32::from_bits(n as u32),
+                f32,
+                from_f32_saturating
+            );
+        }
+
+        for x in [f64::INFINITY, f64::NEG_INFINITY, f64::MIN_POSITIVE, 1e300] {
+            narrow!($fp8, spec, x, f64, from_f64_saturating);
+            narrow!($fp8, spec, x as f32, f32, from_f32_saturating);
+        }
+    }};
+}
+
+#[test]
+fn e4m3() {
+    check!(f8e4m3, E4M3, 0x9e37_79b9_7f4a_7c15);
+
+    assert_eq!(f64::cast_from(f8e4m3::MAX), 448.0);
+    assert_eq!(f64::cast_from(f8e4m3::MIN_POSITIVE), 2f64.powi(-6));
+    assert_eq!(f8e4m3::cast_from(464.0f32).to_bits(), 0x7e);
+    assert!(f8e4m3::cast_from(465.0f32).is_nan());
+    assert!(f8e4m3::cast_from(f32::INFINITY).is_nan());
+    assert_eq!(f8e4m3::from_f32_saturating(f32::INFINITY), f8e4m3::MAX);
+    assert_eq!(f8e4m3::from_f32_saturating(-1e9), f8e4m3::MIN);
+}
+
+#[test]
+fn e5m2() {
+    check!(f8e5m2, E5M2, 0x2545_f491_4f6c_dd1d);
+
+    // The upper half of an f16, so its conversions agree with std's
+    for bits in 0..=u8::MAX {
+        let x = f8e5m2::from_bits(bits);
+        let y = f16::from_bits((bits as u16) << 8);
+        if !y.is_nan() {
+            assert_eq!(f32::cast_from(x).to_bits(), (y as f32).to_bits());
+        }
+    }
+
+    assert_eq!(f64::cast_from(f8e5m2::MAX), 57344.0);
+    assert_eq!(f8e5m2::cast_from(61440.0f32), f8e5m2::INFINITY);
+    assert_eq!(f8e5m2::from_f32_saturating(61440.0), f8e5m2::MAX);
+    assert_eq!(f8e5m2::from_f32_saturating(f32::NEG_INFINITY), f8e5m2::MIN);
+
+    // The bits have the same layout as f16's
+    assert_eq!(<f8e5m2 as IeeeFloat>::BIAS, 15);
+    assert_eq!(<f8e5m2 as IeeeFloat>::EXP_MASK, 0x7c);
+    let mut x = <f8e5m2 as IeeeFloat>::NEG_INFINITY;
+    let mut steps = 0;
+    while x != f8e5m2::INFINITY {
+        x = IeeeFloat::next_up(x);
+        steps += 1;
+    }
+    assert_eq!(steps, 2 * 0x7c);
+}
+
+#[test]
+fn e4m3fnuz() {
+    check!(f8e4m3fnuz, E4M3FNUZ, 0x6a09_e667_f3bc_c908);
+
+    assert_eq!(f64::cast_from(f8e4m3fnuz::MAX), 240.0);
+    assert_eq!(f8e4m3fnuz::cast_from(-0.0f32).to_bits(), 0);
+    assert_eq!(f8e4m3fnuz::cast_from(-1e-9f32).to_bits(), 0);
+    assert_eq!(f8e4m3fnuz::cast_from(f32::NAN).to_bits(), 0x80);
+    assert_eq!(f8e4m3fnuz::cast_from(-f32::NAN).to_bits(), 0x80);
+    assert_eq!((-f8e4m3fnuz::from_bits(0)).to_bits(), 0);
+}
+
+#[test]
+fn e5m2fnuz() {
+    check!(f8e5m2fnuz, E5M2FNUZ, 0xbb67_ae85_84ca_a73b);
+
+    assert_eq!(f64::cast_from(f8e5m2fnuz::MAX), 57344.0);
+    assert_eq!(f64::cast_from(f8e5m2fnuz::from_bits(1)), 2f64.powi(-17));
+    assert!(f8e5m2fnuz::cast_from(f32::INFINITY).is_nan());
+    assert_eq!(
+        f8e5m2fnuz::from_f32_saturating(f32::INFINITY),
+        f8e5m2fnuz::MAX
+    );
+}
+
+#[test]
+fn nan_payload() {
+    // E5M2 NaNs are quieted and keep the payload bit below the quiet bit
+    let nan = f8e5m2::cast_from(f32::from_bits(0xff80_0000 | 1 << 21));
+    assert_eq!(nan.to_bits(), 0xff);
+    assert_eq!(f32::cast_from(nan).to_bits(), 0xffe0_0000);
+    assert_eq!(f8e5m2::from_bits(0x7d).to_bits(), 0x7d);
+    assert_eq!(
+        f32::cast_from(f8e5m2::from_bits(0x7d)).to_bits(),
+        0x7fe0_0000
+    );
+    assert_eq!(f8e5m2::cast_from(f64::NAN).to_bits(), f8e5m2::NAN.to_bits());
+
+    // The E4M3 NaNs keep their sign
+    assert_eq!(f8e4m3::cast_from(-f32::NAN).to_bits(), 0xff);
+    assert!(f32::cast_from(f8e4m3::from_bits(0xff)).is_sign_negative());
+}
+
+#[test]
+fn api() {
+    assert_eq!(f8e4m3::from_bits(0x38).to_bits(), 0x38);
+    assert_eq!(f64::cast_from(f8e4m3::from_bits(0x38)), 1.0);
+    assert_eq!(f64::cast_from(f8e5m2::from_bits(0x3c)), 1.0);
+    assert_eq!(f64::cast_from(f8e4m3fnuz::from_bits(0x40)), 1.0);
+    assert_eq!(f64::cast_from(f8e5m2fnuz::from_bits(0x40)), 1.0);
+
+    assert_eq!(f8e4m3::MANTISSA_DIGITS, 4);
+    assert_eq!(f8e5m2::MANTISSA_DIGITS, 3);
+    assert_eq!(-f8e4m3::MAX, f8e4m3::MIN);
+    assert_eq!(f8e4m3::from_bits(0), f8e4m3::from_bits(0x80));
+    assert_ne!(f8e4m3::NAN, f8e4m3::NAN);
+    assert!(f8e4m3::NAN.is_nan() && !f8e4m3::NAN.is_infinite());
+    assert!(f8e5m2::NEG_INFINITY.is_infinite() && f8e5m2::NEG_INFINITY.is_sign_negative());
+    assert!(!f8e4m3fnuz::MIN.is_finite() == f8e4m3fnuz::MIN.is_nan());
+    assert!((-f8e5m2fnuz::NAN).is_nan());
+}
@@ -0,0 +1,172 @@
+//! Math function tests for f16
+//!
+//! With only 65,536 inputs, every unary function is checked exhaustively
+//! against the nightly std `f128` functions, rounded to `f16`. Those are
+//! accurate to a few `f128` ULPs, which settles the correct `f16` rounding
+//! of every result that is not almost exactly on a rounding boundary.
+
+#![cfg(not(feature = "nightly"))]
+#![feature(f16, f128)]
+
+#[path = "common/rng.rs"]
+mod rng;
+
+use floats::f16 as F16;
+use rng::XorShift;
+
+/// Checks that `our` is the correctly rounded `f16` value of `reference`.
+///
+/// Where the reference is too close to a rounding boundary for its own
+/// error to decide the side, either neighbour is accepted.
+fn check(name: &str, x: f16, our: F16, reference: f128) {
+    let our = f16::from_bits(our.to_bits());
+
+    if reference.is_nan() {
+        assert!(our.is_nan(), "{name}({x:?}) = {our:?}");
+        return;
+    } else if reference == 0.0 || reference.is_infinite() {
+        let expected = reference as f16;
+        assert_eq!(our.to_bits(), expected.to_bits(), "{name}({x:?})");
+        return;
+    }
+
+    let (mut lo, mut hi) = (reference, reference);
+    for _ in 0..8 {
+        lo = lo.next_down();
+        hi = hi.next_up();
+    }
+
+    let (lo, hi) = ((lo as f16).to_bits(), (hi as f16).to_bits());
+    assert!(
+        our.to_bits() == lo || our.to_bits() == hi,
+        "{name}({x:?}) = {our:?}, expected {reference:?}"
+    );
+}
+
+#[rstest::rstest]
+#[case::sqrt("sqrt", F16::sqrt, f128::sqrt)]
+#[case::cbrt("cbrt", F16::cbrt, f128::cbrt)]
+#[case::exp("exp", F16::exp, f128::exp)]
+#[case::exp2("exp2", F16::exp2, f128::exp2)]
+#[case::exp_m1("exp_m1", F16::exp_m1, f128::exp_m1)]
+#[case::ln("ln", F16::ln, f128::ln)]
+#[case::log2("log2", F16::log2, f128::log2)]
+#[case::log10("log10", F16::log10, f128::log10)]
+#[case::ln_1p("ln_1p", F16::ln_1p, f128::ln_1p)]
+#[case::sin("sin", F16::sin, f128::sin)]
+#[case::cos("cos", F16::cos, f128::cos)]
+#[case::tan("tan", F16::tan, f128::tan)]
+#[case::asin("asin", F16::asin, f128::asin)]
+#[case::acos("acos", F16::acos, f128::acos)]
+#[case::atan("atan", F16::atan, f128::atan)]
+#[case::sinh("sinh", F16::sinh, f128::sinh)]
+#[case::cosh("cosh", F16::cosh, f128::cosh)]
+#[case::tanh("tanh", F16::tanh, f128::tanh)]
+#[case::asinh("asinh", F16::asinh, f128::asinh)]
+#[case::acosh("acosh", F16::acosh, f128::acosh)]
+#[case::atanh("atanh", F16::atanh, f128::atanh)]
+fn exhaustive(#[case] name: &str, #[case] f: fn(F16) -> F16, #[case] g: fn(f128) -> f128) {
+    for bits in 0..=u16::MAX {
+        let x = f16::from_bits(bits);
+        check(name, x, f(F16::from_bits(bits)), g(x as f128));
+    }
+}
+
+#[test]
+fn sin_cos() {
+    for bits in (0..=u16::MAX).step_by(7) {
+        let x = F16::from_bits(bits);
+        let (sin, cos) = x.sin_cos();
+        assert_eq!(sin.to_bits(), x.sin().to_bits(), "{bits:#06x}");
+        assert_eq!(cos.to_bits(), x.cos().to_bits(), "{bits:#06x}");
+    }
+}
+
+#[rstest::rstest]
+#[case::powf("powf", F16::powf, f128::powf)]
+#[case::atan2("atan2", F16::atan2, f128::atan2)]
+#[case::hypot("hypot", F16::hypot, f128::hypot)]
+fn binary(#[case] name: &str, #[case] f: fn(F16, F16) -> F16, #[case] g: fn(f128, f128) -> f128) {
+    let mut rng = XorShift(0x6a09_e667_f3bc_c908);
+
+    for _ in 0..100_000 {
+        let bits = rng.next();
+        let (a, b) = (bits as u16, (bits >> 16) as u16);
+        let (x, y) = (f16::from_bits(a), f16::from_bits(b));
+        let our = f(F16::from_bits(a), F16::from_bits(b));
+        check(name, x, our, g(x as f128, y as f128));
+    }
+}
+
+#[test]
+fn powi() {
+    for n in [-25, -3, -2, -1, 1, 2, 3, 7, 40, i32::MAX, i32::MIN] {
+        for bits in (0..=u16::MAX).step_by(3) {
+            let x = f16::from_bits(bits);
+            let our = F16::from_bits(bits).powi(n);
+            check("powi", x, our, (x as f128).powi(n));
+        }
+    }
+}
+
+/// Exact results are exact, and those halfway between two representable
+/// values round to even
+#[rstest::rstest]
+#[case::square(F16::powf, 63.0, 2.0, 3968.0)]
+#[case::root(F16::powf, 3969.0, 0.5, 63.0)]
+#[case::hypot(F16::hypot, 1479.0, 1972.0, 2464.0)]
+#[case::hypot_exact(F16::hypot, 3.0, 4.0, 5.0)]
+fn midpoints(
+    #[case] f: fn(F16, F16) -> F16,
+    #[case] x: f16,
+    #[case] y: f16,
+    #[case] expected: f16,
+) {
+    let our = f(F16::from_bits(x.to_bits()), F16::from_bits(y.to_bits()));
+    assert_eq!(our.to_bits(), expected.to_bits());
+}
+
+#[test]
+fn powi_midpoint() {
+    let x = F16::from_bits(63.0f16.to_bits());
+    assert_eq!(x.powi(2).to_bits(), 3968.0f16.to_bits());
+}
+
+#[rstest::rstest]
+#[case::pow_zero_nan(F16::powf, f16::NAN, 0.0, 1.0)]
+#[case::pow_one_nan(F16::powf, 1.0, f16::NAN, 1.0)]
+#[case::pow_neg_one_inf(F16::powf, -1.0, f16::INFINITY, 1.0)]
+#[case::pow_neg_zero_odd(F16::powf, -0.0, -3.0, f16::NEG_INFINITY)]
+#[case::pow_negative_base(F16::powf, -2.0, 0.5, f16::NAN)]
+#[case::pow_overflow(F16::powf, 2.0, 16.0, f16::INFINITY)]
+#[case::atan2_neg_zero(F16::atan2, 0.0, -0.0, core::f16::consts::PI)]
+#[case::hypot_inf_nan(F16::hypot, f16::NAN, f16::NEG_INFINITY, f16::INFINITY)]
+#[case::hypot_nan(F16::hypot, f16::NAN, 1.0, f16::NAN)]
+fn special(#[case] f: fn(F16, F16) -> F16, #[case] x: f16, #[case] y: f16, #[case] expected: f16) {
+    let our = f(F16::from_bits(x.to_bits()), F16::from_bits(y.to_bits()));
+
+    if expected.is_nan() {
+        assert!(our.is_nan());
+    } else {
+        assert_eq!(our.to_bits(), expected.to_bits(), "{x:?}, {y:?}");
+    }
+}
+
+#[test]
+fn powi_limits() {
+    let two = F16::from_bits(2.0f16.to_bits());
+    assert_eq!(two.powi(i32::MAX).to_bits(), f16::INFINITY.to_bits());
+    assert_eq!(two.powi(i32::MIN).to_bits(), 0);
+
+    let neg = F16::from_bits((-1.0f16).to_bits());
+    assert_eq!(neg.powi(i32::MAX).to_bits(), (-1.0f16).to_bits());
+    assert_eq!(neg.powi(i32::MIN).to_bits(), 1.0f16.to_bits());
+}
+
+#[test]
+fn nan_propagation() {
+    let snan = F16::from_bits(0xfd23);
+    for f in [F16::sqrt, F16::exp, F16::ln, F16::sin, F16::tanh] {
+        assert_eq!(f(snan).to_bits(), 0xff23);
+    }
+}
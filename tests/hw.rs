@@ -0,0 +1,260 @@
+//! Explicit backend tests
+//!
+//! The software conversions must match the standard library on every build,
+//! and every hardware entry point must match them. Run with `runtime-detect`
+//! or with target features enabled to cover the hardware modules.
+
+#![cfg(all(feature = "casting", not(feature = "nightly")))]
+#![feature(f16, f128)]
+
+#[path = "common/rng.rs"]
+mod rng;
+
+use floats::{backend, f16 as F16, hw, soft, Backend};
+use rng::XorShift;
+
+fn halves() -> impl Iterator<Item = F16> {
+    (0..=u16::MAX).map(F16::from_bits)
+}
+
+/// Random inputs concentrated on the f16 range, where rounding matters.
+fn inputs(n: usize) -> Vec<(f32, f64, i64, u64)> {
+    let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+
+    (0..n)
+        .map(|_| {
+            let n = rng.next();
+            let sign = (n >> 32) as u32 & (1 << 31);
+            let x = f32::from_bits((0x3000_0000 + n as u32 % 0x1800_0000) | sign);
+            let y = f64::from(x) * (1.0 + f64::from_bits(0x3e00_0000_0000_0000 | n >> 12));
+            (x, y, (n >> 40) as i64 - (1 << 23), n >> (n & 63))
+        })
+        .collect()
+}
+
+/// Compares the bits of every conversion in `convert` against `soft`, with
+/// `wrap` turning each result into an `Option`.
+macro_rules! compare {
+    ($convert:path, $wrap:path) => {{
+        use $convert as imp;
+        use $wrap as wrap;
+
+        for h in halves() {
+            let bits = h.to_bits();
+            assert_eq!(
+                wrap(imp::f32_from_f16(h)).map(f32::to_bits),
+                Some(soft::f32_from_f16(h).to_bits()),
+                "{bits:#06x}"
+            );
+            assert_eq!(
+                wrap(imp::f64_from_f16(h)).map(f64::to_bits),
+                Some(soft::f64_from_f16(h).to_bits()),
+                "{bits:#06x}"
+            );
+            assert_eq!(
+                wrap(imp::i32_from_f16(h)),
+                Some(soft::i32_from_f16(h)),
+                "{bits:#06x}"
+            );
+            assert_eq!(
+                wrap(imp::u32_from_f16(h)),
+                Some(soft::u32_from_f16(h)),
+                "{bits:#06x}"
+            );
+            assert_eq!(
+                wrap(imp::i64_from_f16(h)),
+                Some(soft::i64_from_f16(h)),
+                "{bits:#06x}"
+            );
+            assert_eq!(
+                wrap(imp::u64_from_f16(h)),
+                Some(soft::u64_from_f16(h)),
+                "{bits:#06x}"
+            );
+        }
+
+        for (x, y, i, u) in inputs(100_000) {
+            let cases = [
+                (wrap(imp::f16_from_f32(x)), soft::f16_from_f32(x)),
+                (wrap(imp::f16_from_f64(y)), soft::f16_from_f64(y)),
+                (
+                    wrap(imp::f16_from_i32(i as i32)),
+                    soft::f16_from_i32(i as i32),
+                ),
+                (
+                    wrap(imp::f16_from_u32(u as u32)),
+                    soft::f16_from_u32(u as u32),
+                ),
+                (wrap(imp::f16_from_i64(i * 3)), soft::f16_from_i64(i * 3)),
+                (wrap(imp::f16_from_u64(u)), soft::f16_from_u64(u)),
+            ];
+
+            for (ours, soft) in cases {
+                assert_eq!(
+                    ours.map(F16::to_bits),
+                    Some(soft.to_bits()),
+                    "{x} {y} {i} {u}"
+                );
+            }
+        }
+    }};
+}
+
+#[test]
+fn soft_matches_std() {
+    for h in halves() {
+        let (ours, std) = (h, f16::from_bits(h.to_bits()));
+        let bits = h.to_bits();
+
+        if std.is_nan() {
+            assert!(soft::f32_from_f16(ours).is_nan());
+            assert!(soft::f64_from_f16(ours).is_nan());
+        } else {
+            assert_eq!(
+                soft::f32_from_f16(ours).to_bits(),
+                (std as f32).to_bits(),
+                "{bits:#06x}"
+            );
+            assert_eq!(
+                soft::f64_from_f16(ours).to_bits(),
+                (std as f64).to_bits(),
+                "{bits:#06x}"
+            );
+        }
+
+        assert_eq!(soft::i32_from_f16(ours), std as i32, "{bits:#06x}");
+        assert_eq!(soft::u32_from_f16(ours), std as u32, "{bits:#06x}");
+        assert_eq!(soft::i64_from_f16(ours), std as i64, "{bits:#06x}");
+        assert_eq!(soft::u64_from_f16(ours), std as u64, "{bits:#06x}");
+    }
+
+    for (x, y, i, u) in inputs(200_000) {
+        let cases = [
+            (soft::f16_from_f32(x), x as f16),
+            (soft::f16_from_f64(y), y as f16),
+            (soft::f16_from_i32(i as i32), i as i32 as f16),
+            (soft::f16_from_u32(u as u32), u as u32 as f16),
+            (soft::f16_from_i64(i * 3), (i * 3) as f16),
+            (soft::f16_from_u64(u), u as f16),
+        ];
+
+        for (ours, std) in cases {
+            assert_eq!(ours.to_bits(), std.to_bits(), "{x} {y} {i} {u}");
+        }
+    }
+}
+
+#[test]
+fn soft_slices() {
+    let src: Vec<F16> = halves().collect();
+    let mut wide = vec![0.0; src.len()];
+    let mut back = vec![F16::from_bits(0); src.len()];
+
+    soft::f32_from_f16_slice(&src, &mut wide);
+    soft::f16_from_f32_slice(&wide, &mut back);
+    for (h, (w, b)) in src.iter().zip(wide.iter().zip(&back)) {
+        assert_eq!(w.to_bits(), soft::f32_from_f16(*h).to_bits());
+        assert_eq!(b.to_bits(), soft::f16_from_f32(*w).to_bits());
+    }
+
+    let mut wide = vec![0.0; src.len()];
+    soft::f64_from_f16_slice(&src, &mut wide);
+    soft::f16_from_f64_slice(&wide, &mut back);
+    for (h, (w, b)) in src.iter().zip(wide.iter().zip(&back)) {
+        assert_eq!(w.to_bits(), soft::f64_from_f16(*h).to_bits());
+        assert_eq!(b.to_bits(), soft::f16_from_f64(*w).to_bits());
+    }
+}
+
+#[test]
+fn checked() {
+    let full = matches!(backend(), Backend::Avx512Fp16 | Backend::Fp16);
+    let h = F16::from_bits(0x3555);
+
+    assert_eq!(
+        hw::f32_from_f16(h).is_some(),
+        backend() != Backend::Software
+    );
+    assert_eq!(
+        hw::f16_from_f32(1.0).is_some(),
+        backend() != Backend::Software
+    );
+    assert_eq!(hw::f64_from_f16(h).is_some(), full);
+    assert_eq!(hw::u64_from_f16(h).is_some(), full);
+
+    if full {
+        compare!(hw, core::convert::identity);
+    } else if let Some(x) = hw::f32_from_f16(h) {
+        assert_eq!(x.to_bits(), soft::f32_from_f16(h).to_bits());
+    }
+}
+
+#[cfg(all(
+    feature = "asm",
+    target_arch = "x86_64",
+    any(target_feature = "f16c", feature = "runtime-detect")
+))]
+#[test]
+fn f16c() {
+    if !hw::f16c::is_available() {
+        return;
+    }
+
+    let src: Vec<F16> = halves().collect();
+    let mut wide = vec![0.0; src.len()];
+    let mut back = vec![F16::from_bits(0); src.len()];
+    unsafe {
+        hw::f16c::f32_from_f16_slice(&src, &mut wide);
+        hw::f16c::f16_from_f32_slice(&wide, &mut back);
+    }
+
+    for (h, (w, b)) in src.iter().zip(wide.iter().zip(&back)) {
+        assert_eq!(w.to_bits(), soft::f32_from_f16(*h).to_bits());
+        assert_eq!(b.to_bits(), soft::f16_from_f32(*w).to_bits());
+        assert_eq!(unsafe { hw::f16c::f32_from_f16(*h) }.to_bits(), w.to_bits());
+    }
+
+    for (x, ..) in inputs(100_000) {
+        let ours = unsafe { hw::f16c::f16_from_f32(x) };
+        assert_eq!(ours.to_bits(), soft::f16_from_f32(x).to_bits(), "{x}");
+    }
+}
+
+#[cfg(all(
+    feature = "asm",
+    target_arch = "x86_64",
+    any(target_feature = "avx512fp16", feature = "runtime-detect")
+))]
+#[test]
+fn avx512fp16() {
+    if !hw::avx512fp16::is_available() {
+        return;
+    }
+
+    unsafe { compare!(hw::avx512fp16, Some) };
+
+    let src: Vec<F16> = halves().collect();
+    let mut wide = vec![0.0; src.len()];
+    let mut back = vec![F16::from_bits(0); src.len()];
+    unsafe {
+        hw::avx512fp16::f64_from_f16_slice(&src, &mut wide);
+        hw::avx512fp16::f16_from_f64_slice(&wide, &mut back);
+    }
+
+    for (h, (w, b)) in src.iter().zip(wide.iter().zip(&back)) {
+        assert_eq!(w.to_bits(), soft::f64_from_f16(*h).to_bits());
+        assert_eq!(b.to_bits(), soft::f16_from_f64(*w).to_bits());
+    }
+}
+
+#[cfg(all(
+    feature = "asm",
+    target_arch = "aarch64",
+    any(target_feature = "fp16", feature = "runtime-detect")
+))]
+#[test]
+fn fp16() {
+    if hw::fp16::is_available() {
+        unsafe { compare!(hw::fp16, Some) };
+    }
+}
@@ -0,0 +1,194 @@
+//! IeeeFloat tests
+//!
+//! Every provided method must agree with the standard library's own method
+//! on the same bits, for our types and for the primitives alike.
+
+#![feature(f16, f128)]
+
+#[path = "common/rng.rs"]
+mod rng;
+
+use floats::{f128 as F128, f16 as F16, IeeeFloat};
+use rng::XorShift;
+
+/// Compares the trait methods of `$ours` on `$bits` against the inherent
+/// methods of `$std`.
+macro_rules! check {
+    ($ours:ty, $std:ty, $bits:expr) => {{
+        let bits = $bits;
+        let x = <$ours as IeeeFloat>::from_bits(bits);
+        let y = <$std>::from_bits(bits);
+
+        assert_eq!(IeeeFloat::to_bits(x), bits);
+        assert_eq!(IeeeFloat::classify(x), y.classify(), "{bits:#x}");
+        assert_eq!(IeeeFloat::is_nan(x), y.is_nan(), "{bits:#x}");
+        assert_eq!(IeeeFloat::is_infinite(x), y.is_infinite(), "{bits:#x}");
+        assert_eq!(IeeeFloat::is_finite(x), y.is_finite(), "{bits:#x}");
+        assert_eq!(IeeeFloat::is_normal(x), y.is_normal(), "{bits:#x}");
+        assert_eq!(IeeeFloat::is_subnormal(x), y.is_subnormal(), "{bits:#x}");
+        assert_eq!(
+            IeeeFloat::is_sign_positive(x),
+            y.is_sign_positive(),
+            "{bits:#x}"
+        );
+        assert_eq!(
+            IeeeFloat::is_sign_negative(x),
+            y.is_sign_negative(),
+            "{bits:#x}"
+        );
+        assert_eq!(
+            IeeeFloat::to_bits(IeeeFloat::abs(x)),
+            y.abs().to_bits(),
+            "{bits:#x}"
+        );
+        assert_eq!(
+            IeeeFloat::to_bits(IeeeFloat::next_up(x)),
+            y.next_up().to_bits(),
+            "{bits:#x}"
+        );
+        assert_eq!(
+            IeeeFloat::to_bits(IeeeFloat::next_down(x)),
+            y.next_down().to_bits(),
+            "{bits:#x}"
+        );
+
+        let neg = <$std>::from_bits(bits ^ <$ours as IeeeFloat>::SIGN_MASK);
+        assert_eq!(
+            IeeeFloat::to_bits(IeeeFloat::copysign(x, <$ours as IeeeFloat>::NEG_INFINITY)),
+            y.copysign(<$std>::NEG_INFINITY).to_bits(),
+            "{bits:#x}"
+        );
+        assert_eq!(
+            IeeeFloat::to_bits(IeeeFloat::copysign(x, <$ours as IeeeFloat>::INFINITY)),
+            y.copysign(<$std>::INFINITY).to_bits(),
+            "{bits:#x}"
+        );
+        assert_eq!(IeeeFloat::to_bits(-x), neg.to_bits(), "{bits:#x}");
+    }};
+}
+
+/// Compares the constants of `$ours` against those of `$std`.
+macro_rules! consts {
+    ($ours:ty, $std:ty, $bits:ty) => {{
+        assert_eq!(<$ours as IeeeFloat>::BITS, <$bits>::BITS);
+        assert_eq!(<$ours as IeeeFloat>::MANT_BITS + 1, <$std>::MANTISSA_DIGITS);
+        assert_eq!(<$ours as IeeeFloat>::BIAS + 1, <$std>::MAX_EXP);
+        assert_eq!(
+            <$ours as IeeeFloat>::SIGN_MASK
+                | <$ours as IeeeFloat>::EXP_MASK
+                | <$ours as IeeeFloat>::MANT_MASK,
+            <$bits>::MAX
+        );
+        assert_eq!(<$ours as IeeeFloat>::EXP_MASK, <$std>::INFINITY.to_bits());
+        assert_eq!(
+            <$ours as IeeeFloat>::EXP_BITS + <$ours as IeeeFloat>::MANT_BITS + 1,
+            <$bits>::BITS
+        );
+
+        let consts = [
+            (<$ours as IeeeFloat>::INFINITY, <$std>::INFINITY),
+            (<$ours as IeeeFloat>::NEG_INFINITY, <$std>::NEG_INFINITY),
+            (<$ours as IeeeFloat>::NAN, <$std>::NAN),
+            (<$ours as IeeeFloat>::MAX, <$std>::MAX),
+            (<$ours as IeeeFloat>::MIN, <$std>::MIN),
+        ];
+        for (ours, std) in consts {
+            assert_eq!(IeeeFloat::to_bits(ours), std.to_bits());
+        }
+    }};
+}
+
+/// Steps up from `from` to `to` and back, written once for every format.
+fn walk<T: IeeeFloat>(from: T, to: T) -> usize {
+    let mut x = from;
+    let mut steps = 0;
+    while x != to {
+        x = x.next_up();
+        steps += 1;
+    }
+    for _ in 0..steps {
+        x = x.next_down();
+    }
+    assert_eq!(x.to_bits(), from.to_bits());
+    steps
+}
+
+#[test]
+fn constants() {
+    consts!(F16, f16, u16);
+    consts!(f32, f32, u32);
+    consts!(f64, f64, u64);
+    consts!(F128, f128, u128);
+}
+
+#[test]
+fn f16_exhaustive() {
+    for bits in 0..=u16::MAX {
+        check!(F16, f16, bits);
+    }
+}
+
+#[test]
+fn f32_f64_random() {
+    let mut rng = XorShift(0x9e37_79b9_7f4a_7c15);
+
+    for bits in [0, 1, 0x007f_ffff, 0x0080_0000, 0x7f7f_ffff, 0x7f80_0000] {
+        check!(f32, f32, bits);
+        check!(f32, f32, bits | 0x8000_0000);
+    }
+    for bits in [0, 1, 0x7fef_ffff_ffff_ffff, 0x7ff0_0000_0000_0000] {
+        check!(f64, f64, bits);
+        check!(f64, f64, bits | 1 << 63);
+    }
+
+    for _ in 0..100_000 {
+        let n = rng.next();
+        check!(f32, f32, n as u32);
+        check!(f64, f64, n);
+    }
+}
+
+#[test]
+fn f128_random() {
+    let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+
+    for exp in [0, 1, 0x3fff, 0x7ffe, 0x7fff] {
+        for mant in [0, 1, (1 << 112) - 1] {
+            check!(F128, f128, exp << 112 | mant);
+            check!(F128, f128, 1 << 127 | exp << 112 | mant);
+        }
+    }
+
+    for _ in 0..100_000 {
+        let center = [0, 1, 0x3fff, 0x7ffe][rng.next() as usize % 4];
+        check!(F128, f128, rng.f128(center, 4));
+    }
+}
+
+#[test]
+fn generic() {
+    // Every value between the largest subnormal and twice the smallest
+    // normal, and across zero
+    assert_eq!(walk(F16::from_bits(0x03ff), F16::from_bits(0x0800)), 0x401);
+    assert_eq!(walk(F16::from_bits(0x8003), F16::from_bits(0x0003)), 6);
+    assert_eq!(walk(f32::from_bits(0x8000_0003), f32::from_bits(3)), 6);
+    assert_eq!(walk(-f64::from_bits(3), f64::from_bits(3)), 6);
+    assert_eq!(
+        walk(
+            <F128 as IeeeFloat>::MAX.next_down(),
+            <F128 as IeeeFloat>::INFINITY
+        ),
+        2
+    );
+    assert_eq!(walk(<f32 as IeeeFloat>::NEG_INFINITY, f32::MIN), 1);
+
+    // The ULP methods are the same code for the primitives
+    assert_eq!(IeeeFloat::ulp(3.0f32), 2.0 * f32::EPSILON);
+    assert_eq!(IeeeFloat::ulp(f64::MAX), f64::MAX - f64::MAX.next_down());
+    assert_eq!(IeeeFloat::ulp(-0.0f64), f64::from_bits(1));
+    assert_eq!(IeeeFloat::next_after(1.0f64, 0.0), 1.0f64.next_down());
+    assert_eq!(
+        IeeeFloat::ulp_distance(f32::MIN, f32::MAX),
+        2 * f32::MAX.to_bits() as u128
+    );
+}
@@ -0,0 +1,361 @@
+//! Legacy format tests
+//!
+//! Each format is decoded by hand into std's f128, where every value but
+//! the lowest VAX H ones is exact, and must match the crate's decoding and
+//! its correctly rounded narrowing. Encoding must round to the nearest
+//! value of the format, checked in exact f128 arithmetic.
+
+#![cfg(feature = "casting")]
+#![feature(f128)]
+
+#[path = "common/quad.rs"]
+mod quad;
+#[path = "common/rng.rs"]
+mod rng;
+
+use floats::casting::CastFrom;
+use floats::f128 as F128;
+use floats::legacy::{HfpExtended, HfpLong, HfpShort, VaxD, VaxF, VaxG, VaxH};
+use quad::{narrow, pow2, wide};
+use rng::XorShift;
+
+/// Converts from std's f128 to the type of `_like`.
+fn narrow_like<T: CastFrom<F128>>(_like: &T, x: f128) -> T {
+    narrow(x)
+}
+
+/// Returns `mant * 2^exp` with the sign, exactly for a mantissa of at most
+/// 113 bits and a result in the normal range of f128.
+fn value(negative: bool, mant: u128, exp: i32) -> f128 {
+    let abs = if exp < -16000 {
+        mant as f128 * pow2(exp + 1000) * pow2(-1000)
+    } else {
+        mant as f128 * pow2(exp)
+    };
+    if negative {
+        -abs
+    } else {
+        abs
+    }
+}
+
+/// Decodes an HFP fraction of `frac_bits` bits.
+fn hfp(negative: bool, char: u128, frac: u128, frac_bits: i32) -> f128 {
+    value(negative, frac, 4 * (char as i32 - 64) - frac_bits)
+}
+
+/// Decodes a VAX format, or returns `None` for a reserved operand.
+fn vax(bits: u128, exp_bits: u32, frac_bits: u32) -> Option<f128> {
+    let negative = bits >> (exp_bits + frac_bits) & 1 != 0;
+    let exp = (bits >> frac_bits) as i32 & ((1 << exp_bits) - 1);
+    let frac = bits & ((1 << frac_bits) - 1);
+
+    match (exp, negative) {
+        (0, true) => None,
+        (0, false) => Some(0.0),
+        _ => Some(value(
+            negative,
+            frac | 1 << frac_bits,
+            exp - (1 << (exp_bits - 1)) - frac_bits as i32 - 1,
+        )),
+    }
+}
+
+/// Checks the decoding of `$x` against the exact `$y`, and that encoding
+/// the exact value gives back `$canonical`.
+macro_rules! decode {
+    ($x:expr, $y:expr, $canonical:expr) => {{
+        let (x, y) = ($x, $y);
+        assert_eq!(wide(x).to_bits(), y.to_bits(), "{x:?}");
+        assert_eq!(f64::cast_from(x).to_bits(), (y as f64).to_bits(), "{x:?}");
+        assert_eq!(f32::cast_from(x).to_bits(), (y as f32).to_bits(), "{x:?}");
+        if let Some(bits) = $canonical {
+            assert_eq!(narrow_like(&x, y), x, "{x:?}");
+            assert_eq!(x.to_bits(), bits);
+        }
+    }};
+}
+
+/// Checks that `$ty::cast_from($x)` is the nearest value of the format,
+/// ties to even, given the spacing `$ulp` of the format at the result.
+macro_rules! nearest {
+    ($ty:ty, $x:expr, $ulp:expr) => {{
+        let x: f128 = $x;
+        let r: $ty = narrow(x);
+        let w = wide(r);
+        let ulp: f128 = $ulp(r);
+        let diff = (x - w).abs();
+        assert!(diff * 2.0 <= ulp, "{x:?} -> {r:?}");
+        if diff * 2.0 == ulp {
+            assert_eq!(r.to_bits() & 1, 0, "{x:?} -> {r:?}");
+        }
+        r
+    }};
+}
+
+#[test]
+fn hfp_decode() {
+    let mut rng = XorShift(0x9e37_79b9_7f4a_7c15);
+
+    for _ in 0..200_000 {
+        let n = rng.next();
+        let m = rng.next();
+
+        let s = n as u32;
+        let frac = s as u128 & 0xff_ffff;
+        let y = hfp(s >> 31 != 0, (s >> 24) as u128 & 0x7f, frac, 24);
+        let canonical = (frac >> 20 != 0).then(|| s);
+        decode!(HfpShort::from_bits(s), y, canonical);
+
+        let frac = n as u128 & ((1 << 56) - 1);
+        let y = hfp(n >> 63 != 0, (n >> 56) as u128 & 0x7f, frac, 56);
+        let canonical = (frac >> 52 != 0).then(|| n);
+        decode!(HfpLong::from_bits(n), y, canonical);
+
+        // The low sign and characteristic are ignored
+        let e = (n as u128) << 64 | m as u128;
+        let frac = (n as u128 & ((1 << 56) - 1)) << 56 | (m as u128 & ((1 << 56) - 1));
+        let y = hfp(n >> 63 != 0, (n >> 56) as u128 & 0x7f, frac, 112);
+        decode!(HfpExtended::from_bits(e), y, None::<u128>);
+
+        if frac >> 108 != 0 {
+            let char = (n >> 56) as u128 & 0x7f;
+            let low = ((n >> 63) as u128) << 63 | (char.wrapping_sub(14) & 0x7f) << 56;
+            let e = e & !(0xff << 56) | low;
+            decode!(HfpExtended::from_bits(e), y, Some(e));
+        }
+    }
+}
+
+#[test]
+fn vax_decode() {
+    let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+
+    for _ in 0..200_000 {
+        let n = rng.next();
+        let m = rng.next();
+
+        // Reserved operands decode as NaN
+        macro_rules! check {
+            ($ty:ty: $bits:expr, $exp_bits:literal, $frac_bits:literal) => {{
+                let bits = $bits;
+                let x = <$ty>::from_bits(bits);
+                let exp = bits as u128 >> $frac_bits & ((1 << $exp_bits) - 1);
+                match vax(bits as u128, $exp_bits, $frac_bits) {
+                    None => {
+                        assert!(x.is_reserved());
+                        assert!(wide(x).is_nan() && f64::cast_from(x).is_nan());
+                        assert!(f32::cast_from(x).is_nan());
+                    }
+
+                    Some(y) => {
+                        let canonical = (exp != 0 || bits == 0).then(|| bits);
+                        assert!(!x.is_reserved());
+                        decode!(x, y, canonical);
+                    }
+                }
+            }};
+        }
+
+        check!(VaxF: n as u32, 8, 23);
+        check!(VaxD: n, 8, 55);
+        check!(VaxG: n, 11, 52);
+
+        // Keep H within the normal range of f128
+        let h = (n as u128) << 64 | m as u128;
+        if h >> 112 & 0x7fff > 2 {
+            check!(VaxH: h, 15, 112);
+        }
+
+        // Zero exponents
+        check!(VaxF: n as u32 & 0x807f_ffff, 8, 23);
+        check!(VaxD: n & 0x807f_ffff_ffff_ffff, 8, 55);
+        check!(VaxG: n & 0x800f_ffff_ffff_ffff, 11, 52);
+    }
+
+    // The lowest two binades of H are rounded to f128 subnormals
+    let min = VaxH::MIN_POSITIVE;
+    assert_eq!(wide(min).to_bits(), 1 << 110);
+    assert_eq!(wide(VaxH::from_bits(1 << 112 | 1)).to_bits(), 1 << 110);
+    assert_eq!(
+        wide(VaxH::from_bits(1 << 112 | 3 << 110)).to_bits(),
+        7 << 108
+    );
+    assert_eq!(wide(VaxH::from_bits(2 << 112 | 1)).to_bits(), 1 << 111);
+    assert_eq!(wide(VaxH::from_bits(3 << 112)).to_bits(), 1 << 112);
+    assert_eq!(narrow::<VaxH>(f128::from_bits(1 << 110)), min);
+    assert_eq!(narrow::<VaxH>(f128::from_bits(1 << 109)).to_bits(), 0);
+}
+
+#[test]
+fn hfp_encode() {
+    let mut rng = XorShift(0x1234_5678_9abc_def1);
+
+    let ulp = |char: u128, frac_bits: i32| pow2(4 * (char as i32 - 64) - frac_bits);
+
+    for &(center, spread) in &[(16383, 250), (16383 - 256, 20), (16383 + 249, 2)] {
+        for _ in 0..100_000 {
+            let x = f128::from_bits(rng.f128(center, spread));
+
+            let r = nearest!(HfpShort, x, |r: HfpShort| {
+                ulp((r.to_bits() >> 24) as u128 & 0x7f, 24)
+            });
+            if x != 0.0 && r.to_bits() & 0x7fff_ffff >= HfpShort::MIN_POSITIVE.to_bits() {
+                assert!(r.to_bits() & 0xf0_0000 != 0, "{x:?} -> {r:?}");
+            }
+
+            nearest!(HfpLong, x, |r: HfpLong| {
+                ulp((r.to_bits() >> 56) as u128 & 0x7f, 56)
+            });
+
+            // The leading hexadecimal digit leaves 109 to 112 bits
+            nearest!(HfpExtended, x, |r: HfpExtended| {
+                ulp((r.to_bits() >> 120) & 0x7f, 112)
+            });
+        }
+    }
+
+    // From f64 and f32, HfpLong is exact and HfpShort rounds
+    for _ in 0..100_000 {
+        let n = rng.next();
+        let d = f64::from_bits(n);
+        let s = f32::from_bits(n as u32);
+
+        if d.is_finite() && d.abs() > 1e-75 && d.abs() < 1e75 {
+            assert_eq!(f64::cast_from(HfpLong::cast_from(d)), d);
+            assert_eq!(HfpShort::cast_from(d), narrow::<HfpShort>(d as f128));
+        }
+        if s.is_finite() {
+            assert_eq!(f32::cast_from(HfpLong::cast_from(s)).to_bits(), s.to_bits());
+            assert_eq!(HfpShort::cast_from(s), narrow::<HfpShort>(s as f128));
+        }
+    }
+}
+
+#[test]
+fn vax_encode() {
+    let mut rng = XorShift(0x0123_4567_89ab_cdef);
+
+    let ulp = |exp: u128, exp_bits: u32, frac_bits: u32| {
+        pow2(exp as i32 - (1 << (exp_bits - 1)) - frac_bits as i32 - 1)
+    };
+
+    for &(center, spread) in &[(16383, 126), (16383 - 126, 2), (16383 + 125, 1)] {
+        for _ in 0..100_000 {
+            let x = f128::from_bits(rng.f128(center, spread));
+
+            let r = nearest!(VaxF, x, |r: VaxF| ulp(
+                r.to_bits() as u128 >> 23 & 0xff,
+                8,
+                23
+            ));
+            assert!(!r.is_reserved() && r.to_bits() >> 23 != 0, "{x:?}");
+            let r = nearest!(VaxD, x, |r: VaxD| ulp(
+                r.to_bits() as u128 >> 55 & 0xff,
+                8,
+                55
+            ));
+            assert!(!r.is_reserved() && r.to_bits() >> 55 != 0, "{x:?}");
+            nearest!(VaxG, x, |r: VaxG| ulp(
+                r.to_bits() as u128 >> 52 & 0x7ff,
+                11,
+                52
+            ));
+            assert_eq!(wide(narrow::<VaxH>(x)), x);
+        }
+    }
+
+    // G and H hold every f64 in their range exactly
+    for _ in 0..100_000 {
+        let d = f64::from_bits(rng.next());
+        if d.is_normal() && d.abs() <= f64::MAX / 2.0 {
+            assert_eq!(f64::cast_from(VaxG::cast_from(d)), d);
+            assert_eq!(f64::cast_from(VaxH::cast_from(d)), d);
+        }
+    }
+}
+
+#[test]
+fn special() {
+    // 1.0 and the classic -118.625
+    assert_eq!(HfpShort::cast_from(1.0f32).to_bits(), 0x4110_0000);
+    assert_eq!(HfpShort::cast_from(-118.625f32).to_bits(), 0xc276_a000);
+    assert_eq!(
+        HfpLong::cast_from(-118.625).to_bits(),
+        0xc276_a000_0000_0000
+    );
+    assert_eq!(
+        HfpExtended::cast_from(-118.625).to_bits(),
+        0xc276_a000_0000_0000_b400_0000_0000_0000
+    );
+    assert_eq!(
+        f64::cast_from(HfpShort::from_be_bytes([0xc2, 0x76, 0xa0, 0])),
+        -118.625
+    );
+    assert_eq!(
+        HfpLong::from_bits(0x4110_0000_0000_0000).to_be_bytes()[..2],
+        [0x41, 0x10]
+    );
+
+    assert_eq!(VaxF::cast_from(1.0f32).to_bits(), 0x4080_0000);
+    assert_eq!(VaxF::cast_from(1.0f32).to_vax_bytes(), [0x80, 0x40, 0, 0]);
+    assert_eq!(
+        VaxD::cast_from(1.0).to_vax_bytes(),
+        [0x80, 0x40, 0, 0, 0, 0, 0, 0]
+    );
+    assert_eq!(
+        VaxG::cast_from(1.0).to_vax_bytes(),
+        [0x10, 0x40, 0, 0, 0, 0, 0, 0]
+    );
+    assert_eq!(VaxH::cast_from(1.0).to_bits(), 0x4001 << 112);
+    assert_eq!(
+        f32::cast_from(VaxF::from_vax_bytes([0xc8, 0xc2, 0x0, 0x0])),
+        -25.0
+    );
+    let d = VaxD::from_bits(0x1234_5678_9abc_def0);
+    assert_eq!(VaxD::from_vax_bytes(d.to_vax_bytes()), d);
+    let h = VaxH::from_bits(0x0123_4567_89ab_cdef_0f1e_2d3c_4b5a_6978);
+    assert_eq!(h.to_vax_bytes()[..4], [0x23, 0x01, 0x67, 0x45]);
+    assert_eq!(VaxH::from_vax_bytes(h.to_vax_bytes()), h);
+
+    // Zeros: HFP keeps the sign, VAX has no negative zero
+    assert_eq!(HfpShort::cast_from(-0.0f32).to_bits(), 0x8000_0000);
+    assert_eq!(HfpExtended::cast_from(0.0).to_bits(), 0);
+    assert_eq!(VaxF::cast_from(-0.0f32).to_bits(), 0);
+    assert!(f64::cast_from(HfpLong::from_bits(0x8000_0000_0000_0000)).is_sign_negative());
+    assert_eq!(f64::cast_from(VaxG::from_bits(0x000f_ffff_ffff_ffff)), 0.0);
+
+    // HFP saturates, VAX stores the reserved operand
+    for x in [f64::INFINITY, f64::NAN, 1e300] {
+        assert_eq!(HfpLong::cast_from(x), HfpLong::MAX);
+        assert_eq!(HfpLong::cast_from(-x), HfpLong::MIN);
+        assert_eq!(VaxF::cast_from(x), VaxF::RESERVED);
+        assert_eq!(VaxD::cast_from(-x), VaxD::RESERVED);
+    }
+    assert_eq!(VaxG::cast_from(f64::INFINITY), VaxG::RESERVED);
+    assert_eq!(narrow::<VaxH>(f128::INFINITY), VaxH::RESERVED);
+    assert_eq!(narrow::<HfpExtended>(f128::NEG_INFINITY), HfpExtended::MIN);
+    assert!(f64::cast_from(VaxG::RESERVED).is_nan());
+
+    // The largest values overflow the narrower IEEE formats
+    assert_eq!(f32::cast_from(HfpShort::MAX), f32::INFINITY);
+    assert_eq!(f64::cast_from(VaxG::MAX), f64::MAX / 2.0);
+    assert_eq!(f32::cast_from(VaxF::MAX), f32::MAX / 2.0);
+    assert_eq!(f32::cast_from(VaxF::MIN_POSITIVE), 2f32.powi(-128));
+
+    // Underflow: HFP loses digits below 16^-65, VAX flushes to zero
+    assert_eq!(HfpShort::cast_from(2f64.powi(-262)).to_bits(), 0x0004_0000);
+    assert_eq!(HfpShort::cast_from(2f64.powi(-290)).to_bits(), 0);
+    assert_eq!(HfpShort::cast_from(2f64.powi(-280)).to_bits(), 1);
+    assert_eq!(HfpShort::cast_from(2f64.powi(-282)).to_bits(), 0);
+    assert_eq!(VaxF::cast_from(2f32.powi(-129)).to_bits(), 0);
+    assert_eq!(VaxF::cast_from(2f32.powi(-128)), VaxF::MIN_POSITIVE);
+    assert_eq!(f64::cast_from(HfpLong::MIN_POSITIVE), 2f64.powi(-260));
+
+    // Rounding carries into the next digit or binade
+    assert_eq!(
+        HfpShort::cast_from(1.0 - 2f64.powi(-30)).to_bits(),
+        0x4110_0000
+    );
+    assert_eq!(VaxF::cast_from(1.0 - 2f64.powi(-30)).to_bits(), 0x4080_0000);
+}
@@ -0,0 +1,231 @@
+//! Exponential, logarithm and power function tests for f128
+//!
+//! Results are compared against the nightly std `f128` functions, which are
+//! accurate to within one ULP, and special cases against C23 Annex F.
+
+#![feature(f128)]
+
+#[path = "common/rng.rs"]
+mod rng;
+
+use floats::f128 as F128;
+use rng::{ulps, XorShift};
+
+fn close(name: &str, x: f128, our: F128, std: f128) {
+    let our = f128::from_bits(our.to_bits());
+
+    if std.is_nan() {
+        assert!(our.is_nan(), "{name}({x:?}) = {our:?}");
+    } else {
+        assert!(ulps(our, std) <= 1, "{name}({x:?}) = {our:?}, std {std:?}");
+    }
+}
+
+fn unary(name: &str, f: fn(F128) -> F128, g: fn(f128) -> f128, x: f128) {
+    close(name, x, f(F128::from_bits(x.to_bits())), g(x));
+}
+
+#[rstest::rstest]
+#[case::exp("exp", F128::exp, f128::exp, -11500.0, 11400.0)]
+#[case::exp_small("exp", F128::exp, f128::exp, -1.0, 1.0)]
+#[case::exp2("exp2", F128::exp2, f128::exp2, -16500.0, 16400.0)]
+#[case::exp_m1("exp_m1", F128::exp_m1, f128::exp_m1, -50.0, 50.0)]
+#[case::exp_m1_small("exp_m1", F128::exp_m1, f128::exp_m1, -0.5, 0.5)]
+#[case::ln_1p("ln_1p", F128::ln_1p, f128::ln_1p, -1.0, 4.0)]
+#[case::ln_near_one("ln", F128::ln, f128::ln, 0.5, 2.0)]
+fn uniform(
+    #[case] name: &str,
+    #[case] f: fn(F128) -> F128,
+    #[case] g: fn(f128) -> f128,
+    #[case] lo: f128,
+    #[case] hi: f128,
+) {
+    let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+
+    for _ in 0..2000 {
+        unary(name, f, g, rng.range(lo, hi));
+    }
+}
+
+#[rstest::rstest]
+#[case::ln("ln", F128::ln, f128::ln)]
+#[case::log2("log2", F128::log2, f128::log2)]
+#[case::log10("log10", F128::log10, f128::log10)]
+#[case::ln_1p("ln_1p", F128::ln_1p, f128::ln_1p)]
+#[case::exp_m1("exp_m1", F128::exp_m1, f128::exp_m1)]
+fn exponents(#[case] name: &str, #[case] f: fn(F128) -> F128, #[case] g: fn(f128) -> f128) {
+    let mut rng = XorShift(0x9e37_79b9_7f4a_7c15);
+
+    for _ in 0..2000 {
+        let x = f128::from_bits(rng.f128(0x3fff, 0x3fff)).abs();
+        let x = if name == "exp_m1" { x.min(11000.0) } else { x };
+        unary(name, f, g, x);
+        unary(name, f, g, -x);
+    }
+}
+
+#[test]
+fn powf() {
+    let mut rng = XorShift(0xdead_beef_cafe_f00d);
+
+    for _ in 0..2000 {
+        let x = f128::from_bits(rng.f128(0x3fff, 64)).abs();
+        let y = rng.range(-200.0, 200.0);
+        let our = F128::from_bits(x.to_bits()).powf(F128::from_bits(y.to_bits()));
+        close("powf", x, our, x.powf(y));
+    }
+}
+
+#[rstest::rstest]
+#[case::e(1.0, core::f128::consts::E)]
+#[case::zero(0.0, 1.0)]
+#[case::neg_zero(-0.0, 1.0)]
+#[case::inf(f128::INFINITY, f128::INFINITY)]
+#[case::neg_inf(f128::NEG_INFINITY, 0.0)]
+#[case::overflow(11357.0, f128::INFINITY)]
+#[case::underflow(-11500.0, 0.0)]
+fn exp_exact(#[case] x: f128, #[case] expected: f128) {
+    let our = F128::from_bits(x.to_bits()).exp();
+    assert_eq!(our.to_bits(), expected.to_bits());
+}
+
+#[rstest::rstest]
+#[case::one(1.0, 0.0, 0.0, 0.0)]
+#[case::zero(0.0, f128::NEG_INFINITY, f128::NEG_INFINITY, f128::NEG_INFINITY)]
+#[case::neg_zero(-0.0, f128::NEG_INFINITY, f128::NEG_INFINITY, f128::NEG_INFINITY)]
+#[case::inf(f128::INFINITY, f128::INFINITY, f128::INFINITY, f128::INFINITY)]
+#[case::pow_two(
+    0.125,
+    -2.0794415416798359282516963643745297042265004,
+    -3.0,
+    -0.903089986991943585641216684173479080304569644
+)]
+#[case::pow_ten(
+    1e40,
+    92.1034037197618273607196581873745683040440595,
+    132.877123795494493914812777179575607034593256,
+    40.0
+)]
+fn log_exact(#[case] x: f128, #[case] ln: f128, #[case] log2: f128, #[case] log10: f128) {
+    let our = F128::from_bits(x.to_bits());
+
+    assert_eq!(our.ln().to_bits(), ln.to_bits());
+    assert_eq!(our.log2().to_bits(), log2.to_bits());
+    assert_eq!(our.log10().to_bits(), log10.to_bits());
+}
+
+#[test]
+fn powers_exact() {
+    for k in -16494i32..=16383 {
+        let bits = if k < -16382 {
+            1u128 << (k + 16494)
+        } else {
+            ((k + 16383) as u128) << 112
+        };
+
+        let x = F128::from_bits((k as f128).to_bits());
+        assert_eq!(x.exp2().to_bits(), bits, "{k}");
+        assert_eq!(F128::from_bits(bits).log2().to_bits(), x.to_bits(), "{k}");
+    }
+
+    // Powers of ten are exact up to 10^48
+    let mut x = 1.0f128;
+    for k in 0..=48 {
+        let our = F128::from_bits(x.to_bits()).log10();
+        assert_eq!(our.to_bits(), (k as f128).to_bits(), "1e{k}");
+        x *= 10.0;
+    }
+}
+
+#[test]
+fn negative_arguments() {
+    let neg = F128::from_bits((-1.5f128).to_bits());
+
+    assert!(neg.ln().is_nan());
+    assert!(neg.log2().is_nan());
+    assert!(neg.log10().is_nan());
+    assert!(neg.ln_1p().is_nan());
+    assert!(F128::NEG_INFINITY.ln().is_nan());
+
+    let minus_one = F128::from_bits((-1.0f128).to_bits());
+    assert_eq!(minus_one.ln_1p().to_bits(), f128::NEG_INFINITY.to_bits());
+    assert_eq!(F128::NEG_INFINITY.exp_m1().to_bits(), minus_one.to_bits());
+    assert_eq!(
+        F128::from_bits((-0.0f128).to_bits()).exp_m1().to_bits(),
+        (-0.0f128).to_bits()
+    );
+    assert_eq!(
+        F128::from_bits((-0.0f128).to_bits()).ln_1p().to_bits(),
+        (-0.0f128).to_bits()
+    );
+}
+
+#[rstest::rstest]
+// pow(x, ±0) and pow(+1, y) are 1, even for NaN
+#[case(f128::NAN, 0.0, 1.0)]
+#[case(f128::NAN, -0.0, 1.0)]
+#[case(1.0, f128::NAN, 1.0)]
+#[case(1.0, f128::INFINITY, 1.0)]
+// pow(-1, ±inf) is 1
+#[case(-1.0, f128::INFINITY, 1.0)]
+#[case(-1.0, f128::NEG_INFINITY, 1.0)]
+// pow(±0, y) for odd, even and non-integral y
+#[case(-0.0, -3.0, f128::NEG_INFINITY)]
+#[case(0.0, -3.0, f128::INFINITY)]
+#[case(-0.0, -2.0, f128::INFINITY)]
+#[case(-0.0, -0.5, f128::INFINITY)]
+#[case(-0.0, f128::NEG_INFINITY, f128::INFINITY)]
+#[case(-0.0, 3.0, -0.0)]
+#[case(-0.0, 2.0, 0.0)]
+#[case(-0.0, 0.5, 0.0)]
+// pow(x, ±inf) by magnitude of x
+#[case(0.5, f128::NEG_INFINITY, f128::INFINITY)]
+#[case(-0.5, f128::INFINITY, 0.0)]
+#[case(2.0, f128::NEG_INFINITY, 0.0)]
+#[case(-2.0, f128::INFINITY, f128::INFINITY)]
+// pow(±inf, y)
+#[case(f128::NEG_INFINITY, -3.0, -0.0)]
+#[case(f128::NEG_INFINITY, -2.0, 0.0)]
+#[case(f128::NEG_INFINITY, 3.0, f128::NEG_INFINITY)]
+#[case(f128::NEG_INFINITY, 0.5, f128::INFINITY)]
+#[case(f128::INFINITY, -0.5, 0.0)]
+#[case(f128::INFINITY, 0.5, f128::INFINITY)]
+// Negative bases
+#[case(-2.0, 3.0, -8.0)]
+#[case(-2.0, -2.0, 0.25)]
+#[case(-1.0, 3.0, -1.0)]
+#[case(-1.0, -2.0, 1.0)]
+#[case(-8.0, 1.0 / 3.0, f128::NAN)]
+// Exact results, including midpoints between representable values
+#[case(9.0, 0.5, 3.0)]
+#[case(10.0, 30.0, 1e30)]
+#[case(144115188075855871.0, 2.0, 20769187434139310225891609165168641.0)]
+#[case(2.0, -16494.0, f128::from_bits(1))]
+#[case(2.0, 16384.0, f128::INFINITY)]
+fn powf_special(#[case] x: f128, #[case] y: f128, #[case] expected: f128) {
+    let our = F128::from_bits(x.to_bits()).powf(F128::from_bits(y.to_bits()));
+
+    if expected.is_nan() {
+        assert!(our.is_nan());
+    } else {
+        assert_eq!(our.to_bits(), expected.to_bits(), "{x:?} ^ {y:?}");
+    }
+}
+
+/// Unlike the repeated multiplication of std, `powi` rounds only once
+#[cfg(not(feature = "nightly"))]
+#[rstest::rstest]
+#[case(3.0, 40)]
+#[case(-3.0, 41)]
+#[case(1.0000001, 1 << 30)]
+#[case(0.75, -1000)]
+#[case(f128::NAN, 0)]
+fn powi(#[case] x: f128, #[case] n: i32) {
+    let our = F128::from_bits(x.to_bits());
+
+    assert_eq!(
+        our.powi(n).to_bits(),
+        our.powf(F128::from_bits((n as f128).to_bits())).to_bits()
+    );
+    close("powi", x, our.powi(n), x.powf(n as f128));
+}
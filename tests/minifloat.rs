@@ -0,0 +1,209 @@
+//! Minifloat tests
+//!
+//! Instances with the layout of a dedicated type must match it bit for bit,
+//! and the others must round to the nearest neighbour, checked against the
+//! midpoints of the value and its neighbours in exact f128 arithmetic.
+
+#![cfg(feature = "casting")]
+#![feature(f16, f128)]
+
+#[path = "common/quad.rs"]
+mod quad;
+#[path = "common/rng.rs"]
+mod rng;
+
+use floats::casting::CastFrom;
+use floats::{bf16, f8e4m3, f8e4m3fnuz, f8e5m2, f8e5m2fnuz, FiniteNan, Fnuz, Minifloat};
+use quad::{narrow, wide};
+use rng::XorShift;
+
+type Half = Minifloat<5, 10>;
+type Tf32 = Minifloat<8, 10>;
+type E3M4 = Minifloat<3, 4>;
+
+/// Compares the classification of `$x` with that of the f64 `$y`.
+macro_rules! classify {
+    ($x:expr, $y:expr) => {{
+        let (x, y) = ($x, $y);
+        assert_eq!(x.is_nan(), y.is_nan(), "{x:?}");
+        assert_eq!(x.is_infinite(), y.is_infinite(), "{x:?}");
+        assert_eq!(x.is_finite(), y.is_finite(), "{x:?}");
+        if !y.is_nan() {
+            assert_eq!(x.is_sign_negative(), y.is_sign_negative(), "{x:?}");
+            assert_eq!(x.is_sign_positive(), y.is_sign_positive(), "{x:?}");
+        }
+    }};
+}
+
+#[test]
+fn f16_exhaustive() {
+    for bits in 0..=u16::MAX {
+        let x = Half::from_bits(bits as u64);
+        let y = f16::from_bits(bits);
+
+        assert_eq!(x.to_bits(), bits as u64);
+        assert_eq!(x.classify(), y.classify(), "{bits:#x}");
+        assert_eq!(x.is_normal(), y.is_normal(), "{bits:#x}");
+        assert_eq!(x.is_subnormal(), y.is_subnormal(), "{bits:#x}");
+        classify!(x, y as f64);
+
+        if !y.is_nan() {
+            assert_eq!(f32::cast_from(x).to_bits(), (y as f32).to_bits());
+            assert_eq!(f64::cast_from(x).to_bits(), (y as f64).to_bits());
+            assert_eq!(wide(x).to_bits(), (y as f128).to_bits());
+            assert_eq!((-x).to_bits(), (-y).to_bits() as u64);
+            assert_eq!(format!("{x}"), format!("{y}"));
+            assert_eq!(format!("{x:.3}"), format!("{y:.3}"));
+        }
+    }
+}
+
+#[test]
+fn f16_bf16_random() {
+    let mut rng = XorShift(0x9e37_79b9_7f4a_7c15);
+
+    for _ in 0..200_000 {
+        let n = rng.next();
+        let s = f32::from_bits(n as u32);
+        let d = f64::from_bits(n);
+        let q = f128::from_bits(rng.f128(16383 + (n % 64) as i32 - 32, 40));
+
+        if !s.is_nan() {
+            assert_eq!(Half::cast_from(s).to_bits(), (s as f16).to_bits() as u64);
+            let b = bf16::cast_from(s).to_bits() as u64;
+            assert_eq!(Minifloat::<8, 7>::cast_from(s).to_bits(), b);
+        }
+        if !d.is_nan() {
+            assert_eq!(Half::cast_from(d).to_bits(), (d as f16).to_bits() as u64);
+            assert_eq!(
+                Minifloat::<8, 23>::cast_from(d).to_bits(),
+                (d as f32).to_bits() as u64
+            );
+        }
+        assert_eq!(narrow::<Half>(q).to_bits(), (q as f16).to_bits() as u64);
+        assert_eq!(
+            narrow::<Minifloat<11, 52>>(q).to_bits(),
+            (q as f64).to_bits()
+        );
+    }
+
+    // Display is shortest round trip, as for f32
+    for _ in 0..10_000 {
+        let s = f32::from_bits(rng.next() as u32);
+        let x = Minifloat::<8, 23>::cast_from(s);
+        assert_eq!(format!("{x}"), format!("{s}"));
+    }
+}
+
+/// Compares `$mini` with the dedicated 8-bit type `$fp8` on every value, and
+/// on the conversion of every f16 and of random f64s.
+macro_rules! fp8 {
+    ($mini:ty, $fp8:ty) => {{
+        for bits in 0..=u8::MAX {
+            let x = <$mini>::from_bits(bits as u64);
+            let y = <$fp8>::from_bits(bits);
+            classify!(x, f64::cast_from(y));
+            if !y.is_nan() {
+                assert_eq!(f32::cast_from(x).to_bits(), f32::cast_from(y).to_bits());
+                assert_eq!((-x).to_bits(), (-y).to_bits() as u64);
+            }
+        }
+
+        for bits in 0..=u16::MAX {
+            let h = f16::from_bits(bits) as f64;
+            let (x, y) = (<$mini>::cast_from(h), <$fp8>::cast_from(h));
+            if y.is_nan() {
+                assert!(x.is_nan());
+            } else {
+                assert_eq!(x.to_bits(), y.to_bits() as u64, "{h}");
+            }
+        }
+
+        assert_eq!(<$mini>::NAN.to_bits(), <$fp8>::NAN.to_bits() as u64);
+        assert_eq!(<$mini>::MAX.to_bits(), <$fp8>::MAX.to_bits() as u64);
+        assert_eq!(<$mini>::MIN.to_bits(), <$fp8>::MIN.to_bits() as u64);
+    }};
+}
+
+#[test]
+fn encodings() {
+    fp8!(Minifloat<4, 3, FiniteNan>, f8e4m3);
+    fp8!(Minifloat<5, 2>, f8e5m2);
+    fp8!(Minifloat<4, 3, Fnuz>, f8e4m3fnuz);
+    fp8!(Minifloat<5, 2, Fnuz>, f8e5m2fnuz);
+
+    type E4M3 = Minifloat<4, 3, FiniteNan>;
+    assert_eq!(f64::cast_from(E4M3::MAX), 448.0);
+    assert!(E4M3::INFINITY.is_nan());
+    assert!(E4M3::cast_from(f32::INFINITY).is_nan());
+    assert!(E4M3::cast_from(f32::NEG_INFINITY).is_sign_negative());
+    assert_eq!(Minifloat::<4, 3, Fnuz>::cast_from(-0.0f32).to_bits(), 0);
+    // The shortest decimal that rounds to MAX
+    assert_eq!(format!("{}", E4M3::MAX), "450");
+    assert_eq!(format!("{}", E4M3::NAN), "NaN");
+}
+
+/// Checks that `$x` rounds to `$mini` as the nearest value, ties to even.
+macro_rules! nearest {
+    ($mini:ty, $x:expr) => {{
+        let x: f128 = $x;
+        let r = narrow::<$mini>(x);
+        let bits = r.to_bits();
+        let value = wide(r);
+
+        if r.is_infinite() {
+            // At least halfway from MAX to the next binade
+            let max = wide(<$mini>::MAX);
+            let ulp = max - wide(<$mini>::from_bits(<$mini>::MAX.to_bits() - 1));
+            assert!(x.abs() >= max + ulp / 2.0, "{x:?}");
+        } else {
+            // Within half an ulp either side, and even at exactly half
+            let abs = bits & !(1 << (<$mini>::BITS - 1));
+            let neighbour = |b: u64| wide(<$mini>::from_bits(b));
+            let below = if abs == 0 {
+                -neighbour(1)
+            } else {
+                neighbour(abs - 1)
+            };
+            let above = neighbour(abs + 1);
+            let (lo, hi) = ((value.abs() + below) / 2.0, (value.abs() + above) / 2.0);
+            assert!(x.abs() >= lo && x.abs() <= hi, "{x:?} -> {bits:#x}");
+            if x.abs() == lo || x.abs() == hi {
+                assert_eq!(bits & 1, 0, "{x:?}");
+            }
+            if x != 0.0 {
+                assert_eq!(value.is_sign_negative(), x.is_sign_negative(), "{x:?}");
+            }
+        }
+    }};
+}
+
+#[test]
+fn rounding() {
+    let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+
+    for _ in 0..100_000 {
+        // TF32 across its subnormals and up to overflow
+        let center = 16383 + (rng.next() % 300) as i32 - 150;
+        let x = f128::from_bits(rng.f128(center, 8));
+        nearest!(Tf32, x);
+
+        let center = 16383 + (rng.next() % 16) as i32 - 8;
+        let x = f128::from_bits(rng.f128(center, 2));
+        nearest!(E3M4, x);
+
+        // Exact ties
+        let t = Tf32::from_bits(rng.next() % 0x3fc00);
+        let next = Tf32::from_bits(t.to_bits() + 1);
+        let mid = (wide(t) + wide(next)) / 2.0;
+        nearest!(Tf32, mid);
+        nearest!(Tf32, -mid);
+    }
+
+    assert_eq!(f64::cast_from(E3M4::MAX), 15.5);
+    assert_eq!(f64::cast_from(E3M4::MIN_POSITIVE), 0.25);
+    assert_eq!(f64::cast_from(E3M4::from_bits(1)), 1.0 / 64.0);
+    assert_eq!(E3M4::cast_from(15.75f32), E3M4::INFINITY);
+    assert_eq!(format!("{}", Tf32::cast_from(0.1f32)), "0.1");
+    assert_eq!(format!("{}", E3M4::cast_from(0.1f32)), "0.09");
+}
@@ -0,0 +1,285 @@
+//! MX block tests
+//!
+//! Each element format is described by its parameters alone. Quantization is
+//! checked against the shared exponent and nearest element chosen by direct
+//! search, and the packed layouts against unpacking by hand.
+
+#![cfg(feature = "casting")]
+#![feature(f16, f128)]
+
+#[path = "common/rng.rs"]
+mod rng;
+
+use floats::casting::CastFrom;
+use floats::f16 as F16;
+use floats::mx::{MxFp4, MxFp6E2M3, MxFp6E3M2, MxFp8E4M3, MxFp8E5M2, MxInt8, BLOCK_SIZE, E8M0};
+use rng::XorShift;
+
+/// The parameters of an element format.
+#[derive(Clone, Copy)]
+enum Spec {
+    Float {
+        width: u32,
+        mant: u32,
+        bias: i32,
+        max: u8,
+        inf: bool,
+        emax: i32,
+    },
+    Int8,
+}
+
+impl Spec {
+    fn emax(self) -> i32 {
+        match self {
+            Spec::Float { emax, .. } => emax,
+            Spec::Int8 => 0,
+        }
+    }
+
+    /// The value of a code.
+    fn value(self, code: u8) -> f64 {
+        match self {
+            Spec::Float {
+                width,
+                mant,
+                bias,
+                max,
+                inf,
+                ..
+            } => {
+                let abs = code & ((1 << (width - 1)) - 1);
+                let exp = (abs >> mant) as i32;
+                let m = (abs & ((1 << mant) - 1)) as f64;
+                let value = if inf && abs == max + 1 {
+                    f64::INFINITY
+                } else if abs > max {
+                    f64::NAN
+                } else if exp == 0 {
+                    m * 2f64.powi(1 - bias - mant as i32)
+                } else {
+                    (m + (1 << mant) as f64) * 2f64.powi(exp - bias - mant as i32)
+                };
+                if code >> (width - 1) == 1 {
+                    -value
+                } else {
+                    value
+                }
+            }
+            Spec::Int8 => code as i8 as f64 / 64.0,
+        }
+    }
+
+    /// Rounds a scaled value to the nearest element, ties to even,
+    /// saturating.
+    fn quantize(self, x: f64) -> f64 {
+        match self {
+            Spec::Float { max, .. } => {
+                // The codes are in increasing order of magnitude
+                let mut best = 0;
+                for code in 1..=max {
+                    let d = (self.value(code) - x.abs()).abs();
+                    let e = (self.value(best) - x.abs()).abs();
+                    if d < e || (d == e && code % 2 == 0) {
+                        best = code;
+                    }
+                }
+                self.value(best).copysign(x)
+            }
+            Spec::Int8 => {
+                let y = x * 64.0;
+                let mut r = y.round();
+                if (y - y.trunc()).abs() == 0.5 && r % 2.0 != 0.0 {
+                    r -= y.signum();
+                }
+                r.clamp(-127.0, 127.0) / 64.0
+            }
+        }
+    }
+}
+
+const E4M3: Spec = Spec::Float {
+    width: 8,
+    mant: 3,
+    bias: 7,
+    max: 0x7e,
+    inf: false,
+    emax: 8,
+};
+const E5M2: Spec = Spec::Float {
+    width: 8,
+    mant: 2,
+    bias: 15,
+    max: 0x7b,
+    inf: true,
+    emax: 15,
+};
+const E2M3: Spec = Spec::Float {
+    width: 6,
+    mant: 3,
+    bias: 1,
+    max: 0x1f,
+    inf: false,
+    emax: 2,
+};
+const E3M2: Spec = Spec::Float {
+    width: 6,
+    mant: 2,
+    bias: 3,
+    max: 0x1f,
+    inf: false,
+    emax: 4,
+};
+const E2M1: Spec = Spec::Float {
+    width: 4,
+    mant: 1,
+    bias: 1,
+    max: 0x07,
+    inf: false,
+    emax: 2,
+};
+
+/// Random values whose magnitudes span a few binades around `2^exp`, with
+/// some zeros and exact midpoints mixed in.
+fn block(rng: &mut XorShift, exp: i32) -> [f32; BLOCK_SIZE] {
+    let mut values = [0.0; BLOCK_SIZE];
+    for value in values.iter_mut() {
+        let n = rng.next();
+        let e = exp - (n % 8) as i32;
+        *value = match n >> 60 {
+            0 => 0.0,
+            1 => 2f32.powi(e) * 1.5,
+            _ => f32::from_bits(
+                ((e + 127).clamp(0, 254) as u32) << 23 | (n >> 8) as u32 & 0x807f_ffff,
+            ),
+        };
+    }
+    values
+}
+
+/// Checks quantizing and dequantizing `$values` against the reference.
+macro_rules! check {
+    ($block:ty, $spec:expr, $values:expr) => {{
+        let spec: Spec = $spec;
+        let values: [f32; BLOCK_SIZE] = $values;
+        let block = <$block>::from_f32_array(values);
+
+        let max = values.iter().fold(0f32, |m, v| m.max(v.abs()));
+        let exp = if max == 0.0 {
+            -127
+        } else {
+            ((max as f64).log2().floor() as i32 - spec.emax()).clamp(-127, 127)
+        };
+        assert_eq!(block.scale().exponent(), Some(exp), "{values:?}");
+
+        let scale = 2f64.powi(exp);
+        for (value, out) in values.iter().zip(block.to_f32_array()) {
+            let expected = spec.quantize(*value as f64 / scale) * scale;
+            assert_eq!(out as f64, expected, "{value:e} in {values:?}");
+        }
+
+        // f16 input goes through the same rounding
+        let halves = values.map(|v| f16::from_bits(F16::cast_from(v).to_bits()));
+        let via = <$block>::from_f32_array(halves.map(|v| v as f32));
+        let block = <$block>::from_f16_array(halves.map(|v| F16::from_bits(v.to_bits())));
+        assert_eq!(block, via);
+        for (a, b) in block.to_f16_array().iter().zip(block.to_f32_array()) {
+            assert_eq!(a.to_bits(), F16::cast_from(b).to_bits());
+        }
+    }};
+}
+
+/// Checks `$block` on random data, and that its codes decode as specified.
+macro_rules! test {
+    ($name:ident, $block:ident, $spec:expr, $bytes:literal, $seed:literal) => {
+        #[test]
+        fn $name() {
+            let spec: Spec = $spec;
+            let mut rng = XorShift($seed);
+
+            for _ in 0..2_000 {
+                let exp = (rng.next() % 277) as i32 - 150;
+                check!($block, spec, block(&mut rng, exp));
+            }
+
+            // Every scale with random elements
+            for bits in 0..=u8::MAX {
+                let mut elements = [0u8; $bytes];
+                for byte in elements.iter_mut() {
+                    *byte = rng.next() as u8;
+                }
+                let block = $block::from_parts(E8M0::from_bits(bits), elements);
+                assert_eq!(block.scale().to_bits(), bits);
+                assert_eq!(block.elements(), elements);
+
+                let codes = unpack(&elements);
+                for (code, out) in codes.iter().zip(block.to_f32_array()) {
+                    if bits == 0xff {
+                        assert!(out.is_nan());
+                    } else {
+                        // Rounded to f32, which may overflow
+                        let expected = (spec.value(*code) * 2f64.powi(bits as i32 - 127)) as f32;
+                        assert!(out == expected || (out.is_nan() && expected.is_nan()));
+                    }
+                }
+            }
+
+            // Infinities and NaN poison the whole block
+            for special in [f32::INFINITY, f32::NEG_INFINITY, f32::NAN] {
+                let mut values = [1.0; BLOCK_SIZE];
+                values[7] = special;
+                let block = $block::from_f32_array(values);
+                assert!(block.scale().is_nan());
+                assert!(block.to_f32_array().iter().all(|v| v.is_nan()));
+            }
+
+            let zero = $block::from_f32_array([0.0; BLOCK_SIZE]);
+            assert_eq!(zero.scale(), E8M0::MIN);
+            assert_eq!(zero.to_f32_array(), [0.0; BLOCK_SIZE]);
+        }
+    };
+}
+
+/// Unpacks element codes by hand: bytes, nibbles or 6-bit fields of a
+/// little endian bit stream.
+fn unpack(bytes: &[u8]) -> Vec<u8> {
+    let width = bytes.len() * 8 / BLOCK_SIZE;
+    (0..BLOCK_SIZE)
+        .map(|i| {
+            let bit = i * width;
+            let word = bytes[bit / 8] as u16 | (*bytes.get(bit / 8 + 1).unwrap_or(&0) as u16) << 8;
+            ((word >> (bit % 8)) & ((1 << width) - 1)) as u8
+        })
+        .collect()
+}
+
+test!(fp8_e4m3, MxFp8E4M3, E4M3, 32, 0x9e37_79b9_7f4a_7c15);
+test!(fp8_e5m2, MxFp8E5M2, E5M2, 32, 0x2545_f491_4f6c_dd1d);
+test!(fp6_e2m3, MxFp6E2M3, E2M3, 24, 0x6a09_e667_f3bc_c908);
+test!(fp6_e3m2, MxFp6E3M2, E3M2, 24, 0xbb67_ae85_84ca_a73b);
+test!(fp4_e2m1, MxFp4, E2M1, 16, 0x3c6e_f372_fe94_f82b);
+test!(int8, MxInt8, Spec::Int8, 32, 0xa54f_f53a_5f1d_36f1);
+
+#[test]
+fn examples() {
+    // The largest magnitude is 6 = 1.5 * 2^2, the largest E2M1 binade, so
+    // the scale is 1 and 0.25 rounds to 0, 0.75 to 1 and 2.5 to 2
+    let mut values = [0.0; BLOCK_SIZE];
+    values[..5].copy_from_slice(&[6.0, -0.25, 0.75, 2.5, -7.0]);
+    let block = MxFp4::from_f32_array(values);
+    assert_eq!(block.scale(), E8M0::ONE);
+    assert_eq!(&block.to_f32_array()[..5], &[6.0, -0.0, 1.0, 2.0, -6.0]);
+    assert_eq!(block.elements()[..3], [0x87, 0x42, 0x0f]);
+
+    // 8-bit integers saturate at 127/64 of the scale
+    let mut values = [0.0; BLOCK_SIZE];
+    values[..3].copy_from_slice(&[1.999, -1.0, 0.5 / 64.0]);
+    let block = MxInt8::from_f32_array(values);
+    assert_eq!(block.scale(), E8M0::ONE);
+    assert_eq!(block.elements()[..3], [127, 0xc0, 0]);
+
+    assert_eq!(f32::cast_from(E8M0::MIN), 2f32.powi(-127));
+    assert_eq!(f64::cast_from(E8M0::MAX), 2f64.powi(127));
+    assert!(f32::cast_from(E8M0::NAN).is_nan());
+    assert_eq!(E8M0::NAN.exponent(), None);
+}
@@ -0,0 +1,222 @@
+//! NaN payload tests for f16 and f128
+//!
+//! These verify the NaN inspection API and the payload propagation rules of
+//! every conversion: NaNs keep their sign, are quieted, and keep the most
+//! significant bits of their payload.
+
+#![cfg_attr(feature = "nightly", feature(f16, f128))]
+
+use floats::{bf16, f128, f16, IeeeFloat};
+
+#[test]
+fn f16_classification() {
+    for bits in 0..=u16::MAX {
+        let x = f16::from_bits(bits);
+        let exp = (bits >> 10) & 0x1F;
+        let mant = bits & 0x3FF;
+        let nan = exp == 0x1F && mant != 0;
+
+        assert_eq!(x.is_nan(), nan);
+        assert_eq!(x.is_quiet_nan(), nan && mant & 0x200 != 0);
+        assert_eq!(x.is_signaling_nan(), nan && mant & 0x200 == 0);
+        assert_eq!(x.payload(), if nan { Some(mant & 0x1FF) } else { None });
+
+        let canonical = x.canonicalize().to_bits();
+        assert_eq!(canonical, if nan { f16::NAN.to_bits() } else { bits });
+    }
+}
+
+#[test]
+fn f16_construction() {
+    for payload in 0..0x200 {
+        let quiet = f16::nan_with_payload(payload).unwrap();
+        assert!(quiet.is_quiet_nan());
+        assert!(quiet.is_sign_positive());
+        assert_eq!(quiet.payload(), Some(payload));
+
+        match f16::signaling_nan_with_payload(payload) {
+            None => assert_eq!(payload, 0),
+            Some(signaling) => {
+                assert!(signaling.is_signaling_nan());
+                assert_eq!(signaling.payload(), Some(payload));
+            }
+        }
+    }
+
+    assert_eq!(
+        f16::nan_with_payload(0).unwrap().to_bits(),
+        f16::NAN.to_bits()
+    );
+    assert!(f16::nan_with_payload(0x200).is_none());
+    assert!(f16::signaling_nan_with_payload(0x200).is_none());
+}
+
+#[rstest::rstest]
+#[case::zero(0)]
+#[case::one(1)]
+#[case::high(1 << 110)]
+#[case::max((1 << 111) - 1)]
+fn f128_construction(#[case] payload: u128) {
+    let quiet = f128::nan_with_payload(payload).unwrap();
+    assert!(quiet.is_nan());
+    assert!(quiet.is_quiet_nan());
+    assert!(!quiet.is_signaling_nan());
+    assert_eq!(quiet.payload(), Some(payload));
+    assert_eq!((-quiet).canonicalize().to_bits(), f128::NAN.to_bits());
+
+    match f128::signaling_nan_with_payload(payload) {
+        None => assert_eq!(payload, 0),
+        Some(signaling) => {
+            assert!(signaling.is_signaling_nan());
+            assert!(!signaling.is_quiet_nan());
+            assert_eq!(signaling.payload(), Some(payload));
+        }
+    }
+}
+
+#[test]
+fn f128_non_nan() {
+    for x in [
+        f128::INFINITY,
+        f128::NEG_INFINITY,
+        f128::MAX,
+        f128::from_bits(0),
+    ] {
+        assert!(!x.is_quiet_nan());
+        assert!(!x.is_signaling_nan());
+        assert_eq!(x.payload(), None);
+        assert_eq!(x.canonicalize().to_bits(), x.to_bits());
+    }
+
+    assert_eq!(f128::nan_with_payload(f128::NAN.to_bits()), None);
+    assert_eq!(f128::signaling_nan_with_payload(1 << 111), None);
+}
+
+#[test]
+fn generic() {
+    // The same code builds the NaNs of the primitives and bf16
+    let x = <f32 as IeeeFloat>::nan_with_payload(5).unwrap();
+    assert_eq!(x.to_bits(), 0x7fc0_0005);
+    assert!(x.is_nan() && x.is_quiet_nan());
+
+    let x = <f64 as IeeeFloat>::signaling_nan_with_payload(1 << 50).unwrap();
+    assert_eq!(x.to_bits(), 0x7ff4_0000_0000_0000);
+    assert!(x.is_signaling_nan());
+    assert_eq!(IeeeFloat::payload(-x), Some(1 << 50));
+
+    let x = bf16::from_bits(0xffa1);
+    assert!(x.is_signaling_nan());
+    assert_eq!(x.payload(), Some(0x21));
+    assert_eq!(x.canonicalize().to_bits(), 0x7fc0);
+    assert_eq!(<bf16 as IeeeFloat>::nan_with_payload(0x40), None);
+    assert_eq!(IeeeFloat::payload(1.0f32), None);
+}
+
+// The propagation rules are this crate's, not those of the nightly casts
+#[cfg(all(feature = "casting", not(feature = "nightly")))]
+mod conversions {
+    use casting::CastFrom;
+    use floats::{f128, f16, IeeeFloat};
+
+    /// Every f16 NaN of both signs
+    fn f16_nans() -> impl Iterator<Item = f16> {
+        (0..=u16::MAX).map(f16::from_bits).filter(|x| x.is_nan())
+    }
+
+    #[test]
+    fn from_f16() {
+        for x in f16_nans() {
+            let sign = x.to_bits() >> 15;
+            let payload = x.payload().unwrap();
+
+            let y = f32::cast_from(x).to_bits();
+            assert_eq!(
+                y,
+                ((sign as u32) << 31) | 0x7FC0_0000 | ((payload as u32) << 13)
+            );
+
+            let y = f64::cast_from(x).to_bits();
+            let expected = ((sign as u64) << 63) | 0x7FF8_0000_0000_0000 | ((payload as u64) << 42);
+            assert_eq!(y, expected);
+
+            let y = f128::cast_from(x);
+            assert!(y.is_quiet_nan());
+            assert_eq!(y.is_sign_negative(), sign == 1);
+            assert_eq!(y.payload(), Some((payload as u128) << 102));
+        }
+    }
+
+    #[test]
+    fn into_f16() {
+        for x in f16_nans() {
+            let sign = x.to_bits() & 0x8000;
+            let quieted = sign | 0x7E00 | x.payload().unwrap();
+
+            // Widening then narrowing quiets and otherwise round trips
+            let via_f32 = f16::cast_from(f32::cast_from(x));
+            assert_eq!(via_f32.to_bits(), quieted);
+
+            let via_f64 = f16::cast_from(f64::cast_from(x));
+            assert_eq!(via_f64.to_bits(), quieted);
+
+            let via_f128 = f16::cast_from(f128::cast_from(x));
+            assert_eq!(via_f128.to_bits(), quieted);
+        }
+
+        // Low payload bits are dropped when narrowing
+        let x = f32::from_bits(0xFF80_2001);
+        assert_eq!(f16::cast_from(x).to_bits(), 0xFE01);
+
+        let x = f64::from_bits(0x7FF0_0400_0000_0001);
+        assert_eq!(f16::cast_from(x).to_bits(), 0x7E01);
+
+        let x = f128::signaling_nan_with_payload((1 << 102) | 1).unwrap();
+        assert_eq!(f16::cast_from(x).to_bits(), 0x7E01);
+    }
+
+    #[rstest::rstest]
+    #[case::quiet(0x7FC0_0000)]
+    #[case::signaling(0x7F80_0001)]
+    #[case::neg_signaling(0xFFA0_0000)]
+    #[case::full(0x7FFF_FFFF)]
+    fn f32_f128(#[case] bits: u32) {
+        let x = f32::from_bits(bits);
+        let y = f128::cast_from(x);
+
+        assert!(y.is_quiet_nan());
+        assert_eq!(y.is_sign_negative(), bits >> 31 == 1);
+        assert_eq!(y.payload(), Some(((bits & 0x3F_FFFF) as u128) << 89));
+
+        let back = f32::cast_from(y).to_bits();
+        assert_eq!(back, bits | 0x0040_0000);
+    }
+
+    #[rstest::rstest]
+    #[case::quiet(0x7FF8_0000_0000_0000)]
+    #[case::signaling(0x7FF0_0000_0000_0001)]
+    #[case::neg_signaling(0xFFF4_0000_0000_0000)]
+    #[case::full(0x7FFF_FFFF_FFFF_FFFF)]
+    fn f64_f128(#[case] bits: u64) {
+        let x = f64::from_bits(bits);
+        let y = f128::cast_from(x);
+
+        assert!(y.is_quiet_nan());
+        assert_eq!(y.is_sign_negative(), bits >> 63 == 1);
+        assert_eq!(
+            y.payload(),
+            Some(((bits & 0x7_FFFF_FFFF_FFFF) as u128) << 60)
+        );
+
+        let back = f64::cast_from(y).to_bits();
+        assert_eq!(back, bits | 0x0008_0000_0000_0000);
+    }
+
+    #[test]
+    fn f128_narrowing_truncates() {
+        let x = -f128::signaling_nan_with_payload(u128::MAX >> 17).unwrap();
+
+        assert_eq!(f64::cast_from(x).to_bits(), u64::MAX);
+        assert_eq!(f32::cast_from(x).to_bits(), u32::MAX);
+        assert_eq!(f16::cast_from(x).to_bits(), u16::MAX);
+    }
+}
@@ -0,0 +1,266 @@
+//! Packed format tests
+//!
+//! Decoding is checked against every encoding decoded by hand, encoding
+//! against references in f64: rounding to the nearest encoding with the
+//! Vulkan clamping for the unsigned floats, and the specification's
+//! formulas for RGB9E5.
+
+#![cfg(feature = "casting")]
+#![feature(f16, f128)]
+
+#[path = "common/rng.rs"]
+mod rng;
+
+use floats::casting::CastFrom;
+use floats::f16 as F16;
+use floats::packed::*;
+use rng::XorShift;
+
+/// Decodes a finite unsigned float with a 5-bit exponent by hand.
+fn value(bits: u32, mant_bits: u32) -> f64 {
+    let exp = (bits >> mant_bits) as i32;
+    let mant = (bits & ((1 << mant_bits) - 1)) as f64;
+    if exp == 0 {
+        mant * 2f64.powi(-14 - mant_bits as i32)
+    } else {
+        (mant + (1 << mant_bits) as f64) * 2f64.powi(exp - 15 - mant_bits as i32)
+    }
+}
+
+/// Rounds a finite f64 to nearest even, with `mant_bits` mantissa bits and
+/// the exponent range of `f16`, clamping to `[0, max]`.
+fn reference(x: f64, mant_bits: u32) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    let exp = ((x.to_bits() >> 52) as i32 - 1023).max(-14);
+    let quantum = 2f64.powi(exp - mant_bits as i32);
+    let scaled = x / quantum;
+    let mut int = scaled.floor();
+    if scaled - int > 0.5 || (scaled - int == 0.5 && int % 2.0 == 1.0) {
+        int += 1.0;
+    }
+    (int * quantum).min(value((31 << mant_bits) - 1, mant_bits))
+}
+
+/// RGB9E5 as the Vulkan specification writes it, in f64.
+fn shared_exponent(rgb: [f32; 3]) -> u32 {
+    let clamp = |x: f32| {
+        if x > 0.0 {
+            (x as f64).min(65408.0)
+        } else {
+            0.0
+        }
+    };
+    let c = [clamp(rgb[0]), clamp(rgb[1]), clamp(rgb[2])];
+    let max = c[0].max(c[1]).max(c[2]);
+    let log2 = if max == 0.0 {
+        -16
+    } else {
+        ((max.to_bits() >> 52) as i32 - 1023).max(-16)
+    };
+    let mut exp = log2 + 16;
+    if (max / 2f64.powi(exp - 24) + 0.5).floor() == 512.0 {
+        exp += 1;
+    }
+    let s = |x: f64| (x / 2f64.powi(exp - 24) + 0.5).floor() as u32;
+    s(c[0]) | s(c[1]) << 9 | s(c[2]) << 18 | (exp as u32) << 27
+}
+
+#[test]
+fn unsigned_floats() {
+    for bits in 0..0x800u16 {
+        let v = unpack_uf11(bits);
+        if bits < 0x7c0 {
+            assert_eq!(v as f64, value(bits as u32, 6), "{bits:#05x}");
+            assert_eq!(pack_uf11(v), bits);
+        } else if bits == 0x7c0 {
+            assert_eq!(v, f32::INFINITY);
+        } else {
+            assert!(v.is_nan());
+        }
+    }
+
+    for bits in 0..0x400u16 {
+        let v = unpack_uf10(bits);
+        if bits < 0x3e0 {
+            assert_eq!(v as f64, value(bits as u32, 5), "{bits:#05x}");
+            assert_eq!(pack_uf10(v), bits);
+        } else if bits == 0x3e0 {
+            assert_eq!(v, f32::INFINITY);
+        } else {
+            assert!(v.is_nan());
+        }
+    }
+
+    let mut rng = XorShift(0x7c15_9e37_79b9_4f4a);
+    for i in 0..2_000_000u32 {
+        let x = if i % 2 == 0 {
+            f32::from_bits(rng.next() as u32)
+        } else {
+            // Exponents around the formats' range, where rounding matters
+            let exp = 127 - 30 + rng.next() % 50;
+            f32::from_bits((exp << 23) as u32 | (rng.next() as u32 & 0x807f_ffff))
+        };
+        if !x.is_finite() {
+            continue;
+        }
+        assert_eq!(
+            unpack_uf11(pack_uf11(x)) as f64,
+            reference(x as f64, 6),
+            "{x:?}"
+        );
+        assert_eq!(
+            unpack_uf10(pack_uf10(x)) as f64,
+            reference(x as f64, 5),
+            "{x:?}"
+        );
+    }
+
+    // Ties go to even, and the largest values clamp rather than overflow
+    assert_eq!(pack_uf11(1.0 + 2f32.powi(-7)), 0x3c0);
+    assert_eq!(pack_uf11(1.0 + 3.0 * 2f32.powi(-7)), 0x3c2);
+    assert_eq!(pack_uf11(65024.0), 0x7bf);
+    assert_eq!(pack_uf11(65535.0), 0x7bf);
+    assert_eq!(pack_uf11(f32::MAX), 0x7bf);
+    assert_eq!(pack_uf10(64512.0), 0x3df);
+    assert_eq!(pack_uf10(1e10), 0x3df);
+
+    // Negative values, negative infinity and NaN
+    assert_eq!(pack_uf11(f32::INFINITY), 0x7c0);
+    assert_eq!(pack_uf10(f32::INFINITY), 0x3e0);
+    assert_eq!(pack_uf11(f32::NEG_INFINITY), 0);
+    assert_eq!(pack_uf11(-1.0), 0);
+    assert_eq!(pack_uf10(-0.0), 0);
+    assert_eq!(pack_uf10(-f32::from_bits(1)), 0);
+    assert_eq!(pack_uf11(f32::NAN), 0x7e0);
+    assert_eq!(pack_uf11(-f32::NAN), 0x7e0);
+    assert_eq!(pack_uf11(f32::from_bits(0x7f80_0001)), 0x7e0);
+    assert_eq!(pack_uf10(f32::from_bits(0xffa0_0000)), 0x3f8);
+    assert_eq!(pack_uf11(f32::from_bits(1)), 0);
+    assert_eq!(unpack_uf11(1), 2f32.powi(-20));
+    assert_eq!(unpack_uf10(1), 2f32.powi(-19));
+}
+
+#[test]
+fn r11g11b10f() {
+    let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+    for _ in 0..100_000 {
+        let bits = rng.next() as u32;
+        let rgb = unpack_r11g11b10f(bits);
+        assert_eq!(rgb[0].to_bits(), unpack_uf11(bits as u16 & 0x7ff).to_bits());
+        assert_eq!(
+            rgb[1].to_bits(),
+            unpack_uf11((bits >> 11) as u16 & 0x7ff).to_bits()
+        );
+        assert_eq!(rgb[2].to_bits(), unpack_uf10((bits >> 22) as u16).to_bits());
+
+        let x = [f32::from_bits(rng.next() as u32), 1.5, -2.0];
+        let packed = pack_r11g11b10f(x);
+        assert_eq!(packed & 0x7ff, pack_uf11(x[0]) as u32);
+        assert_eq!(packed >> 11, 0x3e0);
+    }
+
+    assert_eq!(
+        pack_r11g11b10f([1.0, 2.0, 0.5]),
+        0x3c0 | 0x400 << 11 | 0x1c0 << 22
+    );
+    assert_eq!(
+        unpack_r11g11b10f(0x3c0 | 0x400 << 11 | 0x1c0 << 22),
+        [1.0, 2.0, 0.5]
+    );
+}
+
+#[test]
+fn rgb9e5() {
+    let mut rng = XorShift(0x9e37_79b9_7f4a_7c15);
+    for i in 0..1_000_000u32 {
+        let rgb = if i % 2 == 0 {
+            [
+                f32::from_bits(rng.next() as u32),
+                f32::from_bits(rng.next() as u32),
+                f32::from_bits(rng.next() as u32),
+            ]
+        } else {
+            // Components within a few binades of each other
+            let mut c = [0.0; 3];
+            let center = rng.next() % 60;
+            for x in &mut c {
+                let exp = 127 - 40 + center + rng.next() % 6;
+                *x = f32::from_bits((exp << 23) as u32 | (rng.next() as u32 & 0x807f_ffff));
+            }
+            c
+        };
+        let packed = pack_rgb9e5(rgb);
+        assert_eq!(packed, shared_exponent(rgb), "{rgb:?}");
+    }
+
+    // Decoding is exact, and each encoding with a mantissa at least 256 or
+    // the smallest exponent round trips
+    for _ in 0..100_000 {
+        let bits = rng.next() as u32;
+        let exp = (bits >> 27) as i32;
+        let rgb = unpack_rgb9e5(bits);
+        for (i, x) in rgb.iter().enumerate() {
+            let mant = (bits >> (9 * i) & 0x1ff) as f64;
+            assert_eq!(*x as f64, mant * 2f64.powi(exp - 24));
+        }
+        let top = (0..3).map(|i| bits >> (9 * i) & 0x1ff).max().unwrap();
+        if top >= 256 || (exp == 0 && top > 0) {
+            assert_eq!(pack_rgb9e5(rgb), bits, "{bits:#010x}");
+        }
+    }
+
+    assert_eq!(
+        pack_rgb9e5([1.0, 1.0, 1.0]),
+        16 << 27 | 256 << 18 | 256 << 9 | 256
+    );
+    assert_eq!(pack_rgb9e5([0.0; 3]), 0);
+    assert_eq!(pack_rgb9e5([-1.0, f32::NAN, -0.0]), 0);
+    assert_eq!(pack_rgb9e5([65408.0, 0.0, 0.0]), 31 << 27 | 511);
+    assert_eq!(
+        pack_rgb9e5([f32::INFINITY, 0.0, 1e10]),
+        31 << 27 | 511 << 18 | 511
+    );
+
+    // 511.5 rounds up to 512 and moves to the next exponent
+    assert_eq!(pack_rgb9e5([511.5, 0.0, 2.0]), 25 << 27 | 1 << 18 | 256);
+    assert_eq!(unpack_rgb9e5(31 << 27 | 511), [65408.0, 0.0, 0.0]);
+    assert_eq!(unpack_rgb9e5(1), [2f32.powi(-24), 0.0, 0.0]);
+}
+
+#[test]
+fn half_2x16() {
+    assert_eq!(pack_half_2x16([1.0, -2.0]), 0xc000_3c00);
+    assert_eq!(unpack_half_2x16(0xc000_3c00), [1.0, -2.0]);
+    assert_eq!(pack_half_2x16([65520.0, f32::NEG_INFINITY]), 0xfc00_7c00);
+    assert!(unpack_half_2x16(0x7e00)[0].is_nan());
+
+    let mut rng = XorShift(0x5851_f42d_4c95_7f2d);
+    for _ in 0..100_000 {
+        let (a, b) = (
+            f32::from_bits(rng.next() as u32),
+            f32::from_bits(rng.next() as u32),
+        );
+        let packed = pack_half_2x16([a, b]);
+        assert_eq!(packed as u16, F16::cast_from(a).to_bits());
+        assert_eq!((packed >> 16) as u16, F16::cast_from(b).to_bits());
+        if !a.is_nan() {
+            assert_eq!(packed as u16, (a as f16).to_bits());
+        }
+
+        let bits = rng.next() as u32;
+        let [x, y] = unpack_half_2x16(bits);
+        assert_eq!(
+            x.to_bits(),
+            f32::cast_from(F16::from_bits(bits as u16)).to_bits()
+        );
+        assert_eq!(
+            y.to_bits(),
+            f32::cast_from(F16::from_bits((bits >> 16) as u16)).to_bits()
+        );
+        if !x.is_nan() && !y.is_nan() {
+            assert_eq!(pack_half_2x16([x, y]), bits);
+        }
+    }
+}
@@ -0,0 +1,228 @@
+//! Decomposition and reassembly tests for f16 and f128
+//!
+//! These operations have no nightly std equivalent, so the expected values
+//! are computed with exact std arithmetic on the nightly types.
+
+#![feature(f16, f128)]
+
+use floats::{bf16, f128 as F128, f16 as F16, IeeeFloat};
+
+/// Computes `x * 2^n` with a single rounding using std arithmetic
+fn f16_scale(x: f16, n: i32) -> f16 {
+    // Every f16 times a power of two in this range is exact in f64
+    (x as f64 * 2.0f64.powi(n)) as f16
+}
+
+/// Computes `x * 2^n` with a single rounding using std arithmetic
+fn f128_scale(x: f128, n: i32) -> f128 {
+    if x == 0.0 || !x.is_finite() {
+        return x;
+    }
+
+    // Normalize into [1, 2) exactly
+    let mut x = x;
+    let mut k = n;
+    while x.abs() >= 2.0 {
+        x /= 2.0;
+        k += 1;
+    }
+    while x.abs() < 1.0 {
+        x *= 2.0;
+        k -= 1;
+    }
+
+    // Multiply by a representable power of two so that only one rounding occurs
+    match k {
+        16384.. => x * f128::INFINITY,
+        -16382..=16383 => x * f128::from_bits(((16383 + k) as u128) << 112),
+        -16494..=-16383 => x * f128::from_bits(1 << (k + 16494)),
+        -16495 => (x * 0.5) * f128::from_bits(1),
+        _ => x * 0.0,
+    }
+}
+
+#[test]
+fn f16_parts_roundtrip() {
+    for bits in 0..=u16::MAX {
+        let x = F16::from_bits(bits);
+        let (sign, exp, mant) = x.to_parts();
+
+        assert_eq!(sign, bits >> 15 == 1);
+        assert_eq!(F16::from_parts(sign, exp, mant).unwrap().to_bits(), bits);
+    }
+
+    assert!(F16::from_parts(false, 0x20, 0).is_none());
+    assert!(F16::from_parts(false, 0, 0x400).is_none());
+}
+
+#[test]
+fn f16_integer_decode() {
+    for bits in 0..=u16::MAX {
+        let x = f16::from_bits(bits);
+        if !x.is_finite() {
+            continue;
+        }
+
+        let (mant, exp, sign) = F16::from_bits(bits).integer_decode();
+        let value = sign as f64 * mant as f64 * 2.0f64.powi(exp as i32);
+        assert_eq!(value, x as f64, "{bits:#x}");
+    }
+}
+
+#[test]
+fn f16_ilogb_logb_frexp() {
+    for bits in 0..=u16::MAX {
+        let x = f16::from_bits(bits);
+        let our = F16::from_bits(bits);
+
+        if x.is_nan() {
+            assert_eq!(our.ilogb(), i32::MIN);
+            assert_eq!(our.logb().to_bits(), bits);
+            assert_eq!(our.frexp().0.to_bits(), bits);
+        } else if x.is_infinite() {
+            assert_eq!(our.ilogb(), i32::MAX);
+            assert_eq!(our.logb().to_bits(), f16::INFINITY.to_bits());
+            assert_eq!(our.frexp(), (our, 0));
+        } else if x == 0.0 {
+            assert_eq!(our.ilogb(), i32::MIN);
+            assert_eq!(our.logb().to_bits(), f16::NEG_INFINITY.to_bits());
+            assert_eq!(our.frexp().0.to_bits(), bits);
+        } else {
+            let e = (x.abs() as f64).log2().floor() as i32;
+            assert_eq!(our.ilogb(), e, "{bits:#x}");
+            assert_eq!(our.logb().to_bits(), (e as f16).to_bits(), "{bits:#x}");
+
+            let (m, n) = our.frexp();
+            assert_eq!(n, e + 1);
+            assert_eq!(m.to_bits(), f16_scale(x, -n).to_bits(), "{bits:#x}");
+        }
+    }
+}
+
+#[test]
+fn f16_scalbn() {
+    for bits in 0..=u16::MAX {
+        let x = f16::from_bits(bits);
+
+        for n in -45..=45 {
+            let expected = if x.is_nan() { x } else { f16_scale(x, n) };
+            let our = F16::from_bits(bits).scalbn(n);
+            assert_eq!(our.to_bits(), expected.to_bits(), "{bits:#x} * 2^{n}");
+        }
+
+        let our = F16::from_bits(bits);
+        if x.is_finite() && x != 0.0 {
+            let inf = if x > 0.0 {
+                f16::INFINITY
+            } else {
+                f16::NEG_INFINITY
+            };
+            assert_eq!(our.scalbn(i32::MAX).to_bits(), inf.to_bits());
+            assert_eq!(our.ldexp(i32::MIN).to_bits(), bits & 0x8000);
+        }
+    }
+}
+
+#[rstest::rstest]
+#[case::zero(0.0f128)]
+#[case::neg_zero(-0.0f128)]
+#[case::one(1.0f128)]
+#[case::third(-1.0f128 / 3.0)]
+#[case::max(f128::MAX)]
+#[case::min(f128::MIN)]
+#[case::min_positive(f128::MIN_POSITIVE)]
+#[case::min_subnormal(f128::from_bits(1))]
+#[case::max_subnormal(f128::from_bits((1 << 112) - 1))]
+#[case::odd_subnormal(-f128::from_bits(0x1234_5678_9abc_def0_1234_5678_9abc))]
+fn f128_decompose(#[case] x: f128) {
+    let our = F128::from_bits(x.to_bits());
+
+    let (sign, exp, mant) = our.to_parts();
+    assert_eq!(sign, x.is_sign_negative());
+    assert_eq!(
+        F128::from_parts(sign, exp, mant).unwrap().to_bits(),
+        x.to_bits()
+    );
+
+    let (mant, exp, sign) = our.integer_decode();
+    let value = f128_scale(mant as f128, exp as i32) * sign as f128;
+    assert_eq!(value.to_bits(), x.to_bits());
+
+    if x != 0.0 {
+        let e = our.ilogb();
+        let scaled = f128_scale(x.abs(), -e);
+        assert!((1.0..2.0).contains(&scaled), "{e}");
+        assert_eq!(our.logb().to_bits(), (e as f128).to_bits());
+
+        let (m, n) = our.frexp();
+        assert_eq!(n, e + 1);
+        assert_eq!(m.to_bits(), f128_scale(x, -n).to_bits());
+    }
+}
+
+#[rstest::rstest]
+#[case::one(1.0f128)]
+#[case::third(-1.0f128 / 3.0)]
+#[case::max(f128::MAX)]
+#[case::min_positive(f128::MIN_POSITIVE)]
+#[case::halfway(f128::from_bits((1 << 112) | 3))]
+#[case::min_subnormal(f128::from_bits(1))]
+#[case::odd_subnormal(-f128::from_bits(0x1234_5678_9abc_def0_1234_5678_9abc))]
+fn f128_scalbn(#[case] x: f128) {
+    let our = F128::from_bits(x.to_bits());
+
+    for n in [
+        -40000, -32767, -16495, -16494, -16384, -115, -113, -112, -111, -2, -1, 0, 1, 2, 113,
+        16383, 32767, 40000,
+    ] {
+        let expected = f128_scale(x, n);
+        assert_eq!(our.scalbn(n).to_bits(), expected.to_bits(), "2^{n}");
+    }
+}
+
+#[test]
+fn f128_special() {
+    assert_eq!(F128::INFINITY.ilogb(), i32::MAX);
+    assert_eq!(F128::NAN.ilogb(), i32::MIN);
+    assert_eq!(F128::from_bits(0).ilogb(), i32::MIN);
+    assert_eq!(
+        F128::INFINITY.scalbn(-5).to_bits(),
+        F128::INFINITY.to_bits()
+    );
+    assert_eq!(
+        F128::from_bits(0).logb().to_bits(),
+        F128::NEG_INFINITY.to_bits()
+    );
+    assert!(F128::from_parts(false, 0x8000, 0).is_none());
+    assert!(F128::from_parts(false, 0, 1 << 112).is_none());
+}
+
+#[test]
+fn generic() {
+    // The same code decomposes the primitives, checked against f64 arithmetic
+    let mut x = 1.0f64;
+    for _ in 0..1000 {
+        let (m, e) = IeeeFloat::frexp(x);
+        assert!((0.5..1.0).contains(&m.abs()), "{x:?}");
+        assert_eq!(IeeeFloat::scalbn(m, e), x);
+
+        let (mant, exp, sign) = IeeeFloat::integer_decode(x);
+        assert_eq!(sign as f64 * mant as f64 * 2f64.powi(exp as i32), x);
+        assert_eq!(IeeeFloat::ilogb(x), x.abs().log2().floor() as i32);
+
+        let y = x as f32;
+        if y.is_finite() && y != 0.0 {
+            let (sign, exp, mant) = IeeeFloat::to_parts(y);
+            assert_eq!(<f32 as IeeeFloat>::from_parts(sign, exp, mant), Some(y));
+        }
+        x *= -1.7;
+    }
+
+    assert_eq!(IeeeFloat::scalbn(1.0f32, -149), f32::from_bits(1));
+    assert_eq!(IeeeFloat::scalbn(1.5f32, -149), f32::from_bits(2));
+    assert_eq!(IeeeFloat::scalbn(f64::MAX, 1), f64::INFINITY);
+    assert_eq!(IeeeFloat::logb(-1e300f64), 996.0);
+    assert_eq!(IeeeFloat::ilogb(f64::from_bits(1)), -1074);
+    assert_eq!(IeeeFloat::logb(bf16::from_bits(1)).to_bits(), 0xc305);
+    assert_eq!(<bf16 as IeeeFloat>::from_parts(false, 0x100, 0), None);
+}
@@ -0,0 +1,306 @@
+//! Remainder tests for f128 and f16
+//!
+//! Every remainder is exact. `%` and `rem_euclid` must match the nightly std
+//! `f128` operations bit for bit. std has no IEEE `remainder`, so that is
+//! checked against reference values computed with integer arithmetic and
+//! against `x - n * y` evaluated with a single rounding.
+
+#![feature(f16, f128)]
+
+#[path = "common/rng.rs"]
+mod rng;
+
+use floats::{f128 as F128, IeeeFloat};
+use rng::XorShift;
+
+const SPECIAL: [f128; 14] = [
+    0.0,
+    -0.0,
+    1.0,
+    -1.0,
+    1.5,
+    f128::MAX,
+    f128::MIN,
+    f128::MIN_POSITIVE,
+    f128::EPSILON,
+    f128::from_bits(1),
+    f128::from_bits((1 << 112) - 1),
+    f128::INFINITY,
+    f128::NEG_INFINITY,
+    f128::NAN,
+];
+
+fn check(a: u128, b: u128) {
+    let (x, y) = (f128::from_bits(a), f128::from_bits(b));
+    let (p, q) = (F128::from_bits(a), F128::from_bits(b));
+
+    let cases = [
+        ("%", (p % q).to_bits(), x % y),
+        (
+            "rem_euclid",
+            IeeeFloat::rem_euclid(p, q).to_bits(),
+            x.rem_euclid(y),
+        ),
+    ];
+
+    for (op, our, std) in cases {
+        if std.is_nan() {
+            assert!(f128::from_bits(our).is_nan(), "{a:#x} {op} {b:#x}");
+        } else {
+            assert_eq!(our, std.to_bits(), "{a:#x} {op} {b:#x}");
+        }
+    }
+}
+
+#[test]
+fn special() {
+    for x in SPECIAL {
+        for y in SPECIAL {
+            check(x.to_bits(), y.to_bits());
+
+            // Without a finite nonzero quotient, std's rounded division
+            // gives the exact result too
+            let our =
+                IeeeFloat::div_euclid(F128::from_bits(x.to_bits()), F128::from_bits(y.to_bits()));
+            let std = x.div_euclid(y);
+            if std.is_nan() {
+                assert!(our.is_nan(), "{x:?} div_euclid {y:?}");
+            } else if (x / y).abs() < 2.0 || (x / y).is_infinite() {
+                assert_eq!(our.to_bits(), std.to_bits(), "{x:?} div_euclid {y:?}");
+            }
+        }
+    }
+}
+
+#[rstest::rstest]
+#[case::full_range(0x3fff, 0x3fff)]
+#[case::near_one(0x3fff, 4)]
+#[case::same_binade(0x3fff, 0)]
+#[case::wide_gap(0x3fff, 200)]
+#[case::subnormal(0x0000, 120)]
+fn random(#[case] center: i32, #[case] spread: i32) {
+    let mut rng = XorShift(0x3c6e_f372_fe94_f82b ^ center as u64 ^ (spread as u64) << 32);
+
+    for _ in 0..20_000 {
+        let a = rng.f128(center, spread);
+        let b = rng.f128(center, spread);
+        check(a, b);
+        check(b, a);
+
+        // Divisors with few significant bits have long exact quotients
+        check(a, b & !((1 << 100) - 1));
+    }
+}
+
+/// The dividend and divisor are up to 2^32767 apart
+#[rstest::rstest]
+#[case::fmod_max_tiny(
+    <F128 as core::ops::Rem>::rem,
+    0x7ffeffffffffffffffffffffffffffff,
+    0x00000000000000000000000000000001,
+    0x00000000000000000000000000000000
+)]
+#[case::fmod_max_three(
+    <F128 as core::ops::Rem>::rem,
+    0x7ffeffffffffffffffffffffffffffff,
+    0x00000000000000000000000000000003,
+    0x00000000000000000000000000000002
+)]
+#[case::fmod_max_pi(
+    <F128 as core::ops::Rem>::rem,
+    0x7ffeffffffffffffffffffffffffffff,
+    0x4000921fb54442d18469898cc51701b8,
+    0x3ffd7528a32adfd3d2857436cc1f6940
+)]
+#[case::fmod_huge_one_half(
+    <F128 as core::ops::Rem>::rem,
+    0x7ffe0000000000000000000000000001,
+    0x3fff8000000000000000000000000000,
+    0x3fff0000000000000000000000000000
+)]
+#[case::remainder_max_pi(
+    <F128 as IeeeFloat>::remainder,
+    0x7ffeffffffffffffffffffffffffffff,
+    0x4000921fb54442d18469898cc51701b8,
+    0x3ffd7528a32adfd3d2857436cc1f6940
+)]
+#[case::remainder_max_three(
+    <F128 as IeeeFloat>::remainder,
+    0x7ffeffffffffffffffffffffffffffff,
+    0x00000000000000000000000000000003,
+    0x80000000000000000000000000000001
+)]
+#[case::remainder_rounds_up(
+    <F128 as IeeeFloat>::remainder,
+    0x3fff8000000000000000000000000000,
+    0x40000000000000000000000000000000,
+    0xbffe0000000000000000000000000000
+)]
+#[case::remainder_tie_to_even(
+    <F128 as IeeeFloat>::remainder,
+    0x4000c000000000000000000000000000,
+    0x3fff0000000000000000000000000000,
+    0xbffe0000000000000000000000000000
+)]
+#[case::rem_euclid_tiny(
+    <F128 as IeeeFloat>::rem_euclid,
+    0x80000000000000000000000000000001,
+    0x3fff0000000000000000000000000000,
+    0x3fff0000000000000000000000000000
+)]
+#[case::rem_euclid_negative(
+    <F128 as IeeeFloat>::rem_euclid,
+    0xc0078000000000000000000000000000,
+    0x4000921fb54442d18469898cc51701b8,
+    0x4000353c17cc1aaa9eb516a2b20dd368
+)]
+#[case::div_euclid_max_pi(
+    <F128 as IeeeFloat>::div_euclid,
+    0x7ffeffffffffffffffffffffffffffff,
+    0x4000921fb54442d18469898cc51701b8,
+    0x7ffd45f306dc9c882a53f84eafa3ea69
+)]
+#[case::div_euclid_min(
+    <F128 as IeeeFloat>::div_euclid,
+    0xfffeffffffffffffffffffffffffffff,
+    0x3fff8000000000000000000000000003,
+    0xfffe5555555555555555555555555552
+)]
+#[case::div_euclid_negative(
+    <F128 as IeeeFloat>::div_euclid,
+    0xc0078000000000000000000000000000,
+    0x4000921fb54442d18469898cc51701b8,
+    0xc005ec00000000000000000000000000
+)]
+fn exact(
+    #[case] f: fn(F128, F128) -> F128,
+    #[case] x: u128,
+    #[case] y: u128,
+    #[case] expected: u128,
+) {
+    assert_eq!(
+        f(F128::from_bits(x), F128::from_bits(y)).to_bits(),
+        expected
+    );
+}
+
+/// `x - n * y` is exactly the remainder, so a fused multiply-add recovers it,
+/// up to the sign of a zero
+#[rstest::rstest]
+#[case::near_one(0x3fff, 4)]
+#[case::quotients(0x3fff, 12)]
+fn remquo(#[case] center: i32, #[case] spread: i32) {
+    let mut rng = XorShift(0xa54f_f53a_5f1d_36f1 ^ spread as u64);
+
+    for _ in 0..20_000 {
+        let (a, b) = (rng.f128(center, spread), rng.f128(center, spread));
+        let (x, y) = (f128::from_bits(a), f128::from_bits(b));
+
+        let (rem, n) = IeeeFloat::remquo(F128::from_bits(a), F128::from_bits(b));
+        let rem = f128::from_bits(rem.to_bits());
+        assert!(rem.abs() <= y.abs() / 2.0, "{x:?} remquo {y:?}");
+        assert_eq!(rem, (-(n as f128)).mul_add(y, x), "{x:?} remquo {y:?}");
+        assert_eq!(
+            rem.to_bits(),
+            IeeeFloat::remainder(F128::from_bits(a), F128::from_bits(b)).to_bits()
+        );
+
+        // The Euclidean quotient satisfies the same identity
+        let q = IeeeFloat::div_euclid(F128::from_bits(a), F128::from_bits(b));
+        let r = IeeeFloat::rem_euclid(F128::from_bits(a), F128::from_bits(b));
+        let (q, r) = (f128::from_bits(q.to_bits()), f128::from_bits(r.to_bits()));
+        assert_eq!(r, (-q).mul_add(y, x), "{x:?} div_euclid {y:?}");
+    }
+}
+
+#[rstest::rstest]
+#[case::octant(-384.0, core::f128::consts::PI, -122)]
+#[case::tie(3.5, 1.0, 4)]
+#[case::zero(0.0, 1.0, 0)]
+#[case::nan(f128::NAN, 1.0, 0)]
+fn remquo_quotient(#[case] x: f128, #[case] y: f128, #[case] n: i32) {
+    let (_, our) = IeeeFloat::remquo(F128::from_bits(x.to_bits()), F128::from_bits(y.to_bits()));
+    assert_eq!(our, n);
+}
+
+#[test]
+fn remainder_special() {
+    let f = |x: f128, y: f128| {
+        let our = IeeeFloat::remainder(F128::from_bits(x.to_bits()), F128::from_bits(y.to_bits()));
+        f128::from_bits(our.to_bits())
+    };
+
+    assert!(f(f128::INFINITY, 1.0).is_nan());
+    assert!(f(1.0, 0.0).is_nan());
+    assert!(f(f128::NAN, f128::INFINITY).is_nan());
+    assert_eq!(f(-0.0, 1.0).to_bits(), (-0.0f128).to_bits());
+    assert_eq!(f(-2.0, 1.0).to_bits(), (-0.0f128).to_bits());
+    assert_eq!(f(-2.5, f128::INFINITY).to_bits(), (-2.5f128).to_bits());
+}
+
+#[test]
+fn assign() {
+    let mut x = F128::from_bits(7.5f128.to_bits());
+    x %= F128::from_bits(2.0f128.to_bits());
+    assert_eq!(x.to_bits(), 1.5f128.to_bits());
+}
+
+/// Every f16 remainder is exact in f64, and so are quotients of up to 2^41
+#[test]
+fn half() {
+    use floats::f16 as F16;
+
+    let mut rng = XorShift(0x1f83_d9ab_fb41_bd6b);
+
+    for _ in 0..200_000 {
+        let bits = rng.next();
+        let (a, b) = (bits as u16, (bits >> 16) as u16);
+        let (p, q) = (F16::from_bits(a), F16::from_bits(b));
+        let (x, y) = (f16::from_bits(a) as f64, f16::from_bits(b) as f64);
+
+        let fmod = x % y;
+        let trunc = ((x - fmod) / y).abs();
+        let odd = trunc % 2.0 == 1.0;
+        let (rem, n) = if 2.0 * fmod.abs() > y.abs() || (2.0 * fmod.abs() == y.abs() && odd) {
+            (fmod - y.abs().copysign(fmod), trunc + 1.0)
+        } else {
+            (fmod, trunc)
+        };
+
+        let sign = (x < 0.0) != (y < 0.0);
+        let n = (n % 2147483648.0) as i32;
+        let n = if sign { -n } else { n };
+
+        let expected = [
+            ("%", (p % q).to_bits(), fmod),
+            (
+                "remainder",
+                IeeeFloat::remainder(p, q).to_bits(),
+                if rem == 0.0 { 0.0f64.copysign(x) } else { rem },
+            ),
+            (
+                "rem_euclid",
+                IeeeFloat::rem_euclid(p, q).to_bits(),
+                x.rem_euclid(y),
+            ),
+            (
+                "div_euclid",
+                IeeeFloat::div_euclid(p, q).to_bits(),
+                x.div_euclid(y),
+            ),
+        ];
+
+        for (op, our, reference) in expected {
+            let reference = reference as f16;
+            if reference.is_nan() {
+                assert!(f16::from_bits(our).is_nan(), "{a:#06x} {op} {b:#06x}");
+            } else {
+                assert_eq!(our, reference.to_bits(), "{a:#06x} {op} {b:#06x}");
+            }
+        }
+
+        if x.is_finite() && y.is_finite() && y != 0.0 {
+            assert_eq!(IeeeFloat::remquo(p, q).1, n, "{a:#06x} remquo {b:#06x}");
+        }
+    }
+}
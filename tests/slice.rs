@@ -0,0 +1,156 @@
+//! Slice and array conversion tests
+//!
+//! Bulk conversions must give exactly the scalar `CastFrom` results, for
+//! every length and alignment, whichever backend is active.
+
+#![cfg(feature = "casting")]
+#![cfg_attr(feature = "nightly", feature(f16))]
+#![feature(f128)]
+
+#[path = "common/rng.rs"]
+mod rng;
+
+use casting::CastFrom;
+use floats::{f128 as F128, f16 as F16, ConvertSlice};
+use rng::XorShift;
+
+fn f32s(rng: &mut XorShift, n: usize) -> Vec<f32> {
+    let mut v: Vec<f32> = (0..n)
+        .map(|_| {
+            let bits = rng.next() as u32;
+            // Mostly within the f16 range, where rounding matters
+            f32::from_bits(if bits & 3 == 0 {
+                bits
+            } else {
+                (0x3000_0000 + (bits >> 2) % 0x1800_0000) | (bits << 31)
+            })
+        })
+        .collect();
+    v[..6].copy_from_slice(&[0.0, -0.0, f32::INFINITY, f32::NAN, 65520.0, 2e-8]);
+    v
+}
+
+fn f64s(rng: &mut XorShift, n: usize) -> Vec<f64> {
+    f32s(rng, n)
+        .into_iter()
+        .map(|x| f64::from(x) * (1.0 + f64::from_bits(0x3e00_0000_0000_0000 | rng.next() >> 12)))
+        .collect()
+}
+
+fn f16s() -> Vec<F16> {
+    (0..=u16::MAX).map(F16::from_bits).collect()
+}
+
+/// Converts every window of `src` up to 40 elements long, at offsets that
+/// misalign it, and the whole slice.
+fn windows<S: Copy, D: Copy + Default>(
+    src: &[S],
+    bulk: fn(&[S], &mut [D]),
+    scalar: impl Fn(S) -> D,
+    bits: impl Fn(D) -> u64,
+) {
+    let mut ranges: Vec<_> = (0..4)
+        .flat_map(|start| (0..=40).map(move |len| start..start + len))
+        .collect();
+    ranges.push(0..src.len());
+
+    for range in ranges {
+        let src = &src[range];
+        let mut dst = vec![D::default(); src.len()];
+        bulk(src, &mut dst);
+
+        for (i, (s, d)) in src.iter().zip(&dst).enumerate() {
+            assert_eq!(bits(*d), bits(scalar(*s)), "element {i} of {}", src.len());
+        }
+    }
+}
+
+#[test]
+fn f16_f32() {
+    let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+
+    windows(
+        &f32s(&mut rng, 100_000),
+        F16::convert_from_f32_slice,
+        F16::cast_from,
+        |x: F16| x.to_bits().into(),
+    );
+    windows(
+        &f16s(),
+        F16::convert_to_f32_slice,
+        f32::cast_from,
+        |x: f32| x.to_bits().into(),
+    );
+}
+
+#[test]
+fn f16_f64() {
+    let mut rng = XorShift(0x9e37_79b9_7f4a_7c15);
+
+    windows(
+        &f64s(&mut rng, 100_000),
+        F16::convert_from_f64_slice,
+        F16::cast_from,
+        |x: F16| x.to_bits().into(),
+    );
+    windows(
+        &f16s(),
+        F16::convert_to_f64_slice,
+        f64::cast_from,
+        |x: f64| x.to_bits(),
+    );
+}
+
+#[test]
+fn f128_f64() {
+    let mut rng = XorShift(0x5851_f42d_4c95_7f2d);
+
+    windows(
+        &f64s(&mut rng, 10_000),
+        F128::convert_from_f64_slice,
+        F128::cast_from,
+        |x: F128| {
+            let bits = x.to_bits();
+            (bits >> 64) as u64 ^ bits as u64
+        },
+    );
+
+    let wide: Vec<F128> = (0..10_000)
+        .map(|_| F128::from_bits(rng.f128(0x3fff, 16)))
+        .collect();
+    windows(
+        &wide,
+        F128::convert_to_f64_slice,
+        f64::cast_from,
+        |x: f64| x.to_bits(),
+    );
+}
+
+#[test]
+fn arrays() {
+    let x = [1.0, -2.5, 65504.0, 1e-7, f32::INFINITY];
+    let h = F16::convert_from_f32_array(x);
+    assert_eq!(h.map(F16::to_bits), x.map(|x| F16::cast_from(x).to_bits()));
+    assert_eq!(
+        F16::convert_to_f32_array(h),
+        [1.0, -2.5, 65504.0, 1.1920929e-7, f32::INFINITY]
+    );
+
+    let y = [0.1, 1e10, -3.0];
+    let h = F16::convert_from_f64_array(y);
+    assert_eq!(h.map(F16::to_bits), y.map(|x| F16::cast_from(x).to_bits()));
+    assert_eq!(F16::convert_to_f64_array(h), h.map(f64::cast_from));
+
+    let q = F128::convert_from_f64_array(y);
+    assert_eq!(F128::convert_to_f64_array(q), y);
+    let q = F128::convert_from_f32_array(x);
+    assert_eq!(F128::convert_to_f32_array(q), x);
+
+    assert_eq!(F16::convert_from_f32_array([]).len(), 0);
+}
+
+#[test]
+#[should_panic(expected = "different lengths")]
+fn length_mismatch() {
+    F16::convert_from_f32_slice(&[1.0, 2.0], &mut [F16::from_bits(0)]);
+}
@@ -0,0 +1,538 @@
+//! Gamma, error and Bessel function tests for f128
+//!
+//! `gamma`, `ln_gamma`, `erf` and `erfc` are compared against the nightly std
+//! `f128` functions. Those come from the platform library and can be several
+//! ULPs off, so each sweep allows the error measured for it. The documented
+//! 1 ULP bound is checked against correctly rounded values computed in
+//! arbitrary precision, as are the Bessel functions, which std lacks.
+
+#![feature(f128)]
+
+#[path = "common/rng.rs"]
+mod rng;
+
+use floats::f128 as F128;
+use rng::{ulps, XorShift};
+
+fn check(name: &str, x: f128, our: F128, std: f128, tolerance: u128) {
+    let our = f128::from_bits(our.to_bits());
+
+    if std.is_nan() {
+        assert!(our.is_nan(), "{name}({x:?}) = {our:?}");
+    } else {
+        assert!(
+            ulps(our, std) <= tolerance,
+            "{name}({x:?}) = {our:?}, std {std:?}"
+        );
+    }
+}
+
+#[rstest::rstest]
+#[case::gamma("gamma", F128::gamma, f128::gamma, 0x3fff, 8, 8)]
+#[case::gamma_small("gamma", F128::gamma, f128::gamma, 0x3fff - 60, 60, 4)]
+#[case::erf("erf", F128::erf, f128::erf, 0x3fff, 5, 2)]
+#[case::erf_small("erf", F128::erf, f128::erf, 0x3fff - 200, 200, 2)]
+#[case::erfc("erfc", F128::erfc, f128::erfc, 0x3fff, 7, 3)]
+fn sweep(
+    #[case] name: &str,
+    #[case] f: fn(F128) -> F128,
+    #[case] g: fn(f128) -> f128,
+    #[case] center: i32,
+    #[case] spread: i32,
+    #[case] tolerance: u128,
+) {
+    let mut rng = XorShift(0x510e_527f_ade6_82d1 ^ center as u64);
+
+    for _ in 0..2000 {
+        let x = f128::from_bits(rng.f128(center, spread));
+        check(name, x, f(F128::from_bits(x.to_bits())), g(x), tolerance);
+    }
+}
+
+#[rstest::rstest]
+#[case::moderate(0x3fff, 10, 6)]
+#[case::huge(0x3fff + 8000, 8000, 3)]
+fn ln_gamma(#[case] center: i32, #[case] spread: i32, #[case] tolerance: u128) {
+    let mut rng = XorShift(0x9b05_688c_2b3e_6c1f ^ center as u64);
+
+    for _ in 0..2000 {
+        let x = f128::from_bits(rng.f128(center, spread));
+        let (our, sign) = F128::from_bits(x.to_bits()).ln_gamma();
+        let (std, std_sign) = x.ln_gamma();
+        check("ln_gamma", x, our, std, tolerance);
+        assert_eq!(sign, std_sign, "ln_gamma({x:?})");
+    }
+}
+
+/// Correctly rounded values computed in arbitrary precision, which the
+/// functions must be within 1 ULP of
+#[cfg(not(feature = "nightly"))]
+#[rstest::rstest]
+#[case::gamma(F128::gamma, reference::GAMMA)]
+#[case::gamma_small(F128::gamma, reference::GAMMA_SMALL)]
+#[case::ln_gamma(|x: F128| x.ln_gamma().0, reference::LN_GAMMA)]
+#[case::ln_gamma_huge(|x: F128| x.ln_gamma().0, reference::LN_GAMMA_HUGE)]
+#[case::erf(F128::erf, reference::ERF)]
+#[case::erf_small(F128::erf, reference::ERF_SMALL)]
+#[case::erfc(F128::erfc, reference::ERFC)]
+fn correctly_rounded(#[case] f: fn(F128) -> F128, #[case] table: &[(u128, u128)]) {
+    for &(x, expected) in table {
+        let our = f128::from_bits(f(F128::from_bits(x)).to_bits());
+        let x = f128::from_bits(x);
+        assert!(
+            ulps(our, f128::from_bits(expected)) <= 1,
+            "{x:?} = {our:?}, expected {expected:#x}"
+        );
+    }
+}
+
+/// Factorials are exact whenever they are representable, and 38! lies
+/// exactly between two representable values
+#[cfg(not(feature = "nightly"))]
+#[test]
+fn factorials() {
+    let mut factorial = 1u128;
+    for n in 1..=35u32 {
+        let x = F128::from_bits((n as f128).to_bits());
+        assert_eq!(x.gamma().to_bits(), (factorial as f128).to_bits(), "{n}");
+        factorial = factorial.saturating_mul(n as u128);
+    }
+
+    let x = F128::from_bits(39.0f128.to_bits());
+    assert_eq!(x.gamma().to_bits(), 0x4093774015499125eee9c3c5e4275fe4);
+}
+
+#[rstest::rstest]
+#[case::gamma_zero(F128::gamma, 0.0, f128::INFINITY)]
+#[case::gamma_neg_zero(F128::gamma, -0.0, f128::NEG_INFINITY)]
+#[case::gamma_one(F128::gamma, 1.0, 1.0)]
+#[case::gamma_two(F128::gamma, 2.0, 1.0)]
+#[case::gamma_inf(F128::gamma, f128::INFINITY, f128::INFINITY)]
+#[case::gamma_neg_inf(F128::gamma, f128::NEG_INFINITY, f128::NAN)]
+#[case::gamma_pole(F128::gamma, -3.0, f128::NAN)]
+#[case::gamma_overflow(F128::gamma, 1756.0, f128::INFINITY)]
+#[case::gamma_underflow(F128::gamma, -3000.5, -0.0)]
+#[case::gamma_underflow_positive(F128::gamma, -3001.5, 0.0)]
+#[case::erf_neg_zero(F128::erf, -0.0, -0.0)]
+#[case::erf_inf(F128::erf, f128::INFINITY, 1.0)]
+#[case::erf_neg_inf(F128::erf, f128::NEG_INFINITY, -1.0)]
+#[case::erf_large(F128::erf, -20.0, -1.0)]
+#[case::erfc_zero(F128::erfc, 0.0, 1.0)]
+#[case::erfc_inf(F128::erfc, f128::INFINITY, 0.0)]
+#[case::erfc_neg_inf(F128::erfc, f128::NEG_INFINITY, 2.0)]
+#[case::erfc_underflow(F128::erfc, 107.0, 0.0)]
+#[case::erfc_nan(F128::erfc, f128::NAN, f128::NAN)]
+fn special(#[case] f: fn(F128) -> F128, #[case] x: f128, #[case] expected: f128) {
+    let our = f(F128::from_bits(x.to_bits()));
+
+    if expected.is_nan() {
+        assert!(our.is_nan());
+    } else {
+        assert_eq!(our.to_bits(), expected.to_bits(), "{x:?}");
+    }
+}
+
+#[rstest::rstest]
+#[case::one(1.0, 0.0, 1)]
+#[case::two(2.0, 0.0, 1)]
+#[case::zero(0.0, f128::INFINITY, 1)]
+#[case::neg_zero(-0.0, f128::INFINITY, -1)]
+#[case::pole(-3.0, f128::INFINITY, 1)]
+#[case::inf(f128::INFINITY, f128::INFINITY, 1)]
+#[case::neg_inf(f128::NEG_INFINITY, f128::INFINITY, 1)]
+fn ln_gamma_special(#[case] x: f128, #[case] expected: f128, #[case] sign: i32) {
+    let (our, our_sign) = F128::from_bits(x.to_bits()).ln_gamma();
+
+    assert_eq!(our.to_bits(), expected.to_bits(), "{x:?}");
+    assert_eq!(our_sign, sign, "{x:?}");
+}
+
+/// The reflection formula keeps negative arguments correctly rounded, which
+/// the platform library does not manage for `-0.5`
+#[cfg(not(feature = "nightly"))]
+#[test]
+fn ln_gamma_negative() {
+    let (our, sign) = F128::from_bits((-0.5f128).to_bits()).ln_gamma();
+    let expected: f128 = 1.265512123484645396488945797134705923899;
+
+    assert_eq!(our.to_bits(), expected.to_bits());
+    assert_eq!(sign, -1);
+}
+
+/// Correctly rounded values, including arguments next to zeros where the
+/// result has lost most of its leading bits to cancellation
+#[cfg(not(feature = "nightly"))]
+#[rstest::rstest]
+#[case::small(
+    F128::jn,
+    0,
+    0x3ffd0000000000000000000000000000,
+    0x3ffef807fc72aa86460906600ab835b4
+)]
+#[case::first_zero(
+    F128::jn,
+    0,
+    0x400033d152e971b3fbc2b18a09769c6a,
+    0xbf8d269da9061e1a05f8ff45bd34a7a2
+)]
+#[case::tenth_zero(
+    F128::jn,
+    1,
+    0x4004018476e6b2befc838bc65f32933b,
+    0xbf8d99eb647fd352e6c085ffb709ee6c
+)]
+#[case::transition(
+    F128::jn,
+    0,
+    0x40057f00000000000000000000000000,
+    0x3ffaf8276f30ace5534be6591c595d77
+)]
+#[case::asymptotic_zero(
+    F128::jn,
+    1,
+    0x40073af1876f96dfd094580a1ddf8e08,
+    0x3f90af87c1a849ce0ca294907b8b630f
+)]
+#[case::huge(
+    F128::jn,
+    0,
+    0x7e7f5eb851eb851eb851eb851eb851ec,
+    0x20ba8df627f1277a5284bc89841b3f4d
+)]
+#[case::tiny(
+    F128::jn,
+    1,
+    0x00000000400000000000000000000000,
+    0x00000000200000000000000000000000
+)]
+#[case::order(
+    F128::jn,
+    5,
+    0x40024000000000000000000000000000,
+    0xbffcdf5ba686833257ba60a60f1c727d
+)]
+#[case::negative_order(F128::jn, -3, 0x40004000000000000000000000000000, 0xbffcbb98fc5e82abb20d377d0fadde40)]
+#[case::negative_argument(
+    F128::jn,
+    3,
+    0xc0004000000000000000000000000000,
+    0xbffcbb98fc5e82abb20d377d0fadde40
+)]
+#[case::turning_point(
+    F128::jn,
+    100,
+    0x40056800000000000000000000000000,
+    0x3ff65511037fcb013e44a72e39a31097
+)]
+#[case::high_order(
+    F128::jn,
+    1000,
+    0x4008f400000000000000000000000000,
+    0x3ffa6e6f052e1104c2b9cd1d8482e393
+)]
+#[case::forward(
+    F128::jn,
+    40,
+    0x40072c00000000000000000000000000,
+    0x3ffa6b07fb46382e3283a2578c335103
+)]
+#[case::second_small(
+    F128::yn,
+    0,
+    0x3ffd0000000000000000000000000000,
+    0xbffedcf723b7d21f42a896180a136155
+)]
+#[case::second_first_zero(
+    F128::yn,
+    0,
+    0x3ffec982eb8d417ea3d53a4e068ff05e,
+    0x3f8c36db285592700d7a95afbb1715b8
+)]
+#[case::second_zero(
+    F128::yn,
+    1,
+    0x4004f052c3146d1d9a9b992af3266a2a,
+    0x3f8a62de13df6a05b421e0e0357cdc80
+)]
+#[case::second_pole(
+    F128::yn,
+    1,
+    0x3f9b4484bfeebc29f863424b06f3529a,
+    0xc0620120f1108d0decd2ee784c5641d7
+)]
+#[case::second_order(
+    F128::yn,
+    10,
+    0x4008f400000000000000000000000000,
+    0xbff785dfaae8f01dc68d7039a71b0370
+)]
+#[case::second_negative_order(F128::yn, -3, 0x40004000000000000000000000000000, 0x3ffe8319b4c1093dfe62d671ffdb32be)]
+#[case::second_high_order(
+    F128::yn,
+    1000,
+    0x4008c200000000000000000000000000,
+    0xc0274e72d8a6f97eeaecaa142ee6e518
+)]
+#[case::second_huge(
+    F128::yn,
+    1,
+    0x6327e666666666666666666666666666,
+    0xae661eeafe9ab4bc9e0f5accaab67fc1
+)]
+fn bessel(
+    #[case] f: fn(F128, i32) -> F128,
+    #[case] n: i32,
+    #[case] x: u128,
+    #[case] expected: u128,
+) {
+    assert_eq!(f(F128::from_bits(x), n).to_bits(), expected);
+}
+
+#[cfg(not(feature = "nightly"))]
+#[test]
+fn bessel_orders() {
+    let mut rng = XorShift(0x1f83_d9ab_fb41_bd6b);
+
+    for _ in 0..200 {
+        let x = F128::from_bits(rng.f128(0x3fff, 12));
+        assert_eq!(x.j0().to_bits(), x.jn(0).to_bits());
+        assert_eq!(x.j1().to_bits(), x.jn(1).to_bits());
+        assert_eq!(x.y0().to_bits(), x.yn(0).to_bits());
+        assert_eq!(x.y1().to_bits(), x.yn(1).to_bits());
+        assert_eq!(x.jn(-7).to_bits(), (-x.jn(7)).to_bits());
+    }
+}
+
+#[cfg(not(feature = "nightly"))]
+#[rstest::rstest]
+#[case::j0_zero(F128::jn, 0, 0.0, 1.0)]
+#[case::j0_inf(F128::jn, 0, f128::NEG_INFINITY, 0.0)]
+#[case::j1_neg_zero(F128::jn, 1, -0.0, -0.0)]
+#[case::j1_neg_inf(F128::jn, 1, f128::NEG_INFINITY, -0.0)]
+#[case::jn_neg_zero(F128::jn, 2, -0.0, 0.0)]
+#[case::jn_neg_order(F128::jn, -3, f128::NEG_INFINITY, 0.0)]
+#[case::jn_underflow(F128::jn, 20000, 100.0, 0.0)]
+#[case::jn_nan(F128::jn, 3, f128::NAN, f128::NAN)]
+#[case::y0_zero(F128::yn, 0, 0.0, f128::NEG_INFINITY)]
+#[case::y0_neg_zero(F128::yn, 0, -0.0, f128::NEG_INFINITY)]
+#[case::y0_inf(F128::yn, 0, f128::INFINITY, 0.0)]
+#[case::y0_negative(F128::yn, 0, -1.0, f128::NAN)]
+#[case::yn_overflow(F128::yn, 2000, 1.0, f128::NEG_INFINITY)]
+#[case::yn_neg_order_overflow(F128::yn, -2001, 1.0, f128::INFINITY)]
+fn bessel_special(
+    #[case] f: fn(F128, i32) -> F128,
+    #[case] n: i32,
+    #[case] x: f128,
+    #[case] expected: f128,
+) {
+    let our = f(F128::from_bits(x.to_bits()), n);
+
+    if expected.is_nan() {
+        assert!(our.is_nan());
+    } else {
+        assert_eq!(our.to_bits(), expected.to_bits(), "{x:?}");
+    }
+}
+
+/// Pairs of argument and correctly rounded result bits, for random arguments
+/// drawn like those of the sweeps above
+#[cfg(not(feature = "nightly"))]
+#[rustfmt::skip]
+mod reference {
+    pub const GAMMA: &[(u128, u128)] = &[
+        (0x3ff9c2ce7ed4d57b1e2feb89414c343c, 0x40041e583733eeb7152732a371c048f9),
+        (0x400635bfc9e9c616612e7696a6cecc1b, 0x4383f15cb0909621e8386810c0481cf0),
+        (0x400663cad5f4b3b2e4b06ce60741c7a8, 0x442d9e0873a1b6ba610c5be820df47c2),
+        (0x3ff7b8b6442e3d437204e52db2221a58, 0x40062844b92de95aa54fecd113974b9d),
+        (0xbffee6c31a2b8f1ff1fd42a29755d4c1, 0xc0034c7df2448c499693b0dabcc89c14),
+        (0x3ff78a9aa648a7dd06839eb905b6e6e3, 0x40064b04a76d481efc8fb417f19a8460),
+        (0x40036c0ff8130c4237730edfafbd67f9, 0x4043c5b3b94cc9bd3361b13c62b118c0),
+        (0xbff77019c381e88f38c0c8fd8712b8bc, 0xc006653dfc7d01a0736bc3740dc5a1df),
+        (0xc0063b1a587fd2803bab6c398d88348a, 0x3c60e39a85a84e3a6e819b52e111e969),
+        (0xbffe4a2ff3c64af775a89294c2cd789a, 0xc000f1d15c7b778979a0d3b4db01e03a),
+        (0xbff78e73ea90a8f0d66b829e6a8ac4ba, 0xc0064a1e71391d1a117dfb43c9f95732),
+        (0xbffab940fe175330a11d459a2f978d87, 0xc003332d4e82f64d062163276ec58e77),
+        (0xc000e544552b82f6be3edc0a1ef2a4f0, 0x3ffd2b77efca2b9dc77f936c0a88ffac),
+        (0xc00781f96c0f3459f79b17aeefba91fc, 0x35370d8a11b97ff0b0b5bcbfb9443a66),
+        (0xbffdf934966baea148beab134da98f1d, 0xc000c5f2841f2e004eca0a6851452704),
+        (0xc00664b2815a47c5f0dfb4a5d8a064df, 0xbbc734349f25318155f89209d5a4472f),
+        (0xbff8cc22be6521cc3e2434e37af027bc, 0xc0051f38610ceabe44b804f601f65fc5),
+        (0x40048c7e5dfbd3d12c4a3698aa2ca1af, 0x40cd12536d9355d4f9b13932e5d7adfe),
+        (0xc0028228a9ec0806705fca161622bd79, 0xbfe5c3a076c74bbc0ecaae316818d85c),
+        (0x3ffc5eda64ac5db9d707107e855c3844, 0x40015a0c67709a07280dae93eccc9fa2),
+        (0x3ff7b4104efbc8d60b21fbac78255d68, 0x40062b704723745b0115930cc1442fba),
+        (0xc00380922b28fef02b9c014ea5ac06d8, 0xbfb4867e7114ce653cd2c82d00345b09),
+        (0x3ff7eb8a8a245e6b33138131c541013d, 0x40060983f6d9546b68b4453814302ec5),
+        (0xbffef3d45804f92283868a29678a5aa3, 0xc00454342d0800ef950e92b6aa09dad1),
+        (0xc002a8c244ef7febe8e5b4617589a82b, 0x3fdf6606def01566108f2a6ff8ef2483),
+        (0xbff7d20bdb610487c89da11b62397bc7, 0xc0061a6938dfb2b898b5bee4215df6e8),
+        (0xc007c70384c8199921167d8fcf23cae8, 0x32de2b5275e060183059da69b46fea64),
+        (0xbffd7b290e5e18baf320cd576d14475b, 0xc000ec8649f7240359d3c38cf55a52f1),
+        (0xc002f0e63328ad088ded3c9691eb79fa, 0x3fd63c71d75ca4c51ee7dd5b3e23ea38),
+        (0xc0046a175b569643d037cdff7c240d49, 0x3f454bd4b6546b7e527e2f7f2f13bc08),
+        (0x3ff7c9549f9d01298a449ebe89d9bf02, 0x40061d762bc8db7eb59c98f7476b1a17),
+        (0xc001cdf807295e4299901c0475491bc3, 0x3ff44b5ea892c09660ea0d876a9b4929),
+    ];
+
+    pub const GAMMA_SMALL: &[(u128, u128)] = &[
+        (0xbff517720e7a269fd95bafc8f2a4d27b, 0xc008d5550d8ee6fd2088bee98f74daf8),
+        (0x3fb5cf18bc6887782b491044d5e34124, 0x40481b08a77830e89b7dda00a14a6caa),
+        (0xbff436539b1f282e4067c3584ee207f8, 0xc009a6832bdbbb1e17959a5c4c8e5d73),
+        (0xbf8bffed288bc781ae66267594c9c950, 0xc07200096c12dfee7ba3d00bcd21004a),
+        (0x3fd8dc38b91751dacdbd47d364be8049, 0x4025133bb4acc4d40108b2de0a1ab93f),
+        (0xbfc8ef8a8b4f2fc15f3f57ebf30b94fa, 0xc0350880b34bd806887a143dcc3ef1f6),
+        (0x3fc7defc09325626e6b58de744ab6cce, 0x403611a545586eeff329407261efdc34),
+        (0x3fb5e8625186ee32ee8d7ee9770348a0, 0x40480c6102d45633cdaed333ce5bc730),
+        (0x3fbd2a1b8697bbd0e2520e33e44c5055, 0x4040b7ae270f90d9d392fe6179719f18),
+        (0xbf9d2d3d061b90303b08c6e33c729578, 0xc060b31c4e239c2ceafa5ab2f75dd06d),
+        (0x3f9d5c14829e07b0829a48d422fe99a2, 0x4060788e969b7e9224d80406474ea08c),
+        (0xbfc8fec32e8d4b8a8f54f8ceacaab39e, 0xc035009ecafb96f0c02466fb700da0ed),
+        (0xbfc0867ebc01bfce6a27e0dfcbf87544, 0xc03d4fa7ee2c152b8c5e86e585f0f273),
+        (0xbffb97eeca2ce6bc5d3fd983c34c769f, 0xc0025740db6299a50085268cb205176b),
+        (0x3fb5721df63f23d0dbe53fcafb2147df, 0x40486222fa0b9179e2c5989a8d55cb7d),
+        (0x3fe7761ebd143fa9b714210c665d7435, 0x40165e58eef9a7a1419c9452cb8d73eb),
+    ];
+
+    pub const LN_GAMMA: &[(u128, u128)] = &[
+        (0x4007ea7b5eb561a4216363698b529b4a, 0x400a3e4218455a47c6b08a7e0ffe6f8f),
+        (0xc0049b0810c67fd994b2b8fda02f34a6, 0xc006315f18f45038caa6b346cf254d3c),
+        (0x400431163bfd1d338d0038ec42650644, 0x40058f4b02d07b71c44f2e0a28a12199),
+        (0xc00479f28cb4a0d7d62256758a7d43b5, 0xc00610762c95b46c25d6b8d8e7d7a005),
+        (0x4009a2863b5f3d86268ecc45dc6bf1e1, 0x400c4fefdf8bdac6383f8579f764b50f),
+        (0x4005abe103e0a813bdc2ae9963d2e490, 0x4007876f4b29f32da699df0769efc7fa),
+        (0xbff79752f51e8722c21b609228ce6f24, 0x400145663d79435d99e2d4a52c58109e),
+        (0x3ffedd93d2d5844307f062cec7b317d9, 0x3ffa5d686bd420c289079fdd10eef98b),
+        (0x4004e0f9eb8f624fb804d82098418117, 0x40067210ad565782387fddc723a92dc3),
+        (0x400293b0cd085b71ba6676b3651c5253, 0x400330582813b83a323f2151940df023),
+        (0x3ff9092f18f2c41c5d92b243e0fd67dd, 0x400107541913a85963c626d3189231eb),
+        (0x4004ac0af729b4c8420b0ebe378c74dc, 0x40063cbad1d1b6092f77c2d4aaae686d),
+        (0x400981da6bd0638b4d100d8fdaf0105b, 0x400c31c9822384e45047f41ab0edd5cb),
+        (0xc00195c788bafad959d5450592f3277b, 0xc0017cbd6b7fc54a9d645b11ab729749),
+        (0x4007ae9656363b4be779c4703b7dae04, 0x400a105c04b2f87fdab5c284e9a51b9c),
+        (0xbff59b16fdb17f5447997b6bdb3d1150, 0x40019d5c12f1e8bd32a815696ba033e9),
+        (0xbffaf6f2538e504edc52bdcab2d87d5e, 0x40006a1ec7ce59a67ecbbfa8ce35ecbb),
+        (0xc007a7cfb6bcb64f1aa4b64091b1078e, 0xc00a0bc532e8829edbef69e0fd26d58e),
+        (0x4009445f92d3043afcf249f3d4e441c3, 0x400bf4085082288613f28662aeb566f2),
+        (0x3ff8a385da9bf98c7b6471e2103ef3c2, 0x400116740f099ea7b3a24db5dabb36c4),
+        (0xc004110dccf3d0b35815a3d516a91f39, 0xc0055bf7b8c1243993915f1e7bcd0d6b),
+        (0x3ff9c4cf6d59298c4b3c74f70526ef70, 0x4000c96067a714343f4de3395587c89a),
+        (0x3ff8c2fa9d5200ef9ae085bf0b500a3f, 0x400111cab6bf1ac6bac104fc6e4f55af),
+        (0x40018d0454b9693c961cadbcb7ebb70c, 0x400148d8520513843439290a42563367),
+        (0xbffd0938ff9e48403c67523f81633acf, 0x3fff9066abd97a9b0d5c33f8f157d85f),
+        (0x3ff5891b998a0e311badb4f513b45a39, 0x4001a01be110c99cb204023f7cbabc09),
+        (0x3ffb9c474aa71c38686e80a9f8af8c79, 0x40001f7a3cab87b7f87910d47cb9cd51),
+        (0x3ff9de26fad9d3a90add12e3b09258ce, 0x4000c24d0df6a826fa475e07fa01ce34),
+        (0x3fffe58b236955e7f56ab44e5c35d7ed, 0xbffa4916fe121f7f80ea4bc32a711932),
+        (0xc0018523deb135fa75dd67de6072c48f, 0xc0010ca75eb5e4649a9d31a8d98c8e70),
+        (0x40098f2bae541ad6987c88bbdde8bcb9, 0x400c3e0962cbfb2e8aba443d007a2689),
+        (0x400881cccfbf40b8f0cc8de3f90ee1f2, 0x400b103f3b5dc6517917be2b56fb9b0f),
+    ];
+
+    pub const LN_GAMMA_HUGE: &[(u128, u128)] = &[
+        (0x54b8a6a36513270e269e0d37f2a74de4, 0x54c47b5e3c014932cfe42925fe4e9d43),
+        (0x43161818892f902bd23f0824128b2f33, 0x431f2b71f1779ddc15d879dd6aaf2ee2),
+        (0x576681e7e8e25d940ed904759531985d, 0x5772872fe93eb4c33dd5dee94b52294c),
+        (0x4dbc6b0d6f03675a1600a35a099950d8, 0x4dc7b00993afdb81cc470f14f7d69f28),
+        (0x44776cad8d116ece1738f7d93d9c1724, 0x44811a2ae09dc0dc7d988ccd5c6b6f3f),
+        (0x43c7f28c1fb17c2390c192cfd3ac94af, 0x43d1468193a137db39da52674923bc56),
+        (0x4e48f29d953f48f1a09f76b5a170b338, 0x4e543488012dcb121de9a48d95554da9),
+        (0x43f40cb1658cda1495e60af593bd04cf, 0x43fd6ffc9f2397d5eee27fc5a8b47ca9),
+        (0x7e79dbc48e81973e0becd7b03898d190, 0x7e8741ea88acc10ebaa689cf5dd04e0a),
+        (0x48848a6a24ede6a46b4cb2424a23d596, 0x488f23083e67bd636e60e3fb2871180a),
+        (0x4788d0ed8f6d05584ef8aa3892276658, 0x47932f7259eeab5b88a2b943fb52f6b3),
+        (0x6ba4923a94e3bf911a61dbe22e44158b, 0x6bb17c3b316b75cdda9c9ff91d2d0d99),
+        (0x68e28c3818f135d25f557203301850c5, 0x68ef5ee174d6b26cba9fcd625b4e9cbe),
+        (0x6d929e770f4205b4907a70c31012f037, 0x6d9f991fb685e1393aefbfd7dd30a18f),
+        (0x4d2d6d76881ed162ae2eb1547f150524, 0x4d38a138253de26b75afeec4f6f5871f),
+        (0x71bdec6695e761d17731af10506bf2ef, 0x71cb09429a07d0e80f1bf0b41b22df67),
+    ];
+
+    pub const ERF: &[(u128, u128)] = &[
+        (0x3ffe7a97656412a9b8a1abcd1a6916c7, 0x3ffe689b545ceef802f5035d92fec21f),
+        (0x3ffb8ca566ceab360512bd1311072231, 0x3ffbbe2c0f082c196aeaa5664bf15089),
+        (0xbffe0f10c3e1b258fd724452ccea71ff, 0xbffe1789e202ccb7d9e61070e452794e),
+        (0x4002c79d46d4ac7a5c3902b38963dc6e, 0x3fff0000000000000000000000000000),
+        (0x3ffbed6ff165c8ce36e2f24b43000de0, 0x3ffc150d212bc7a75c31f54a151babda),
+        (0x40044591ccea264542a00403ce80c4b0, 0x3fff0000000000000000000000000000),
+        (0x3ffcde08a08179104a25e4664f5253a0, 0x3ffd08e1f5b23832225abe396a5a34fd),
+        (0xbfff56609b191bf4d8441b5616332aca, 0xbffee202bbfec096e1695200b3737595),
+        (0xc0003f502d83a8233fb62d2c81862fc9, 0xbffeffc91b782775e43a8dceb1059fd9),
+        (0x3ffed160ef412ed6f1cfd99216df6486, 0x3ffe9a4c00953989b305c4ba6f29cfed),
+        (0xc002e8ab01d89a024cdce7a6d7288ff6, 0xbfff0000000000000000000000000000),
+        (0x4003d9194fcfa583e1df8af9b474c7e8, 0x3fff0000000000000000000000000000),
+        (0xc00299526c79a3de69f85e3131f3b923, 0xbfff0000000000000000000000000000),
+        (0x40004e1b3bb4a570294c4ea3738d243a, 0x3ffeffe2c4426033cfc4b28c7dcb1446),
+        (0x3ffaa0587671863c0bdbc23a14c15c91, 0x3ffad5643750a896f1db650b2160d2c8),
+        (0xbffe78a3a5e333cb88dcf94384d4cd1f, 0xbffe675402a164880de86e3e20d565d2),
+        (0xbfff3211ac7cc4a4ff4dab102522d538, 0xbffed1790b7675851d1b8cfc82e6fc9a),
+        (0x4000a1f6a2909cb633e238b4e9dd38b8, 0x3ffeffff7deba297f483b2388bc3711b),
+        (0x3ffebf376f98bca35b17b9662f0733c8, 0x3ffe9108e6834fc60b9871bd68e0f187),
+        (0xbfffe78132decd6b8efbc170a26a25c8, 0xbffefc6026a5f483167af26111c47805),
+        (0x3ffb3a97b54a23020fc5b043d6e4a515, 0x3ffb62487100e5486050c8c1ab85b7a9),
+        (0x40031f443cc75f3edcb285f89d8cf4d4, 0x3fff0000000000000000000000000000),
+        (0x3ffc0af50692b534758240df4a7a0305, 0x3ffc2b880d2794ada38f2de7efeb18ea),
+        (0x3ffb4922f469aef8f6e7d078e55b85dd, 0x3ffb7297cf13a790d3d8b5e8f46a6a67),
+        (0xc00452a304a7f00753be4721f5b9e1f5, 0xbfff0000000000000000000000000000),
+        (0x3fffa6e4c676176a272515cdf74c3816, 0x3ffef6067838f3a4c313aa8c0dbc560e),
+        (0x40034b1c13e7d611d163b764ae17584a, 0x3fff0000000000000000000000000000),
+        (0xbffd22ed4ac034cf71b34e47e4e2aafd, 0xbffd3fa7365778bb2efff947722151e0),
+        (0x400054d028adf9c6f6396ae3994b9717, 0x3ffeffea3675135fafddbd693db37a6f),
+        (0xbffa2b68746ccfcd0b77d43a5d02db43, 0xbffa51b20628341d8de5e13730cd5205),
+        (0x3fff18db924aecbe4a53583bff478895, 0x3ffec229f2f37fdd335de488c38c4b2b),
+        (0x3ffd1d16353a09cfeaa1b2956c8826ec, 0x3ffd3990812b5a50211083a2658be733),
+    ];
+
+    pub const ERF_SMALL: &[(u128, u128)] = &[
+        (0xbef1b0c1cb91ce375bc8fbbcbde5c099, 0xbef1e85062206072336f9cefcbebccee),
+        (0xbfe9076c87b0b125ec1d7da0a6eb8c9e, 0xbfe9293df9c0cd6f689414ca7369b771),
+        (0xbf5da6233fc1ea36f17fd374c6a53877, 0xbf5ddc54d51d2b5ebb458ee147ffdd11),
+        (0x3ebfde527814e8a25f2dd97f1cfb10f6, 0x3ec00ddd477653efdc3af09a74aac9c4),
+        (0x3f313fd492edcf451a1afe878b33e968, 0x3f3168e3d1ffbb10195e68e430e3b470),
+        (0x3fe52e9c478c281d687c966c377b9aa2, 0x3fe5557597b9c992fb427a99c25a19e3),
+        (0xbff7cc11c30d8b7628dbd25e63b229f1, 0xbff8038fe517a7d5268fd6b9fd202517),
+        (0x3eb6206f71e0c07e9e115e4b9e30691c, 0x3eb64576e429c75df2b01bb4642071c3),
+        (0x3e6f359e015c33b2df1461aaf8eb18b9, 0x3e6f5d5d9875264c6ee6518cdff56571),
+        (0xbedddf562a759159fb7ff337f5cae3bf, 0xbede0e6fcc208bef9b3c94fd2ed7c1d8),
+        (0x3f038a0a32ea6928f6236bf2504b74ba, 0x3f03bca05832bc4dd24127aafdcdf212),
+        (0xbfcaf0e32e81d66d346c6e2ba02fdaa1, 0xbfcb1856b5c8900b210d82e46c94c91b),
+        (0xbfd0e4ccf7108e96f770c2263266aa3b, 0xbfd11184fc3ebc6932b39f3c0aa9b252),
+        (0x3f072a7c6a3753915c76f18a0585a01c, 0x3f0750ce2d55a6e6b3d6ac74b567eedc),
+        (0xbeb94d2554f46a6910acff0043892dfc, 0xbeb977ea3114c967fb2db5b48bd03f9b),
+        (0xbfa3ad8d9892139600ddb74d960d5a8f, 0xbfa3e4b2e339bf133dab9f49d6a062c0),
+    ];
+
+    pub const ERFC: &[(u128, u128)] = &[
+        (0xc001c3207c2b3abe14a03569d26b9496, 0x3fffffffffffffffffffffe6b64aa6bc),
+        (0x3ff89623a9ae7a34254499c7001d9a88, 0x3ffef8d6fa322da4bd0d6636cebac0c2),
+        (0x4004c52751c342505f877031bc1e3ac1, 0x2de34a9af1dc4826e563f71d334c8c46),
+        (0x3ffcbad532b7228fcd4a55577d24b396, 0x3ffe84ffbfc22dba5d73a31d409cd19e),
+        (0xbffeae9a8a0c510089ce5ef7e91b4ad1, 0x3fffc405b5c657bc57d3022ac719a033),
+        (0x3ffbce1cb313fc7e8db9b92c903c2ac9, 0x3ffebf18853ac4133455f6eafaf3bae2),
+        (0xbffcaf899c2c0ac2cda95957a9b3d1a2, 0x3fff3bfa7197f1f52bb22b42888720db),
+        (0x400517d7fd80206055e8b3eb6cb9185e, 0x2462a86e83c3a4a9b10d440bdeed0dd7),
+        (0x400471f9401fe4fcce06294d68f22599, 0x33eb0c46226ecbe99df7f0b834006914),
+        (0xbff9a28ab3642b1932793637c16cf5c5, 0x3fff0760af3dde2d97a73ef0dc335e3a),
+        (0xbffc96ac0bb1e330f38d2e6418f918e2, 0x3fff389d6e4035ae408f2be126fbc3e7),
+        (0xbffb5c41e9a51fb2a7c88e4bd3f18766, 0x3fff1880587aa47494510f6c4d452b2e),
+        (0x400683c03197d4e2e8d5b9e3d52d5759, 0x00000000000000000000000000000000),
+        (0xc002ce35e474e007b2aa40b4f8540d95, 0x40000000000000000000000000000000),
+        (0xc0063eb15c832a51a22ba4d70746a9ba, 0x40000000000000000000000000000000),
+        (0xbffe1e9d96d2f9e05b7d3b0f4de8344e, 0x3fff924a634c37310939389e607201b6),
+        (0x400032f98677a5f2adb9ce1be14cd8df, 0x3ff46c464cac53be7ea3fc5953346162),
+        (0x4001b8104ff1a00144941663a9a967c1, 0x3fb71fa6a5ad8c379f27bca7c3636323),
+        (0x3ffe98eb235ae280390567c27bd6aa42, 0x3ffd08e682b02c23b66800a2a623ae1a),
+        (0x400585abb2beca81e0408802dd93fa0c, 0x0a7c122c7a7973203eb8d81471f5c6b9),
+        (0x3ffba5e2045df64a2b4cf14cc72ec6f0, 0x3ffec4b44b78f64bf16df29ba06b9fc3),
+        (0x40009e5d9ec9b42fabddfcd9e7893d2b, 0x3fed3ad4a9748a7b8b4463b8eab49b2b),
+        (0x3ffd4a0563a05b058701f8ad609d99cc, 0x3ffe4c0e5e12f133dd01bc6cecb2fb22),
+        (0x40022f730dc749b07d1f3b5cfff80b3b, 0x3f7923d4d4119aab22a224dc6efe3af6),
+        (0x4001189265d3e198ff9264ffbf0e5eee, 0x3fe036c34e53d3bcc339a0ef25faaae6),
+        (0xbfff16ccfab0861cce400e5a3eccb44d, 0x3fffe06113c49426cf58a06f5c7ecaaa),
+        (0xc0017234e3b71827ded773c7ab935923, 0x3fffffffffffffffeba12449256446ee),
+        (0x40041393cbcd22316148e544d4cd6c70, 0x39491d05fc3ce97ebadd0903387c1c00),
+        (0xbffed108b4a6fbe14dafab5e788edb0f, 0x3fffcd1032f65f652927616063e5ec15),
+        (0x3ff9440cae2041fabf1baa5e31307085, 0x3ffef4932ceaf936f0284d79cda59519),
+        (0x3fff2c10f70f14c2c8b62470b8fe1ac7, 0x3ffb8ee75cf71bf6f8e0f42792440b8a),
+        (0x3ff896e3404a8ce91f9d639689b695fe, 0x3ffef8d399978e3c68640bbce61ccc27),
+    ];
+}
@@ -0,0 +1,184 @@
+//! Trigonometric and hyperbolic function tests for f128
+//!
+//! Results are compared against the nightly std `f128` functions, and special
+//! cases against C23 Annex F. Some of the platform quad-precision functions
+//! std calls into are only accurate to a few ULPs, so results need only agree
+//! within three.
+
+#![feature(f128)]
+
+#[path = "common/rng.rs"]
+mod rng;
+
+use floats::f128 as F128;
+use rng::{ulps, XorShift};
+
+fn check(name: &str, x: f128, our: F128, std: f128) {
+    let our = f128::from_bits(our.to_bits());
+
+    if std.is_nan() {
+        assert!(our.is_nan(), "{name}({x:?}) = {our:?}");
+    } else {
+        assert!(ulps(our, std) <= 3, "{name}({x:?}) = {our:?}, std {std:?}");
+    }
+}
+
+#[rstest::rstest]
+#[case::sin("sin", F128::sin, f128::sin, 0x3fff, 40)]
+#[case::cos("cos", F128::cos, f128::cos, 0x3fff, 40)]
+#[case::tan("tan", F128::tan, f128::tan, 0x3fff, 40)]
+#[case::sin_huge("sin", F128::sin, f128::sin, 0x3fff + 8000, 8000)]
+#[case::cos_huge("cos", F128::cos, f128::cos, 0x3fff + 8000, 8000)]
+#[case::tan_huge("tan", F128::tan, f128::tan, 0x3fff + 8000, 8000)]
+#[case::asin("asin", F128::asin, f128::asin, 0x3fff - 40, 40)]
+#[case::acos("acos", F128::acos, f128::acos, 0x3fff - 40, 40)]
+#[case::atan("atan", F128::atan, f128::atan, 0x3fff, 120)]
+#[case::sinh("sinh", F128::sinh, f128::sinh, 0x3fff - 20, 34)]
+#[case::cosh("cosh", F128::cosh, f128::cosh, 0x3fff - 20, 34)]
+#[case::tanh("tanh", F128::tanh, f128::tanh, 0x3fff - 20, 26)]
+#[case::asinh("asinh", F128::asinh, f128::asinh, 0x3fff, 120)]
+#[case::acosh("acosh", F128::acosh, f128::acosh, 0x3fff + 60, 60)]
+#[case::atanh("atanh", F128::atanh, f128::atanh, 0x3fff - 40, 40)]
+fn sweep(
+    #[case] name: &str,
+    #[case] f: fn(F128) -> F128,
+    #[case] g: fn(f128) -> f128,
+    #[case] center: i32,
+    #[case] spread: i32,
+) {
+    let mut rng = XorShift(0x6a09_e667_f3bc_c908 ^ center as u64);
+
+    for _ in 0..2000 {
+        let x = f128::from_bits(rng.f128(center, spread));
+        check(name, x, f(F128::from_bits(x.to_bits())), g(x));
+    }
+}
+
+#[test]
+fn atan2() {
+    let mut rng = XorShift(0xbb67_ae85_84ca_a73b);
+
+    for _ in 0..2000 {
+        let y = f128::from_bits(rng.f128(0x3fff, 60));
+        let x = f128::from_bits(rng.f128(0x3fff, 60));
+        let our = F128::from_bits(y.to_bits()).atan2(F128::from_bits(x.to_bits()));
+        check("atan2", y, our, y.atan2(x));
+    }
+}
+
+#[test]
+fn sin_cos() {
+    let mut rng = XorShift(0x3c6e_f372_fe94_f82b);
+
+    for _ in 0..200 {
+        let x = F128::from_bits(rng.f128(0x3fff, 2000));
+        let (sin, cos) = x.sin_cos();
+        assert_eq!(sin.to_bits(), x.sin().to_bits());
+        assert_eq!(cos.to_bits(), x.cos().to_bits());
+    }
+}
+
+/// Inputs where the platform functions are off by more than one ULP
+#[cfg(not(feature = "nightly"))]
+#[rstest::rstest]
+#[case::tanh(
+    F128::tanh,
+    0xbffed445_80a5b88e_e360952f_b525107f,
+    0xbffe7258_39389b27_07950d8b_c870cfea
+)]
+#[case::atanh(
+    F128::atanh,
+    0xbffcd583_e33e29b7_5dd5a63e_5e0cd824,
+    0xbffcde02_a56eb3aa_7e886479_f7aa5046
+)]
+fn correctly_rounded(#[case] f: fn(F128) -> F128, #[case] x: u128, #[case] expected: u128) {
+    assert_eq!(f(F128::from_bits(x)).to_bits(), expected);
+}
+
+/// The representable values closest to multiples of pi/2 lose the most bits
+/// to cancellation during argument reduction
+#[rstest::rstest]
+#[case::frac_pi_2(core::f128::consts::FRAC_PI_2)]
+#[case::pi(core::f128::consts::PI)]
+#[case::three_pi_2(3.0 * core::f128::consts::FRAC_PI_2)]
+#[case::tau(core::f128::consts::TAU)]
+#[case::max(f128::MAX)]
+fn near_multiples_of_pi(#[case] x: f128) {
+    for x in [x.next_down(), x, x.next_up()] {
+        let our = F128::from_bits(x.to_bits());
+        check("sin", x, our.sin(), x.sin());
+        check("cos", x, our.cos(), x.cos());
+        check("tan", x, our.tan(), x.tan());
+    }
+}
+
+#[rstest::rstest]
+#[case::sin_zero(F128::sin, 0.0, 0.0)]
+#[case::sin_neg_zero(F128::sin, -0.0, -0.0)]
+#[case::sin_inf(F128::sin, f128::INFINITY, f128::NAN)]
+#[case::cos_zero(F128::cos, -0.0, 1.0)]
+#[case::cos_inf(F128::cos, f128::NEG_INFINITY, f128::NAN)]
+#[case::tan_neg_zero(F128::tan, -0.0, -0.0)]
+#[case::tan_inf(F128::tan, f128::INFINITY, f128::NAN)]
+#[case::asin_neg_zero(F128::asin, -0.0, -0.0)]
+#[case::asin_one(F128::asin, 1.0, core::f128::consts::FRAC_PI_2)]
+#[case::asin_domain(F128::asin, 1.5, f128::NAN)]
+#[case::acos_one(F128::acos, 1.0, 0.0)]
+#[case::acos_neg_one(F128::acos, -1.0, core::f128::consts::PI)]
+#[case::acos_domain(F128::acos, -1.5, f128::NAN)]
+#[case::atan_neg_zero(F128::atan, -0.0, -0.0)]
+#[case::atan_inf(F128::atan, f128::INFINITY, core::f128::consts::FRAC_PI_2)]
+#[case::atan_neg_inf(F128::atan, f128::NEG_INFINITY, -core::f128::consts::FRAC_PI_2)]
+#[case::sinh_neg_zero(F128::sinh, -0.0, -0.0)]
+#[case::sinh_neg_inf(F128::sinh, f128::NEG_INFINITY, f128::NEG_INFINITY)]
+#[case::sinh_overflow(F128::sinh, -12000.0, f128::NEG_INFINITY)]
+#[case::cosh_neg_zero(F128::cosh, -0.0, 1.0)]
+#[case::cosh_neg_inf(F128::cosh, f128::NEG_INFINITY, f128::INFINITY)]
+#[case::tanh_neg_zero(F128::tanh, -0.0, -0.0)]
+#[case::tanh_neg_inf(F128::tanh, f128::NEG_INFINITY, -1.0)]
+#[case::tanh_large(F128::tanh, 50.0, 1.0)]
+#[case::asinh_neg_zero(F128::asinh, -0.0, -0.0)]
+#[case::asinh_neg_inf(F128::asinh, f128::NEG_INFINITY, f128::NEG_INFINITY)]
+#[case::acosh_one(F128::acosh, 1.0, 0.0)]
+#[case::acosh_inf(F128::acosh, f128::INFINITY, f128::INFINITY)]
+#[case::acosh_domain(F128::acosh, 0.5, f128::NAN)]
+#[case::atanh_neg_zero(F128::atanh, -0.0, -0.0)]
+#[case::atanh_one(F128::atanh, 1.0, f128::INFINITY)]
+#[case::atanh_neg_one(F128::atanh, -1.0, f128::NEG_INFINITY)]
+#[case::atanh_domain(F128::atanh, 2.0, f128::NAN)]
+fn special(#[case] f: fn(F128) -> F128, #[case] x: f128, #[case] expected: f128) {
+    let our = f(F128::from_bits(x.to_bits()));
+
+    if expected.is_nan() {
+        assert!(our.is_nan());
+    } else {
+        assert_eq!(our.to_bits(), expected.to_bits());
+    }
+}
+
+#[rstest::rstest]
+#[case(0.0, -0.0, core::f128::consts::PI)]
+#[case(-0.0, -0.0, -core::f128::consts::PI)]
+#[case(0.0, 0.0, 0.0)]
+#[case(-0.0, 0.0, -0.0)]
+#[case(0.0, -1.0, core::f128::consts::PI)]
+#[case(-0.0, -1.0, -core::f128::consts::PI)]
+#[case(-0.0, 1.0, -0.0)]
+#[case(-1.0, 0.0, -core::f128::consts::FRAC_PI_2)]
+#[case(1.0, -0.0, core::f128::consts::FRAC_PI_2)]
+#[case(-1.0, f128::NEG_INFINITY, -core::f128::consts::PI)]
+#[case(-1.0, f128::INFINITY, -0.0)]
+#[case(f128::INFINITY, 1.0, core::f128::consts::FRAC_PI_2)]
+#[case(f128::NEG_INFINITY, f128::NEG_INFINITY, -2.35619449019234492884698253745962716314787705)]
+#[case(f128::INFINITY, f128::INFINITY, core::f128::consts::FRAC_PI_4)]
+#[case(f128::NAN, 1.0, f128::NAN)]
+#[case(1.0, f128::NAN, f128::NAN)]
+fn atan2_special(#[case] y: f128, #[case] x: f128, #[case] expected: f128) {
+    let our = F128::from_bits(y.to_bits()).atan2(F128::from_bits(x.to_bits()));
+
+    if expected.is_nan() {
+        assert!(our.is_nan());
+    } else {
+        assert_eq!(our.to_bits(), expected.to_bits(), "atan2({y:?}, {x:?})");
+    }
+}
@@ -0,0 +1,175 @@
+//! ULP navigation tests for f16 and f128
+//!
+//! `next_up`/`next_down` are checked against the nightly standard library
+//! types. `next_after`, `ulp` and `ulp_distance` have no std equivalent and
+//! are checked against properties derived from the std types.
+
+#![feature(f16, f128)]
+
+use floats::{f128 as F128, f16 as F16, IeeeFloat};
+
+/// Every f16 bit pattern
+fn all_f16() -> impl Iterator<Item = u16> {
+    0..=u16::MAX
+}
+
+#[test]
+fn f16_next_up_down() {
+    for bits in all_f16() {
+        let std = f16::from_bits(bits);
+        let our = F16::from_bits(bits);
+
+        assert_eq!(
+            our.next_up().to_bits(),
+            std.next_up().to_bits(),
+            "{bits:#x}"
+        );
+        assert_eq!(
+            our.next_down().to_bits(),
+            std.next_down().to_bits(),
+            "{bits:#x}"
+        );
+    }
+}
+
+#[rstest::rstest]
+#[case::zero(0.0f128)]
+#[case::neg_zero(-0.0f128)]
+#[case::one(1.0f128)]
+#[case::neg_one(-1.0f128)]
+#[case::max(f128::MAX)]
+#[case::min(f128::MIN)]
+#[case::min_positive(f128::MIN_POSITIVE)]
+#[case::neg_min_positive(-f128::MIN_POSITIVE)]
+#[case::min_subnormal(f128::from_bits(1))]
+#[case::neg_min_subnormal(f128::from_bits((1 << 127) | 1))]
+#[case::max_subnormal(f128::from_bits((1 << 112) - 1))]
+#[case::inf(f128::INFINITY)]
+#[case::neg_inf(f128::NEG_INFINITY)]
+#[case::nan(f128::NAN)]
+#[case::neg_nan(-f128::NAN)]
+fn f128_next_up_down(#[case] value: f128) {
+    let our = F128::from_bits(value.to_bits());
+
+    assert_eq!(our.next_up().to_bits(), value.next_up().to_bits());
+    assert_eq!(our.next_down().to_bits(), value.next_down().to_bits());
+}
+
+#[test]
+fn f16_next_after() {
+    let targets = [0.0f16, -0.0, 1.0, -1.0, f16::INFINITY, f16::NEG_INFINITY];
+
+    for bits in all_f16() {
+        let x = f16::from_bits(bits);
+
+        for &y in &targets {
+            let expected = if x.is_nan() {
+                x
+            } else if x == y {
+                y
+            } else if x < y {
+                x.next_up()
+            } else {
+                x.next_down()
+            };
+
+            let our = F16::from_bits(bits).next_after(F16::from_bits(y.to_bits()));
+            assert_eq!(our.to_bits(), expected.to_bits(), "{bits:#x} -> {y:?}");
+        }
+
+        let nan = F16::from_bits(0x7e01);
+        if !x.is_nan() {
+            assert_eq!(F16::from_bits(bits).next_after(nan).to_bits(), 0x7e01);
+        }
+    }
+}
+
+#[test]
+fn f16_ulp() {
+    for bits in all_f16() {
+        let x = f16::from_bits(bits).abs();
+        let ulp = F16::from_bits(bits).ulp();
+
+        if x.is_nan() {
+            assert_eq!(ulp.to_bits(), bits);
+        } else if x.is_infinite() {
+            assert_eq!(ulp.to_bits(), f16::INFINITY.to_bits());
+        } else if x == f16::MAX {
+            assert_eq!(ulp.to_bits(), 32.0f16.to_bits());
+        } else {
+            let expected = x.next_up() - x;
+            assert_eq!(ulp.to_bits(), expected.to_bits(), "{bits:#x}");
+        }
+    }
+}
+
+#[test]
+fn f16_ulp_distance() {
+    for bits in all_f16() {
+        let x = F16::from_bits(bits);
+
+        if x.is_nan() {
+            assert_eq!(x.ulp_distance(F16::from_bits(0)), u128::MAX);
+            assert_eq!(F16::from_bits(0).ulp_distance(x), u128::MAX);
+        } else {
+            assert_eq!(x.ulp_distance(x), 0);
+
+            if x != F16::INFINITY {
+                assert_eq!(x.ulp_distance(x.next_up()), 1, "{bits:#x}");
+                assert_eq!(x.next_up().ulp_distance(x), 1, "{bits:#x}");
+            }
+        }
+    }
+
+    assert_eq!(F16::from_bits(0x8000).ulp_distance(F16::from_bits(0)), 0);
+    assert_eq!(F16::MIN.ulp_distance(F16::MAX), 2 * 0x7bff);
+    assert_eq!(F16::NEG_INFINITY.ulp_distance(F16::INFINITY), 2 * 0x7c00);
+}
+
+#[rstest::rstest]
+#[case::zero(0.0f128, 1.0f128, f128::from_bits(1))]
+#[case::neg_zero(-0.0f128, -1.0f128, -f128::from_bits(1))]
+#[case::signed_zero(0.0f128, -0.0f128, -0.0f128)]
+#[case::up(1.0f128, f128::INFINITY, 1.0f128.next_up())]
+#[case::down(1.0f128, -1.0f128, 1.0f128.next_down())]
+#[case::max(f128::MAX, f128::INFINITY, f128::INFINITY)]
+#[case::inf(f128::INFINITY, 0.0f128, f128::MAX)]
+#[case::nan(f128::NAN, 0.0f128, f128::NAN)]
+fn f128_next_after(#[case] from: f128, #[case] toward: f128, #[case] expected: f128) {
+    let from = F128::from_bits(from.to_bits());
+    let toward = F128::from_bits(toward.to_bits());
+
+    assert_eq!(from.next_after(toward).to_bits(), expected.to_bits());
+}
+
+#[rstest::rstest]
+#[case::zero(0.0f128, f128::from_bits(1))]
+#[case::neg_zero(-0.0f128, f128::from_bits(1))]
+#[case::min_subnormal(f128::from_bits(1), f128::from_bits(1))]
+#[case::min_positive(f128::MIN_POSITIVE, f128::from_bits(1))]
+#[case::one(1.0f128, f128::EPSILON)]
+#[case::neg_one(-1.0f128, f128::EPSILON)]
+#[case::three(3.0f128, 2.0 * f128::EPSILON)]
+#[case::max(f128::MAX, f128::MAX - f128::MAX.next_down())]
+#[case::inf(f128::NEG_INFINITY, f128::INFINITY)]
+fn f128_ulp(#[case] value: f128, #[case] expected: f128) {
+    let our = F128::from_bits(value.to_bits());
+
+    assert_eq!(our.ulp().to_bits(), expected.to_bits());
+}
+
+#[rstest::rstest]
+#[case::zeros(0.0f128, -0.0f128, 0)]
+#[case::subnormals(f128::from_bits(1), -f128::from_bits(1), 2)]
+#[case::adjacent(1.0f128, 1.0f128.next_up(), 1)]
+#[case::binade(1.0f128, 2.0f128, 1 << 112)]
+#[case::extremes(f128::MIN, f128::MAX, 2 * f128::MAX.to_bits())]
+#[case::infinities(f128::NEG_INFINITY, f128::INFINITY, (2 * 0x7fff) << 112)]
+#[case::nan(f128::NAN, 1.0f128, u128::MAX)]
+fn f128_ulp_distance(#[case] a: f128, #[case] b: f128, #[case] expected: u128) {
+    let a = F128::from_bits(a.to_bits());
+    let b = F128::from_bits(b.to_bits());
+
+    assert_eq!(a.ulp_distance(b), expected);
+    assert_eq!(b.ulp_distance(a), expected);
+}